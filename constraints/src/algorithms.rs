@@ -3,7 +3,9 @@
 //! [mediacapture_streams]: https://www.w3.org/TR/mediacapture-streams/
 
 mod fitness_distance;
+mod sanitize;
 mod select_settings;
 
 pub use self::fitness_distance::*;
+pub use self::sanitize::*;
 pub use self::select_settings::*;
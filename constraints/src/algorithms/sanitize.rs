@@ -0,0 +1,97 @@
+use crate::{
+    MediaTrackSupportedConstraints, ResolvedMediaTrackConstraints, SanitizedMediaTrackConstraints,
+};
+
+/// Sanitizes resolved constraints against the constraints supported by the platform.
+///
+/// As specified in step 1 of the `SelectSettings` algorithm:
+/// <https://www.w3.org/TR/mediacapture-streams/#dfn-selectsettings>
+///
+/// > Note that unknown properties are discarded by WebIDL,
+/// > which means that unknown/unsupported required constraints will silently disappear.
+///
+/// Constraints whose property is not contained in `supported_constraints` are dropped,
+/// as are empty constraints and advanced constraint sets left empty after sanitization.
+/// The result is suitable for passing to [`select_settings_candidates`](super::select_settings_candidates).
+pub fn sanitize(
+    resolved: &ResolvedMediaTrackConstraints,
+    supported_constraints: &MediaTrackSupportedConstraints,
+) -> SanitizedMediaTrackConstraints {
+    resolved.to_sanitized(supported_constraints)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::FromIterator;
+
+    use super::*;
+    use crate::property::all::name::*;
+    use crate::{
+        AdvancedMediaTrackConstraints, FacingMode, MandatoryMediaTrackConstraints,
+        MediaTrackConstraintSet, MediaTrackConstraints, ResolvedValueConstraint,
+        ResolvedValueRangeConstraint, SanitizedMandatoryMediaTrackConstraints,
+    };
+
+    fn resolved() -> ResolvedMediaTrackConstraints {
+        MediaTrackConstraints {
+            mandatory: MandatoryMediaTrackConstraints::from_iter([
+                (&FACING_MODE, FacingMode::user().into()),
+                (&WIDTH, 1920.into()),
+            ]),
+            advanced: AdvancedMediaTrackConstraints::new(vec![
+                MediaTrackConstraintSet::from_iter([(&FACING_MODE, FacingMode::user().into())]),
+                MediaTrackConstraintSet::from_iter([(&WIDTH, 1280.into())]),
+            ]),
+        }
+        .into_resolved()
+    }
+
+    #[test]
+    fn drops_unsupported() {
+        let supported_constraints = MediaTrackSupportedConstraints::from_iter([&WIDTH]);
+
+        let actual = sanitize(&resolved(), &supported_constraints);
+
+        assert!(!actual.mandatory.contains_key(&FACING_MODE));
+        assert_eq!(
+            actual
+                .mandatory
+                .get(&WIDTH)
+                .map(|constraint| (**constraint).clone()),
+            Some(ResolvedValueRangeConstraint::default().ideal(1920).into())
+        );
+
+        // The advanced set containing only `facingMode` is dropped entirely:
+        assert_eq!(actual.advanced.len(), 1);
+        assert!(actual.advanced[0].contains_key(&WIDTH));
+        assert!(!actual.advanced[0].contains_key(&FACING_MODE));
+    }
+
+    #[test]
+    fn keeps_supported() {
+        let supported_constraints = MediaTrackSupportedConstraints::default();
+
+        let actual = sanitize(&resolved(), &supported_constraints);
+
+        assert!(actual.mandatory.contains_key(&FACING_MODE));
+        assert!(actual.mandatory.contains_key(&WIDTH));
+        assert_eq!(actual.advanced.len(), 2);
+    }
+
+    #[test]
+    fn drops_empty() {
+        let resolved = ResolvedMediaTrackConstraints {
+            mandatory: FromIterator::from_iter([(
+                DEVICE_ID.clone(),
+                ResolvedValueConstraint::<String>::default().into(),
+            )]),
+            advanced: Default::default(),
+        };
+        let supported_constraints = MediaTrackSupportedConstraints::default();
+
+        let actual = sanitize(&resolved, &supported_constraints);
+        let expected = SanitizedMandatoryMediaTrackConstraints::default();
+
+        assert_eq!(actual.mandatory, expected);
+    }
+}
@@ -1,9 +1,7 @@
 use std::collections::HashSet;
 
-use thiserror::Error;
-
 use crate::algorithms::fitness_distance::SettingFitnessDistanceError;
-use crate::errors::OverconstrainedError;
+use crate::errors::{ConstraintError, Result};
 use crate::{MediaTrackSettings, SanitizedMediaTrackConstraints};
 
 mod apply_advanced;
@@ -26,12 +24,7 @@ pub enum DeviceInformationExposureMode {
 }
 
 /// An error type indicating a failure of the `SelectSettings` algorithm.
-#[derive(Error, Clone, Eq, PartialEq, Debug)]
-pub enum SelectSettingsError {
-    /// An error caused by one or more over-constrained settings.
-    #[error(transparent)]
-    Overconstrained(#[from] OverconstrainedError),
-}
+pub type SelectSettingsError = ConstraintError;
 
 /// This function implements steps 1-5 of the `SelectSettings` algorithm
 /// as defined by the W3C spec:
@@ -44,7 +37,7 @@ pub fn select_settings_candidates<'a, I>(
     possible_settings: I,
     constraints: &SanitizedMediaTrackConstraints,
     exposure_mode: DeviceInformationExposureMode,
-) -> Result<Vec<&'a MediaTrackSettings>, SelectSettingsError>
+) -> Result<Vec<&'a MediaTrackSettings>>
 where
     I: IntoIterator<Item = &'a MediaTrackSettings>,
 {
@@ -4,7 +4,7 @@ use lazy_static::lazy_static;
 
 use super::DeviceInformationExposureMode;
 use crate::algorithms::{select_settings_candidates, SelectSettingsError};
-use crate::errors::OverconstrainedError;
+use crate::errors::{ConstraintError, OverconstrainedError};
 use crate::property::all::name::*;
 use crate::property::all::names as all_properties;
 use crate::{
@@ -271,6 +271,48 @@ mod overconstrained {
                 "Setting was too large ([120, 15, 240, 30, 60] do not satisfy (x <= 10))."
             );
         }
+
+        #[test]
+        fn impossible_exact_width() {
+            let constraints = ResolvedMediaTrackConstraints {
+                mandatory: ResolvedMandatoryMediaTrackConstraints::from_iter([(
+                    WIDTH.clone(),
+                    ResolvedValueRangeConstraint::default().exact(1234).into(),
+                )]),
+                advanced: ResolvedAdvancedMediaTrackConstraints::default(),
+            }
+            .to_sanitized(&default_supported_constraints());
+
+            let possible_settings = default_possible_settings();
+            let actual = select_settings_candidates(
+                possible_settings.iter(),
+                &constraints,
+                DeviceInformationExposureMode::Exposed,
+            );
+
+            match actual {
+                Err(ConstraintError::Overconstrained(error)) => {
+                    assert_eq!(error.constraint, WIDTH);
+                    assert_eq!(error.constraint.name(), "width");
+                }
+                other => panic!("expected an overconstrained error, got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn no_possible_settings() {
+            let error = test_overconstrained(
+                &[],
+                ResolvedMandatoryMediaTrackConstraints::from_iter([(
+                    WIDTH.clone(),
+                    ResolvedValueRangeConstraint::default().exact(1234).into(),
+                )]),
+                DeviceInformationExposureMode::Exposed,
+            );
+
+            assert_eq!(error.constraint, MediaTrackProperty::from(""));
+            assert!(error.message.is_some());
+        }
     }
 }
 
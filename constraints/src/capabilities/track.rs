@@ -5,6 +5,7 @@ use std::ops::{Deref, DerefMut};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::property::ordered_entries;
 use crate::{MediaTrackCapability, MediaTrackProperty};
 
 /// The capabilities of a [`MediaStreamTrack`][media_stream_track] object.
@@ -38,6 +39,15 @@ impl MediaTrackCapabilities {
     pub fn into_inner(self) -> HashMap<MediaTrackProperty, MediaTrackCapability> {
         self.0
     }
+
+    /// Returns an iterator over the populated `(property, value)` pairs.
+    ///
+    /// Unlike iterating the inner hashmap the order is stable: standard properties come first,
+    /// in the order of [`property::all::names()`](crate::property::all::names),
+    /// followed by any non-standard properties sorted by name.
+    pub fn iter(&self) -> std::vec::IntoIter<(&MediaTrackProperty, &MediaTrackCapability)> {
+        ordered_entries(&self.0).into_iter()
+    }
 }
 
 impl Deref for MediaTrackCapabilities {
@@ -66,6 +76,15 @@ where
     }
 }
 
+impl<'a> IntoIterator for &'a MediaTrackCapabilities {
+    type Item = (&'a MediaTrackProperty, &'a MediaTrackCapability);
+    type IntoIter = std::vec::IntoIter<(&'a MediaTrackProperty, &'a MediaTrackCapability)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl IntoIterator for MediaTrackCapabilities {
     type Item = (MediaTrackProperty, MediaTrackCapability);
     type IntoIter = std::collections::hash_map::IntoIter<MediaTrackProperty, MediaTrackCapability>;
@@ -118,6 +137,30 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn iter() {
+        let subject = Subject::from_iter([
+            (MediaTrackProperty::from("zoom"), (1..=4).into()),
+            (WIDTH.clone(), (640..=1920).into()),
+            (MediaTrackProperty::from("brightness"), (0.0..=1.0).into()),
+            (DEVICE_ID.clone(), "device-id".into()),
+            (HEIGHT.clone(), (480..=1080).into()),
+        ]);
+
+        let actual: Vec<_> = subject
+            .iter()
+            .map(|(property, _)| property.name())
+            .collect();
+
+        let expected = vec!["deviceId", "height", "width", "brightness", "zoom"];
+
+        assert_eq!(actual, expected);
+
+        for (property, value) in &subject {
+            assert_eq!(subject.get(property), Some(value));
+        }
+    }
+
     #[test]
     fn deref_and_deref_mut() {
         let mut subject = Subject::default();
@@ -9,6 +9,18 @@ use thiserror::Error;
 use crate::algorithms::{ConstraintFailureInfo, SettingFitnessDistanceErrorKind};
 use crate::MediaTrackProperty;
 
+/// An error returned by the algorithms of this crate.
+#[derive(Error, Clone, Eq, PartialEq, Debug)]
+pub enum ConstraintError {
+    /// An error caused by one or more over-constrained settings,
+    /// carrying the name of the offending constraint.
+    #[error(transparent)]
+    Overconstrained(#[from] OverconstrainedError),
+}
+
+/// A specialized `Result` type for the algorithms of this crate.
+pub type Result<T, E = ConstraintError> = std::result::Result<T, E>;
+
 /// An error indicating one or more over-constrained settings.
 #[derive(Error, Clone, Eq, PartialEq, Debug)]
 pub struct OverconstrainedError {
@@ -45,8 +57,16 @@ impl OverconstrainedError {
             .into_iter()
             .max_by_key(|(_, failure_info)| failure_info.failures);
 
-        let (constraint, failure_info) =
-            failed_constraint.expect("Empty candidates implies non-empty failed constraints");
+        // Without any possible settings there is no constraint to blame:
+        let (constraint, failure_info) = match failed_constraint {
+            Some(failed_constraint) => failed_constraint,
+            None => {
+                return Self {
+                    message: Some("No settings to select from.".to_owned()),
+                    ..Self::default()
+                }
+            }
+        };
 
         struct Violation {
             constraint: String,
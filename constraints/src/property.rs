@@ -5,6 +5,7 @@
 //! [media_track_supported_constraints]: https://www.w3.org/TR/mediacapture-streams/#dom-mediatracksupportedconstraints
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::Display;
 
 #[cfg(feature = "serde")]
@@ -57,6 +58,28 @@ impl MediaTrackProperty {
     }
 }
 
+/// Returns the entries of `map` with the standard properties first, in the order of
+/// [`all::names()`], followed by any non-standard properties sorted by name.
+pub(crate) fn ordered_entries<V>(
+    map: &HashMap<MediaTrackProperty, V>,
+) -> Vec<(&MediaTrackProperty, &V)> {
+    let standard = all::names();
+
+    let mut entries: Vec<_> = standard
+        .iter()
+        .filter_map(|property| map.get_key_value(*property))
+        .collect();
+
+    let mut others: Vec<_> = map
+        .iter()
+        .filter(|(property, _)| !standard.contains(property))
+        .collect();
+    others.sort_by_key(|(property, _)| *property);
+
+    entries.append(&mut others);
+    entries
+}
+
 /// Standard properties that apply to both, audio and video device types.
 pub mod common {
     use super::*;
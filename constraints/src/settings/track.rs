@@ -5,6 +5,7 @@ use std::ops::{Deref, DerefMut};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::property::ordered_entries;
 use crate::{MediaTrackProperty, MediaTrackSetting};
 
 /// The settings of a [`MediaStreamTrack`][media_stream_track] object.
@@ -40,6 +41,15 @@ impl MediaTrackSettings {
     pub fn into_inner(self) -> HashMap<MediaTrackProperty, MediaTrackSetting> {
         self.0
     }
+
+    /// Returns an iterator over the populated `(property, value)` pairs.
+    ///
+    /// Unlike iterating the inner hashmap the order is stable: standard properties come first,
+    /// in the order of [`property::all::names()`](crate::property::all::names),
+    /// followed by any non-standard properties sorted by name.
+    pub fn iter(&self) -> std::vec::IntoIter<(&MediaTrackProperty, &MediaTrackSetting)> {
+        ordered_entries(&self.0).into_iter()
+    }
 }
 
 impl Deref for MediaTrackSettings {
@@ -68,6 +78,15 @@ where
     }
 }
 
+impl<'a> IntoIterator for &'a MediaTrackSettings {
+    type Item = (&'a MediaTrackProperty, &'a MediaTrackSetting);
+    type IntoIter = std::vec::IntoIter<(&'a MediaTrackProperty, &'a MediaTrackSetting)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl IntoIterator for MediaTrackSettings {
     type Item = (MediaTrackProperty, MediaTrackSetting);
     type IntoIter = std::collections::hash_map::IntoIter<MediaTrackProperty, MediaTrackSetting>;
@@ -120,6 +139,30 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn iter() {
+        let subject = Subject::from_iter([
+            (MediaTrackProperty::from("zoom"), 2.into()),
+            (WIDTH.clone(), 1920.into()),
+            (MediaTrackProperty::from("brightness"), 0.5.into()),
+            (DEVICE_ID.clone(), "device-id".into()),
+            (HEIGHT.clone(), 1080.into()),
+        ]);
+
+        let actual: Vec<_> = subject
+            .iter()
+            .map(|(property, _)| property.name())
+            .collect();
+
+        let expected = vec!["deviceId", "height", "width", "brightness", "zoom"];
+
+        assert_eq!(actual, expected);
+
+        for (property, value) in &subject {
+            assert_eq!(subject.get(property), Some(value));
+        }
+    }
+
     #[test]
     fn deref_and_deref_mut() {
         let mut subject = Subject::default();
@@ -30,13 +30,8 @@ async fn create_new_association_pair(
 
     // Setup client
     tokio::spawn(async move {
-        let client = Association::client(sctp::association::Config {
-            net_conn: ca,
-            max_receive_buffer_size: 0,
-            max_message_size: 0,
-            name: "client".to_owned(),
-        })
-        .await;
+        let client =
+            Association::client(sctp::association::Config::new(ca, "client".to_owned())).await;
 
         let _ = handshake0ch_tx.send(client).await;
         let _ = closed_rx0.recv().await;
@@ -46,13 +41,8 @@ async fn create_new_association_pair(
 
     // Setup server
     tokio::spawn(async move {
-        let server = Association::server(sctp::association::Config {
-            net_conn: cb,
-            max_receive_buffer_size: 0,
-            max_message_size: 0,
-            name: "server".to_owned(),
-        })
-        .await;
+        let server =
+            Association::server(sctp::association::Config::new(cb, "server".to_owned())).await;
 
         let _ = handshake1ch_tx.send(server).await;
         let _ = closed_rx1.recv().await;
@@ -101,13 +101,13 @@ impl DataChannel {
     /// Client opens a data channel over an SCTP stream
     pub async fn client(stream: Arc<Stream>, config: Config) -> Result<Self> {
         if !config.negotiated {
-            let msg = Message::DataChannelOpen(DataChannelOpen {
-                channel_type: config.channel_type,
-                priority: config.priority,
-                reliability_parameter: config.reliability_parameter,
-                label: config.label.bytes().collect(),
-                protocol: config.protocol.bytes().collect(),
-            })
+            let msg = Message::DataChannelOpen(DataChannelOpen::new(
+                config.channel_type,
+                config.priority,
+                config.reliability_parameter,
+                &config.label,
+                &config.protocol,
+            ))
             .marshal()?;
 
             stream
@@ -134,8 +134,8 @@ impl DataChannel {
             config.channel_type = dco.channel_type;
             config.priority = dco.priority;
             config.reliability_parameter = dco.reliability_parameter;
-            config.label = String::from_utf8(dco.label)?;
-            config.protocol = String::from_utf8(dco.protocol)?;
+            config.label = dco.label()?;
+            config.protocol = dco.protocol()?;
         } else {
             return Err(Error::InvalidMessageType(msg.message_type() as u8));
         };
@@ -341,6 +341,12 @@ impl DataChannel {
         self.stream.on_buffered_amount_low(f)
     }
 
+    /// OnMessageAbandoned sets the callback handler which would be called when a partially
+    /// reliable channel gives up on sending a message.
+    pub fn on_message_abandoned(&self, f: OnMessageAbandonedFn) {
+        self.stream.on_message_abandoned(f)
+    }
+
     fn commit_reliability_params(&self) {
         let (unordered, reliability_type) = match self.config.channel_type {
             ChannelType::Reliable => (false, ReliabilityType::Reliable),
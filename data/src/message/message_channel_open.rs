@@ -46,6 +46,16 @@ pub enum ChannelType {
     PartialReliableTimedUnordered,
 }
 
+impl ChannelType {
+    /// Returns whether messages on a channel of this type are delivered in order.
+    pub fn is_ordered(&self) -> bool {
+        matches!(
+            self,
+            Self::Reliable | Self::PartialReliableRexmit | Self::PartialReliableTimed
+        )
+    }
+}
+
 impl Default for ChannelType {
     fn default() -> Self {
         Self::Reliable
@@ -151,6 +161,39 @@ pub struct DataChannelOpen {
     pub protocol: Vec<u8>,
 }
 
+impl DataChannelOpen {
+    /// Creates an OPEN message for a channel called `label` speaking `protocol`.
+    ///
+    /// `reliability_parameter` is the maximum number of retransmissions for the
+    /// `PartialReliableRexmit*` channel types, the lifetime of a message in milliseconds for the
+    /// `PartialReliableTimed*` ones, and is ignored otherwise.
+    pub fn new(
+        channel_type: ChannelType,
+        priority: u16,
+        reliability_parameter: u32,
+        label: &str,
+        protocol: &str,
+    ) -> Self {
+        Self {
+            channel_type,
+            priority,
+            reliability_parameter,
+            label: label.as_bytes().to_vec(),
+            protocol: protocol.as_bytes().to_vec(),
+        }
+    }
+
+    /// Returns the label of the channel, failing if it isn't valid UTF-8.
+    pub fn label(&self) -> std::result::Result<String, Error> {
+        Ok(String::from_utf8(self.label.clone())?)
+    }
+
+    /// Returns the subprotocol of the channel, failing if it isn't valid UTF-8.
+    pub fn protocol(&self) -> std::result::Result<String, Error> {
+        Ok(String::from_utf8(self.protocol.clone())?)
+    }
+}
+
 impl MarshalSize for DataChannelOpen {
     fn marshal_size(&self) -> usize {
         let label_len = self.label.len();
@@ -94,3 +94,61 @@ fn test_message_marshal() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn test_message_open_round_trip() -> Result<()> {
+    let channel_types = [
+        (ChannelType::Reliable, 0x00, true),
+        (ChannelType::ReliableUnordered, 0x80, false),
+        (ChannelType::PartialReliableRexmit, 0x01, true),
+        (ChannelType::PartialReliableRexmitUnordered, 0x81, false),
+        (ChannelType::PartialReliableTimed, 0x02, true),
+        (ChannelType::PartialReliableTimedUnordered, 0x82, false),
+    ];
+
+    for (channel_type, channel_type_byte, ordered) in channel_types {
+        assert_eq!(channel_type.is_ordered(), ordered, "{channel_type:?}");
+
+        let msg = Message::DataChannelOpen(DataChannelOpen::new(
+            channel_type,
+            CHANNEL_PRIORITY_HIGH,
+            1500,
+            "chat",
+            "json",
+        ));
+
+        let raw = msg.marshal()?;
+        assert_eq!(raw[0], 0x03, "{channel_type:?}");
+        assert_eq!(raw[1], channel_type_byte, "{channel_type:?}");
+
+        let actual = Message::unmarshal(&mut raw.clone())?;
+        assert_eq!(actual, msg, "{channel_type:?}");
+
+        let Message::DataChannelOpen(open) = actual else {
+            panic!("expected an OPEN message, got {actual:?}");
+        };
+        assert_eq!(open.channel_type, channel_type);
+        assert_eq!(open.priority, CHANNEL_PRIORITY_HIGH);
+        assert_eq!(open.reliability_parameter, 1500);
+        assert_eq!(open.label()?, "chat");
+        assert_eq!(open.protocol()?, "json");
+    }
+
+    let ack = Message::DataChannelAck(DataChannelAck);
+    let raw = ack.marshal()?;
+    assert_eq!(&raw[..], &[0x02]);
+    assert_eq!(Message::unmarshal(&mut raw.clone())?, ack);
+
+    Ok(())
+}
+
+#[test]
+fn test_message_open_invalid_label() {
+    let open = DataChannelOpen {
+        label: vec![0xff, 0xfe],
+        ..DataChannelOpen::new(ChannelType::Reliable, 0, 0, "", "")
+    };
+
+    assert!(open.label().is_err());
+    assert_eq!(open.protocol(), Ok(String::new()));
+}
@@ -498,6 +498,13 @@ impl DTLSConn {
         self.state.srtp_protection_profile
     }
 
+    /// selected_cipher_suite returns the cipher suite chosen by the handshake, None before one
+    /// has been chosen.
+    pub async fn selected_cipher_suite(&self) -> Option<CipherSuiteId> {
+        let cipher_suite = self.state.cipher_suite.lock().await;
+        cipher_suite.as_ref().map(|cipher_suite| cipher_suite.id())
+    }
+
     pub(crate) async fn notify(&self, level: AlertLevel, desc: AlertDescription) -> Result<()> {
         self.write_packets(vec![Packet {
             record: RecordLayer::new(
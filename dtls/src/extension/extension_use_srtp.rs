@@ -10,6 +10,7 @@ use super::*;
 pub enum SrtpProtectionProfile {
     Srtp_Aes128_Cm_Hmac_Sha1_80 = 0x0001,
     Srtp_Aes128_Cm_Hmac_Sha1_32 = 0x0002,
+    Srtp_Null_Hmac_Sha1_80 = 0x0005,
     Srtp_Aead_Aes_128_Gcm = 0x0007,
     Srtp_Aead_Aes_256_Gcm = 0x0008,
     Unsupported,
@@ -20,6 +21,7 @@ impl From<u16> for SrtpProtectionProfile {
         match val {
             0x0001 => SrtpProtectionProfile::Srtp_Aes128_Cm_Hmac_Sha1_80,
             0x0002 => SrtpProtectionProfile::Srtp_Aes128_Cm_Hmac_Sha1_32,
+            0x0005 => SrtpProtectionProfile::Srtp_Null_Hmac_Sha1_80,
             0x0007 => SrtpProtectionProfile::Srtp_Aead_Aes_128_Gcm,
             0x0008 => SrtpProtectionProfile::Srtp_Aead_Aes_256_Gcm,
             _ => SrtpProtectionProfile::Unsupported,
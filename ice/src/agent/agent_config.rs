@@ -103,8 +103,8 @@ pub struct AgentConfig {
     pub candidate_types: Vec<CandidateType>,
 
     //LoggerFactory logging.LoggerFactory
-    /// Controls how often our internal task loop runs when in the connecting state.
-    /// Only useful for testing.
+    /// Controls how often connectivity checks are sent while connecting (Ta, RFC 8445 Sec 14.2).
+    /// Lower values connect faster at the cost of a burstier checklist. Defaults to 400ms.
     pub check_interval: Duration,
 
     /// The max amount of binding requests the agent will send over a candidate pair for validation
@@ -153,6 +153,18 @@ pub struct AgentConfig {
     /// Controls if self-signed certificates are accepted when connecting to TURN servers via TLS or
     /// DTLS.
     pub insecure_skip_verify: bool,
+
+    /// Marks the IPv6 datagrams sent from host and srflx candidates with this flow label, for
+    /// networks that use it for QoS classification. Only supported on Linux.
+    pub ipv6_flow_label: Option<u32>,
+
+    /// Limits how many STUN and TURN servers are queried at once while gathering, as each query
+    /// holds a socket open until answered or timed out. 0 means no limit.
+    pub max_concurrent_gather_probes: usize,
+
+    /// Allows `Agent::nominate_pair` to override the automatic selection of the nominated
+    /// candidate pair. Meant for deterministic tests.
+    pub nomination_override: bool,
 }
 
 impl AgentConfig {
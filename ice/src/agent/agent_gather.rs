@@ -1,7 +1,9 @@
+use std::collections::HashSet;
 use std::net::{Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 use std::sync::Arc;
 
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use util::vnet::net::*;
 use util::Conn;
 use waitgroup::WaitGroup;
@@ -50,6 +52,8 @@ struct GatherCandidatesLocalParams {
 
 struct GatherCandidatesLocalUDPMuxParams {
     network_types: Vec<NetworkType>,
+    mdns_mode: MulticastDnsMode,
+    mdns_name: String,
     interface_filter: Arc<Option<InterfaceFilterFn>>,
     ip_filter: Arc<Option<IpFilterFn>>,
     ext_ip_mapper: Arc<Option<ExternalIpMapper>>,
@@ -76,15 +80,38 @@ struct GatherCandidatesSrflxParams {
     agent_internal: Arc<AgentInternal>,
 }
 
+/// GatherProbeLimiter bounds how many STUN and TURN servers are queried at once while gathering.
+#[derive(Default)]
+pub(crate) struct GatherProbeLimiter {
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl GatherProbeLimiter {
+    /// Allows `max_concurrent` probes at once, or any number if 0.
+    pub(crate) fn new(max_concurrent: usize) -> Self {
+        GatherProbeLimiter {
+            semaphore: (max_concurrent > 0).then(|| Arc::new(Semaphore::new(max_concurrent))),
+        }
+    }
+
+    /// Waits until a probe may start. It's deemed running until the returned guard is dropped.
+    pub(crate) async fn acquire(&self) -> GatherProbeGuard {
+        let permit = match &self.semaphore {
+            // The semaphore is never closed
+            Some(semaphore) => Arc::clone(semaphore).acquire_owned().await.ok(),
+            None => None,
+        };
+
+        GatherProbeGuard { _permit: permit }
+    }
+}
+
+pub(crate) struct GatherProbeGuard {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
 impl Agent {
     pub(crate) async fn gather_candidates_internal(params: GatherCandidatesInternalParams) {
-        Self::set_gathering_state(
-            &params.chan_candidate_tx,
-            &params.gathering_state,
-            GatheringState::Gathering,
-        )
-        .await;
-
         let wg = WaitGroup::new();
 
         for t in &params.candidate_types {
@@ -180,16 +207,14 @@ impl Agent {
         gathering_state: &Arc<AtomicU8>,
         new_state: GatheringState,
     ) {
-        if GatheringState::from(gathering_state.load(Ordering::SeqCst)) != new_state
-            && new_state == GatheringState::Complete
-        {
+        // Gathering may have been stopped early, in which case it's already complete.
+        let old_state = gathering_state.swap(new_state as u8, Ordering::SeqCst);
+        if GatheringState::from(old_state) != new_state && new_state == GatheringState::Complete {
             let cand_tx = chan_candidate_tx.lock().await;
             if let Some(tx) = &*cand_tx {
                 let _ = tx.send(None).await;
             }
         }
-
-        gathering_state.store(new_state as u8, Ordering::SeqCst);
     }
 
     async fn gather_candidates_local(params: GatherCandidatesLocalParams) {
@@ -210,6 +235,8 @@ impl Agent {
         if let UDPNetwork::Muxed(udp_mux) = udp_network {
             let result = Self::gather_candidates_local_udp_mux(GatherCandidatesLocalUDPMuxParams {
                 network_types,
+                mdns_mode,
+                mdns_name,
                 interface_filter,
                 ip_filter,
                 ext_ip_mapper,
@@ -273,26 +300,28 @@ impl Agent {
                     // accessible from the current interface.
                 case udp:*/
 
-                let conn: Arc<dyn Conn + Send + Sync> = match listen_udp_in_port_range(
-                    &net,
-                    ephemeral_config.port_max(),
-                    ephemeral_config.port_min(),
-                    SocketAddr::new(ip, 0),
-                )
-                .await
-                {
-                    Ok(conn) => conn,
-                    Err(err) => {
-                        log::warn!(
-                            "[{}]: could not listen {} {}: {}",
-                            agent_internal.get_name(),
-                            network,
-                            ip,
-                            err
-                        );
-                        continue;
-                    }
-                };
+                let conn: Arc<dyn Conn + Send + Sync> =
+                    match listen_udp_in_port_range_with_flow_label(
+                        &net,
+                        ephemeral_config.port_max(),
+                        ephemeral_config.port_min(),
+                        SocketAddr::new(ip, 0),
+                        agent_internal.ipv6_flow_label,
+                    )
+                    .await
+                    {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            log::warn!(
+                                "[{}]: could not listen {} {}: {}",
+                                agent_internal.get_name(),
+                                network,
+                                ip,
+                                err
+                            );
+                            continue;
+                        }
+                    };
 
                 let port = match conn.local_addr() {
                     Ok(addr) => addr.port(),
@@ -374,6 +403,8 @@ impl Agent {
     ) -> Result<()> {
         let GatherCandidatesLocalUDPMuxParams {
             network_types,
+            mdns_mode,
+            mdns_name,
             interface_filter,
             ip_filter,
             ext_ip_mapper,
@@ -388,8 +419,23 @@ impl Agent {
 
         let udp_mux = Arc::clone(&udp_mux);
 
-        let local_ips =
-            local_interfaces(&net, &interface_filter, &ip_filter, &relevant_network_types).await;
+        let ufrag = {
+            let ufrag_pwd = agent_internal.ufrag_pwd.lock().await;
+
+            ufrag_pwd.local_ufrag.clone()
+        };
+
+        let conn = udp_mux.get_conn(&ufrag).await?;
+        let local_addr = conn.local_addr()?;
+        let port = local_addr.port();
+
+        // A mux bound to a specific address only receives on that address, so there is no
+        // point in advertising any other interface.
+        let local_ips = if local_addr.ip().is_unspecified() {
+            local_interfaces(&net, &interface_filter, &ip_filter, &relevant_network_types).await
+        } else {
+            HashSet::from([local_addr.ip()])
+        };
 
         let candidate_ips: Vec<std::net::IpAddr> = ext_ip_mapper
             .as_ref() // Arc
@@ -422,20 +468,19 @@ impl Agent {
             return Err(Error::ErrCandidateIpNotFound);
         }
 
-        let ufrag = {
-            let ufrag_pwd = agent_internal.ufrag_pwd.lock().await;
-
-            ufrag_pwd.local_ufrag.clone()
-        };
-
-        let conn = udp_mux.get_conn(&ufrag).await?;
-        let port = conn.local_addr()?.port();
-
         for candidate_ip in candidate_ips {
+            // There is no 1:1 NAT mapping of host candidates along with mDNS, so candidate_ip is
+            // the local address the name resolves to.
+            let address = if mdns_mode == MulticastDnsMode::QueryAndGather {
+                mdns_name.clone()
+            } else {
+                candidate_ip.to_string()
+            };
+
             let host_config = CandidateHostConfig {
                 base_config: CandidateBaseConfig {
                     network: UDP.to_owned(),
-                    address: candidate_ip.to_string(),
+                    address,
                     port,
                     conn: Some(conn.clone()),
                     component: COMPONENT_RTP,
@@ -444,9 +489,12 @@ impl Agent {
                 tcp_type: TcpType::Unspecified,
             };
 
-            let candidate: Arc<dyn Candidate + Send + Sync> =
-                Arc::new(host_config.new_candidate_host()?);
+            let candidate = host_config.new_candidate_host()?;
+            if mdns_mode == MulticastDnsMode::QueryAndGather {
+                candidate.set_ip(&candidate_ip)?;
+            }
 
+            let candidate: Arc<dyn Candidate + Send + Sync> = Arc::new(candidate);
             agent_internal.add_candidate(&candidate).await?;
         }
 
@@ -479,29 +527,31 @@ impl Agent {
             tokio::spawn(async move {
                 let _d = w;
 
-                let conn: Arc<dyn Conn + Send + Sync> = match listen_udp_in_port_range(
-                    &net2,
-                    port_max,
-                    port_min,
-                    if network_type.is_ipv4() {
-                        SocketAddr::new(Ipv4Addr::new(0, 0, 0, 0).into(), 0)
-                    } else {
-                        SocketAddr::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0).into(), 0)
-                    },
-                )
-                .await
-                {
-                    Ok(conn) => conn,
-                    Err(err) => {
-                        log::warn!(
-                            "[{}]: Failed to listen {}: {}",
-                            agent_internal2.get_name(),
-                            network,
-                            err
-                        );
-                        return Ok(());
-                    }
-                };
+                let conn: Arc<dyn Conn + Send + Sync> =
+                    match listen_udp_in_port_range_with_flow_label(
+                        &net2,
+                        port_max,
+                        port_min,
+                        if network_type.is_ipv4() {
+                            SocketAddr::new(Ipv4Addr::new(0, 0, 0, 0).into(), 0)
+                        } else {
+                            SocketAddr::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0).into(), 0)
+                        },
+                        agent_internal2.ipv6_flow_label,
+                    )
+                    .await
+                    {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            log::warn!(
+                                "[{}]: Failed to listen {}: {}",
+                                agent_internal2.get_name(),
+                                network,
+                                err
+                            );
+                            return Ok(());
+                        }
+                    };
 
                 let laddr = conn.local_addr()?;
                 let mapped_ip = {
@@ -606,9 +656,14 @@ impl Agent {
                 let w = wg.worker();
                 tokio::spawn(async move {
                     let _d = w;
+                    let _probe = agent_internal2.gather_probe_limiter.acquire().await;
 
                     let host_port = format!("{}:{}", url.host, url.port);
-                    let server_addr = match net2.resolve_addr(is_ipv4, &host_port).await {
+                    let resolved = match url.resolved_addr {
+                        Some(addr) => Ok(addr),
+                        None => net2.resolve_addr(is_ipv4, &host_port).await,
+                    };
+                    let server_addr = match resolved {
                         Ok(addr) => addr,
                         Err(err) => {
                             log::warn!(
@@ -621,29 +676,31 @@ impl Agent {
                         }
                     };
 
-                    let conn: Arc<dyn Conn + Send + Sync> = match listen_udp_in_port_range(
-                        &net2,
-                        port_max,
-                        port_min,
-                        if is_ipv4 {
-                            SocketAddr::new(Ipv4Addr::new(0, 0, 0, 0).into(), 0)
-                        } else {
-                            SocketAddr::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0).into(), 0)
-                        },
-                    )
-                    .await
-                    {
-                        Ok(conn) => conn,
-                        Err(err) => {
-                            log::warn!(
-                                "[{}]: Failed to listen for {}: {}",
-                                agent_internal2.get_name(),
-                                server_addr,
-                                err
-                            );
-                            return Ok(());
-                        }
-                    };
+                    let conn: Arc<dyn Conn + Send + Sync> =
+                        match listen_udp_in_port_range_with_flow_label(
+                            &net2,
+                            port_max,
+                            port_min,
+                            if is_ipv4 {
+                                SocketAddr::new(Ipv4Addr::new(0, 0, 0, 0).into(), 0)
+                            } else {
+                                SocketAddr::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0).into(), 0)
+                            },
+                            agent_internal2.ipv6_flow_label,
+                        )
+                        .await
+                        {
+                            Ok(conn) => conn,
+                            Err(err) => {
+                                log::warn!(
+                                    "[{}]: Failed to listen for {}: {}",
+                                    agent_internal2.get_name(),
+                                    server_addr,
+                                    err
+                                );
+                                return Ok(());
+                            }
+                        };
 
                     let xoraddr =
                         match get_xormapped_addr(&conn, server_addr, STUN_GATHER_TIMEOUT).await {
@@ -752,8 +809,12 @@ impl Agent {
             let w = wg.worker();
             tokio::spawn(async move {
                 let _d = w;
+                let _probe = agent_internal2.gather_probe_limiter.acquire().await;
 
-                let turn_server_addr = format!("{}:{}", url.host, url.port);
+                let turn_server_addr = match url.resolved_addr {
+                    Some(addr) => addr.to_string(),
+                    None => format!("{}:{}", url.host, url.port),
+                };
 
                 let (loc_conn, rel_addr, rel_port) =
                     if url.proto == ProtoType::Udp && url.scheme == SchemeType::Turn {
@@ -786,17 +847,10 @@ impl Agent {
                         return Ok(());
                     };
 
-                let cfg = turn::client::ClientConfig {
-                    stun_serv_addr: String::new(),
-                    turn_serv_addr: turn_server_addr.clone(),
-                    username: url.username,
-                    password: url.password,
-                    realm: String::new(),
-                    software: String::new(),
-                    rto_in_ms: 0,
-                    conn: loc_conn,
-                    vnet: Some(Arc::clone(&net2)),
-                };
+                let cfg = turn::client::ClientConfig::new(loc_conn)
+                    .with_turn_serv_addr(turn_server_addr.clone())
+                    .with_credentials(url.username, url.password)
+                    .with_vnet(Arc::clone(&net2));
                 let client = match turn::client::Client::new(cfg).await {
                     Ok(client) => Arc::new(client),
                     Err(err) => {
@@ -846,6 +900,8 @@ impl Agent {
                     },
                     rel_addr,
                     rel_port,
+                    relay_protocol: RelayProtocol::Udp,
+                    relay_server_addr: turn_server_addr.clone(),
                     relay_client: Some(Arc::clone(&client)),
                 };
 
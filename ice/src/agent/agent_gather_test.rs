@@ -1,9 +1,11 @@
+use std::net::IpAddr;
 use std::str::FromStr;
 
 use ipnet::IpNet;
 use tokio::net::UdpSocket;
 use util::vnet::*;
 
+use super::agent_gather::GatherProbeLimiter;
 use super::agent_vnet_test::*;
 use super::*;
 use crate::udp_mux::{UDPMuxDefault, UDPMuxParams};
@@ -384,6 +386,7 @@ async fn test_vnet_gather_turn_connection_leak() -> Result<()> {
         username: "user".to_owned(),
         password: "pass".to_owned(),
         proto: ProtoType::Udp,
+        ..Default::default()
     };
 
     // buildVNet with a Symmetric NATs for both LANs
@@ -423,6 +426,170 @@ async fn test_vnet_gather_turn_connection_leak() -> Result<()> {
     Ok(())
 }
 
+/// Gathers relay candidates from a TURN server whose hostname the vnet can't resolve.
+async fn gather_relay_from_unresolvable_host(resolved_addr: Option<SocketAddr>) -> Result<usize> {
+    let nat_type = nat::NatType {
+        mapping_behavior: nat::EndpointDependencyType::EndpointAddrPortDependent,
+        filtering_behavior: nat::EndpointDependencyType::EndpointAddrPortDependent,
+        ..Default::default()
+    };
+    let v = build_vnet(nat_type, nat_type).await?;
+
+    let turn_server_url = Url {
+        scheme: SchemeType::Turn,
+        host: "turn.webrtc.invalid".to_owned(),
+        port: VNET_STUN_SERVER_PORT,
+        username: "user".to_owned(),
+        password: "pass".to_owned(),
+        proto: ProtoType::Udp,
+        resolved_addr,
+    };
+    let a = Agent::new(AgentConfig {
+        urls: vec![turn_server_url],
+        network_types: vec![NetworkType::Udp4],
+        candidate_types: vec![CandidateType::Relay],
+        multicast_dns_mode: MulticastDnsMode::Disabled,
+        net: Some(Arc::clone(&v.net0)),
+        ..Default::default()
+    })
+    .await?;
+
+    let (done_tx, mut done_rx) = mpsc::channel::<()>(1);
+    let done_tx = Arc::new(Mutex::new(Some(done_tx)));
+    a.on_candidate(Box::new(
+        move |c: Option<Arc<dyn Candidate + Send + Sync>>| {
+            let done_tx_clone = Arc::clone(&done_tx);
+            Box::pin(async move {
+                if c.is_none() {
+                    let mut tx = done_tx_clone.lock().await;
+                    tx.take();
+                }
+            })
+        },
+    ));
+
+    a.gather_candidates()?;
+    let _ = done_rx.recv().await;
+
+    let relays = a
+        .get_local_candidates()
+        .await?
+        .iter()
+        .filter(|c| c.candidate_type() == CandidateType::Relay)
+        .count();
+
+    a.close().await?;
+    v.close().await?;
+
+    Ok(relays)
+}
+
+#[tokio::test]
+async fn test_vnet_gather_turn_pre_resolved_addr() -> Result<()> {
+    // The hostname can't be looked up, so nothing is gathered without a resolved address
+    assert_eq!(gather_relay_from_unresolvable_host(None).await?, 0);
+
+    let turn_server_addr = SocketAddr::new(
+        IpAddr::from_str(VNET_STUN_SERVER_IP)?,
+        VNET_STUN_SERVER_PORT,
+    );
+    assert_eq!(
+        gather_relay_from_unresolvable_host(Some(turn_server_addr)).await?,
+        1,
+        "the pre-resolved TURN address should be used as is"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_gather_probe_limiter() {
+    let limiter = GatherProbeLimiter::new(2);
+    let first = tokio_test::assert_ready!(tokio_test::task::spawn(limiter.acquire()).poll());
+    let _second = tokio_test::assert_ready!(tokio_test::task::spawn(limiter.acquire()).poll());
+
+    // A third probe waits for a running one to finish
+    let mut third = tokio_test::task::spawn(limiter.acquire());
+    assert!(third.poll().is_pending());
+    drop(first);
+    assert!(third.is_woken());
+    tokio_test::assert_ready!(third.poll());
+
+    // Without a limit, every probe starts at once
+    let limiter = GatherProbeLimiter::new(0);
+    let mut guards = vec![];
+    for _ in 0..8 {
+        let mut probe = tokio_test::task::spawn(limiter.acquire());
+        guards.push(tokio_test::assert_ready!(probe.poll()));
+    }
+}
+
+#[tokio::test]
+async fn test_vnet_gather_concurrency_limit() -> Result<()> {
+    const SERVERS: usize = 8;
+
+    let nat_type = nat::NatType {
+        mapping_behavior: nat::EndpointDependencyType::EndpointAddrPortDependent,
+        filtering_behavior: nat::EndpointDependencyType::EndpointAddrPortDependent,
+        ..Default::default()
+    };
+    let v = build_vnet(nat_type, nat_type).await?;
+
+    let stun_server_url = Url {
+        scheme: SchemeType::Stun,
+        host: VNET_STUN_SERVER_IP.to_owned(),
+        port: VNET_STUN_SERVER_PORT,
+        proto: ProtoType::Udp,
+        ..Default::default()
+    };
+    let a = Agent::new(AgentConfig {
+        urls: vec![stun_server_url; SERVERS],
+        network_types: vec![NetworkType::Udp4],
+        candidate_types: vec![CandidateType::ServerReflexive],
+        multicast_dns_mode: MulticastDnsMode::Disabled,
+        net: Some(Arc::clone(&v.net0)),
+        max_concurrent_gather_probes: 2,
+        ..Default::default()
+    })
+    .await?;
+
+    // The agent's limiter lets no more than 2 probes run at once
+    {
+        let _first = a.internal.gather_probe_limiter.acquire().await;
+        let _second = a.internal.gather_probe_limiter.acquire().await;
+        let mut third = tokio_test::task::spawn(a.internal.gather_probe_limiter.acquire());
+        assert!(third.poll().is_pending());
+    }
+
+    let (done_tx, mut done_rx) = mpsc::channel::<()>(1);
+    let done_tx = Arc::new(Mutex::new(Some(done_tx)));
+    a.on_candidate(Box::new(
+        move |c: Option<Arc<dyn Candidate + Send + Sync>>| {
+            let done_tx_clone = Arc::clone(&done_tx);
+            Box::pin(async move {
+                if c.is_none() {
+                    let mut tx = done_tx_clone.lock().await;
+                    tx.take();
+                }
+            })
+        },
+    ));
+
+    a.gather_candidates()?;
+    let _ = done_rx.recv().await;
+
+    assert_eq!(
+        a.get_local_candidates().await?.len(),
+        SERVERS,
+        "every server should still be queried"
+    );
+
+    a.close().await?;
+    v.close().await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_vnet_gather_muxed_udp() -> Result<()> {
     let udp_socket = UdpSocket::bind("0.0.0.0:0").await?;
@@ -488,3 +655,63 @@ async fn test_vnet_gather_muxed_udp() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_vnet_gather_muxed_udp_multicast_dns() -> Result<()> {
+    let udp_socket = UdpSocket::bind("0.0.0.0:0").await?;
+    let udp_mux = UDPMuxDefault::new(UDPMuxParams::new(udp_socket));
+
+    let lan = Arc::new(Mutex::new(router::Router::new(router::RouterConfig {
+        cidr: "10.0.0.0/24".to_owned(),
+        ..Default::default()
+    })?));
+
+    let nw = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["10.0.0.1".to_owned()],
+        ..Default::default()
+    })));
+
+    connect_net2router(&nw, &lan).await?;
+
+    let a = Agent::new(AgentConfig {
+        network_types: vec![NetworkType::Udp4],
+        candidate_types: vec![CandidateType::Host],
+        multicast_dns_mode: MulticastDnsMode::QueryAndGather,
+        multicast_dns_host_name: "validName.local".to_owned(),
+        net: Some(nw),
+        udp_network: UDPNetwork::Muxed(udp_mux),
+        ..Default::default()
+    })
+    .await?;
+
+    let (done_tx, mut done_rx) = mpsc::channel::<()>(1);
+    let done_tx = Arc::new(Mutex::new(Some(done_tx)));
+    a.on_candidate(Box::new(
+        move |c: Option<Arc<dyn Candidate + Send + Sync>>| {
+            let done_tx_clone = Arc::clone(&done_tx);
+            Box::pin(async move {
+                if c.is_none() {
+                    let mut tx = done_tx_clone.lock().await;
+                    tx.take();
+                }
+            })
+        },
+    ));
+
+    a.gather_candidates()?;
+
+    let _ = done_rx.recv().await;
+
+    let candidates = a.get_local_candidates().await?;
+    assert_eq!(candidates.len(), 1, "There must be a single candidate");
+
+    // The candidate is published under the mDNS name but still bound to the local address.
+    let candi = &candidates[0];
+    assert_eq!(candi.address(), "validName.local");
+    assert_eq!(candi.addr().ip(), IpAddr::from_str("10.0.0.1")?);
+    assert_eq!(candi.port(), candi.get_conn().unwrap().local_addr()?.port());
+
+    a.close().await?;
+
+    Ok(())
+}
@@ -3,6 +3,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64};
 use arc_swap::ArcSwapOption;
 use util::sync::Mutex as SyncMutex;
 
+use super::agent_gather::GatherProbeLimiter;
 use super::agent_transport::*;
 use super::*;
 use crate::candidate::candidate_base::CandidateBaseConfig;
@@ -33,6 +34,7 @@ pub struct AgentInternal {
         Mutex<Option<(mpsc::Receiver<()>, mpsc::Receiver<bool>)>>,
 
     pub(crate) chan_candidate_tx: ChanCandidateTx,
+    pub(crate) gathering_state: Arc<AtomicU8>, //GatheringState,
     pub(crate) chan_candidate_pair_tx: Mutex<Option<mpsc::Sender<()>>>,
     pub(crate) chan_state_tx: Mutex<Option<mpsc::Sender<ConnectionState>>>,
 
@@ -60,11 +62,16 @@ pub struct AgentInternal {
 
     // LRU of outbound Binding request Transaction IDs
     pub(crate) pending_binding_requests: Mutex<Vec<BindingRequest>>,
+    // Outstanding path MTU probes, by transaction ID
+    pub(crate) path_mtu_probes: Mutex<HashMap<TransactionId, oneshot::Sender<()>>>,
 
     pub(crate) agent_conn: Arc<AgentConn>,
 
     // the following variables won't be changed after init_with_defaults()
     pub(crate) insecure_skip_verify: bool,
+    pub(crate) nomination_override: bool,
+    pub(crate) ipv6_flow_label: Option<u32>,
+    pub(crate) gather_probe_limiter: Arc<GatherProbeLimiter>,
     pub(crate) max_binding_requests: u16,
     pub(crate) host_acceptance_min_wait: Duration,
     pub(crate) srflx_acceptance_min_wait: Duration,
@@ -105,6 +112,7 @@ impl AgentInternal {
             ))),
 
             chan_candidate_tx: Arc::new(Mutex::new(Some(chan_candidate_tx))),
+            gathering_state: Arc::new(AtomicU8::new(GatheringState::New as u8)),
             chan_candidate_pair_tx: Mutex::new(Some(chan_candidate_pair_tx)),
             chan_state_tx: Mutex::new(Some(chan_state_tx)),
 
@@ -122,6 +130,11 @@ impl AgentInternal {
             connection_state: AtomicU8::new(ConnectionState::New as u8),
 
             insecure_skip_verify: config.insecure_skip_verify,
+            nomination_override: config.nomination_override,
+            ipv6_flow_label: config.ipv6_flow_label,
+            gather_probe_limiter: Arc::new(GatherProbeLimiter::new(
+                config.max_concurrent_gather_probes,
+            )),
 
             started_ch_tx: Mutex::new(Some(started_ch_tx)),
 
@@ -154,6 +167,7 @@ impl AgentInternal {
 
             // LRU of outbound Binding request Transaction IDs
             pending_binding_requests: Mutex::new(vec![]),
+            path_mtu_probes: Mutex::new(HashMap::new()),
 
             // AgentConn
             agent_conn: Arc::new(AgentConn::new()),
@@ -261,7 +275,11 @@ impl AgentInternal {
             let ai = Arc::clone(self);
             tokio::spawn(async move {
                 loop {
-                    let mut interval = DEFAULT_CHECK_INTERVAL;
+                    let mut interval = match last_connection_state {
+                        // While connecting, pace checks by Ta, even when it is slower than the default
+                        ConnectionState::New | ConnectionState::Checking => check_interval,
+                        _ => DEFAULT_CHECK_INTERVAL,
+                    };
 
                     let mut update_interval = |x: Duration| {
                         if x != ZERO_DURATION && (interval == ZERO_DURATION || interval > x) {
@@ -270,10 +288,6 @@ impl AgentInternal {
                     };
 
                     match last_connection_state {
-                        ConnectionState::New | ConnectionState::Checking => {
-                            // While connecting, check candidates more frequently
-                            update_interval(check_interval);
-                        }
                         ConnectionState::Connected | ConnectionState::Disconnected => {
                             update_interval(keepalive_interval);
                         }
@@ -556,6 +570,18 @@ impl AgentInternal {
         self: &Arc<Self>,
         c: &Arc<dyn Candidate + Send + Sync>,
     ) -> Result<()> {
+        // Gathering was stopped early, drop what the probes still in flight come up with.
+        if self.gathering_state.load(Ordering::SeqCst) == GatheringState::Complete as u8 {
+            if let Err(err) = c.close().await {
+                log::warn!(
+                    "[{}]: Failed to close candidate gathered too late: {}",
+                    self.get_name(),
+                    err
+                );
+            }
+            return Ok(());
+        }
+
         let initialized_ch = {
             let started_ch_tx = self.started_ch_tx.lock().await;
             (*started_ch_tx).as_ref().map(|tx| tx.subscribe())
@@ -894,7 +920,9 @@ impl AgentInternal {
             }
 
             if let Some(rc) = &remote_candidate {
-                self.handle_success_response(m, local, rc, remote).await;
+                if !self.handle_path_mtu_probe_success(m.transaction_id).await {
+                    self.handle_success_response(m, local, rc, remote).await;
+                }
             } else {
                 log::warn!(
                     "[{}]: discard success message from ({}), no such remote",
@@ -0,0 +1,160 @@
+use stun::textattrs::*;
+
+use super::*;
+use crate::control::*;
+use crate::priority::*;
+
+/// How many times a probe of a given size is sent before the size is considered too large.
+const PATH_MTU_PROBE_ATTEMPTS: usize = 3;
+
+/// The attribute carrying the padding of path MTU probes. It's in the comprehension-optional
+/// range (RFC 8489 section 14), so peers that don't know it ignore it and still answer the
+/// probe. The PADDING attribute of RFC 5780 is comprehension-required and would get the probe
+/// rejected.
+const ATTR_PATH_MTU_PADDING: AttrType = AttrType(0xC026);
+
+/// Padding attribute of a given length, used to inflate binding requests to the probed size.
+struct Padding(usize);
+
+impl Setter for Padding {
+    fn add_to(&self, m: &mut Message) -> std::result::Result<(), stun::Error> {
+        m.add(ATTR_PATH_MTU_PADDING, &vec![0; self.0]);
+        Ok(())
+    }
+}
+
+impl Agent {
+    /// Discovers the largest datagram that can be sent over the selected candidate pair, in the
+    /// spirit of DPLPMTUD (RFC 8899) with STUN binding requests as probe packets.
+    ///
+    /// Binding requests padded to sizes between `min_size` and `max_size` are sent to the remote
+    /// peer, and a size is deemed usable once the peer answers it within `probe_timeout`. The
+    /// largest usable size is returned, rounded down to a multiple of 4 as STUN messages always
+    /// are. Fails with `ErrPathMtuProbeFailed` if not even `min_size` gets through.
+    pub async fn probe_path_mtu(
+        &self,
+        min_size: usize,
+        max_size: usize,
+        probe_timeout: Duration,
+    ) -> Result<usize> {
+        let pair = self
+            .internal
+            .agent_conn
+            .get_selected_pair()
+            .ok_or(Error::ErrNoCandidatePairs)?;
+
+        // Search in units of 4 bytes.
+        let (mut lo, mut hi) = (min_size.div_ceil(4), max_size / 4);
+        if lo > hi
+            || !self
+                .internal
+                .probe_path_mtu_size(&pair, lo * 4, probe_timeout)
+                .await?
+        {
+            return Err(Error::ErrPathMtuProbeFailed);
+        }
+
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if self
+                .internal
+                .probe_path_mtu_size(&pair, mid * 4, probe_timeout)
+                .await?
+            {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        log::debug!(
+            "[{}]: discovered path MTU of {}",
+            self.internal.get_name(),
+            lo * 4
+        );
+
+        Ok(lo * 4)
+    }
+}
+
+impl AgentInternal {
+    /// Returns whether a binding request of `size` bytes sent over `pair` was answered.
+    async fn probe_path_mtu_size(
+        &self,
+        pair: &Arc<CandidatePair>,
+        size: usize,
+        probe_timeout: Duration,
+    ) -> Result<bool> {
+        for _ in 0..PATH_MTU_PROBE_ATTEMPTS {
+            let msg = self.build_path_mtu_probe(&pair.local, size).await?;
+            let (answered_tx, answered_rx) = oneshot::channel();
+            {
+                let mut path_mtu_probes = self.path_mtu_probes.lock().await;
+                path_mtu_probes.insert(msg.transaction_id, answered_tx);
+            }
+
+            self.send_stun(&msg, &pair.local, &pair.remote).await;
+            let answered = tokio::time::timeout(probe_timeout, answered_rx).await;
+
+            self.path_mtu_probes
+                .lock()
+                .await
+                .remove(&msg.transaction_id);
+            if matches!(answered, Ok(Ok(()))) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    async fn build_path_mtu_probe(
+        &self,
+        local: &Arc<dyn Candidate + Send + Sync>,
+        size: usize,
+    ) -> Result<Message> {
+        let ufrag_pwd = self.ufrag_pwd.lock().await;
+        let username = ufrag_pwd.remote_ufrag.clone() + ":" + ufrag_pwd.local_ufrag.as_str();
+        let tie_breaker = self.tie_breaker.load(Ordering::SeqCst);
+        let is_controlling = self.is_controlling.load(Ordering::SeqCst);
+        let setters = |padding: usize| -> Vec<Box<dyn Setter>> {
+            vec![
+                Box::new(BINDING_REQUEST),
+                Box::new(TransactionId::new()),
+                Box::new(Username::new(ATTR_USERNAME, username.clone())),
+                if is_controlling {
+                    Box::new(AttrControlling(tie_breaker))
+                } else {
+                    Box::new(AttrControlled(tie_breaker))
+                },
+                Box::new(PriorityAttr(local.priority())),
+                Box::new(Padding(padding)),
+                Box::new(MessageIntegrity::new_short_term_integrity(
+                    ufrag_pwd.remote_pwd.clone(),
+                )),
+                Box::new(FINGERPRINT),
+            ]
+        };
+
+        // Build once without padding to learn how much is needed to reach `size`.
+        let mut msg = Message::new();
+        msg.build(&setters(0))?;
+        let padding = size.saturating_sub(msg.raw.len());
+        let mut msg = Message::new();
+        msg.build(&setters(padding))?;
+
+        Ok(msg)
+    }
+
+    /// Hands a success response to the path MTU probe waiting for it, if any. Returns whether
+    /// the response answered a probe.
+    pub(crate) async fn handle_path_mtu_probe_success(&self, id: TransactionId) -> bool {
+        let mut path_mtu_probes = self.path_mtu_probes.lock().await;
+        if let Some(answered_tx) = path_mtu_probes.remove(&id) {
+            let _ = answered_tx.send(());
+            true
+        } else {
+            false
+        }
+    }
+}
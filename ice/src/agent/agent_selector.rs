@@ -14,6 +14,7 @@ use tokio::time::{Duration, Instant};
 use crate::agent::agent_internal::*;
 use crate::candidate::*;
 use crate::control::*;
+use crate::error::*;
 use crate::priority::*;
 use crate::use_candidate::*;
 
@@ -159,6 +160,42 @@ impl AgentInternal {
         }
     }
 
+    /// Nominates the pair formed by `local` and `remote`, bypassing the automatic selection.
+    pub(crate) async fn force_nominate_pair(
+        &self,
+        local: &Arc<dyn Candidate + Send + Sync>,
+        remote: &Arc<dyn Candidate + Send + Sync>,
+    ) -> Result<()> {
+        if !self.nomination_override {
+            return Err(Error::ErrNominationOverrideDisabled);
+        }
+        if !self.is_controlling.load(Ordering::SeqCst) {
+            return Err(Error::ErrNominateNotControlling);
+        }
+        if self.agent_conn.get_selected_pair().is_some() {
+            return Err(Error::ErrCandidatePairAlreadySelected);
+        }
+
+        let p = self
+            .find_pair(local, remote)
+            .await
+            .ok_or(Error::ErrNoCandidatePairs)?;
+        log::trace!(
+            "[{}]: forcing nomination of ({}, {})",
+            self.get_name(),
+            p.local,
+            p.remote
+        );
+        p.nominated.store(true, Ordering::SeqCst);
+        {
+            let mut nominated_pair = self.nominated_pair.lock().await;
+            *nominated_pair = Some(p);
+        }
+        self.nominate_pair().await;
+
+        Ok(())
+    }
+
     pub(crate) async fn start(&self) {
         if self.is_controlling.load(Ordering::SeqCst) {
             ControllingSelector::start(self).await;
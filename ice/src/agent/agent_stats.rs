@@ -3,7 +3,7 @@ use std::sync::atomic::Ordering;
 use tokio::time::Instant;
 
 use crate::agent::agent_internal::AgentInternal;
-use crate::candidate::{CandidatePairState, CandidateType};
+use crate::candidate::{CandidatePairState, CandidateType, RelayProtocol};
 use crate::network_type::NetworkType;
 
 /// Contains ICE candidate pair statistics.
@@ -187,6 +187,9 @@ pub struct CandidateStats {
     /// for local candidates. Valid values for the TURN url protocol is one of udp, tcp, or tls.
     pub relay_protocol: String,
 
+    /// The address of the TURN server used by a local relay candidate.
+    pub relay_server_addr: String,
+
     /// It is true if the candidate has been deleted/freed. For host candidates, this means that any
     /// network resources (typically a socket) associated with the candidate have been released. For
     /// TURN candidates, this means the TURN allocation is no longer active.
@@ -207,6 +210,7 @@ impl Default for CandidateStats {
             priority: 0,
             url: String::new(),
             relay_protocol: String::new(),
+            relay_server_addr: String::new(),
             deleted: false,
         }
     }
@@ -246,7 +250,8 @@ impl AgentInternal {
                     candidate_type: c.candidate_type(),
                     priority: c.priority(),
                     // URL string
-                    relay_protocol: "udp".to_owned(),
+                    relay_protocol: relay_protocol_stat(c.relay_protocol()),
+                    relay_server_addr: c.relay_server_addr(),
                     // Deleted bool
                     ..CandidateStats::default()
                 };
@@ -271,7 +276,8 @@ impl AgentInternal {
                     candidate_type: c.candidate_type(),
                     priority: c.priority(),
                     // URL string
-                    relay_protocol: "udp".to_owned(),
+                    relay_protocol: relay_protocol_stat(c.relay_protocol()),
+                    relay_server_addr: c.relay_server_addr(),
                     // Deleted bool
                     ..CandidateStats::default()
                 };
@@ -281,3 +287,12 @@ impl AgentInternal {
         res
     }
 }
+
+/// relay_protocol_stat returns the relay protocol as reported in stats, empty for non-relay candidates.
+fn relay_protocol_stat(relay_protocol: RelayProtocol) -> String {
+    if relay_protocol == RelayProtocol::Unspecified {
+        String::new()
+    } else {
+        relay_protocol.to_string()
+    }
+}
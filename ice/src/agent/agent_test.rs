@@ -1545,6 +1545,36 @@ async fn test_init_ext_ip_mapping() -> Result<()> {
     Ok(())
 }
 
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_invalid_ipv6_flow_label() -> Result<()> {
+    for flow_label in [0, 0x80000] {
+        if let Err(err) = Agent::new(AgentConfig {
+            ipv6_flow_label: Some(flow_label),
+            ..Default::default()
+        })
+        .await
+        {
+            assert_eq!(
+                Error::Util(util::Error::ErrInvalidFlowLabel),
+                err,
+                "Unexpected error: {err}"
+            );
+        } else {
+            panic!("expected error, but got ok");
+        }
+    }
+
+    let a = Agent::new(AgentConfig {
+        ipv6_flow_label: Some(0x12345),
+        ..Default::default()
+    })
+    .await?;
+    a.close().await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_binding_request_timeout() -> Result<()> {
     const EXPECTED_REMOVAL_COUNT: usize = 2;
@@ -1,7 +1,7 @@
 use std::net::{IpAddr, Ipv4Addr};
 use std::result::Result;
 use std::str::FromStr;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, AtomicU64};
 
 use async_trait::async_trait;
 use util::vnet::chunk::Chunk;
@@ -11,7 +11,8 @@ use util::Conn;
 use waitgroup::WaitGroup;
 
 use super::*;
-use crate::candidate::candidate_base::unmarshal_candidate;
+use crate::candidate::candidate_base::{unmarshal_candidate, CandidateBaseConfig};
+use crate::candidate::candidate_host::CandidateHostConfig;
 
 pub(crate) struct MockConn;
 
@@ -534,6 +535,7 @@ async fn test_connectivity_vnet_full_cone_nats_on_both_ends() -> Result<(), Erro
         username: "user".to_owned(),
         password: "pass".to_owned(),
         proto: ProtoType::Udp,
+        ..Default::default()
     };
 
     // buildVNet with a Full-cone NATs both LANs
@@ -596,6 +598,7 @@ async fn test_connectivity_vnet_symmetric_nats_on_both_ends() -> Result<(), Erro
         username: "user".to_owned(),
         password: "pass".to_owned(),
         proto: ProtoType::Udp,
+        ..Default::default()
     };
 
     // buildVNet with a Symmetric NATs for both LANs
@@ -1014,3 +1017,318 @@ async fn test_write_use_valid_pair() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_nominate_pair_overrides_selection() -> Result<(), Error> {
+    let lan = Arc::new(Mutex::new(router::Router::new(router::RouterConfig {
+        cidr: format!("{VNET_LOCAL_IPA}/{VNET_LOCAL_SUBNET_MASK_A}"),
+        ..Default::default()
+    })?));
+
+    // The controlling agent has two host candidates to choose from
+    let net0 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.1".to_owned(), "192.168.0.3".to_owned()],
+        ..Default::default()
+    })));
+    let net1 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.2".to_owned()],
+        ..Default::default()
+    })));
+
+    connect_net2router(&net0, &lan).await?;
+    connect_net2router(&net1, &lan).await?;
+    start_router(&lan).await?;
+
+    let a_agent = Arc::new(
+        Agent::new(AgentConfig {
+            network_types: vec![NetworkType::Udp4],
+            multicast_dns_mode: MulticastDnsMode::Disabled,
+            host_acceptance_min_wait: Some(Duration::from_secs(3600)),
+            net: Some(net0),
+            nomination_override: true,
+            ..Default::default()
+        })
+        .await?,
+    );
+    let b_agent = Arc::new(
+        Agent::new(AgentConfig {
+            network_types: vec![NetworkType::Udp4],
+            multicast_dns_mode: MulticastDnsMode::Disabled,
+            net: Some(net1),
+            nomination_override: true,
+            ..Default::default()
+        })
+        .await?,
+    );
+
+    gather_and_exchange_candidates(&a_agent, &b_agent).await?;
+
+    let local = a_agent
+        .get_local_candidates()
+        .await?
+        .into_iter()
+        .find(|c| c.address() == "192.168.0.3")
+        .expect("missing host candidate");
+    let remote = b_agent
+        .get_local_candidates()
+        .await?
+        .pop()
+        .expect("missing host candidate");
+
+    assert_eq!(
+        b_agent.nominate_pair(&remote, &local).await,
+        Err(Error::ErrNominateNotControlling)
+    );
+
+    // Without the opt-in the selection can't be overridden
+    let c_agent = Agent::new(AgentConfig {
+        network_types: vec![NetworkType::Udp4],
+        multicast_dns_mode: MulticastDnsMode::Disabled,
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(
+        c_agent.nominate_pair(&local, &remote).await,
+        Err(Error::ErrNominationOverrideDisabled)
+    );
+    c_agent.close().await?;
+
+    let (a_ufrag, a_pwd) = a_agent.get_local_user_credentials().await;
+    let (b_ufrag, b_pwd) = b_agent.get_local_user_credentials().await;
+
+    let agent_b = Arc::clone(&b_agent);
+    tokio::spawn(async move {
+        let (_cancel_tx, cancel_rx) = mpsc::channel(1);
+        agent_b.accept(cancel_rx, a_ufrag, a_pwd).await
+    });
+    let agent_a = Arc::clone(&a_agent);
+    let dial = tokio::spawn(async move {
+        let (_cancel_tx, cancel_rx) = mpsc::channel(1);
+        agent_a.dial(cancel_rx, b_ufrag, b_pwd).await
+    });
+
+    // The checklist is only formed once connectivity checks have started
+    let mut nominated = false;
+    for _ in 0..100 {
+        if a_agent.nominate_pair(&local, &remote).await.is_ok() {
+            nominated = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(nominated, "candidate pair never became available");
+
+    tokio::time::timeout(Duration::from_secs(5), dial)
+        .await
+        .expect("dial timed out")
+        .expect("dial panicked")?;
+
+    let selected = a_agent
+        .get_selected_candidate_pair()
+        .expect("no selected candidate pair");
+    assert_eq!(selected.local.address(), "192.168.0.3");
+    assert_eq!(selected.remote.address(), "192.168.0.2");
+
+    assert_eq!(
+        a_agent.nominate_pair(&local, &remote).await,
+        Err(Error::ErrCandidatePairAlreadySelected)
+    );
+
+    a_agent.close().await?;
+    b_agent.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_probe_path_mtu_constrained_link() -> Result<(), Error> {
+    const LINK_MTU: usize = 1000;
+
+    let lan = Arc::new(Mutex::new(router::Router::new(router::RouterConfig {
+        cidr: format!("{VNET_LOCAL_IPA}/{VNET_LOCAL_SUBNET_MASK_A}"),
+        ..Default::default()
+    })?));
+    // Probes must not carry comprehension-required attributes the peer may not know.
+    let saw_padding = Arc::new(AtomicBool::new(false));
+    {
+        let saw_padding = Arc::clone(&saw_padding);
+        let r = lan.lock().await;
+        r.add_chunk_filter(Box::new(move |c: &(dyn Chunk + Send + Sync)| {
+            let data = c.user_data();
+            if is_message(&data) {
+                let mut m = Message::new();
+                if m.unmarshal_binary(&data).is_ok() && m.contains(ATTR_PADDING) {
+                    saw_padding.store(true, Ordering::SeqCst);
+                }
+            }
+            data.len() <= LINK_MTU
+        }))
+        .await;
+    }
+
+    let net0 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.1".to_owned()],
+        ..Default::default()
+    })));
+    let net1 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.2".to_owned()],
+        ..Default::default()
+    })));
+
+    connect_net2router(&net0, &lan).await?;
+    connect_net2router(&net1, &lan).await?;
+    start_router(&lan).await?;
+
+    let mut agents = vec![];
+    for net in [net0, net1] {
+        agents.push(Arc::new(
+            Agent::new(AgentConfig {
+                network_types: vec![NetworkType::Udp4],
+                multicast_dns_mode: MulticastDnsMode::Disabled,
+                net: Some(net),
+                ..Default::default()
+            })
+            .await?,
+        ));
+    }
+    let (a_agent, b_agent) = (&agents[0], &agents[1]);
+
+    assert_eq!(
+        a_agent
+            .probe_path_mtu(508, 1500, Duration::from_millis(100))
+            .await,
+        Err(Error::ErrNoCandidatePairs)
+    );
+
+    let (_a_conn, _b_conn) = connect_with_vnet(a_agent, b_agent).await?;
+
+    // Probing works from either side of the connection
+    for agent in &agents {
+        assert_eq!(
+            agent
+                .probe_path_mtu(508, 1500, Duration::from_millis(100))
+                .await?,
+            LINK_MTU
+        );
+    }
+    assert_eq!(
+        a_agent
+            .probe_path_mtu(1200, 1500, Duration::from_millis(100))
+            .await,
+        Err(Error::ErrPathMtuProbeFailed)
+    );
+    assert!(!saw_padding.load(Ordering::SeqCst));
+
+    a_agent.close().await?;
+    b_agent.close().await?;
+
+    Ok(())
+}
+
+// Counts the connectivity checks an agent sends over `window` to a peer that never answers.
+async fn count_checks_sent(check_interval: Duration, window: Duration) -> Result<usize, Error> {
+    let wan = router::Router::new(router::RouterConfig {
+        cidr: "0.0.0.0/0".to_owned(),
+        ..Default::default()
+    })?;
+
+    let checks_sent = Arc::new(AtomicUsize::new(0));
+    let checks_sent2 = Arc::clone(&checks_sent);
+    wan.add_chunk_filter(Box::new(move |c: &(dyn Chunk + Send + Sync)| -> bool {
+        if c.destination_addr().ip().to_string() == "192.168.0.2" {
+            checks_sent2.fetch_add(1, Ordering::SeqCst);
+        }
+        true
+    }))
+    .await;
+    let wan = Arc::new(Mutex::new(wan));
+
+    let net0 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.1".to_owned()],
+        ..Default::default()
+    })));
+    let net1 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.2".to_owned()],
+        ..Default::default()
+    })));
+
+    connect_net2router(&net0, &wan).await?;
+    connect_net2router(&net1, &wan).await?;
+    start_router(&wan).await?;
+
+    let agent = Arc::new(
+        Agent::new(AgentConfig {
+            network_types: vec![NetworkType::Udp4],
+            multicast_dns_mode: MulticastDnsMode::Disabled,
+            net: Some(Arc::clone(&net0)),
+            check_interval,
+            max_binding_requests: Some(u16::MAX),
+            ..Default::default()
+        })
+        .await?,
+    );
+
+    let (gathered_tx, mut gathered_rx) = mpsc::channel::<()>(1);
+    let gathered_tx = Arc::new(Mutex::new(Some(gathered_tx)));
+    agent.on_candidate(Box::new(
+        move |c: Option<Arc<dyn Candidate + Send + Sync>>| {
+            let gathered_tx = Arc::clone(&gathered_tx);
+            Box::pin(async move {
+                if c.is_none() {
+                    gathered_tx.lock().await.take();
+                }
+            })
+        },
+    ));
+    agent.gather_candidates()?;
+    let _ = gathered_rx.recv().await;
+
+    // Nothing listens on the remote candidate, so the pair stays in progress
+    let remote: Arc<dyn Candidate + Send + Sync> = Arc::new(
+        CandidateHostConfig {
+            base_config: CandidateBaseConfig {
+                network: "udp".to_owned(),
+                address: "192.168.0.2".to_owned(),
+                port: 5000,
+                component: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .new_candidate_host()?,
+    );
+    agent.add_remote_candidate(&remote)?;
+
+    let (_cancel_tx, cancel_rx) = mpsc::channel(1);
+    let dialer = Arc::clone(&agent);
+    tokio::spawn(async move {
+        let _ = dialer
+            .dial(cancel_rx, "remoteufrag".to_owned(), "remotepwd".to_owned())
+            .await;
+    });
+
+    tokio::time::sleep(window).await;
+    let sent = checks_sent.load(Ordering::SeqCst);
+
+    agent.close().await?;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(sent)
+}
+
+// Runs on paused time, so the first check goes out right away and then one every Ta
+#[tokio::test(start_paused = true)]
+async fn test_check_interval_paces_connectivity_checks() -> Result<(), Error> {
+    // Ends between two ticks so the last check doesn't race the end of the window
+    let window = Duration::from_millis(990);
+    let fast = count_checks_sent(Duration::from_millis(20), window).await?;
+    let slow = count_checks_sent(Duration::from_millis(200), window).await?;
+
+    assert_eq!(fast, 50, "a 20ms Ta should send a check every 20ms");
+    assert_eq!(slow, 5, "a 200ms Ta should send a check every 200ms");
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ pub(crate) mod agent_vnet_test;
 pub mod agent_config;
 pub mod agent_gather;
 pub(crate) mod agent_internal;
+pub mod agent_path_mtu;
 pub mod agent_selector;
 pub mod agent_stats;
 pub mod agent_transport;
@@ -32,8 +33,9 @@ use stun::fingerprint::*;
 use stun::integrity::*;
 use stun::message::*;
 use stun::xoraddr::*;
-use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
 use tokio::time::{Duration, Instant};
+use util::conn::conn_flow_label::FLOW_LABEL_MAX;
 use util::vnet::net::*;
 use util::Buffer;
 
@@ -131,6 +133,15 @@ impl Agent {
             return Err(Error::ErrInvalidMulticastDnshostName);
         }
 
+        if let Some(flow_label) = config.ipv6_flow_label {
+            if cfg!(not(target_os = "linux")) {
+                return Err(util::Error::ErrFlowLabelUnsupported.into());
+            }
+            if flow_label == 0 || flow_label > FLOW_LABEL_MAX {
+                return Err(util::Error::ErrInvalidFlowLabel.into());
+            }
+        }
+
         let mdns_mode = config.multicast_dns_mode;
 
         let mdns_conn =
@@ -195,6 +206,7 @@ impl Agent {
             Arc::new(Net::new(None))
         };
 
+        let gathering_state = Arc::clone(&ai.gathering_state);
         let agent = Self {
             udp_network: config.udp_network,
             internal: Arc::new(ai),
@@ -205,7 +217,7 @@ impl Agent {
             mdns_conn,
             net,
             ext_ip_mapper: Arc::new(ext_ip_mapper),
-            gathering_state: Arc::new(AtomicU8::new(0)), //GatheringState::New,
+            gathering_state,
             candidate_types,
             urls: config.urls.clone(),
             network_types: config.network_types.clone(),
@@ -355,6 +367,30 @@ impl Agent {
         self.internal.agent_conn.get_selected_pair()
     }
 
+    /// Sends a consent check (RFC 7675) on the selected pair right away instead of waiting for
+    /// the next keepalive, so a pair that went Disconnected can recover without a restart.
+    pub async fn check_selected_pair(&self) -> Result<()> {
+        let pair = self
+            .get_selected_candidate_pair()
+            .ok_or(Error::ErrNoCandidatePairs)?;
+        self.internal
+            .ping_candidate(&pair.local, &pair.remote)
+            .await;
+        Ok(())
+    }
+
+    /// Forces the controlling agent to nominate the pair formed by `local` and `remote`
+    /// instead of the one picked by the automatic selection. Intended for deterministic tests,
+    /// it must be enabled with `AgentConfig::nomination_override`; raise
+    /// `host_acceptance_min_wait` and friends so the agent doesn't nominate a pair first.
+    pub async fn nominate_pair(
+        &self,
+        local: &Arc<dyn Candidate + Send + Sync>,
+        remote: &Arc<dyn Candidate + Send + Sync>,
+    ) -> Result<()> {
+        self.internal.force_nominate_pair(local, remote).await
+    }
+
     /// Sets the credentials of the remote agent.
     pub async fn set_remote_credentials(
         &self,
@@ -450,6 +486,9 @@ impl Agent {
 
         //TODO: a.gatherCandidateCancel = cancel
 
+        self.gathering_state
+            .store(GatheringState::Gathering as u8, Ordering::SeqCst);
+
         let params = GatherCandidatesInternalParams {
             udp_network: self.udp_network.clone(),
             candidate_types: self.candidate_types.clone(),
@@ -472,6 +511,27 @@ impl Agent {
         Ok(())
     }
 
+    /// Completes gathering with the candidates gathered so far, without waiting for the pending
+    /// STUN and TURN probes. Candidates they find afterwards are discarded. Does nothing unless
+    /// gathering is in progress.
+    pub async fn stop_gathering(&self) {
+        if self
+            .gathering_state
+            .compare_exchange(
+                GatheringState::Gathering as u8,
+                GatheringState::Complete as u8,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            )
+            .is_ok()
+        {
+            let cand_tx = self.internal.chan_candidate_tx.lock().await;
+            if let Some(tx) = &*cand_tx {
+                let _ = tx.send(None).await;
+            }
+        }
+    }
+
     /// Returns a list of candidate pair stats.
     pub async fn get_candidate_pairs_stats(&self) -> Vec<CandidatePairStats> {
         self.internal.get_candidate_pairs_stats().await
@@ -26,6 +26,16 @@ pub struct CandidateBaseConfig {
     pub component: u16,
     pub priority: u32,
     pub foundation: String,
+    /// The `generation` extension of the candidate line, if any.
+    pub generation: Option<u32>,
+    /// The `ufrag` extension of the candidate line, if any.
+    pub ufrag: Option<String>,
+    /// The `network-id` extension of the candidate line, if any.
+    pub network_id: Option<u32>,
+    /// The `network-cost` extension of the candidate line, if any. It lowers
+    /// the priority computed for a local candidate, while a candidate parsed
+    /// from a remote line keeps the priority it advertises.
+    pub network_cost: Option<u16>,
     pub conn: Option<Arc<dyn util::Conn + Send + Sync>>,
     pub initialized_ch: Option<broadcast::Receiver<()>>,
 }
@@ -52,10 +62,17 @@ pub struct CandidateBase {
     pub(crate) foundation_override: String,
     pub(crate) priority_override: u32,
 
+    pub(crate) generation: Option<u32>,
+    pub(crate) ufrag: Option<String>,
+    pub(crate) network_id: Option<u32>,
+    pub(crate) network_cost: Option<u16>,
+
     //CandidateHost
     pub(crate) network: String,
     //CandidateRelay
     pub(crate) relay_client: Option<Arc<turn::client::Client>>,
+    pub(crate) relay_protocol: RelayProtocol,
+    pub(crate) relay_server_addr: String,
 }
 
 impl Default for CandidateBase {
@@ -81,8 +98,14 @@ impl Default for CandidateBase {
 
             foundation_override: String::new(),
             priority_override: 0,
+            generation: None,
+            ufrag: None,
+            network_id: None,
+            network_cost: None,
             network: String::new(),
             relay_client: None,
+            relay_protocol: RelayProtocol::default(),
+            relay_server_addr: String::new(),
         }
     }
 }
@@ -202,6 +225,30 @@ impl Candidate for CandidateBase {
         self.tcp_type
     }
 
+    fn relay_protocol(&self) -> RelayProtocol {
+        self.relay_protocol
+    }
+
+    fn relay_server_addr(&self) -> String {
+        self.relay_server_addr.clone()
+    }
+
+    fn generation(&self) -> Option<u32> {
+        self.generation
+    }
+
+    fn ufrag(&self) -> Option<&str> {
+        self.ufrag.as_deref()
+    }
+
+    fn network_id(&self) -> Option<u32> {
+        self.network_id
+    }
+
+    fn network_cost(&self) -> Option<u16> {
+        self.network_cost
+    }
+
     /// Returns the string representation of the ICECandidate.
     fn marshal(&self) -> String {
         let mut val = format!(
@@ -227,6 +274,26 @@ impl Candidate for CandidateBase {
             .as_str();
         }
 
+        if self.relay_protocol != RelayProtocol::Unspecified {
+            val += format!(" relay-protocol {}", self.relay_protocol).as_str();
+        }
+
+        if let Some(generation) = self.generation {
+            val += format!(" generation {generation}").as_str();
+        }
+
+        if let Some(ufrag) = &self.ufrag {
+            val += format!(" ufrag {ufrag}").as_str();
+        }
+
+        if let Some(network_id) = self.network_id {
+            val += format!(" network-id {network_id}").as_str();
+        }
+
+        if let Some(network_cost) = self.network_cost {
+            val += format!(" network-cost {network_cost}").as_str();
+        }
+
         val
     }
 
@@ -359,7 +426,7 @@ impl CandidateBase {
             // other-pref is the preference for the particular IP address from which
             // the candidate was obtained.  When there is only a single IP address,
             // this value SHOULD be set to the maximum allowed value (8191).
-            let other_pref: u16 = 8191;
+            let other_pref: u16 = 8191 - self.network_cost_penalty().min(8191);
 
             let direction_pref: u16 = match self.candidate_type() {
                 CandidateType::Host | CandidateType::Relay => match self.tcp_type() {
@@ -381,9 +448,18 @@ impl CandidateBase {
 
             (1 << 13) * direction_pref + other_pref
         } else {
-            DEFAULT_LOCAL_PREFERENCE
+            DEFAULT_LOCAL_PREFERENCE - self.network_cost_penalty()
         }
     }
+
+    /// Returns how much the local preference is lowered because of the
+    /// advertised network cost, so that e.g. cellular candidates rank
+    /// below wifi or ethernet ones on the same host. It only applies to
+    /// locally computed priorities: a remote candidate already folded its
+    /// network cost into the priority it advertises, which takes precedence.
+    fn network_cost_penalty(&self) -> u16 {
+        self.network_cost.unwrap_or(0).min(MAX_NETWORK_COST)
+    }
 }
 
 /// Creates a Candidate from its string representation.
@@ -420,6 +496,11 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
     let mut rel_addr = String::new();
     let mut rel_port = 0;
     let mut tcp_type = TcpType::Unspecified;
+    let mut relay_protocol = RelayProtocol::Unspecified;
+    let mut generation = None;
+    let mut ufrag = None;
+    let mut network_id = None;
+    let mut network_cost = None;
 
     if split.len() > 8 {
         let split2 = &split[8..];
@@ -447,6 +528,25 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
 
             tcp_type = TcpType::from(split2[1]);
         }
+
+        // relay-protocol is an extension attribute following the related address
+        if let Some(pos) = split2.iter().position(|s| *s == "relay-protocol") {
+            if let Some(protocol) = split2.get(pos + 1) {
+                relay_protocol = RelayProtocol::from(*protocol);
+            }
+        }
+
+        // generation, ufrag, network-id and network-cost are extensions added by browsers.
+        // They are informational, so a malformed value drops the extension, not the candidate.
+        for (key, value) in split2.iter().zip(split2.iter().skip(1)) {
+            match *key {
+                "generation" => generation = parse_extension(key, value),
+                "ufrag" => ufrag = Some((*value).to_owned()),
+                "network-id" => network_id = parse_extension(key, value),
+                "network-cost" => network_cost = parse_extension(key, value),
+                _ => {}
+            }
+        }
     }
 
     match typ {
@@ -459,6 +559,10 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
                     component,
                     priority,
                     foundation,
+                    generation,
+                    ufrag,
+                    network_id,
+                    network_cost,
                     ..CandidateBaseConfig::default()
                 },
                 tcp_type,
@@ -474,6 +578,10 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
                     component,
                     priority,
                     foundation,
+                    generation,
+                    ufrag,
+                    network_id,
+                    network_cost,
                     ..CandidateBaseConfig::default()
                 },
                 rel_addr,
@@ -490,6 +598,10 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
                     component,
                     priority,
                     foundation,
+                    generation,
+                    ufrag,
+                    network_id,
+                    network_cost,
                     ..CandidateBaseConfig::default()
                 },
                 rel_addr,
@@ -507,10 +619,15 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
                     component,
                     priority,
                     foundation,
+                    generation,
+                    ufrag,
+                    network_id,
+                    network_cost,
                     ..CandidateBaseConfig::default()
                 },
                 rel_addr,
                 rel_port,
+                relay_protocol,
                 ..CandidateRelayConfig::default()
             };
             config.new_candidate_relay()
@@ -522,3 +639,14 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
         ))),
     }
 }
+
+/// Parses the value of a candidate extension, ignoring it if it's malformed.
+fn parse_extension<T: std::str::FromStr>(key: &str, value: &str) -> Option<T> {
+    match value.parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            log::debug!("ignoring malformed candidate extension {key} {value}");
+            None
+        }
+    }
+}
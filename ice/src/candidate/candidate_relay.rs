@@ -16,6 +16,8 @@ pub struct CandidateRelayConfig {
 
     pub rel_addr: String,
     pub rel_port: u16,
+    pub relay_protocol: RelayProtocol,
+    pub relay_server_addr: String,
     pub relay_client: Option<Arc<turn::client::Client>>,
 }
 
@@ -43,12 +45,18 @@ impl CandidateRelayConfig {
             component: AtomicU16::new(self.base_config.component),
             foundation_override: self.base_config.foundation,
             priority_override: self.base_config.priority,
+            generation: self.base_config.generation,
+            ufrag: self.base_config.ufrag,
+            network_id: self.base_config.network_id,
+            network_cost: self.base_config.network_cost,
             related_address: Some(CandidateRelatedAddress {
                 address: self.rel_addr,
                 port: self.rel_port,
             }),
             conn: self.base_config.conn,
             relay_client: self.relay_client.clone(),
+            relay_protocol: self.relay_protocol,
+            relay_server_addr: self.relay_server_addr,
             ..CandidateBase::default()
         };
 
@@ -74,6 +74,7 @@ async fn test_relay_only_connection() -> Result<(), Error> {
             password: "password".to_owned(),
             port: server_port,
             proto: ProtoType::Udp,
+            ..Default::default()
         }],
         candidate_types: vec![CandidateType::Relay],
         ..Default::default()
@@ -92,6 +93,7 @@ async fn test_relay_only_connection() -> Result<(), Error> {
             password: "password".to_owned(),
             port: server_port,
             proto: ProtoType::Udp,
+            ..Default::default()
         }],
         candidate_types: vec![CandidateType::Relay],
         ..Default::default()
@@ -1,5 +1,7 @@
 use std::time::UNIX_EPOCH;
 
+use super::candidate_host::CandidateHostConfig;
+use super::candidate_relay::CandidateRelayConfig;
 use super::*;
 
 #[test]
@@ -353,6 +355,22 @@ fn test_candidate_marshal() -> Result<()> {
             }),
             "848194626 1 udp 16777215 50.0.0.1 5000 typ relay raddr 192.168.0.1 rport 5001",
         ),
+        (
+            Some(CandidateBase{
+                    network_type:   AtomicU8::new(NetworkType::Udp4 as u8),
+                    candidate_type:  CandidateType::Relay,
+                    address:        "50.0.0.1".to_owned(),
+                    port:           5000,
+                    related_address: Some(
+                        CandidateRelatedAddress{
+                            address: "192.168.0.1".to_owned(),
+                            port:5001}
+                    ),
+                    relay_protocol: RelayProtocol::Tcp,
+                ..Default::default()
+            }),
+            "848194626 1 udp 16777215 50.0.0.1 5000 typ relay raddr 192.168.0.1 rport 5001 relay-protocol tcp",
+        ),
         (
             Some(CandidateBase{
                     network_type:   AtomicU8::new(NetworkType::Tcp4 as u8),
@@ -409,3 +427,101 @@ fn test_candidate_marshal() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_candidate_relay_protocol_marshal() -> Result<()> {
+    let candidate = CandidateRelayConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "50.0.0.1".to_owned(),
+            port: 5000,
+            component: COMPONENT_RTP,
+            ..Default::default()
+        },
+        rel_addr: "192.168.0.1".to_owned(),
+        rel_port: 5001,
+        relay_protocol: RelayProtocol::Tcp,
+        relay_server_addr: "10.0.0.1:3478".to_owned(),
+        ..Default::default()
+    }
+    .new_candidate_relay()?;
+
+    assert_eq!(candidate.relay_protocol(), RelayProtocol::Tcp);
+    assert_eq!(candidate.relay_server_addr(), "10.0.0.1:3478");
+
+    let marshaled = candidate.marshal();
+    assert!(
+        marshaled.ends_with(" relay-protocol tcp"),
+        "unexpected candidate string {marshaled}"
+    );
+
+    let actual = unmarshal_candidate(&marshaled)?;
+    assert_eq!(actual.relay_protocol(), RelayProtocol::Tcp);
+    assert_eq!(actual.marshal(), marshaled);
+
+    Ok(())
+}
+
+#[test]
+fn test_candidate_browser_extensions_marshal() -> Result<()> {
+    let marshaled = "842163049 1 udp 1677729535 203.0.113.5 56143 typ srflx raddr 192.168.1.5 rport 56143 generation 0 network-id 1 network-cost 10";
+
+    let candidate = unmarshal_candidate(marshaled)?;
+    assert_eq!(candidate.generation(), Some(0));
+    assert_eq!(candidate.network_id(), Some(1));
+    assert_eq!(candidate.network_cost(), Some(10));
+    // The advertised priority is kept as is, the network cost only weighs on
+    // priorities computed locally.
+    assert_eq!(candidate.priority(), 1677729535);
+    assert_eq!(candidate.marshal(), marshaled);
+
+    let cheap = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "192.168.1.5".to_owned(),
+            component: COMPONENT_RTP,
+            network_cost: Some(10),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .new_candidate_host()?;
+    let expensive = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "10.0.0.5".to_owned(),
+            component: COMPONENT_RTP,
+            network_cost: Some(900),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .new_candidate_host()?;
+    assert!(cheap.priority() > expensive.priority());
+
+    Ok(())
+}
+
+#[test]
+fn test_candidate_browser_extensions_ufrag_and_malformed() -> Result<()> {
+    let marshaled = "1052353102 1 udp 2122260223 192.168.1.5 50735 typ host generation 0 ufrag EsAw network-id 1 network-cost 10";
+
+    let candidate = unmarshal_candidate(marshaled)?;
+    assert_eq!(candidate.ufrag(), Some("EsAw"));
+    assert_eq!(candidate.marshal(), marshaled);
+
+    // Malformed extension values are dropped without failing the candidate.
+    let candidate = unmarshal_candidate(
+        "1052353102 1 udp 2122260223 192.168.1.5 50735 typ host generation x ufrag EsAw network-id -1 network-cost 1e3",
+    )?;
+    assert_eq!(candidate.generation(), None);
+    assert_eq!(candidate.ufrag(), Some("EsAw"));
+    assert_eq!(candidate.network_id(), None);
+    assert_eq!(candidate.network_cost(), None);
+    assert_eq!(
+        candidate.marshal(),
+        "1052353102 1 udp 2122260223 192.168.1.5 50735 typ host ufrag EsAw"
+    );
+
+    Ok(())
+}
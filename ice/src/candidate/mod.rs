@@ -30,6 +30,8 @@ use crate::tcp_type::*;
 
 pub(crate) const RECEIVE_MTU: usize = 8192;
 pub(crate) const DEFAULT_LOCAL_PREFERENCE: u16 = 65535;
+/// The highest network cost browsers advertise, used for unknown/cellular networks.
+pub(crate) const MAX_NETWORK_COST: u16 = 999;
 
 /// Indicates that the candidate is used for RTP.
 pub(crate) const COMPONENT_RTP: u16 = 1;
@@ -73,6 +75,37 @@ pub trait Candidate: fmt::Display {
     fn candidate_type(&self) -> CandidateType;
     fn tcp_type(&self) -> TcpType;
 
+    /// The transport used to reach the TURN server of a relay candidate.
+    fn relay_protocol(&self) -> RelayProtocol {
+        RelayProtocol::Unspecified
+    }
+    /// The address of the TURN server of a relay candidate,
+    /// empty if the candidate isn't a local relay candidate.
+    fn relay_server_addr(&self) -> String {
+        String::new()
+    }
+
+    /// The `generation` extension of the candidate line, used by browsers
+    /// to tell candidates of successive ICE restarts apart.
+    fn generation(&self) -> Option<u32> {
+        None
+    }
+    /// The `ufrag` extension, the ICE username fragment the candidate
+    /// belongs to.
+    fn ufrag(&self) -> Option<&str> {
+        None
+    }
+    /// The `network-id` extension, identifying the network interface
+    /// the candidate was gathered on.
+    fn network_id(&self) -> Option<u32> {
+        None
+    }
+    /// The `network-cost` extension, a higher value meaning a more
+    /// expensive network (e.g. cellular).
+    fn network_cost(&self) -> Option<u16> {
+        None
+    }
+
     fn marshal(&self) -> String;
 
     fn addr(&self) -> SocketAddr;
@@ -140,6 +173,47 @@ impl CandidateType {
     }
 }
 
+/// Represents the transport used between a relay candidate and its TURN server.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum RelayProtocol {
+    /// The default value. Non-relay candidates do not have a relay protocol.
+    #[default]
+    #[serde(rename = "unspecified")]
+    Unspecified,
+    /// TURN over UDP.
+    #[serde(rename = "udp")]
+    Udp,
+    /// TURN over TCP.
+    #[serde(rename = "tcp")]
+    Tcp,
+    /// TURN over TLS.
+    #[serde(rename = "tls")]
+    Tls,
+}
+
+impl From<&str> for RelayProtocol {
+    fn from(raw: &str) -> Self {
+        match raw {
+            "udp" => Self::Udp,
+            "tcp" => Self::Tcp,
+            "tls" => Self::Tls,
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+impl fmt::Display for RelayProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match *self {
+            Self::Udp => "udp",
+            Self::Tcp => "tcp",
+            Self::Tls => "tls",
+            Self::Unspecified => "unspecified",
+        };
+        write!(f, "{s}")
+    }
+}
+
 pub(crate) fn contains_candidate_type(
     candidate_type: CandidateType,
     candidate_type_list: &[CandidateType],
@@ -55,6 +55,22 @@ pub enum Error {
     #[error("no candidate pairs available")]
     ErrNoCandidatePairs,
 
+    /// Indicates a candidate pair was nominated without enabling the nomination override.
+    #[error("overriding the nominated candidate pair is not enabled in the agent config")]
+    ErrNominationOverrideDisabled,
+
+    /// Indicates a candidate pair was nominated by the controlled agent.
+    #[error("only the controlling agent can nominate a candidate pair")]
+    ErrNominateNotControlling,
+
+    /// Indicates a candidate pair was nominated after one was already selected.
+    #[error("a candidate pair has already been selected")]
+    ErrCandidatePairAlreadySelected,
+
+    /// Indicates not even the smallest path MTU probe was answered.
+    #[error("path MTU probe failed")]
+    ErrPathMtuProbeFailed,
+
     /// Indicates agent connection was canceled by the caller.
     #[error("connecting canceled by caller")]
     ErrCanceledByCaller,
@@ -168,6 +168,9 @@ pub struct CandidateStats {
     // the TURN url protocol is one of udp, tcp, or tls.
     pub relay_protocol: String,
 
+    // relay_server_addr is the address of the TURN server used by a local relay candidate.
+    pub relay_server_addr: String,
+
     // deleted is true if the candidate has been deleted/freed. For host candidates,
     // this means that any network resources (typically a socket) associated with the
     // candidate have been released. For TURN candidates, this means the TURN allocation
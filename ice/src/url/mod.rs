@@ -4,6 +4,7 @@ mod url_test;
 use std::borrow::Cow;
 use std::convert::From;
 use std::fmt;
+use std::net::SocketAddr;
 
 use crate::error::*;
 
@@ -111,6 +112,9 @@ pub struct Url {
     pub username: String,
     pub password: String,
     pub proto: ProtoType,
+    /// An already-resolved address of the server. When set, it is used as is and `host` is never
+    /// looked up, for environments without DNS or to pin the server address.
+    pub resolved_addr: Option<SocketAddr>,
 }
 
 impl fmt::Display for Url {
@@ -233,6 +237,7 @@ impl Url {
             username: "".to_owned(),
             password: "".to_owned(),
             proto,
+            resolved_addr: None,
         })
     }
 
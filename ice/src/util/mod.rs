@@ -144,8 +144,27 @@ pub async fn listen_udp_in_port_range(
     port_min: u16,
     laddr: SocketAddr,
 ) -> Result<Arc<dyn Conn + Send + Sync>> {
+    listen_udp_in_port_range_with_flow_label(vnet, port_max, port_min, laddr, None).await
+}
+
+/// listen_udp_in_port_range_with_flow_label is listen_udp_in_port_range, with the IPv6
+/// datagrams sent on the conn marked with `ipv6_flow_label` if set.
+pub async fn listen_udp_in_port_range_with_flow_label(
+    vnet: &Arc<Net>,
+    port_max: u16,
+    port_min: u16,
+    laddr: SocketAddr,
+    ipv6_flow_label: Option<u32>,
+) -> Result<Arc<dyn Conn + Send + Sync>> {
+    let bind = |laddr: SocketAddr| async move {
+        match ipv6_flow_label {
+            Some(flow_label) => vnet.bind_with_flow_label(laddr, flow_label).await,
+            None => vnet.bind(laddr).await,
+        }
+    };
+
     if laddr.port() != 0 || (port_min == 0 && port_max == 0) {
-        return Ok(vnet.bind(laddr).await?);
+        return Ok(bind(laddr).await?);
     }
     let i = if port_min == 0 { 1 } else { port_min };
     let j = if port_max == 0 { 0xFFFF } else { port_max };
@@ -157,7 +176,7 @@ pub async fn listen_udp_in_port_range(
     let mut port_current = port_start;
     loop {
         let laddr = SocketAddr::new(laddr.ip(), port_current);
-        match vnet.bind(laddr).await {
+        match bind(laddr).await {
             Ok(c) => return Ok(c),
             Err(err) => log::debug!("failed to listen {}: {}", laddr, err),
         };
@@ -0,0 +1,173 @@
+use std::io::Cursor;
+use std::time::Duration;
+
+use rtcp::payload_feedbacks::picture_loss_indication::PictureLossIndication;
+
+use super::*;
+use crate::mock::mock_stream::MockStream;
+use crate::test::timeout_or_fail;
+
+/// SharedBuffer is an in-memory sink the test can look into while the Dumper writes to it.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedBuffer {
+    /// wait_for_len waits until at least `len` bytes have been written.
+    async fn wait_for_len(&self, len: usize) -> Vec<u8> {
+        timeout_or_fail(Duration::from_secs(1), async {
+            loop {
+                {
+                    let buf = self.0.lock();
+                    if buf.len() >= len {
+                        return buf.clone();
+                    }
+                }
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+    }
+}
+
+fn rtp_packet(sequence_number: u16) -> rtp::packet::Packet {
+    rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            ssrc: 1,
+            sequence_number,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0xde, 0xad]),
+    }
+}
+
+#[tokio::test]
+async fn test_dumper_length_prefixed() -> Result<()> {
+    let sink = SharedBuffer::default();
+    let icpr = Dumper::builder(sink.clone()).build("")?;
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    stream.write_rtp(&rtp_packet(1)).await?;
+    stream.receive_rtp(rtp_packet(2)).await;
+    timeout_or_fail(Duration::from_millis(10), stream.read_rtp())
+        .await
+        .expect("A packet")?;
+    let pli: Vec<Box<dyn rtcp::packet::Packet + Send + Sync>> =
+        vec![Box::new(PictureLossIndication {
+            sender_ssrc: 2,
+            media_ssrc: 1,
+        })];
+    stream.write_rtcp(&pli).await?;
+
+    let rtp_len = rtp_packet(1).marshal()?.len();
+    let rtcp = rtcp::packet::marshal(&pli)?;
+    let buf = sink
+        .wait_for_len(3 * RECORD_HEADER_SIZE + 2 * rtp_len + rtcp.len())
+        .await;
+
+    let mut reader = Cursor::new(buf);
+    let mut records = vec![];
+    while let Some(record) = DumpRecord::read_from(&mut reader)? {
+        assert!(record.timestamp > UNIX_EPOCH);
+        records.push(record);
+    }
+    let records: Vec<_> = records
+        .into_iter()
+        .map(|r| (r.direction, r.packet_type, r.data))
+        .collect();
+    assert_eq!(
+        records,
+        vec![
+            (
+                TapDirection::Outbound,
+                DumpPacketType::Rtp,
+                rtp_packet(1).marshal()?
+            ),
+            (
+                TapDirection::Inbound,
+                DumpPacketType::Rtp,
+                rtp_packet(2).marshal()?
+            ),
+            (TapDirection::Outbound, DumpPacketType::Rtcp, rtcp),
+        ]
+    );
+
+    stream.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dumper_pcap() -> Result<()> {
+    let sink = SharedBuffer::default();
+    let icpr = Dumper::builder(sink.clone())
+        .with_format(DumpFormat::Pcap)
+        .build("")?;
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    stream.write_rtp(&rtp_packet(1)).await?;
+
+    let rtp = rtp_packet(1).marshal()?;
+    let buf = sink
+        .wait_for_len(24 + 16 + IPV4_HEADER_SIZE + UDP_HEADER_SIZE + rtp.len())
+        .await;
+
+    assert_eq!(&buf[..4], &[0xd4, 0xc3, 0xb2, 0xa1]);
+    assert_eq!(&buf[20..24], &PCAP_LINKTYPE_RAW.to_le_bytes());
+
+    let record = &buf[24..];
+    let ip_len = IPV4_HEADER_SIZE + UDP_HEADER_SIZE + rtp.len();
+    assert_eq!(&record[8..12], &(ip_len as u32).to_le_bytes());
+    let ip = &record[16..16 + IPV4_HEADER_SIZE];
+    assert_eq!(ipv4_checksum(ip), 0, "the header checksum must verify");
+    assert_eq!(&ip[12..16], &PCAP_LOCAL_ADDR);
+    assert_eq!(&ip[16..20], &PCAP_REMOTE_ADDR);
+    assert_eq!(&record[16 + IPV4_HEADER_SIZE + UDP_HEADER_SIZE..], &rtp[..]);
+
+    stream.close().await?;
+
+    Ok(())
+}
+
+#[test]
+fn test_dump_record_truncated() {
+    let record = DumpRecord {
+        timestamp: UNIX_EPOCH + Duration::from_micros(1_500_000),
+        direction: TapDirection::Inbound,
+        packet_type: DumpPacketType::Rtcp,
+        data: Bytes::from_static(&[1, 2, 3, 4]),
+    };
+    let buf = record.marshal_length_prefixed();
+
+    let mut reader = Cursor::new(&buf);
+    assert_eq!(DumpRecord::read_from(&mut reader), Ok(Some(record)));
+    assert_eq!(DumpRecord::read_from(&mut reader), Ok(None));
+
+    let mut reader = Cursor::new(&buf[..buf.len() - 1]);
+    assert_eq!(DumpRecord::read_from(&mut reader), Err(Error::ErrIoEOF));
+}
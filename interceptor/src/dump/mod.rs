@@ -0,0 +1,478 @@
+#[cfg(test)]
+mod dump_test;
+
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+use util::sync::Mutex;
+use util::Marshal;
+
+use super::*;
+use crate::error::{Error, Result};
+use crate::tap::TapDirection;
+
+/// DumpFormat selects how the Dumper writes packets to its sink.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Every packet is written as one record, all fields big endian:
+    /// the timestamp in microseconds since the UNIX epoch (8 bytes), the direction
+    /// (1 byte, 0 inbound, 1 outbound), the packet type (1 byte, 0 RTP, 1 RTCP), the length
+    /// of the packet (4 bytes) and the marshaled packet. See [`DumpRecord::read_from`].
+    LengthPrefixed,
+    /// A PCAP capture. Packets are wrapped in synthesized IPv4/UDP headers between
+    /// 10.0.0.1:5004 (local) and 10.0.0.2:5004 (remote), so tools like Wireshark can decode
+    /// them as RTP and RTCP.
+    Pcap,
+}
+
+/// DumpPacketType tells RTP and RTCP records apart.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DumpPacketType {
+    Rtp,
+    Rtcp,
+}
+
+/// DumpRecord is a single packet seen by the Dumper.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpRecord {
+    pub timestamp: SystemTime,
+    pub direction: TapDirection,
+    pub packet_type: DumpPacketType,
+    /// The marshaled packet. For RTCP it holds the whole compound packet.
+    pub data: Bytes,
+}
+
+const RECORD_HEADER_SIZE: usize = 14;
+
+const PCAP_MAGIC: u32 = 0xa1b2_c3d4;
+const PCAP_SNAPLEN: u32 = 65535;
+const PCAP_LINKTYPE_RAW: u32 = 101;
+const PCAP_LOCAL_ADDR: [u8; 4] = [10, 0, 0, 1];
+const PCAP_REMOTE_ADDR: [u8; 4] = [10, 0, 0, 2];
+const PCAP_PORT: u16 = 5004;
+const IPV4_HEADER_SIZE: usize = 20;
+const UDP_HEADER_SIZE: usize = 8;
+const PCAP_MAX_PAYLOAD: usize = u16::MAX as usize - IPV4_HEADER_SIZE - UDP_HEADER_SIZE;
+const DROP_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+impl DumpRecord {
+    /// read_from reads the next record written in [`DumpFormat::LengthPrefixed`].
+    /// It returns None at the end of the input.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Option<Self>> {
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        let mut n = 0;
+        while n < header.len() {
+            match reader.read(&mut header[n..]) {
+                Ok(0) if n == 0 => return Ok(None),
+                Ok(0) => return Err(Error::ErrIoEOF),
+                Ok(read) => n += read,
+                Err(err) => return Err(Error::Other(err.to_string())),
+            }
+        }
+
+        let timestamp = u64::from_be_bytes([
+            header[0], header[1], header[2], header[3], header[4], header[5], header[6], header[7],
+        ]);
+        let direction = match header[8] {
+            0 => TapDirection::Inbound,
+            1 => TapDirection::Outbound,
+            _ => return Err(Error::Other(format!("invalid direction {}", header[8]))),
+        };
+        let packet_type = match header[9] {
+            0 => DumpPacketType::Rtp,
+            1 => DumpPacketType::Rtcp,
+            _ => return Err(Error::Other(format!("invalid packet type {}", header[9]))),
+        };
+        let len = u32::from_be_bytes([header[10], header[11], header[12], header[13]]) as usize;
+
+        let mut data = vec![0u8; len];
+        reader.read_exact(&mut data).map_err(|_| Error::ErrIoEOF)?;
+
+        Ok(Some(DumpRecord {
+            timestamp: UNIX_EPOCH + Duration::from_micros(timestamp),
+            direction,
+            packet_type,
+            data: Bytes::from(data),
+        }))
+    }
+
+    fn marshal_length_prefixed(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(RECORD_HEADER_SIZE + self.data.len());
+        buf.extend_from_slice(&(micros_since_epoch(self.timestamp)).to_be_bytes());
+        buf.push(match self.direction {
+            TapDirection::Inbound => 0,
+            TapDirection::Outbound => 1,
+        });
+        buf.push(match self.packet_type {
+            DumpPacketType::Rtp => 0,
+            DumpPacketType::Rtcp => 1,
+        });
+        buf.extend_from_slice(&(self.data.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+
+    fn marshal_pcap(&self) -> Vec<u8> {
+        let udp_len = UDP_HEADER_SIZE + self.data.len();
+        let ip_len = IPV4_HEADER_SIZE + udp_len;
+        let (src, dst) = match self.direction {
+            TapDirection::Inbound => (PCAP_REMOTE_ADDR, PCAP_LOCAL_ADDR),
+            TapDirection::Outbound => (PCAP_LOCAL_ADDR, PCAP_REMOTE_ADDR),
+        };
+        let micros = micros_since_epoch(self.timestamp);
+
+        let mut buf = Vec::with_capacity(16 + ip_len);
+        // Record header, in the byte order of the file header
+        buf.extend_from_slice(&((micros / 1_000_000) as u32).to_le_bytes());
+        buf.extend_from_slice(&((micros % 1_000_000) as u32).to_le_bytes());
+        buf.extend_from_slice(&(ip_len as u32).to_le_bytes());
+        buf.extend_from_slice(&(ip_len as u32).to_le_bytes());
+
+        let mut ip = [0u8; IPV4_HEADER_SIZE];
+        ip[0] = 0x45; // version 4, 5 words
+        ip[2..4].copy_from_slice(&(ip_len as u16).to_be_bytes());
+        ip[6] = 0x40; // don't fragment
+        ip[8] = 64; // TTL
+        ip[9] = 17; // UDP
+        ip[12..16].copy_from_slice(&src);
+        ip[16..20].copy_from_slice(&dst);
+        let checksum = ipv4_checksum(&ip);
+        ip[10..12].copy_from_slice(&checksum.to_be_bytes());
+        buf.extend_from_slice(&ip);
+
+        buf.extend_from_slice(&PCAP_PORT.to_be_bytes());
+        buf.extend_from_slice(&PCAP_PORT.to_be_bytes());
+        buf.extend_from_slice(&(udp_len as u16).to_be_bytes());
+        buf.extend_from_slice(&[0, 0]); // no checksum
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+}
+
+fn micros_since_epoch(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+fn ipv4_checksum(header: &[u8]) -> u16 {
+    let mut sum = header
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], c[1]])))
+        .sum::<u32>();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+fn pcap_file_header() -> Vec<u8> {
+    let mut buf = Vec::with_capacity(24);
+    buf.extend_from_slice(&PCAP_MAGIC.to_le_bytes());
+    buf.extend_from_slice(&2u16.to_le_bytes()); // version major
+    buf.extend_from_slice(&4u16.to_le_bytes()); // version minor
+    buf.extend_from_slice(&0i32.to_le_bytes()); // GMT offset
+    buf.extend_from_slice(&0u32.to_le_bytes()); // timestamp accuracy
+    buf.extend_from_slice(&PCAP_SNAPLEN.to_le_bytes());
+    buf.extend_from_slice(&PCAP_LINKTYPE_RAW.to_le_bytes());
+    buf
+}
+
+/// run_dump_writer writes the queued records to the sink until every sender is gone.
+fn run_dump_writer(
+    mut writer: Box<dyn Write + Send>,
+    format: DumpFormat,
+    mut rx: mpsc::Receiver<DumpRecord>,
+    dropped: Arc<AtomicU64>,
+) {
+    if format == DumpFormat::Pcap {
+        if let Err(err) = writer.write_all(&pcap_file_header()) {
+            log::warn!("dump: failed to write the pcap header: {}", err);
+            return;
+        }
+    }
+
+    let mut last_drop_report = Instant::now();
+    while let Some(record) = rx.blocking_recv() {
+        let mut next = Some(record);
+        // Drain what's queued before flushing
+        while let Some(record) = next {
+            let buf = match format {
+                DumpFormat::LengthPrefixed => record.marshal_length_prefixed(),
+                DumpFormat::Pcap if record.data.len() > PCAP_MAX_PAYLOAD => {
+                    log::warn!(
+                        "dump: {} byte packet doesn't fit a pcap record",
+                        record.data.len()
+                    );
+                    next = rx.try_recv().ok();
+                    continue;
+                }
+                DumpFormat::Pcap => record.marshal_pcap(),
+            };
+            if let Err(err) = writer.write_all(&buf) {
+                log::warn!("dump: failed to write a record: {}", err);
+                return;
+            }
+            next = rx.try_recv().ok();
+        }
+        if let Err(err) = writer.flush() {
+            log::warn!("dump: failed to flush: {}", err);
+            return;
+        }
+
+        if last_drop_report.elapsed() >= DROP_REPORT_INTERVAL {
+            report_dropped(&dropped);
+            last_drop_report = Instant::now();
+        }
+    }
+    report_dropped(&dropped);
+}
+
+fn report_dropped(dropped: &AtomicU64) {
+    let n = dropped.swap(0, Ordering::Relaxed);
+    if n > 0 {
+        log::warn!("dump: sink is too slow, dropped {} packets", n);
+    }
+}
+
+/// DumperBuilder can be used to configure the Dumper interceptor.
+pub struct DumperBuilder {
+    writer: Mutex<Option<Box<dyn Write + Send>>>,
+    format: DumpFormat,
+    queue_size: usize,
+    tx: Mutex<Option<DumpSender>>,
+}
+
+impl DumperBuilder {
+    /// with_format sets the format records are written in, defaults to
+    /// [`DumpFormat::LengthPrefixed`].
+    pub fn with_format(mut self, format: DumpFormat) -> DumperBuilder {
+        self.format = format;
+        self
+    }
+
+    /// with_queue_size sets how many packets may wait for the sink. Packets are dropped
+    /// while the queue is full. Defaults to 1024.
+    pub fn with_queue_size(mut self, queue_size: usize) -> DumperBuilder {
+        self.queue_size = queue_size;
+        self
+    }
+}
+
+impl InterceptorBuilder for DumperBuilder {
+    /// build creates a Dumper. All Dumpers built by the same builder share its sink.
+    fn build(&self, _id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
+        let mut tx = self.tx.lock();
+        let tx = match &mut *tx {
+            Some(tx) => tx.clone(),
+            None => {
+                let writer = match self.writer.lock().take() {
+                    Some(writer) => writer,
+                    None => return Err(Error::Other("dump: sink is gone".to_owned())),
+                };
+                let (record_tx, record_rx) = mpsc::channel(self.queue_size.max(1));
+                let dropped = Arc::new(AtomicU64::new(0));
+                let format = self.format;
+                let writer_dropped = Arc::clone(&dropped);
+                std::thread::Builder::new()
+                    .name("interceptor-dump".to_owned())
+                    .spawn(move || run_dump_writer(writer, format, record_rx, writer_dropped))
+                    .map_err(|err| Error::Other(err.to_string()))?;
+                tx.insert(DumpSender {
+                    tx: record_tx,
+                    dropped,
+                })
+                .clone()
+            }
+        };
+
+        Ok(Arc::new(Dumper { tx }))
+    }
+}
+
+/// Dumper writes every RTP and RTCP packet that passes it to a sink, see [`DumpFormat`]. It's
+/// meant for debugging interop issues. The sink is written on a thread of its own, packets
+/// that arrive while it's behind are dropped rather than holding up media, and counted in a
+/// warning logged at most once a second.
+pub struct Dumper {
+    tx: DumpSender,
+}
+
+impl Dumper {
+    /// builder returns a new DumperBuilder writing to `writer`.
+    pub fn builder<W: Write + Send + 'static>(writer: W) -> DumperBuilder {
+        DumperBuilder {
+            writer: Mutex::new(Some(Box::new(writer))),
+            format: DumpFormat::LengthPrefixed,
+            queue_size: 1024,
+            tx: Mutex::new(None),
+        }
+    }
+}
+
+/// DumpSender queues records for the writer thread and counts the ones it drops.
+#[derive(Clone)]
+struct DumpSender {
+    tx: mpsc::Sender<DumpRecord>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl DumpSender {
+    fn dump(&self, direction: TapDirection, packet_type: DumpPacketType, data: Bytes) {
+        let record = DumpRecord {
+            timestamp: SystemTime::now(),
+            direction,
+            packet_type,
+            data,
+        };
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.tx.try_send(record) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[async_trait]
+impl Interceptor for Dumper {
+    /// bind_rtcp_reader lets you modify any incoming RTCP packets. It is called once per sender/receiver, however this might
+    /// change in the future. The returned method will be called once per packet batch.
+    async fn bind_rtcp_reader(
+        &self,
+        reader: Arc<dyn RTCPReader + Send + Sync>,
+    ) -> Arc<dyn RTCPReader + Send + Sync> {
+        Arc::new(DumpRtcpReader {
+            tx: self.tx.clone(),
+            next: reader,
+        })
+    }
+
+    /// bind_rtcp_writer lets you modify any outgoing RTCP packets. It is called once per PeerConnection. The returned method
+    /// will be called once per packet batch.
+    async fn bind_rtcp_writer(
+        &self,
+        writer: Arc<dyn RTCPWriter + Send + Sync>,
+    ) -> Arc<dyn RTCPWriter + Send + Sync> {
+        Arc::new(DumpRtcpWriter {
+            tx: self.tx.clone(),
+            next: writer,
+        })
+    }
+
+    /// bind_local_stream lets you modify any outgoing RTP packets. It is called once for per LocalStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_local_stream(
+        &self,
+        _info: &StreamInfo,
+        writer: Arc<dyn RTPWriter + Send + Sync>,
+    ) -> Arc<dyn RTPWriter + Send + Sync> {
+        Arc::new(DumpRtpWriter {
+            tx: self.tx.clone(),
+            next: writer,
+        })
+    }
+
+    /// unbind_local_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_local_stream(&self, _info: &StreamInfo) {}
+
+    /// bind_remote_stream lets you modify any incoming RTP packets. It is called once for per RemoteStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_remote_stream(
+        &self,
+        _info: &StreamInfo,
+        reader: Arc<dyn RTPReader + Send + Sync>,
+    ) -> Arc<dyn RTPReader + Send + Sync> {
+        Arc::new(DumpRtpReader {
+            tx: self.tx.clone(),
+            next: reader,
+        })
+    }
+
+    /// unbind_remote_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_remote_stream(&self, _info: &StreamInfo) {}
+
+    /// close closes the Interceptor, cleaning up any data if necessary.
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct DumpRtpWriter {
+    tx: DumpSender,
+    next: Arc<dyn RTPWriter + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPWriter for DumpRtpWriter {
+    async fn write(&self, pkt: &rtp::packet::Packet, attributes: &Attributes) -> Result<usize> {
+        if let Ok(data) = pkt.marshal() {
+            self.tx
+                .dump(TapDirection::Outbound, DumpPacketType::Rtp, data);
+        }
+        self.next.write(pkt, attributes).await
+    }
+}
+
+struct DumpRtpReader {
+    tx: DumpSender,
+    next: Arc<dyn RTPReader + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPReader for DumpRtpReader {
+    async fn read(
+        &self,
+        buf: &mut [u8],
+        attributes: &Attributes,
+    ) -> Result<(rtp::packet::Packet, Attributes)> {
+        let (pkt, attr) = self.next.read(buf, attributes).await?;
+        if let Ok(data) = pkt.marshal() {
+            self.tx
+                .dump(TapDirection::Inbound, DumpPacketType::Rtp, data);
+        }
+        Ok((pkt, attr))
+    }
+}
+
+struct DumpRtcpWriter {
+    tx: DumpSender,
+    next: Arc<dyn RTCPWriter + Send + Sync>,
+}
+
+#[async_trait]
+impl RTCPWriter for DumpRtcpWriter {
+    async fn write(
+        &self,
+        pkts: &[Box<dyn rtcp::packet::Packet + Send + Sync>],
+        attributes: &Attributes,
+    ) -> Result<usize> {
+        if let Ok(data) = rtcp::packet::marshal(pkts) {
+            self.tx
+                .dump(TapDirection::Outbound, DumpPacketType::Rtcp, data);
+        }
+        self.next.write(pkts, attributes).await
+    }
+}
+
+struct DumpRtcpReader {
+    tx: DumpSender,
+    next: Arc<dyn RTCPReader + Send + Sync>,
+}
+
+#[async_trait]
+impl RTCPReader for DumpRtcpReader {
+    async fn read(
+        &self,
+        buf: &mut [u8],
+        attributes: &Attributes,
+    ) -> Result<(Vec<Box<dyn rtcp::packet::Packet + Send + Sync>>, Attributes)> {
+        let (pkts, attr) = self.next.read(buf, attributes).await?;
+        if let Ok(data) = rtcp::packet::marshal(&pkts) {
+            self.tx
+                .dump(TapDirection::Inbound, DumpPacketType::Rtcp, data);
+        }
+        Ok((pkts, attr))
+    }
+}
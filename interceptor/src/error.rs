@@ -21,6 +21,10 @@ pub enum Error {
     ErrShortBuffer,
     #[error("Invalid buffer size")]
     ErrInvalidSize,
+    #[error("Invalid FlexFEC packet")]
+    ErrInvalidFlexFecPacket,
+    #[error("FlexFEC packet uses unsupported features")]
+    ErrUnsupportedFlexFecPacket,
 
     #[error("{0}")]
     Srtp(#[from] srtp::Error),
@@ -0,0 +1,117 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use util::Unmarshal;
+
+use crate::error::{Error, Result};
+
+const FEC_HEADER_SIZE: usize = 8;
+const RTP_HEADER_SIZE: usize = 12;
+const RTP_VERSION: u8 = 2;
+
+/// FecPacket is a FlexFEC repair packet (RFC 8627) with a flexible mask, protecting the packets
+/// of a single SSRC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FecPacket {
+    pub(super) protected_ssrc: u32,
+    /// The sequence numbers of the protected packets.
+    pub(super) protected: Vec<u16>,
+    /// The recovery fields of the FEC header followed by the repair payload.
+    bit_string: Bytes,
+}
+
+impl FecPacket {
+    /// parse parses the FEC header of a received FlexFEC packet.
+    pub(super) fn parse(pkt: &rtp::packet::Packet) -> Result<Self> {
+        let payload = &pkt.payload;
+        if payload.len() < FEC_HEADER_SIZE + 4 {
+            return Err(Error::ErrInvalidFlexFecPacket);
+        }
+
+        // The R and F bits select retransmission and the fixed masks, which aren't supported.
+        if payload[0] & 0xc0 != 0 {
+            return Err(Error::ErrUnsupportedFlexFecPacket);
+        }
+        // The protected SSRCs are carried in the CSRC list.
+        let protected_ssrc = match pkt.header.csrc.as_slice() {
+            [ssrc] => *ssrc,
+            _ => return Err(Error::ErrUnsupportedFlexFecPacket),
+        };
+
+        let sn_base = u16::from_be_bytes([payload[8], payload[9]]);
+        let mut protected = vec![];
+        let mut offset = 10;
+        let mut index = 0u16;
+        // The mask is 15, 46 or 110 bits long, the k bit tells whether it ends.
+        for (bits, size) in [(15u16, 2), (31, 4), (64, 8)] {
+            if payload.len() < offset + size {
+                return Err(Error::ErrInvalidFlexFecPacket);
+            }
+            let mut field = [0u8; 8];
+            field[8 - size..].copy_from_slice(&payload[offset..offset + size]);
+            let field = u64::from_be_bytes(field);
+            offset += size;
+
+            for i in 0..bits {
+                if field & (1 << (bits - 1 - i)) != 0 {
+                    protected.push(sn_base.wrapping_add(index + i));
+                }
+            }
+            index += bits;
+
+            if bits == 64 || field & (1 << bits) != 0 {
+                break;
+            }
+        }
+
+        let mut bit_string = BytesMut::with_capacity(FEC_HEADER_SIZE + payload.len() - offset);
+        bit_string.put_slice(&payload[..FEC_HEADER_SIZE]);
+        bit_string.put_slice(&payload[offset..]);
+
+        Ok(FecPacket {
+            protected_ssrc,
+            protected,
+            bit_string: bit_string.freeze(),
+        })
+    }
+
+    /// recover rebuilds the protected packet with the sequence number `missing` from all the
+    /// other protected packets, given as marshaled RTP packets.
+    pub(super) fn recover<'a>(
+        &self,
+        missing: u16,
+        received: impl IntoIterator<Item = &'a Bytes>,
+    ) -> Result<rtp::packet::Packet> {
+        let mut x = self.bit_string.to_vec();
+        for raw in received {
+            if raw.len() < RTP_HEADER_SIZE {
+                return Err(Error::ErrInvalidFlexFecPacket);
+            }
+            let length = ((raw.len() - RTP_HEADER_SIZE) as u16).to_be_bytes();
+            let header = [
+                raw[0], raw[1], length[0], length[1], raw[4], raw[5], raw[6], raw[7],
+            ];
+            let body = &raw[RTP_HEADER_SIZE..];
+
+            if x.len() < FEC_HEADER_SIZE + body.len() {
+                x.resize(FEC_HEADER_SIZE + body.len(), 0);
+            }
+            for (x, b) in x.iter_mut().zip(header.iter().chain(body)) {
+                *x ^= b;
+            }
+        }
+
+        let length = u16::from_be_bytes([x[2], x[3]]) as usize;
+        if x.len() < FEC_HEADER_SIZE + length {
+            return Err(Error::ErrInvalidFlexFecPacket);
+        }
+
+        let mut raw = BytesMut::with_capacity(RTP_HEADER_SIZE + length);
+        raw.put_u8((x[0] & 0x3f) | (RTP_VERSION << 6));
+        raw.put_u8(x[1]);
+        raw.put_u16(missing);
+        raw.put_slice(&x[4..8]);
+        raw.put_u32(self.protected_ssrc);
+        raw.put_slice(&x[FEC_HEADER_SIZE..FEC_HEADER_SIZE + length]);
+
+        Ok(rtp::packet::Packet::unmarshal(&mut raw.freeze())?)
+    }
+}
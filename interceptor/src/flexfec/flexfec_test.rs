@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use bytes::BytesMut;
+
+use super::*;
+use crate::mock::mock_stream::MockStream;
+use crate::test::timeout_or_fail;
+
+const MEDIA_SSRC: u32 = 1234;
+const FEC_SSRC: u32 = 5678;
+
+fn media_packet(sequence_number: u16) -> rtp::packet::Packet {
+    rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            marker: sequence_number % 3 == 1,
+            payload_type: 96,
+            sequence_number,
+            timestamp: 3000 * (sequence_number as u32 / 2),
+            ssrc: MEDIA_SSRC,
+            ..Default::default()
+        },
+        // Varying lengths, so the length recovery is exercised.
+        payload: Bytes::from(vec![
+            sequence_number as u8;
+            10 + sequence_number as usize % 7
+        ]),
+    }
+}
+
+/// fec_packet builds a FlexFEC repair packet protecting `packets`, which must be within 46
+/// sequence numbers from `sn_base`.
+fn fec_packet(
+    sequence_number: u16,
+    sn_base: u16,
+    packets: &[rtp::packet::Packet],
+) -> Result<rtp::packet::Packet> {
+    let mut x = vec![0u8; 8];
+    let mut mask = 0u64;
+    let mut max_index = 0;
+    for p in packets {
+        let raw = p.marshal()?;
+        let length = ((raw.len() - 12) as u16).to_be_bytes();
+        let header = [
+            raw[0], raw[1], length[0], length[1], raw[4], raw[5], raw[6], raw[7],
+        ];
+        let body = &raw[12..];
+        if x.len() < 8 + body.len() {
+            x.resize(8 + body.len(), 0);
+        }
+        for (x, b) in x.iter_mut().zip(header.iter().chain(body)) {
+            *x ^= b;
+        }
+
+        let index = p.header.sequence_number.wrapping_sub(sn_base);
+        mask |= 1 << (45 - index);
+        max_index = max_index.max(index);
+    }
+    x[0] &= 0x3f;
+
+    let mut payload = BytesMut::new();
+    payload.extend_from_slice(&x[..8]);
+    payload.extend_from_slice(&sn_base.to_be_bytes());
+    if max_index < 15 {
+        payload.extend_from_slice(&(0x8000 | (mask >> 31) as u16).to_be_bytes());
+    } else {
+        payload.extend_from_slice(&((mask >> 31) as u16).to_be_bytes());
+        payload.extend_from_slice(&(0x8000_0000 | (mask & 0x7fff_ffff) as u32).to_be_bytes());
+    }
+    payload.extend_from_slice(&x[8..]);
+
+    Ok(rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            payload_type: 118,
+            sequence_number,
+            ssrc: FEC_SSRC,
+            csrc: vec![MEDIA_SSRC],
+            ..Default::default()
+        },
+        payload: payload.freeze(),
+    })
+}
+
+async fn new_streams() -> Result<(Arc<MockStream>, Arc<MockStream>)> {
+    let icpr = FlexFecReceiver::builder().build("")?;
+    let media = MockStream::new(
+        &StreamInfo {
+            ssrc: MEDIA_SSRC,
+            mime_type: "video/VP8".to_owned(),
+            ..Default::default()
+        },
+        Arc::clone(&icpr),
+    )
+    .await;
+    let fec = MockStream::new(
+        &StreamInfo {
+            ssrc: FEC_SSRC,
+            mime_type: MIME_TYPE_FLEXFEC.to_owned(),
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+    Ok((media, fec))
+}
+
+async fn receive(stream: &MockStream, pkt: rtp::packet::Packet) -> Result<rtp::packet::Packet> {
+    stream.receive_rtp(pkt).await;
+    read(stream).await
+}
+
+async fn read(stream: &MockStream) -> Result<rtp::packet::Packet> {
+    timeout_or_fail(Duration::from_millis(100), stream.read_rtp())
+        .await
+        .expect("a packet")
+}
+
+#[tokio::test]
+async fn test_flexfec_receiver_recovers_lost_packet() -> Result<()> {
+    let (media, fec) = new_streams().await?;
+
+    // Packets 100 to 119 are protected, 117 is lost.
+    let packets: Vec<_> = (100..120).map(media_packet).collect();
+    for p in &packets {
+        if p.header.sequence_number != 117 {
+            assert_eq!(&receive(&media, p.clone()).await?, p);
+        }
+    }
+
+    let repair = fec_packet(1, 100, &packets)?;
+    assert_eq!(receive(&fec, repair.clone()).await?, repair);
+
+    // The read waiting on the protected stream returns first, the recovered packet follows.
+    assert_eq!(receive(&media, media_packet(120)).await?, media_packet(120));
+    assert_eq!(read(&media).await?, packets[17]);
+
+    media.close().await?;
+    fec.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flexfec_receiver_waits_for_protected_packets() -> Result<()> {
+    let (media, fec) = new_streams().await?;
+
+    let packets: Vec<_> = (65534..=65535).chain(0..=2).map(media_packet).collect();
+    for i in [0, 2, 3] {
+        receive(&media, packets[i].clone()).await?;
+    }
+
+    // Two protected packets are missing, nothing can be recovered yet.
+    receive(&fec, fec_packet(1, 65534, &packets)?).await?;
+
+    // Once one of them arrives, the other one is recovered.
+    assert_eq!(receive(&media, packets[4].clone()).await?, packets[4]);
+    assert_eq!(read(&media).await?, packets[1]);
+
+    media.close().await?;
+    fec.close().await?;
+
+    Ok(())
+}
+
+#[test]
+fn test_fec_packet_parse() -> Result<()> {
+    let packets: Vec<_> = [10, 12, 24].into_iter().map(media_packet).collect();
+    let fec = FecPacket::parse(&fec_packet(1, 10, &packets)?)?;
+    assert_eq!(fec.protected_ssrc, MEDIA_SSRC);
+    assert_eq!(fec.protected, vec![10, 12, 24]);
+
+    let mut repair = fec_packet(1, 10, &packets)?;
+    repair.header.csrc = vec![];
+    assert_eq!(
+        FecPacket::parse(&repair),
+        Err(Error::ErrUnsupportedFlexFecPacket)
+    );
+
+    repair.header.csrc = vec![MEDIA_SSRC];
+    repair.payload = repair.payload.slice(..11);
+    assert_eq!(
+        FecPacket::parse(&repair),
+        Err(Error::ErrInvalidFlexFecPacket)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,307 @@
+mod fec_packet;
+#[cfg(test)]
+mod flexfec_test;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use fec_packet::FecPacket;
+use util::sync::Mutex;
+use util::{Marshal, MarshalSize};
+
+use super::*;
+use crate::error::{Error, Result};
+
+/// MIME_TYPE_FLEXFEC is the media type of FlexFEC repair streams, see RFC 8627.
+pub const MIME_TYPE_FLEXFEC: &str = "video/flexfec";
+
+/// The number of repair packets kept per protected stream while they can't be used yet.
+const MAX_PENDING_FEC_PACKETS: usize = 16;
+
+/// FlexFecReceiverBuilder can be used to configure a FlexFecReceiver Interceptor.
+#[derive(Default)]
+pub struct FlexFecReceiverBuilder {
+    log2_size: Option<u8>,
+}
+
+impl FlexFecReceiverBuilder {
+    /// with_log2_size sets the number of received packets kept per stream to recover from.
+    /// Size must be one of: 128, 256, 512, 1024, 2048, 4096, 8192, 16384, 32768
+    pub fn with_log2_size(mut self, log2_size: u8) -> FlexFecReceiverBuilder {
+        self.log2_size = Some(log2_size);
+        self
+    }
+}
+
+impl InterceptorBuilder for FlexFecReceiverBuilder {
+    fn build(&self, _id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
+        let log2_size = self.log2_size.unwrap_or(9); // 512 = 1 << 9
+        if !(7..=15).contains(&log2_size) {
+            return Err(Error::ErrInvalidSize);
+        }
+
+        Ok(Arc::new(FlexFecReceiver {
+            internal: Arc::new(FlexFecInternal {
+                size: 1 << log2_size,
+                streams: Mutex::new(HashMap::new()),
+            }),
+        }))
+    }
+}
+
+/// FlexFecReceiver recovers lost RTP packets from received FlexFEC repair packets (RFC 8627).
+///
+/// Remote streams with the [`MIME_TYPE_FLEXFEC`] media type are treated as repair streams, all
+/// other remote streams as protected ones. Only repair packets with a flexible mask protecting a
+/// single SSRC are supported. A recovered packet is returned by the next read of the protected
+/// stream, so it comes out after packets with higher sequence numbers and only once another
+/// packet of the stream arrives. Consumers must reorder by sequence number, e.g. with a jitter
+/// buffer, to play out recovered packets.
+pub struct FlexFecReceiver {
+    internal: Arc<FlexFecInternal>,
+}
+
+impl FlexFecReceiver {
+    /// builder returns a new FlexFecReceiverBuilder.
+    pub fn builder() -> FlexFecReceiverBuilder {
+        FlexFecReceiverBuilder::default()
+    }
+}
+
+struct FlexFecInternal {
+    size: u16,
+    streams: Mutex<HashMap<u32, ProtectedStream>>,
+}
+
+struct ProtectedStream {
+    /// The received packets, marshaled, indexed by sequence number modulo the size.
+    packets: Vec<Option<(u16, Bytes)>>,
+    pending: VecDeque<FecPacket>,
+    recovered: VecDeque<rtp::packet::Packet>,
+}
+
+impl ProtectedStream {
+    fn new(size: u16) -> Self {
+        ProtectedStream {
+            packets: vec![None; size as usize],
+            pending: VecDeque::new(),
+            recovered: VecDeque::new(),
+        }
+    }
+
+    fn get(&self, seq: u16) -> Option<&Bytes> {
+        match &self.packets[seq as usize % self.packets.len()] {
+            Some((s, raw)) if *s == seq => Some(raw),
+            _ => None,
+        }
+    }
+
+    fn insert(&mut self, seq: u16, raw: Bytes) {
+        let pos = seq as usize % self.packets.len();
+        self.packets[pos] = Some((seq, raw));
+    }
+
+    /// try_recover recovers what it can with the pending repair packets. Repair packets are
+    /// dropped once nothing they protect is missing.
+    fn try_recover(&mut self) {
+        let mut i = 0;
+        while i < self.pending.len() {
+            let fec = &self.pending[i];
+            let missing: Vec<u16> = fec
+                .protected
+                .iter()
+                .copied()
+                .filter(|seq| self.get(*seq).is_none())
+                .collect();
+
+            let seq = match missing.as_slice() {
+                [] => {
+                    self.pending.remove(i);
+                    continue;
+                }
+                [seq] => *seq,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+
+            let received = fec.protected.iter().filter_map(|s| self.get(*s));
+            match fec.recover(seq, received) {
+                Ok(pkt) => {
+                    log::trace!("recovered packet SSRC {} SN {}", pkt.header.ssrc, seq);
+                    match pkt.marshal() {
+                        Ok(raw) => self.insert(seq, raw),
+                        Err(err) => log::debug!("failed to marshal recovered packet: {}", err),
+                    }
+                    self.recovered.push_back(pkt);
+                }
+                Err(err) => log::debug!("failed to recover packet SN {}: {}", seq, err),
+            }
+            self.pending.remove(i);
+            // The recovered packet may complete earlier repair packets.
+            i = 0;
+        }
+    }
+}
+
+impl FlexFecInternal {
+    fn received(&self, ssrc: u32, pkt: &rtp::packet::Packet) -> Result<()> {
+        let raw = pkt.marshal()?;
+        let mut streams = self.streams.lock();
+        if let Some(stream) = streams.get_mut(&ssrc) {
+            stream.insert(pkt.header.sequence_number, raw);
+            if !stream.pending.is_empty() {
+                stream.try_recover();
+            }
+        }
+        Ok(())
+    }
+
+    fn received_fec(&self, fec: FecPacket) {
+        let mut streams = self.streams.lock();
+        let stream = match streams.get_mut(&fec.protected_ssrc) {
+            Some(stream) => stream,
+            None => {
+                log::debug!("FlexFEC packet for unknown SSRC {}", fec.protected_ssrc);
+                return;
+            }
+        };
+
+        if stream.pending.len() == MAX_PENDING_FEC_PACKETS {
+            stream.pending.pop_front();
+        }
+        stream.pending.push_back(fec);
+        stream.try_recover();
+    }
+
+    fn pop_recovered(&self, ssrc: u32) -> Option<rtp::packet::Packet> {
+        let mut streams = self.streams.lock();
+        streams.get_mut(&ssrc)?.recovered.pop_front()
+    }
+}
+
+/// ProtectedStreamReader keeps the packets of a protected stream and returns recovered ones.
+/// A read that is already waiting on the parent reader isn't woken when a packet is recovered,
+/// cancelling it could lose the packet it's reading. The recovered packet is returned by the
+/// read after it instead.
+struct ProtectedStreamReader {
+    ssrc: u32,
+    internal: Arc<FlexFecInternal>,
+    parent_rtp_reader: Arc<dyn RTPReader + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPReader for ProtectedStreamReader {
+    async fn read(
+        &self,
+        buf: &mut [u8],
+        attributes: &Attributes,
+    ) -> Result<(rtp::packet::Packet, Attributes)> {
+        if let Some(pkt) = self.internal.pop_recovered(self.ssrc) {
+            if buf.len() < pkt.marshal_size() {
+                return Err(Error::ErrShortBuffer);
+            }
+            pkt.marshal_to(buf)?;
+            return Ok((pkt, attributes.clone()));
+        }
+
+        let (pkt, attr) = self.parent_rtp_reader.read(buf, attributes).await?;
+        self.internal.received(self.ssrc, &pkt)?;
+        Ok((pkt, attr))
+    }
+}
+
+/// FecStreamReader hands the repair packets of a FlexFEC stream to the protected streams.
+struct FecStreamReader {
+    internal: Arc<FlexFecInternal>,
+    parent_rtp_reader: Arc<dyn RTPReader + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPReader for FecStreamReader {
+    async fn read(
+        &self,
+        buf: &mut [u8],
+        attributes: &Attributes,
+    ) -> Result<(rtp::packet::Packet, Attributes)> {
+        let (pkt, attr) = self.parent_rtp_reader.read(buf, attributes).await?;
+        match FecPacket::parse(&pkt) {
+            Ok(fec) => self.internal.received_fec(fec),
+            Err(err) => log::debug!("ignoring FlexFEC packet: {}", err),
+        }
+        Ok((pkt, attr))
+    }
+}
+
+#[async_trait]
+impl Interceptor for FlexFecReceiver {
+    /// bind_rtcp_reader lets you modify any incoming RTCP packets. It is called once per sender/receiver, however this might
+    /// change in the future. The returned method will be called once per packet batch.
+    async fn bind_rtcp_reader(
+        &self,
+        reader: Arc<dyn RTCPReader + Send + Sync>,
+    ) -> Arc<dyn RTCPReader + Send + Sync> {
+        reader
+    }
+
+    /// bind_rtcp_writer lets you modify any outgoing RTCP packets. It is called once per PeerConnection. The returned method
+    /// will be called once per packet batch.
+    async fn bind_rtcp_writer(
+        &self,
+        writer: Arc<dyn RTCPWriter + Send + Sync>,
+    ) -> Arc<dyn RTCPWriter + Send + Sync> {
+        writer
+    }
+
+    /// bind_local_stream lets you modify any outgoing RTP packets. It is called once for per LocalStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_local_stream(
+        &self,
+        _info: &StreamInfo,
+        writer: Arc<dyn RTPWriter + Send + Sync>,
+    ) -> Arc<dyn RTPWriter + Send + Sync> {
+        writer
+    }
+
+    /// unbind_local_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_local_stream(&self, _info: &StreamInfo) {}
+
+    /// bind_remote_stream lets you modify any incoming RTP packets. It is called once for per RemoteStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_remote_stream(
+        &self,
+        info: &StreamInfo,
+        reader: Arc<dyn RTPReader + Send + Sync>,
+    ) -> Arc<dyn RTPReader + Send + Sync> {
+        if info.mime_type.eq_ignore_ascii_case(MIME_TYPE_FLEXFEC) {
+            return Arc::new(FecStreamReader {
+                internal: Arc::clone(&self.internal),
+                parent_rtp_reader: reader,
+            });
+        }
+
+        {
+            let mut streams = self.internal.streams.lock();
+            streams.insert(info.ssrc, ProtectedStream::new(self.internal.size));
+        }
+
+        Arc::new(ProtectedStreamReader {
+            ssrc: info.ssrc,
+            internal: Arc::clone(&self.internal),
+            parent_rtp_reader: reader,
+        })
+    }
+
+    /// unbind_remote_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_remote_stream(&self, info: &StreamInfo) {
+        let mut streams = self.internal.streams.lock();
+        streams.remove(&info.ssrc);
+    }
+
+    /// close closes the Interceptor, cleaning up any data if necessary.
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -11,15 +11,19 @@ use error::Result;
 use stream_info::StreamInfo;
 
 pub mod chain;
+pub mod dump;
 mod error;
+pub mod flexfec;
 pub mod mock;
 pub mod nack;
 pub mod noop;
 pub mod registry;
+pub mod remb;
 pub mod report;
 pub mod stats;
 pub mod stream_info;
 pub mod stream_reader;
+pub mod tap;
 pub mod twcc;
 
 pub use error::Error;
@@ -64,3 +64,84 @@ async fn test_generator_interceptor() -> Result<()> {
 
     Ok(())
 }
+
+async fn receive_rtp_sequence(stream: &MockStream, seq_nums: &[u16]) {
+    for &seq_num in seq_nums {
+        stream
+            .receive_rtp(rtp::packet::Packet {
+                header: rtp::header::Header {
+                    sequence_number: seq_num,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await;
+
+        let r = timeout_or_fail(Duration::from_millis(10), stream.read_rtp())
+            .await
+            .expect("A read packet")
+            .expect("Not an error");
+        assert_eq!(r.header.sequence_number, seq_num);
+    }
+}
+
+async fn written_nack_packet_ids(stream: &MockStream) -> Vec<u16> {
+    let r = timeout_or_fail(Duration::from_millis(100), stream.written_rtcp())
+        .await
+        .expect("Write rtcp");
+    let p = r[0]
+        .as_any()
+        .downcast_ref::<TransportLayerNack>()
+        .expect("single packet RTCP Compound Packet expected");
+    assert_eq!(p.media_ssrc, 1);
+    p.nacks.iter().flat_map(|pair| pair.packet_list()).collect()
+}
+
+fn nack_stream_info() -> StreamInfo {
+    StreamInfo {
+        ssrc: 1,
+        rtcp_feedback: vec![RTCPFeedback {
+            typ: "nack".to_owned(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_generator_interceptor_nacks_dropped_packet() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> = Generator::builder()
+        .with_interval(Duration::from_millis(10))
+        .build("")?;
+    let stream = MockStream::new(&nack_stream_info(), icpr).await;
+
+    // seq 5 is lost on the way
+    receive_rtp_sequence(&stream, &[1, 2, 3, 4, 6, 7, 8]).await;
+
+    assert_eq!(written_nack_packet_ids(&stream).await, vec![5]);
+
+    stream.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_generator_interceptor_ignores_packets_older_than_window() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> = Generator::builder()
+        .with_log2_size_minus_6(0)
+        .with_interval(Duration::from_millis(10))
+        .build("")?;
+    let stream = MockStream::new(&nack_stream_info(), icpr).await;
+
+    // Everything between 0 and 200 is lost, but only the last 64 packets are tracked.
+    receive_rtp_sequence(&stream, &[0, 200]).await;
+
+    assert_eq!(
+        written_nack_packet_ids(&stream).await,
+        (200 - 64 + 1..200).collect::<Vec<u16>>()
+    );
+
+    stream.close().await?;
+
+    Ok(())
+}
@@ -5,6 +5,9 @@ pub mod responder;
 
 const UINT16SIZE_HALF: u16 = 1 << 15;
 
+/// The largest buffer size, as a power of two, supported by the NACK streams: 32768 packets.
+pub const MAX_LOG2_SIZE: u8 = 15;
+
 fn stream_support_nack(info: &StreamInfo) -> bool {
     for fb in &info.rtcp_feedback {
         if fb.typ == "nack" && fb.parameter.is_empty() {
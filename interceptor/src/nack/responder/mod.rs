@@ -12,8 +12,8 @@ use responder_stream::ResponderStream;
 use rtcp::transport_feedbacks::transport_layer_nack::TransportLayerNack;
 use tokio::sync::Mutex;
 
-use crate::error::Result;
-use crate::nack::stream_support_nack;
+use crate::error::{Error, Result};
+use crate::nack::{stream_support_nack, MAX_LOG2_SIZE};
 use crate::stream_info::StreamInfo;
 use crate::{
     Attributes, Interceptor, InterceptorBuilder, RTCPReader, RTCPWriter, RTPReader, RTPWriter,
@@ -27,7 +27,8 @@ pub struct ResponderBuilder {
 
 impl ResponderBuilder {
     /// with_log2_size sets the size of the interceptor.
-    /// Size must be one of: 1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096, 8192, 16384, 32768
+    /// Size must be one of: 1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096, 8192, 16384, 32768,
+    /// build fails with `Error::ErrInvalidSize` for larger ones
     pub fn with_log2_size(mut self, log2_size: u8) -> ResponderBuilder {
         self.log2_size = Some(log2_size);
         self
@@ -36,13 +37,18 @@ impl ResponderBuilder {
 
 impl InterceptorBuilder for ResponderBuilder {
     fn build(&self, _id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
+        let log2_size = if let Some(log2_size) = self.log2_size {
+            log2_size
+        } else {
+            13 // 8192 = 1 << 13
+        };
+        if log2_size > MAX_LOG2_SIZE {
+            return Err(Error::ErrInvalidSize);
+        }
+
         Ok(Arc::new(Responder {
             internal: Arc::new(ResponderInternal {
-                log2_size: if let Some(log2_size) = self.log2_size {
-                    log2_size
-                } else {
-                    13 // 8192 = 1 << 13
-                },
+                log2_size,
                 streams: Arc::new(Mutex::new(HashMap::new())),
             }),
         }))
@@ -77,6 +83,7 @@ impl ResponderInternal {
                     let stream3 = Arc::clone(&stream2);
                     Box::pin(async move {
                         if let Some(p) = stream3.get(seq).await {
+                            let p = stream3.retransmission(p);
                             let a = Attributes::new();
                             if let Err(err) = stream3.next_rtp_writer.write(&p, &a).await {
                                 log::warn!("failed resending nacked packet: {}", err);
@@ -164,11 +171,25 @@ impl Interceptor for Responder {
         info: &StreamInfo,
         writer: Arc<dyn RTPWriter + Send + Sync>,
     ) -> Arc<dyn RTPWriter + Send + Sync> {
-        if !stream_support_nack(info) {
+        if !stream_support_nack(info) || !info.nack_settings.rtx_enabled {
             return writer;
         }
 
-        let stream = Arc::new(ResponderStream::new(self.internal.log2_size, writer));
+        if let Err(err) = info.nack_settings.validate() {
+            log::warn!("not answering nacks for stream {}: {}", info.ssrc, err);
+            return writer;
+        }
+
+        let log2_size = info
+            .nack_settings
+            .log2_history_size
+            .unwrap_or(self.internal.log2_size);
+        let mut stream =
+            ResponderStream::new(log2_size, info.nack_settings.max_retransmissions, writer);
+        if info.ssrc_retransmission != 0 {
+            stream = stream.with_rtx(info.ssrc_retransmission, info.payload_type_retransmission);
+        }
+        let stream = Arc::new(stream);
         {
             let mut streams = self.internal.streams.lock().await;
             streams.insert(info.ssrc, Arc::clone(&stream));
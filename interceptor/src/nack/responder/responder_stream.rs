@@ -1,6 +1,8 @@
+use std::sync::atomic::{AtomicU16, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::{BufMut, BytesMut};
 use tokio::sync::Mutex;
 
 use crate::error::Result;
@@ -9,6 +11,7 @@ use crate::{Attributes, RTPWriter};
 
 struct ResponderStreamInternal {
     packets: Vec<Option<rtp::packet::Packet>>,
+    retransmissions: Vec<u16>,
     size: u16,
     last_added: u16,
     started: bool,
@@ -18,6 +21,7 @@ impl ResponderStreamInternal {
     fn new(log2_size: u8) -> Self {
         ResponderStreamInternal {
             packets: vec![None; 1 << log2_size],
+            retransmissions: vec![0; 1 << log2_size],
             size: 1 << log2_size,
             last_added: 0,
             started: false,
@@ -28,6 +32,7 @@ impl ResponderStreamInternal {
         let seq = packet.header.sequence_number;
         if !self.started {
             self.packets[(seq % self.size) as usize] = Some(packet.clone());
+            self.retransmissions[(seq % self.size) as usize] = 0;
             self.last_added = seq;
             self.started = true;
             return;
@@ -45,6 +50,7 @@ impl ResponderStreamInternal {
         }
 
         self.packets[(seq % self.size) as usize] = Some(packet.clone());
+        self.retransmissions[(seq % self.size) as usize] = 0;
         self.last_added = seq;
     }
 
@@ -60,29 +66,99 @@ impl ResponderStreamInternal {
 
         self.packets[(seq % self.size) as usize].as_ref()
     }
+
+    /// get_for_retransmission returns the packet with the given sequence number unless it has
+    /// already been retransmitted `max_retransmissions` times.
+    fn get_for_retransmission(
+        &mut self,
+        seq: u16,
+        max_retransmissions: Option<u16>,
+    ) -> Option<rtp::packet::Packet> {
+        let packet = self.get(seq)?.clone();
+
+        let retransmissions = &mut self.retransmissions[(seq % self.size) as usize];
+        if let Some(max_retransmissions) = max_retransmissions {
+            if *retransmissions >= max_retransmissions {
+                return None;
+            }
+        }
+        *retransmissions = retransmissions.saturating_add(1);
+
+        Some(packet)
+    }
+}
+
+/// RtxStream is the RTX stream (RFC 4588) retransmissions of a stream are sent on.
+struct RtxStream {
+    ssrc: u32,
+    payload_type: u8,
+    sequence_number: AtomicU16,
 }
 
 pub(super) struct ResponderStream {
     internal: Mutex<ResponderStreamInternal>,
+    max_retransmissions: Option<u16>,
+    rtx: Option<RtxStream>,
     pub(super) next_rtp_writer: Arc<dyn RTPWriter + Send + Sync>,
 }
 
 impl ResponderStream {
-    pub(super) fn new(log2_size: u8, writer: Arc<dyn RTPWriter + Send + Sync>) -> Self {
+    pub(super) fn new(
+        log2_size: u8,
+        max_retransmissions: Option<u16>,
+        writer: Arc<dyn RTPWriter + Send + Sync>,
+    ) -> Self {
         ResponderStream {
             internal: Mutex::new(ResponderStreamInternal::new(log2_size)),
+            max_retransmissions,
+            rtx: None,
             next_rtp_writer: writer,
         }
     }
 
+    /// with_rtx makes the stream send retransmissions on the given RTX stream rather than
+    /// resending the original packets.
+    pub(super) fn with_rtx(mut self, ssrc: u32, payload_type: u8) -> Self {
+        self.rtx = Some(RtxStream {
+            ssrc,
+            payload_type,
+            sequence_number: AtomicU16::new(rand::random()),
+        });
+        self
+    }
+
     async fn add(&self, pkt: &rtp::packet::Packet) {
         let mut internal = self.internal.lock().await;
         internal.add(pkt);
     }
 
     pub(super) async fn get(&self, seq: u16) -> Option<rtp::packet::Packet> {
-        let internal = self.internal.lock().await;
-        internal.get(seq).cloned()
+        let mut internal = self.internal.lock().await;
+        internal.get_for_retransmission(seq, self.max_retransmissions)
+    }
+
+    /// retransmission returns the packet to send for the lost packet `pkt`. With RTX the
+    /// payload is prefixed with the original sequence number and sent on the RTX stream.
+    pub(super) fn retransmission(&self, pkt: rtp::packet::Packet) -> rtp::packet::Packet {
+        let rtx = match &self.rtx {
+            Some(rtx) => rtx,
+            None => return pkt,
+        };
+
+        let mut payload = BytesMut::with_capacity(2 + pkt.payload.len());
+        payload.put_u16(pkt.header.sequence_number);
+        payload.put_slice(&pkt.payload);
+
+        rtp::packet::Packet {
+            header: rtp::header::Header {
+                ssrc: rtx.ssrc,
+                payload_type: rtx.payload_type,
+                sequence_number: rtx.sequence_number.fetch_add(1, Ordering::SeqCst),
+                padding: false,
+                ..pkt.header
+            },
+            payload: payload.freeze(),
+        }
     }
 }
 
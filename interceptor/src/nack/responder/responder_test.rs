@@ -1,9 +1,10 @@
+use bytes::Bytes;
 use rtcp::transport_feedbacks::transport_layer_nack::{NackPair, TransportLayerNack};
 use tokio::time::Duration;
 
 use super::*;
 use crate::mock::mock_stream::MockStream;
-use crate::stream_info::RTCPFeedback;
+use crate::stream_info::{NackSettings, RTCPFeedback};
 use crate::test::timeout_or_fail;
 
 #[tokio::test]
@@ -74,3 +75,204 @@ async fn test_responder_interceptor() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_responder_interceptor_rtx_disabled() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> =
+        Responder::builder().with_log2_size(3).build("")?;
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "nack".to_owned(),
+                ..Default::default()
+            }],
+            nack_settings: NackSettings {
+                rtx_enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    for seq_num in [10, 11, 12] {
+        stream
+            .write_rtp(&rtp::packet::Packet {
+                header: rtp::header::Header {
+                    sequence_number: seq_num,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await?;
+
+        let p = timeout_or_fail(Duration::from_millis(10), stream.written_rtp())
+            .await
+            .expect("A packet");
+        assert_eq!(p.header.sequence_number, seq_num);
+    }
+
+    stream
+        .receive_rtcp(vec![Box::new(TransportLayerNack {
+            media_ssrc: 1,
+            sender_ssrc: 2,
+            nacks: vec![NackPair {
+                packet_id: 10,
+                lost_packets: 0b11,
+            }],
+        })])
+        .await;
+
+    let result = tokio::time::timeout(Duration::from_millis(50), stream.written_rtp()).await;
+    assert!(result.is_err(), "no packets should be retransmitted");
+
+    stream.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_responder_interceptor_max_retransmissions() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> =
+        Responder::builder().with_log2_size(3).build("")?;
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "nack".to_owned(),
+                ..Default::default()
+            }],
+            nack_settings: NackSettings {
+                max_retransmissions: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    stream
+        .write_rtp(&rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await?;
+    let p = timeout_or_fail(Duration::from_millis(10), stream.written_rtp())
+        .await
+        .expect("A packet");
+    assert_eq!(p.header.sequence_number, 10);
+
+    let nack = TransportLayerNack {
+        media_ssrc: 1,
+        sender_ssrc: 2,
+        nacks: vec![NackPair {
+            packet_id: 10,
+            lost_packets: 0,
+        }],
+    };
+
+    stream.receive_rtcp(vec![Box::new(nack.clone())]).await;
+    let p = timeout_or_fail(Duration::from_millis(50), stream.written_rtp())
+        .await
+        .expect("A retransmitted packet");
+    assert_eq!(p.header.sequence_number, 10);
+
+    // The packet was already retransmitted once.
+    stream.receive_rtcp(vec![Box::new(nack)]).await;
+    let result = tokio::time::timeout(Duration::from_millis(50), stream.written_rtp()).await;
+    assert!(result.is_err(), "packet should not be retransmitted again");
+
+    stream.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_responder_interceptor_rtx() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> =
+        Responder::builder().with_log2_size(3).build("")?;
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            payload_type: 96,
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "nack".to_owned(),
+                ..Default::default()
+            }],
+            ssrc_retransmission: 2,
+            payload_type_retransmission: 97,
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    for seq_num in [10, 11, 12] {
+        stream
+            .write_rtp(&rtp::packet::Packet {
+                header: rtp::header::Header {
+                    payload_type: 96,
+                    sequence_number: seq_num,
+                    timestamp: 3000,
+                    ssrc: 1,
+                    ..Default::default()
+                },
+                payload: Bytes::from(vec![seq_num as u8; 4]),
+            })
+            .await?;
+
+        let p = timeout_or_fail(Duration::from_millis(10), stream.written_rtp())
+            .await
+            .expect("A packet");
+        assert_eq!(p.header.sequence_number, seq_num);
+    }
+
+    stream
+        .receive_rtcp(vec![Box::new(TransportLayerNack {
+            media_ssrc: 1,
+            sender_ssrc: 3,
+            nacks: vec![NackPair {
+                packet_id: 10,
+                lost_packets: 0b10,
+            }], // sequence numbers: 10, 12
+        })])
+        .await;
+
+    let mut rtx_seq = None;
+    for seq_num in [10u16, 12] {
+        let p = timeout_or_fail(Duration::from_millis(50), stream.written_rtp())
+            .await
+            .expect("A retransmitted packet");
+        assert_eq!(p.header.ssrc, 2);
+        assert_eq!(p.header.payload_type, 97);
+        assert_eq!(p.header.timestamp, 3000);
+        // The RTX stream has its own consecutive sequence numbers.
+        if let Some(rtx_seq) = rtx_seq {
+            assert_eq!(p.header.sequence_number, u16::wrapping_add(rtx_seq, 1));
+        }
+        rtx_seq = Some(p.header.sequence_number);
+
+        assert_eq!(&p.payload[..2], &seq_num.to_be_bytes());
+        assert_eq!(&p.payload[2..], &[seq_num as u8; 4]);
+    }
+
+    stream.close().await?;
+
+    Ok(())
+}
+
+#[test]
+fn test_responder_interceptor_rejects_invalid_size() {
+    // 1 << 16 packets don't fit the u16 sequence space.
+    assert!(Responder::builder().with_log2_size(16).build("").is_err());
+    assert!(Responder::builder().with_log2_size(15).build("").is_ok());
+}
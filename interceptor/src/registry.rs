@@ -21,6 +21,12 @@ impl Registry {
         self.builders.push(builder);
     }
 
+    /// add_interceptor adds an already built Interceptor to the registry. Every Interceptor built
+    /// from the registry shares it, so it must be able to serve all of them.
+    pub fn add_interceptor(&mut self, icpr: Arc<dyn Interceptor + Send + Sync>) {
+        self.builders.push(Box::new(Prebuilt(icpr)));
+    }
+
     /// build constructs a single Interceptor from an InterceptorRegistry
     pub fn build(&self, id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
         if self.builders.is_empty() {
@@ -42,3 +48,99 @@ impl Registry {
         Ok(Chain::new(interceptors?))
     }
 }
+
+/// Prebuilt is an InterceptorBuilder that hands out the same Interceptor on every build.
+struct Prebuilt(Arc<dyn Interceptor + Send + Sync>);
+
+impl InterceptorBuilder for Prebuilt {
+    fn build(&self, _id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
+        Ok(Arc::clone(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::error::Error;
+    use crate::mock::mock_interceptor::MockInterceptor;
+    use crate::mock::mock_stream::MockStream;
+    use crate::report::sender::SenderReport;
+    use crate::stream_info::StreamInfo;
+    use crate::test::timeout_or_fail;
+
+    #[tokio::test]
+    async fn test_registry_chains_interceptors() -> Result<()> {
+        let bound = Arc::new(AtomicUsize::new(0));
+        let closed = Arc::new(AtomicUsize::new(0));
+        let mock = {
+            let bound = Arc::clone(&bound);
+            let closed = Arc::clone(&closed);
+            MockInterceptor {
+                bind_local_stream_fn: Some(Box::new(move |_, writer| {
+                    bound.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async move { writer })
+                })),
+                close_fn: Some(Box::new(move || {
+                    closed.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async move { Err(Error::Other("mock close".to_owned())) })
+                })),
+                ..Default::default()
+            }
+        };
+
+        let mut registry = Registry::new();
+        registry.add(Box::new(
+            SenderReport::builder().with_interval(Duration::from_millis(10)),
+        ));
+        registry.add_interceptor(Arc::new(NoOp {}));
+        registry.add_interceptor(Arc::new(mock));
+        let icpr = registry.build("")?;
+
+        let stream = MockStream::new(
+            &StreamInfo {
+                ssrc: 123456,
+                clock_rate: 90000,
+                ..Default::default()
+            },
+            Arc::clone(&icpr),
+        )
+        .await;
+
+        stream
+            .write_rtp(&rtp::packet::Packet {
+                header: rtp::header::Header {
+                    ssrc: 123456,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .await?;
+        assert!(stream.written_rtp().await.is_some());
+        assert_eq!(bound.load(Ordering::SeqCst), 1);
+
+        // The SenderReport runs in the same chain and reports the written packet.
+        timeout_or_fail(Duration::from_secs(1), async {
+            loop {
+                let pkts = stream.written_rtcp().await.expect("a sender report");
+                let sr = pkts[0]
+                    .as_any()
+                    .downcast_ref::<rtcp::sender_report::SenderReport>()
+                    .expect("a sender report");
+                assert_eq!(sr.ssrc, 123456);
+                if sr.packet_count == 1 {
+                    break;
+                }
+            }
+        })
+        .await;
+
+        // Closing the chain closes all members, and reports the failure of one of them.
+        assert!(stream.close().await.is_err());
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+}
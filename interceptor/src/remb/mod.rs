@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod remb_test;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rtcp::payload_feedbacks::receiver_estimated_maximum_bitrate::ReceiverEstimatedMaximumBitrate;
+use tokio::sync::{mpsc, Mutex};
+use waitgroup::WaitGroup;
+
+use crate::error::{Error, Result};
+use crate::stream_info::StreamInfo;
+use crate::{
+    Attributes, Interceptor, InterceptorBuilder, RTCPReader, RTCPWriter, RTPReader, RTPWriter,
+};
+
+/// TYPE_RTCP_FB_GOOG_REMB is the rtcp-fb type that negotiates REMB feedback for a stream.
+pub const TYPE_RTCP_FB_GOOG_REMB: &str = "goog-remb";
+
+fn stream_support_remb(info: &StreamInfo) -> bool {
+    info.rtcp_feedback
+        .iter()
+        .any(|fb| fb.typ == TYPE_RTCP_FB_GOOG_REMB)
+}
+
+/// RembBuilder can be used to configure Remb Interceptor.
+pub struct RembBuilder {
+    bitrate: f32,
+    interval: Option<Duration>,
+}
+
+impl RembBuilder {
+    /// with_interval sets the REMB send interval for the interceptor.
+    pub fn with_interval(mut self, interval: Duration) -> RembBuilder {
+        self.interval = Some(interval);
+        self
+    }
+}
+
+impl InterceptorBuilder for RembBuilder {
+    fn build(&self, _id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
+        let (close_tx, close_rx) = mpsc::channel(1);
+        Ok(Arc::new(Remb {
+            internal: Arc::new(RembInternal {
+                bitrate: self.bitrate,
+                interval: self.interval.unwrap_or(Duration::from_secs(1)),
+
+                ssrcs: Mutex::new(HashSet::new()),
+                close_rx: Mutex::new(Some(close_rx)),
+            }),
+
+            wg: Mutex::new(Some(WaitGroup::new())),
+            close_tx: Mutex::new(Some(close_tx)),
+        }))
+    }
+}
+
+struct RembInternal {
+    bitrate: f32,
+    interval: Duration,
+
+    ssrcs: Mutex<HashSet<u32>>,
+    close_rx: Mutex<Option<mpsc::Receiver<()>>>,
+}
+
+/// Remb interceptor periodically advertises a fixed maximum bitrate to the senders of the remote
+/// streams that negotiated goog-remb. Streams that didn't negotiate it are left alone. It doesn't
+/// estimate the available bandwidth, the advertised bitrate is the one it was built with.
+pub struct Remb {
+    internal: Arc<RembInternal>,
+
+    pub(crate) wg: Mutex<Option<WaitGroup>>,
+    pub(crate) close_tx: Mutex<Option<mpsc::Sender<()>>>,
+}
+
+impl Remb {
+    /// builder returns a new RembBuilder advertising the given maximum bitrate, in bits per
+    /// second, to the remote senders.
+    pub fn builder(bitrate: f32) -> RembBuilder {
+        RembBuilder {
+            bitrate,
+            interval: None,
+        }
+    }
+
+    async fn is_closed(&self) -> bool {
+        let close_tx = self.close_tx.lock().await;
+        close_tx.is_none()
+    }
+
+    async fn run(
+        rtcp_writer: Arc<dyn RTCPWriter + Send + Sync>,
+        internal: Arc<RembInternal>,
+    ) -> Result<()> {
+        let mut ticker = tokio::time::interval(internal.interval);
+        let mut close_rx = {
+            let mut close_rx = internal.close_rx.lock().await;
+            if let Some(close) = close_rx.take() {
+                close
+            } else {
+                return Err(Error::ErrInvalidCloseRx);
+            }
+        };
+
+        let sender_ssrc = rand::random::<u32>();
+        loop {
+            tokio::select! {
+                _ = ticker.tick() =>{
+                    let mut ssrcs: Vec<u32> = {
+                        let ssrcs = internal.ssrcs.lock().await;
+                        ssrcs.iter().copied().collect()
+                    };
+                    if ssrcs.is_empty() {
+                        continue;
+                    }
+                    ssrcs.sort_unstable();
+
+                    let remb = ReceiverEstimatedMaximumBitrate {
+                        sender_ssrc,
+                        bitrate: internal.bitrate,
+                        ssrcs,
+                    };
+
+                    let a = Attributes::new();
+                    if let Err(err) = rtcp_writer.write(&[Box::new(remb)], &a).await{
+                        log::warn!("failed sending remb: {}", err);
+                    }
+                }
+                _ = close_rx.recv() =>{
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Interceptor for Remb {
+    /// bind_rtcp_reader lets you modify any incoming RTCP packets. It is called once per sender/receiver, however this might
+    /// change in the future. The returned method will be called once per packet batch.
+    async fn bind_rtcp_reader(
+        &self,
+        reader: Arc<dyn RTCPReader + Send + Sync>,
+    ) -> Arc<dyn RTCPReader + Send + Sync> {
+        reader
+    }
+
+    /// bind_rtcp_writer lets you modify any outgoing RTCP packets. It is called once per PeerConnection. The returned method
+    /// will be called once per packet batch.
+    async fn bind_rtcp_writer(
+        &self,
+        writer: Arc<dyn RTCPWriter + Send + Sync>,
+    ) -> Arc<dyn RTCPWriter + Send + Sync> {
+        if self.is_closed().await {
+            return writer;
+        }
+
+        let mut w = {
+            let wait_group = self.wg.lock().await;
+            wait_group.as_ref().map(|wg| wg.worker())
+        };
+        let writer2 = Arc::clone(&writer);
+        let internal = Arc::clone(&self.internal);
+        tokio::spawn(async move {
+            let _d = w.take();
+            if let Err(err) = Remb::run(writer2, internal).await {
+                log::warn!("bind_rtcp_writer REMB Remb::run got error: {}", err);
+            }
+        });
+
+        writer
+    }
+
+    /// bind_local_stream lets you modify any outgoing RTP packets. It is called once for per LocalStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_local_stream(
+        &self,
+        _info: &StreamInfo,
+        writer: Arc<dyn RTPWriter + Send + Sync>,
+    ) -> Arc<dyn RTPWriter + Send + Sync> {
+        writer
+    }
+
+    /// unbind_local_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_local_stream(&self, _info: &StreamInfo) {}
+
+    /// bind_remote_stream lets you modify any incoming RTP packets. It is called once for per RemoteStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_remote_stream(
+        &self,
+        info: &StreamInfo,
+        reader: Arc<dyn RTPReader + Send + Sync>,
+    ) -> Arc<dyn RTPReader + Send + Sync> {
+        if stream_support_remb(info) {
+            let mut ssrcs = self.internal.ssrcs.lock().await;
+            ssrcs.insert(info.ssrc);
+        }
+
+        reader
+    }
+
+    /// unbind_remote_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_remote_stream(&self, info: &StreamInfo) {
+        let mut ssrcs = self.internal.ssrcs.lock().await;
+        ssrcs.remove(&info.ssrc);
+    }
+
+    /// close closes the Interceptor, cleaning up any data if necessary.
+    async fn close(&self) -> Result<()> {
+        {
+            let mut close_tx = self.close_tx.lock().await;
+            close_tx.take();
+        }
+
+        {
+            let mut wait_group = self.wg.lock().await;
+            if let Some(wg) = wait_group.take() {
+                wg.wait().await;
+            }
+        }
+
+        Ok(())
+    }
+}
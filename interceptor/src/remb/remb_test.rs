@@ -0,0 +1,69 @@
+use super::*;
+use crate::mock::mock_stream::MockStream;
+use crate::stream_info::RTCPFeedback;
+use crate::test::timeout_or_fail;
+
+#[tokio::test]
+async fn test_remb_interceptor_negotiated() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> = Remb::builder(1_500_000.0)
+        .with_interval(Duration::from_millis(10))
+        .build("")?;
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 123456,
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: TYPE_RTCP_FB_GOOG_REMB.to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    let pkts = timeout_or_fail(Duration::from_millis(100), stream.written_rtcp())
+        .await
+        .expect("Write rtcp");
+    let remb = pkts[0]
+        .as_any()
+        .downcast_ref::<ReceiverEstimatedMaximumBitrate>()
+        .expect("single packet RTCP Compound Packet expected");
+    assert_eq!(remb.bitrate, 1_500_000.0);
+    assert_eq!(remb.ssrcs, vec![123456]);
+
+    stream.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remb_interceptor_not_negotiated() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> = Remb::builder(1_500_000.0)
+        .with_interval(Duration::from_millis(10))
+        .build("")?;
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 123456,
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "nack".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    // The stream didn't negotiate goog-remb, so the interceptor stays dormant.
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), stream.written_rtcp())
+            .await
+            .is_err()
+    );
+
+    stream.close().await?;
+
+    Ok(())
+}
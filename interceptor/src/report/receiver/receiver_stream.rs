@@ -4,21 +4,20 @@ use async_trait::async_trait;
 use util::sync::Mutex;
 
 use super::*;
+use crate::stats::Jitter;
 use crate::{Attributes, RTPReader};
 
 struct ReceiverStreamInternal {
     ssrc: u32,
     receiver_ssrc: u32,
-    clock_rate: f64,
+    clock_rate: u32,
 
     packets: Vec<u64>,
     started: bool,
     seq_num_cycles: u16,
     last_seq_num: i32,
     last_report_seq_num: i32,
-    last_rtp_time_rtp: u32,
-    last_rtp_time_time: SystemTime,
-    jitter: f64,
+    jitter: Jitter,
     last_sender_report: u32,
     last_sender_report_time: SystemTime,
     total_lost: u32,
@@ -65,20 +64,12 @@ impl ReceiverStreamInternal {
 
                 self.last_seq_num = pkt.header.sequence_number as i32;
             }
-
-            // compute jitter
-            // https://tools.ietf.org/html/rfc3550#page-39
-            let d = now
-                .duration_since(self.last_rtp_time_time)
-                .unwrap_or_else(|_| Duration::from_secs(0))
-                .as_secs_f64()
-                * self.clock_rate
-                - (pkt.header.timestamp as f64 - self.last_rtp_time_rtp as f64);
-            self.jitter += (d.abs() - self.jitter) / 16.0;
         }
 
-        self.last_rtp_time_rtp = pkt.header.timestamp;
-        self.last_rtp_time_time = now;
+        // compute jitter
+        // https://tools.ietf.org/html/rfc3550#page-39
+        self.jitter
+            .update_with_time(now, self.clock_rate, pkt.header.timestamp);
     }
 
     fn process_sender_report(&mut self, now: SystemTime, sr: &rtcp::sender_report::SenderReport) {
@@ -134,7 +125,7 @@ impl ReceiverStreamInternal {
                         }
                     }
                 },
-                jitter: self.jitter as u32,
+                jitter: self.jitter.value(),
             }],
             ..Default::default()
         };
@@ -167,16 +158,14 @@ impl ReceiverStream {
             internal: Mutex::new(ReceiverStreamInternal {
                 ssrc,
                 receiver_ssrc,
-                clock_rate: clock_rate as f64,
+                clock_rate,
 
                 packets: vec![0u64; 128],
                 started: false,
                 seq_num_cycles: 0,
                 last_seq_num: 0,
                 last_report_seq_num: 0,
-                last_rtp_time_rtp: 0,
-                last_rtp_time_time: SystemTime::UNIX_EPOCH,
-                jitter: 0.0,
+                jitter: Jitter::new(),
                 last_sender_report: 0,
                 last_sender_report_time: SystemTime::UNIX_EPOCH,
                 total_lost: 0,
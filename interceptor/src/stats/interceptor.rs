@@ -16,7 +16,7 @@ use tokio::time::Duration;
 use util::sync::Mutex;
 use util::MarshalSize;
 
-use super::{inbound, outbound, StatsContainer};
+use super::{inbound, outbound, Jitter, SequenceGapTracker, StatsContainer};
 use crate::error::Result;
 use crate::stream_info::StreamInfo;
 use crate::{Attributes, Interceptor, RTCPReader, RTCPWriter, RTPReader, RTPWriter};
@@ -35,6 +35,9 @@ enum Message {
         ssrcs: Vec<u32>,
         chan: oneshot::Sender<Vec<Option<outbound::StatsSnapshot>>>,
     },
+    ResetStats {
+        ssrcs: Vec<u32>,
+    },
 }
 
 #[derive(Debug)]
@@ -45,6 +48,10 @@ enum StatsUpdate {
         header_bytes: u64,
         payload_bytes: u64,
         last_packet_timestamp: SystemTime,
+        /// The current interarrival jitter estimate in seconds.
+        jitter: f64,
+        /// The number of packets missing right before this one, 0 if there was no gap.
+        missing: u16,
     },
     /// Stats collected on the sending end(outbound) of an RTP stream.
     OutboundRTP {
@@ -167,6 +174,17 @@ impl StatsInterceptor {
 
         rx.await.unwrap_or_default()
     }
+
+    /// reset_stats discards the stats collected so far for the given SSRCs, both inbound and
+    /// outbound. Counting starts over from zero with the next packet.
+    pub async fn reset_stats(&self, ssrcs: Vec<u32>) {
+        if let Err(e) = self.tx.send(Message::ResetStats { ssrcs }).await {
+            log::debug!(
+                "Failed to reset RTP stream stats in stats task with error: {}",
+                e
+            );
+        }
+    }
 }
 
 async fn run_stats_reducer(mut rx: mpsc::Receiver<Message>) {
@@ -202,6 +220,11 @@ async fn run_stats_reducer(mut rx: mpsc::Receiver<Message>) {
                         let _ = chan.send(result);
 
                     }
+                    Message::ResetStats { ssrcs } => {
+                        for ssrc in ssrcs {
+                            ssrc_stats.reset(ssrc);
+                        }
+                    }
                 }
 
             }
@@ -219,12 +242,16 @@ fn handle_stats_update(ssrc_stats: &mut StatsContainer, ssrc: u32, update: Stats
             header_bytes,
             payload_bytes,
             last_packet_timestamp,
+            jitter,
+            missing,
         } => {
             let stats = ssrc_stats.get_or_create_inbound_stream_stats(ssrc);
 
             stats
                 .rtp_stats
                 .update(header_bytes, payload_bytes, packets, last_packet_timestamp);
+            stats.record_jitter(jitter);
+            stats.record_sequence_gap(missing);
             stats.mark_updated();
         }
         StatsUpdate::OutboundRTP {
@@ -307,9 +334,13 @@ impl Interceptor for StatsInterceptor {
     ) -> Arc<dyn RTPReader + Send + Sync> {
         let mut lock = self.recv_streams.lock();
 
-        let e = lock
-            .entry(info.ssrc)
-            .or_insert_with(|| Arc::new(RTPReadRecorder::new(reader, self.tx.clone())));
+        let e = lock.entry(info.ssrc).or_insert_with(|| {
+            Arc::new(RTPReadRecorder::new(
+                reader,
+                info.clock_rate,
+                self.tx.clone(),
+            ))
+        });
 
         e.clone()
     }
@@ -705,12 +736,25 @@ where
 
 pub struct RTPReadRecorder {
     rtp_reader: Arc<dyn RTPReader + Send + Sync>,
+    clock_rate: u32,
+    jitter: Mutex<Jitter>,
+    sequence_gap_tracker: Mutex<SequenceGapTracker>,
     tx: mpsc::Sender<Message>,
 }
 
 impl RTPReadRecorder {
-    fn new(rtp_reader: Arc<dyn RTPReader + Send + Sync>, tx: mpsc::Sender<Message>) -> Self {
-        Self { rtp_reader, tx }
+    fn new(
+        rtp_reader: Arc<dyn RTPReader + Send + Sync>,
+        clock_rate: u32,
+        tx: mpsc::Sender<Message>,
+    ) -> Self {
+        Self {
+            rtp_reader,
+            clock_rate,
+            jitter: Mutex::new(Jitter::new()),
+            sequence_gap_tracker: Mutex::new(SequenceGapTracker::new()),
+            tx,
+        }
     }
 }
 
@@ -728,6 +772,22 @@ impl RTPReader for RTPReadRecorder {
         attributes: &Attributes,
     ) -> Result<(rtp::packet::Packet, Attributes)> {
         let (pkt, attributes) = self.rtp_reader.read(buf, attributes).await?;
+        let now = SystemTime::now();
+
+        let jitter = {
+            let mut jitter = self.jitter.lock();
+            jitter.update_with_time(now, self.clock_rate, pkt.header.timestamp);
+            if self.clock_rate > 0 {
+                jitter.jitter() / f64::from(self.clock_rate)
+            } else {
+                0.0
+            }
+        };
+
+        let missing = self
+            .sequence_gap_tracker
+            .lock()
+            .update(pkt.header.sequence_number);
 
         let _ = self
             .tx
@@ -737,7 +797,9 @@ impl RTPReader for RTPReadRecorder {
                     packets: 1,
                     header_bytes: pkt.header.marshal_size() as u64,
                     payload_bytes: pkt.payload.len() as u64,
-                    last_packet_timestamp: SystemTime::now(),
+                    last_packet_timestamp: now,
+                    jitter,
+                    missing,
                 },
             })
             .await;
@@ -917,6 +979,7 @@ mod test {
         assert_eq!(recv_snapshot.packets_received(), 1);
         assert_eq!(recv_snapshot.header_bytes_received(), 12);
         assert_eq!(recv_snapshot.payload_bytes_received(), 4);
+        assert_eq!(recv_snapshot.jitter(), 0.0);
 
         let snapshots = icpr.fetch_outbound_stats(vec![234567]).await;
         let send_snapshot = snapshots[0]
@@ -929,6 +992,156 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_stats_interceptor_sequence_gaps() -> Result<()> {
+        let icpr: Arc<_> = Arc::new(StatsInterceptor::new("Hello".to_owned()));
+
+        let recv_stream = MockStream::new(
+            &StreamInfo {
+                ssrc: 123456,
+                ..Default::default()
+            },
+            icpr.clone(),
+        )
+        .await;
+
+        // A single loss at 3, a burst of four losses from 6 to 9 and a double loss at 13 and 14.
+        for sequence_number in [1, 2, 4, 5, 10, 11, 12, 15, 16] {
+            recv_stream
+                .receive_rtp(rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        ssrc: 123456,
+                        sequence_number,
+                        ..Default::default()
+                    },
+                    payload: Bytes::from_static(b"\xde\xad\xbe\xef"),
+                })
+                .await;
+
+            let _ = recv_stream
+                .read_rtp()
+                .await
+                .expect("After calling receive_rtp read_rtp should return Some")?;
+        }
+
+        let snapshots = icpr.fetch_inbound_stats(vec![123456]).await;
+        let recv_snapshot = snapshots[0]
+            .as_ref()
+            .expect("Stats should exist for ssrc: 123456");
+        assert_eq!(recv_snapshot.packets_received(), 9);
+        let gaps = recv_snapshot.sequence_gaps();
+        assert_eq!((gaps.single(), gaps.double(), gaps.burst()), (1, 1, 1));
+
+        icpr.reset_stats(vec![123456]).await;
+
+        // Gaps counted before the reset don't come back, only the new single loss at 18.
+        for sequence_number in [17, 19] {
+            recv_stream
+                .receive_rtp(rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        ssrc: 123456,
+                        sequence_number,
+                        ..Default::default()
+                    },
+                    payload: Bytes::from_static(b"\xde\xad\xbe\xef"),
+                })
+                .await;
+
+            let _ = recv_stream
+                .read_rtp()
+                .await
+                .expect("After calling receive_rtp read_rtp should return Some")?;
+        }
+
+        let snapshots = icpr.fetch_inbound_stats(vec![123456]).await;
+        let recv_snapshot = snapshots[0]
+            .as_ref()
+            .expect("Stats should exist for ssrc: 123456");
+        assert_eq!(recv_snapshot.packets_received(), 2);
+        let gaps = recv_snapshot.sequence_gaps();
+        assert_eq!((gaps.single(), gaps.double(), gaps.burst()), (1, 0, 0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stats_interceptor_reset() -> Result<()> {
+        let icpr: Arc<_> = Arc::new(StatsInterceptor::new("Hello".to_owned()));
+
+        let recv_stream = MockStream::new(
+            &StreamInfo {
+                ssrc: 123456,
+                ..Default::default()
+            },
+            icpr.clone(),
+        )
+        .await;
+
+        let send_stream = MockStream::new(
+            &StreamInfo {
+                ssrc: 234567,
+                ..Default::default()
+            },
+            icpr.clone(),
+        )
+        .await;
+
+        let receive = |payload: &'static [u8]| {
+            let recv_stream = &recv_stream;
+            async move {
+                recv_stream
+                    .receive_rtp(rtp::packet::Packet {
+                        header: rtp::header::Header {
+                            ssrc: 123456,
+                            ..Default::default()
+                        },
+                        payload: Bytes::from_static(payload),
+                    })
+                    .await;
+
+                recv_stream
+                    .read_rtp()
+                    .await
+                    .expect("After calling receive_rtp read_rtp should return Some")
+            }
+        };
+
+        receive(b"\xde\xad").await?;
+        receive(b"\xbe\xef").await?;
+        let _ = send_stream
+            .write_rtp(&rtp::packet::Packet {
+                header: rtp::header::Header {
+                    ssrc: 234567,
+                    ..Default::default()
+                },
+                payload: Bytes::from_static(&[0x13, 0x37]),
+            })
+            .await;
+
+        icpr.reset_stats(vec![123456]).await;
+        receive(b"\x13\x37\x13").await?;
+
+        let snapshots = icpr.fetch_inbound_stats(vec![123456]).await;
+        let recv_snapshot = snapshots[0]
+            .as_ref()
+            .expect("Stats should exist for ssrc: 123456");
+        assert_eq!(recv_snapshot.packets_received(), 1);
+        assert_eq!(recv_snapshot.header_bytes_received(), 12);
+        assert_eq!(recv_snapshot.payload_bytes_received(), 3);
+
+        // Other streams are left alone
+        let snapshots = icpr.fetch_outbound_stats(vec![234567]).await;
+        let send_snapshot = snapshots[0]
+            .as_ref()
+            .expect("Stats should exist for ssrc: 234567");
+        assert_eq!(send_snapshot.packets_sent(), 1);
+
+        icpr.reset_stats(vec![234567]).await;
+        assert!(icpr.fetch_outbound_stats(vec![234567]).await[0].is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_stats_interceptor_rtcp() -> Result<()> {
         let icpr: Arc<_> = Arc::new(StatsInterceptor::with_time_gen("Hello".to_owned(), || {
@@ -0,0 +1,138 @@
+use std::time::SystemTime;
+
+/// Jitter estimates the interarrival jitter of an RTP stream as defined in
+/// [RFC 3550 section 6.4.1](https://www.rfc-editor.org/rfc/rfc3550#section-6.4.1),
+/// following the reference implementation in
+/// [appendix A.8](https://www.rfc-editor.org/rfc/rfc3550#appendix-A.8).
+///
+/// All values are in RTP timestamp units, i.e. in units of the stream's clock rate.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Jitter {
+    /// The relative transit time of the previous packet, [`None`] until the first packet.
+    transit: Option<u32>,
+    jitter: f64,
+}
+
+impl Jitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// update accounts for a packet carrying `rtp_timestamp` that arrived at `arrival`,
+    /// where `arrival` is expressed in the same clock units as the RTP timestamp.
+    ///
+    /// Both values may wrap around, only their differences are meaningful.
+    pub fn update(&mut self, arrival: u32, rtp_timestamp: u32) {
+        let transit = arrival.wrapping_sub(rtp_timestamp);
+
+        if let Some(last_transit) = self.transit {
+            let d = (transit.wrapping_sub(last_transit) as i32).unsigned_abs();
+            self.jitter += (f64::from(d) - self.jitter) / 16.0;
+        }
+
+        self.transit = Some(transit);
+    }
+
+    /// update_with_time is like [`Jitter::update`] but takes the arrival time as wall clock time
+    /// and converts it to clock units using `clock_rate`.
+    pub fn update_with_time(&mut self, arrival: SystemTime, clock_rate: u32, rtp_timestamp: u32) {
+        let since_epoch = arrival
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        // Truncating to 32 bits is fine since only differences of arrival times are used.
+        let arrival = (since_epoch.as_nanos() * u128::from(clock_rate) / 1_000_000_000) as u32;
+
+        self.update(arrival, rtp_timestamp);
+    }
+
+    /// jitter returns the current estimate in RTP timestamp units.
+    pub fn jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    /// value returns the current estimate truncated to an integer, as carried in
+    /// the interarrival jitter field of RTCP reception reports.
+    pub fn value(&self) -> u32 {
+        self.jitter as u32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_jitter_first_packet() {
+        let mut jitter = Jitter::new();
+        jitter.update(1000, 0);
+
+        assert_eq!(jitter.jitter(), 0.0);
+        assert_eq!(jitter.value(), 0);
+    }
+
+    #[test]
+    fn test_jitter_constant_transit() {
+        // 20ms audio frames at 8kHz, arriving exactly one frame apart.
+        let mut jitter = Jitter::new();
+        for i in 0..10u32 {
+            jitter.update(5000 + i * 160, i * 160);
+        }
+
+        assert_eq!(jitter.jitter(), 0.0);
+    }
+
+    #[test]
+    fn test_jitter_update() {
+        // J(i) = J(i-1) + (|D(i-1,i)| - J(i-1))/16
+        let mut jitter = Jitter::new();
+
+        // transit 0
+        jitter.update(0, 0);
+        assert_eq!(jitter.jitter(), 0.0);
+
+        // transit 0, D = 0
+        jitter.update(160, 160);
+        assert_eq!(jitter.jitter(), 0.0);
+
+        // transit 10, D = 10: J = 0 + (10 - 0) / 16
+        jitter.update(330, 320);
+        assert_eq!(jitter.jitter(), 0.625);
+
+        // transit 0, D = -10: J = 0.625 + (10 - 0.625) / 16
+        jitter.update(480, 480);
+        assert_eq!(jitter.jitter(), 1.2109375);
+        assert_eq!(jitter.value(), 1);
+    }
+
+    #[test]
+    fn test_jitter_wraparound() {
+        let mut wrapped = Jitter::new();
+        let mut unwrapped = Jitter::new();
+
+        for (i, delay) in [0u32, 30, 5, 42, 0].into_iter().enumerate() {
+            let ts = i as u32 * 3000;
+            wrapped.update(
+                (u32::MAX - 1000).wrapping_add(ts + delay),
+                (u32::MAX - 2000).wrapping_add(ts),
+            );
+            unwrapped.update(1000 + ts + delay, ts);
+        }
+
+        assert!(wrapped.jitter() > 0.0);
+        assert_eq!(wrapped, unwrapped);
+    }
+
+    #[test]
+    fn test_jitter_update_with_time() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut jitter = Jitter::new();
+
+        // 20ms audio frames at 8kHz, the second frame arrives 1ms (8 ticks) late.
+        jitter.update_with_time(start, 8000, 0);
+        jitter.update_with_time(start + Duration::from_millis(21), 8000, 160);
+
+        assert_eq!(jitter.jitter(), 0.5);
+    }
+}
@@ -5,8 +5,12 @@ use std::time::SystemTime;
 use tokio::time::Duration;
 
 mod interceptor;
+mod jitter;
+mod sequence_gaps;
 
 pub use self::interceptor::StatsInterceptor;
+pub use self::jitter::Jitter;
+pub use self::sequence_gaps::{SequenceGapTracker, SequenceGaps};
 
 pub fn make_stats_interceptor(id: &str) -> Arc<StatsInterceptor> {
     Arc::new(StatsInterceptor::new(id.to_owned()))
@@ -18,7 +22,7 @@ mod inbound {
 
     use tokio::time::{Duration, Instant};
 
-    use super::{RTCPStats, RTPStats};
+    use super::{RTCPStats, RTPStats, SequenceGaps};
 
     #[derive(Debug, Clone)]
     /// Stats collected for an inbound RTP stream.
@@ -33,6 +37,12 @@ mod inbound {
         /// The last time any stats where update, used for garbage collection to remove obsolete stats.
         last_update: Instant,
 
+        /// The latest interarrival jitter estimate in seconds.
+        jitter: f64,
+
+        /// The gaps in the received sequence numbers, by the number of packets missing.
+        sequence_gaps: SequenceGaps,
+
         /// The number of packets sent as reported in the latest SR from the remote.
         remote_packets_sent: u32,
 
@@ -59,6 +69,8 @@ mod inbound {
                 rtp_stats: RTPStats::default(),
                 rtcp_stats: RTCPStats::default(),
                 last_update: Instant::now(),
+                jitter: 0.0,
+                sequence_gaps: SequenceGaps::default(),
                 remote_packets_sent: 0,
                 remote_bytes_sent: 0,
                 remote_reports_sent: 0,
@@ -82,6 +94,14 @@ mod inbound {
             self.last_update.elapsed()
         }
 
+        pub(super) fn record_jitter(&mut self, jitter: f64) {
+            self.jitter = jitter;
+        }
+
+        pub(super) fn record_sequence_gap(&mut self, missing: u16) {
+            self.sequence_gaps.record(missing);
+        }
+
         pub(super) fn record_sender_report(&mut self, packets_sent: u32, bytes_sent: u32) {
             self.remote_reports_sent += 1;
             self.remote_packets_sent = packets_sent;
@@ -110,6 +130,12 @@ mod inbound {
         /// Common RTCP stats derived from inbound and outbound RTCP packets.
         rtcp_stats: RTCPStats,
 
+        /// The latest interarrival jitter estimate in seconds.
+        jitter: f64,
+
+        /// The gaps in the received sequence numbers, by the number of packets missing.
+        sequence_gaps: SequenceGaps,
+
         /// The number of packets sent as reported in the latest SR from the remote.
         remote_packets_sent: u32,
 
@@ -147,6 +173,17 @@ mod inbound {
             self.rtp_stats.last_packet_timestamp
         }
 
+        /// jitter returns the interarrival jitter of the stream in seconds.
+        pub fn jitter(&self) -> f64 {
+            self.jitter
+        }
+
+        /// sequence_gaps returns the gaps in the received sequence numbers, counted by the
+        /// number of consecutive packets missing.
+        pub fn sequence_gaps(&self) -> SequenceGaps {
+            self.sequence_gaps
+        }
+
         pub fn nacks_sent(&self) -> u64 {
             self.rtcp_stats.nack_count
         }
@@ -188,6 +225,8 @@ mod inbound {
             Self {
                 rtp_stats: stream_stats.rtp_stats.clone(),
                 rtcp_stats: stream_stats.rtcp_stats.clone(),
+                jitter: stream_stats.jitter,
+                sequence_gaps: stream_stats.sequence_gaps,
                 remote_packets_sent: stream_stats.remote_packets_sent,
                 remote_bytes_sent: stream_stats.remote_bytes_sent,
                 remote_reports_sent: stream_stats.remote_reports_sent,
@@ -474,6 +513,11 @@ impl StatsContainer {
         self.outbound_stats.get(&ssrc)
     }
 
+    fn reset(&mut self, ssrc: u32) {
+        self.inbound_stats.remove(&ssrc);
+        self.outbound_stats.remove(&ssrc);
+    }
+
     fn remove_stale_entries(&mut self) {
         const MAX_AGE: Duration = Duration::from_secs(60);
 
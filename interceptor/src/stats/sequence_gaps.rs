@@ -0,0 +1,129 @@
+/// SequenceGapTracker tracks the sequence numbers of an RTP stream to find the gaps in them.
+///
+/// Gaps are found as packets arrive: a reordered packet shows up as a gap even when the missing
+/// packet follows shortly after, which is then ignored like any other late packet.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceGapTracker {
+    /// The highest sequence number seen so far, [`None`] until the first packet.
+    highest: Option<u16>,
+}
+
+impl SequenceGapTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// update accounts for a packet carrying `sequence_number` and returns the number of
+    /// packets missing right before it, 0 for in order, duplicate and late packets.
+    pub fn update(&mut self, sequence_number: u16) -> u16 {
+        let highest = match self.highest {
+            Some(highest) => highest,
+            None => {
+                self.highest = Some(sequence_number);
+                return 0;
+            }
+        };
+
+        let diff = sequence_number.wrapping_sub(highest);
+        if diff == 0 || diff >= 0x8000 {
+            return 0;
+        }
+        self.highest = Some(sequence_number);
+
+        diff - 1
+    }
+}
+
+/// SequenceGaps counts the gaps in the sequence numbers of an RTP stream by the number of
+/// consecutive packets missing, which tells random loss apart from bursts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceGaps {
+    single: u64,
+    double: u64,
+    burst: u64,
+}
+
+impl SequenceGaps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// record counts a gap of `missing` consecutive packets, nothing if `missing` is 0.
+    pub fn record(&mut self, missing: u16) {
+        match missing {
+            0 => {}
+            1 => self.single += 1,
+            2 => self.double += 1,
+            _ => self.burst += 1,
+        }
+    }
+
+    /// single returns the number of gaps of a single lost packet.
+    pub fn single(&self) -> u64 {
+        self.single
+    }
+
+    /// double returns the number of gaps of two consecutive lost packets.
+    pub fn double(&self) -> u64 {
+        self.double
+    }
+
+    /// burst returns the number of gaps of three or more consecutive lost packets.
+    pub fn burst(&self) -> u64 {
+        self.burst
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Feeds the sequence numbers through a tracker, returning the gaps found.
+    fn record_all(sequence_numbers: &[u16]) -> (Vec<u16>, (u64, u64, u64)) {
+        let mut tracker = SequenceGapTracker::new();
+        let mut gaps = SequenceGaps::new();
+        let missing: Vec<u16> = sequence_numbers
+            .iter()
+            .map(|&seq| {
+                let missing = tracker.update(seq);
+                gaps.record(missing);
+                missing
+            })
+            .collect();
+
+        (missing, (gaps.single(), gaps.double(), gaps.burst()))
+    }
+
+    #[test]
+    fn test_sequence_gaps_in_order() {
+        let (missing, counts) = record_all(&(100..200).collect::<Vec<u16>>());
+
+        assert!(missing.iter().all(|&missing| missing == 0));
+        assert_eq!(counts, (0, 0, 0));
+    }
+
+    #[test]
+    fn test_sequence_gaps_sizes() {
+        let (missing, counts) = record_all(&[1, 3, 6, 10, 21, 23]);
+
+        assert_eq!(missing, vec![0, 1, 2, 3, 10, 1]);
+        assert_eq!(counts, (2, 1, 2));
+    }
+
+    #[test]
+    fn test_sequence_gaps_late_and_duplicate() {
+        // The missing packet arriving late and a duplicate change nothing.
+        let (missing, counts) = record_all(&[10, 12, 11, 12, 13]);
+
+        assert_eq!(missing, vec![0, 1, 0, 0, 0]);
+        assert_eq!(counts, (1, 0, 0));
+    }
+
+    #[test]
+    fn test_sequence_gaps_wraparound() {
+        let (missing, counts) = record_all(&[65533, 65534, 1, 2]);
+
+        assert_eq!(missing, vec![0, 0, 2, 0]);
+        assert_eq!(counts, (0, 1, 0));
+    }
+}
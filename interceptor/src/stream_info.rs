@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+use crate::nack::MAX_LOG2_SIZE;
 use crate::Attributes;
 
 /// RTPHeaderExtension represents a negotiated RFC5285 RTP header extension.
@@ -20,6 +22,12 @@ pub struct StreamInfo {
     pub channels: u16,
     pub sdp_fmtp_line: String,
     pub rtcp_feedback: Vec<RTCPFeedback>,
+    pub nack_settings: NackSettings,
+    /// The SSRC of the RTX stream retransmissions are sent on (RFC 4588), 0 if RTX wasn't
+    /// negotiated and lost packets are resent as they are.
+    pub ssrc_retransmission: u32,
+    /// The payload type of the RTX stream.
+    pub payload_type_retransmission: u8,
 }
 
 /// RTCPFeedback signals the connection to use additional RTCP packet types.
@@ -35,3 +43,44 @@ pub struct RTCPFeedback {
     /// For example, type="nack" parameter="pli" will send Picture Loss Indicator packets.
     pub parameter: String,
 }
+
+/// NackSettings configures how a local stream answers NACKs with retransmissions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NackSettings {
+    /// Whether sent packets are kept so that they can be retransmitted. When disabled NACKs
+    /// for the stream are ignored.
+    pub rtx_enabled: bool,
+
+    /// The number of packets kept for retransmission as a power of two, see
+    /// [`ResponderBuilder::with_log2_size`](crate::nack::responder::ResponderBuilder::with_log2_size).
+    /// [`None`] uses the size the responder was built with.
+    pub log2_history_size: Option<u8>,
+
+    /// The maximum number of times a single packet is retransmitted, unlimited if [`None`].
+    pub max_retransmissions: Option<u16>,
+}
+
+impl NackSettings {
+    /// validate checks that the history fits the NACK responder's send buffer, failing with
+    /// `Error::ErrInvalidSize` if it's larger than 2^[`MAX_LOG2_SIZE`] packets.
+    pub fn validate(&self) -> Result<()> {
+        if self
+            .log2_history_size
+            .is_some_and(|log2_size| log2_size > MAX_LOG2_SIZE)
+        {
+            return Err(Error::ErrInvalidSize);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for NackSettings {
+    fn default() -> Self {
+        NackSettings {
+            rtx_enabled: true,
+            log2_history_size: None,
+            max_retransmissions: None,
+        }
+    }
+}
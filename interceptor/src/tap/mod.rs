@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tap_test;
+
+use std::sync::Arc;
+
+use util::sync::RwLock;
+
+use super::*;
+use crate::error::Result;
+
+/// TapDirection identifies which way a tapped RTP packet travels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TapDirection {
+    /// Packets received from the remote peer, after decryption.
+    Inbound,
+    /// Packets sent to the remote peer, before encryption.
+    Outbound,
+}
+
+/// TapHandlerFn is called with every RTP packet seen by the tap.
+pub type TapHandlerFn = Box<dyn Fn(&rtp::packet::Packet) + Send + Sync>;
+
+type TapHandler = Arc<RwLock<Option<Arc<TapHandlerFn>>>>;
+
+pub fn make_tap_interceptor() -> Arc<TapInterceptor> {
+    Arc::new(TapInterceptor::default())
+}
+
+/// TapInterceptor hands every inbound and outbound RTP packet to a handler, without
+/// modifying it. It's meant for recording and debugging.
+#[derive(Default)]
+pub struct TapInterceptor {
+    inbound: TapHandler,
+    outbound: TapHandler,
+}
+
+impl TapInterceptor {
+    /// set_handler sets the handler called for every packet travelling in `direction`,
+    /// replacing any previous one.
+    pub fn set_handler(&self, direction: TapDirection, f: TapHandlerFn) {
+        let handler = match direction {
+            TapDirection::Inbound => &self.inbound,
+            TapDirection::Outbound => &self.outbound,
+        };
+        *handler.write() = Some(Arc::new(f));
+    }
+}
+
+#[async_trait]
+impl Interceptor for TapInterceptor {
+    /// bind_rtcp_reader lets you modify any incoming RTCP packets. It is called once per sender/receiver, however this might
+    /// change in the future. The returned method will be called once per packet batch.
+    async fn bind_rtcp_reader(
+        &self,
+        reader: Arc<dyn RTCPReader + Send + Sync>,
+    ) -> Arc<dyn RTCPReader + Send + Sync> {
+        reader
+    }
+
+    /// bind_rtcp_writer lets you modify any outgoing RTCP packets. It is called once per PeerConnection. The returned method
+    /// will be called once per packet batch.
+    async fn bind_rtcp_writer(
+        &self,
+        writer: Arc<dyn RTCPWriter + Send + Sync>,
+    ) -> Arc<dyn RTCPWriter + Send + Sync> {
+        writer
+    }
+
+    /// bind_local_stream lets you modify any outgoing RTP packets. It is called once for per LocalStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_local_stream(
+        &self,
+        _info: &StreamInfo,
+        writer: Arc<dyn RTPWriter + Send + Sync>,
+    ) -> Arc<dyn RTPWriter + Send + Sync> {
+        Arc::new(TapWriter {
+            handler: Arc::clone(&self.outbound),
+            next: writer,
+        })
+    }
+
+    /// unbind_local_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_local_stream(&self, _info: &StreamInfo) {}
+
+    /// bind_remote_stream lets you modify any incoming RTP packets. It is called once for per RemoteStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_remote_stream(
+        &self,
+        _info: &StreamInfo,
+        reader: Arc<dyn RTPReader + Send + Sync>,
+    ) -> Arc<dyn RTPReader + Send + Sync> {
+        Arc::new(TapReader {
+            handler: Arc::clone(&self.inbound),
+            next: reader,
+        })
+    }
+
+    /// unbind_remote_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_remote_stream(&self, _info: &StreamInfo) {}
+
+    /// close closes the Interceptor, cleaning up any data if necessary.
+    async fn close(&self) -> Result<()> {
+        *self.inbound.write() = None;
+        *self.outbound.write() = None;
+        Ok(())
+    }
+}
+
+struct TapWriter {
+    handler: TapHandler,
+    next: Arc<dyn RTPWriter + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPWriter for TapWriter {
+    async fn write(&self, pkt: &rtp::packet::Packet, attributes: &Attributes) -> Result<usize> {
+        let handler = self.handler.read().clone();
+        if let Some(f) = handler {
+            f(pkt);
+        }
+        self.next.write(pkt, attributes).await
+    }
+}
+
+struct TapReader {
+    handler: TapHandler,
+    next: Arc<dyn RTPReader + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPReader for TapReader {
+    async fn read(
+        &self,
+        buf: &mut [u8],
+        attributes: &Attributes,
+    ) -> Result<(rtp::packet::Packet, Attributes)> {
+        let (pkt, attr) = self.next.read(buf, attributes).await?;
+        let handler = self.handler.read().clone();
+        if let Some(f) = handler {
+            f(&pkt);
+        }
+        Ok((pkt, attr))
+    }
+}
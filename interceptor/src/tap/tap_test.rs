@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use super::*;
+use crate::mock::mock_stream::MockStream;
+use crate::test::timeout_or_fail;
+
+#[tokio::test]
+async fn test_tap_interceptor() -> Result<()> {
+    let tap = make_tap_interceptor();
+    let (tapped_tx, mut tapped_rx) = mpsc::unbounded_channel();
+    for direction in [TapDirection::Inbound, TapDirection::Outbound] {
+        let tapped_tx = tapped_tx.clone();
+        tap.set_handler(
+            direction,
+            Box::new(move |pkt: &rtp::packet::Packet| {
+                let _ = tapped_tx.send((direction, pkt.header.sequence_number));
+            }),
+        );
+    }
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            ..Default::default()
+        },
+        tap,
+    )
+    .await;
+
+    let pkt = |sequence_number| rtp::packet::Packet {
+        header: rtp::header::Header {
+            sequence_number,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    stream.write_rtp(&pkt(1)).await?;
+    let written = timeout_or_fail(Duration::from_millis(10), stream.written_rtp())
+        .await
+        .expect("A packet");
+    assert_eq!(written.header.sequence_number, 1);
+    assert_eq!(tapped_rx.recv().await, Some((TapDirection::Outbound, 1)));
+
+    stream.receive_rtp(pkt(2)).await;
+    let read = timeout_or_fail(Duration::from_millis(10), stream.read_rtp())
+        .await
+        .expect("A packet")?;
+    assert_eq!(read.header.sequence_number, 2);
+    assert_eq!(tapped_rx.recv().await, Some((TapDirection::Inbound, 2)));
+
+    stream.close().await?;
+
+    Ok(())
+}
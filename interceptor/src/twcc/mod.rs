@@ -11,6 +11,24 @@ use rtcp::transport_feedbacks::transport_layer_cc::{
     SymbolSizeTypeTcc, SymbolTypeTcc, TransportLayerCc,
 };
 
+use crate::stream_info::StreamInfo;
+use crate::twcc::sender::TRANSPORT_CC_URI;
+
+/// transport_cc_header_extension_id returns the id of the transport wide sequence number
+/// header extension if both the extension and the transport-cc feedback were negotiated for
+/// the stream. Otherwise the TWCC interceptors leave the stream alone.
+pub(crate) fn transport_cc_header_extension_id(info: &StreamInfo) -> Option<u8> {
+    if !info.rtcp_feedback.iter().any(|fb| fb.typ == "transport-cc") {
+        return None;
+    }
+
+    info.rtp_header_extensions
+        .iter()
+        .find(|e| e.uri == TRANSPORT_CC_URI)
+        // 0 is an invalid extension ID
+        .and_then(|e| if e.id == 0 { None } else { Some(e.id as u8) })
+}
+
 #[derive(Default, Debug, PartialEq, Clone)]
 struct PktInfo {
     sequence_number: u32,
@@ -45,6 +63,15 @@ impl Recorder {
     /// record marks a packet with media_ssrc and a transport wide sequence number sequence_number as received at arrival_time.
     pub fn record(&mut self, media_ssrc: u32, sequence_number: u16, arrival_time: i64) {
         self.media_ssrc = media_ssrc;
+        if sequence_number > 0xf000 && self.last_sequence_number < 0x0fff && self.cycles > 0 {
+            // A packet from before the last wraparound that arrived late belongs to the
+            // previous cycle. It must not move last_sequence_number back across the wrap.
+            self.received_packets.push(PktInfo {
+                sequence_number: (self.cycles - (1 << 16)) | sequence_number as u32,
+                arrival_time,
+            });
+            return;
+        }
         if sequence_number < 0x0fff && self.last_sequence_number > 0xf000 {
             self.cycles += 1 << 16;
         }
@@ -127,7 +154,8 @@ impl Feedback {
 
     fn get_rtcp(&mut self) -> TransportLayerCc {
         self.rtcp.packet_status_count = self.sequence_number_count;
-        self.rtcp.reference_time = self.ref_timestamp64ms as u32;
+        // reference_time is a 24-bit field, so wrap it here like it wraps on the wire
+        self.rtcp.reference_time = (self.ref_timestamp64ms & 0xFFFFFF) as u32;
         self.rtcp.base_sequence_number = self.base_sequence_number;
         while !self.last_chunk.deltas.is_empty() {
             self.chunks.push(self.last_chunk.encode());
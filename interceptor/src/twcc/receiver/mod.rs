@@ -11,8 +11,7 @@ use tokio::time::MissedTickBehavior;
 use util::Unmarshal;
 use waitgroup::WaitGroup;
 
-use crate::twcc::sender::TRANSPORT_CC_URI;
-use crate::twcc::Recorder;
+use crate::twcc::{transport_cc_header_extension_id, Recorder};
 use crate::*;
 
 /// ReceiverBuilder is a InterceptorBuilder for a SenderInterceptor
@@ -209,17 +208,10 @@ impl Interceptor for Receiver {
         info: &StreamInfo,
         reader: Arc<dyn RTPReader + Send + Sync>,
     ) -> Arc<dyn RTPReader + Send + Sync> {
-        let mut hdr_ext_id = 0u8;
-        for e in &info.rtp_header_extensions {
-            if e.uri == TRANSPORT_CC_URI {
-                hdr_ext_id = e.id as u8;
-                break;
-            }
-        }
-        if hdr_ext_id == 0 {
-            // Don't try to read header extension if ID is 0, because 0 is an invalid extension ID
-            return reader;
-        }
+        let hdr_ext_id = match transport_cc_header_extension_id(info) {
+            Some(id) => id,
+            None => return reader,
+        };
 
         let stream = Arc::new(ReceiverStream::new(
             reader,
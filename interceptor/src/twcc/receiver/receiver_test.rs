@@ -9,7 +9,8 @@ use util::Marshal;
 
 use super::*;
 use crate::mock::mock_stream::MockStream;
-use crate::stream_info::RTPHeaderExtension;
+use crate::stream_info::{RTCPFeedback, RTPHeaderExtension};
+use crate::twcc::sender::TRANSPORT_CC_URI;
 
 #[tokio::test]
 async fn test_twcc_receiver_interceptor_before_any_packets() -> Result<()> {
@@ -24,6 +25,10 @@ async fn test_twcc_receiver_interceptor_before_any_packets() -> Result<()> {
                 id: 1,
                 ..Default::default()
             }],
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "transport-cc".to_owned(),
+                ..Default::default()
+            }],
             ..Default::default()
         },
         icpr,
@@ -57,6 +62,10 @@ async fn test_twcc_receiver_interceptor_after_rtp_packets() -> Result<()> {
                 id: 1,
                 ..Default::default()
             }],
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "transport-cc".to_owned(),
+                ..Default::default()
+            }],
             ..Default::default()
         },
         icpr,
@@ -113,6 +122,10 @@ async fn test_twcc_receiver_interceptor_different_delays_between_rtp_packets() -
                 id: 1,
                 ..Default::default()
             }],
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "transport-cc".to_owned(),
+                ..Default::default()
+            }],
             ..Default::default()
         },
         icpr,
@@ -185,6 +198,10 @@ async fn test_twcc_receiver_interceptor_packet_loss() -> Result<()> {
                 id: 1,
                 ..Default::default()
             }],
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "transport-cc".to_owned(),
+                ..Default::default()
+            }],
             ..Default::default()
         },
         icpr,
@@ -292,6 +309,10 @@ async fn test_twcc_receiver_interceptor_overflow() -> Result<()> {
                 id: 1,
                 ..Default::default()
             }],
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "transport-cc".to_owned(),
+                ..Default::default()
+            }],
             ..Default::default()
         },
         icpr,
@@ -359,3 +380,69 @@ async fn test_twcc_receiver_interceptor_overflow() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_twcc_receiver_interceptor_single_lost_packet() -> Result<()> {
+    let builder = Receiver::builder();
+    let icpr = builder.build("")?;
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            rtp_header_extensions: vec![RTPHeaderExtension {
+                uri: TRANSPORT_CC_URI.to_owned(),
+                id: 1,
+                ..Default::default()
+            }],
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "transport-cc".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    // 2 is lost on the way
+    for i in [0, 1, 3] {
+        let mut hdr = rtp::header::Header::default();
+        let tcc = TransportCcExtension {
+            transport_sequence: i,
+        }
+        .marshal()?;
+        hdr.set_extension(1, tcc)?;
+        stream
+            .receive_rtp(rtp::packet::Packet {
+                header: hdr,
+                ..Default::default()
+            })
+            .await;
+    }
+
+    let pkts = stream.written_rtcp().await.unwrap();
+    assert_eq!(pkts.len(), 1);
+    if let Some(cc) = pkts[0].as_any().downcast_ref::<TransportLayerCc>() {
+        assert_eq!(cc.base_sequence_number, 0);
+        assert_eq!(cc.packet_status_count, 4);
+        assert_eq!(
+            cc.packet_chunks,
+            vec![PacketStatusChunk::StatusVectorChunk(StatusVectorChunk {
+                type_tcc: StatusChunkTypeTcc::StatusVectorChunk,
+                symbol_size: SymbolSizeTypeTcc::TwoBit,
+                symbol_list: vec![
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                    SymbolTypeTcc::PacketNotReceived,
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                ],
+            })]
+        );
+    } else {
+        panic!();
+    }
+
+    stream.close().await?;
+
+    Ok(())
+}
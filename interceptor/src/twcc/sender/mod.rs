@@ -10,6 +10,7 @@ use sender_stream::SenderStream;
 use tokio::sync::Mutex;
 use util::Marshal;
 
+use crate::twcc::transport_cc_header_extension_id;
 use crate::{Attributes, RTPWriter, *};
 
 pub(crate) const TRANSPORT_CC_URI: &str =
@@ -73,23 +74,17 @@ impl Interceptor for Sender {
     }
 
     /// bind_local_stream returns a writer that adds a rtp TransportCCExtension
-    /// header with increasing sequence numbers to each outgoing packet.
+    /// header with increasing sequence numbers to each outgoing packet, if transport-cc
+    /// feedback was negotiated for the stream.
     async fn bind_local_stream(
         &self,
         info: &StreamInfo,
         writer: Arc<dyn RTPWriter + Send + Sync>,
     ) -> Arc<dyn RTPWriter + Send + Sync> {
-        let mut hdr_ext_id = 0u8;
-        for e in &info.rtp_header_extensions {
-            if e.uri == TRANSPORT_CC_URI {
-                hdr_ext_id = e.id as u8;
-                break;
-            }
-        }
-        if hdr_ext_id == 0 {
-            // Don't add header extension if ID is 0, because 0 is an invalid extension ID
-            return writer;
-        }
+        let hdr_ext_id = match transport_cc_header_extension_id(info) {
+            Some(id) => id,
+            None => return writer,
+        };
 
         let stream = Arc::new(SenderStream::new(
             writer,
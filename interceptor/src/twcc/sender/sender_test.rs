@@ -6,7 +6,7 @@ use waitgroup::WaitGroup;
 
 use super::*;
 use crate::mock::mock_stream::MockStream;
-use crate::stream_info::RTPHeaderExtension;
+use crate::stream_info::{RTCPFeedback, RTPHeaderExtension};
 
 #[tokio::test]
 async fn test_twcc_sender_interceptor() -> Result<()> {
@@ -30,6 +30,10 @@ async fn test_twcc_sender_interceptor() -> Result<()> {
                             uri: TRANSPORT_CC_URI.to_owned(),
                             id: 1,
                         }],
+                        rtcp_feedback: vec![RTCPFeedback {
+                            typ: "transport-cc".to_owned(),
+                            ..Default::default()
+                        }],
                         ..Default::default()
                     },
                     icpr2,
@@ -83,3 +87,45 @@ async fn test_twcc_sender_interceptor() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_twcc_sender_interceptor_without_transport_cc_feedback() -> Result<()> {
+    // the header extension alone, without transport-cc feedback, leaves packets untouched
+    let icpr = Sender::builder().build("")?;
+    let stream = MockStream::new(
+        &StreamInfo {
+            rtp_header_extensions: vec![RTPHeaderExtension {
+                uri: TRANSPORT_CC_URI.to_owned(),
+                id: 1,
+            }],
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "nack".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    stream
+        .write_rtp(&rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await?;
+
+    let p = tokio::time::timeout(Duration::from_millis(10), stream.written_rtp())
+        .await
+        .expect("written rtp packet not found")
+        .expect("stream.written_rtp none");
+    assert_eq!(p.header.sequence_number, 1);
+    assert!(p.header.get_extension(1).is_none());
+
+    stream.close().await?;
+
+    Ok(())
+}
@@ -364,7 +364,8 @@ fn test_feedback() -> Result<()> {
 
     //"get RTCP"
     {
-        let tests = vec![(320, 1, 5, 1), (1000, 2, 15, 2)];
+        // The last case is past the 24-bit reference time range and wraps around
+        let tests = vec![(320, 1, 5, 1), (1000, 2, 15, 2), (0x1000005 * 64, 3, 5, 3)];
         for (arrival_ts, sequence_number, want_ref_time, want_base_sequence_number) in tests {
             let mut f = Feedback::new(0, 0, 0);
             f.set_base(sequence_number, arrival_ts * 1000);
@@ -563,3 +564,50 @@ fn test_build_feedback_packet_rolling() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_build_feedback_packet_reordered_across_wraparound() -> Result<()> {
+    let mut r = Recorder::new(5000);
+
+    // 65535 is sent before 1, but arrives after it.
+    let mut arrival_time = SCALE_FACTOR_REFERENCE_TIME;
+    add_run(
+        &mut r,
+        &[65534, 1, 65535, 2],
+        &[
+            arrival_time,
+            increase_time(&mut arrival_time, TYPE_TCC_DELTA_SCALE_FACTOR),
+            increase_time(&mut arrival_time, TYPE_TCC_DELTA_SCALE_FACTOR),
+            increase_time(&mut arrival_time, TYPE_TCC_DELTA_SCALE_FACTOR),
+        ],
+    );
+
+    let rtcp_packets = r.build_feedback_packet();
+    assert_eq!(rtcp_packets.len(), 1);
+
+    if let Some(tcc) = rtcp_packets[0].as_any().downcast_ref::<TransportLayerCc>() {
+        assert_eq!(tcc.base_sequence_number, 65534);
+        assert_eq!(tcc.packet_status_count, 5);
+        assert_eq!(
+            tcc.packet_chunks,
+            vec![PacketStatusChunk::StatusVectorChunk(StatusVectorChunk {
+                type_tcc: StatusChunkTypeTcc::StatusVectorChunk,
+                symbol_size: SymbolSizeTypeTcc::TwoBit,
+                symbol_list: vec![
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                    SymbolTypeTcc::PacketNotReceived,
+                    // 1 arrived before 65535, its delta is negative
+                    SymbolTypeTcc::PacketReceivedLargeDelta,
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                ],
+            })]
+        );
+    } else {
+        panic!();
+    }
+
+    marshal_all(&rtcp_packets[..])?;
+
+    Ok(())
+}
@@ -7,7 +7,7 @@ use byteorder::{LittleEndian, WriteBytesExt};
 use bytes::Bytes;
 use rtp::packetizer::Depacketizer;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::io::ogg_reader::*;
 use crate::io::Writer;
 
@@ -169,6 +169,11 @@ impl<W: Write + Seek> OggWriter<W> {
 impl<W: Write + Seek> Writer for OggWriter<W> {
     /// write_rtp adds a new packet and writes the appropriate headers for it
     fn write_rtp(&mut self, packet: &rtp::packet::Packet) -> Result<()> {
+        // The depacketizer passes empty DTX payloads through, but an Ogg page needs data
+        if packet.payload.is_empty() {
+            return Err(Error::ErrInvalidNilPacket);
+        }
+
         let mut opus_packet = rtp::codecs::opus::OpusPacket;
         let payload = opus_packet.depacketize(&packet.payload)?;
 
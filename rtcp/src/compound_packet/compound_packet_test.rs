@@ -178,6 +178,34 @@ fn test_cname() {
         }],
     };
 
+    let no_cname = SourceDescription {
+        chunks: vec![SourceDescriptionChunk {
+            source: 1234,
+            items: vec![SourceDescriptionItem {
+                sdes_type: SdesType::SdesName,
+                text: Bytes::from_static(b"name"),
+            }],
+        }],
+    };
+    let cname_in_second_chunk = SourceDescription {
+        chunks: vec![
+            no_cname.chunks[0].clone(),
+            SourceDescriptionChunk {
+                source: 5678,
+                items: vec![
+                    SourceDescriptionItem {
+                        sdes_type: SdesType::SdesTool,
+                        text: Bytes::from_static(b"tool"),
+                    },
+                    SourceDescriptionItem {
+                        sdes_type: SdesType::SdesCname,
+                        text: Bytes::from_static(b"second"),
+                    },
+                ],
+            },
+        ],
+    };
+
     let tests: Vec<(&str, CompoundPacket, Option<Error>, &str)> = vec![
         (
             "no cname",
@@ -236,12 +264,66 @@ fn test_cname() {
             "goodbye",
             CompoundPacket(vec![
                 Box::<ReceiverReport>::default(),
-                Box::new(cname),
+                Box::new(cname.clone()),
+                Box::<Goodbye>::default(),
+            ]),
+            None,
+            "cname",
+        ),
+        (
+            "multi-chunk SDES / cname in later chunk",
+            CompoundPacket(vec![
+                Box::<ReceiverReport>::default(),
+                Box::new(cname_in_second_chunk.clone()),
+                Box::<Goodbye>::default(),
+            ]),
+            None,
+            "second",
+        ),
+        (
+            "SDES without cname / SDES with cname",
+            CompoundPacket(vec![
+                Box::<ReceiverReport>::default(),
+                Box::new(no_cname.clone()),
+                Box::new(cname.clone()),
                 Box::<Goodbye>::default(),
             ]),
             None,
             "cname",
         ),
+        (
+            "multi-chunk SDES without cname / goodbye",
+            CompoundPacket(vec![
+                Box::<ReceiverReport>::default(),
+                Box::new(SourceDescription {
+                    chunks: vec![no_cname.chunks[0].clone(), no_cname.chunks[0].clone()],
+                }),
+                Box::<Goodbye>::default(),
+            ]),
+            Some(Error::PacketBeforeCname),
+            "",
+        ),
+        (
+            "goodbye before cname",
+            CompoundPacket(vec![
+                Box::<ReceiverReport>::default(),
+                Box::new(no_cname),
+                Box::<Goodbye>::default(),
+                Box::new(cname_in_second_chunk),
+            ]),
+            Some(Error::PacketBeforeCname),
+            "",
+        ),
+        (
+            "goodbye first after report",
+            CompoundPacket(vec![
+                Box::<ReceiverReport>::default(),
+                Box::<Goodbye>::default(),
+                Box::new(cname),
+            ]),
+            Some(Error::PacketBeforeCname),
+            "",
+        ),
     ];
 
     for (name, compound_packet, want_error, text) in tests {
@@ -137,37 +137,11 @@ impl CompoundPacket {
             return Err(Error::BadFirstPacket.into());
         }
 
-        for pkt in &self.0[1..] {
-            // If the number of RecetpionReports exceeds 31 additional ReceiverReports
-            // can be included here.
-            if pkt.as_any().downcast_ref::<ReceiverReport>().is_some() {
-                continue;
-            // A SourceDescription containing a CNAME must be included in every
-            // CompoundPacket.
-            } else if let Some(e) = pkt.as_any().downcast_ref::<SourceDescription>() {
-                let mut has_cname = false;
-                for c in &e.chunks {
-                    for it in &c.items {
-                        if it.sdes_type == SdesType::SdesCname {
-                            has_cname = true
-                        }
-                    }
-                }
-
-                if !has_cname {
-                    return Err(Error::MissingCname.into());
-                }
-
-                return Ok(());
+        // A SourceDescription containing a CNAME must be included in every CompoundPacket,
+        // with only ReceiverReports before it. The CNAME may be in any chunk of any SDES.
+        self.cname()?;
 
-            // Other packets are not permitted before the CNAME
-            } else {
-                return Err(Error::PacketBeforeCname.into());
-            }
-        }
-
-        // CNAME never reached
-        Err(Error::MissingCname.into())
+        Ok(())
     }
 
     /// CNAME returns the CNAME that *must* be present in every CompoundPacket
@@ -186,6 +160,8 @@ impl CompoundPacket {
                     }
                 }
             } else if pkt.as_any().downcast_ref::<ReceiverReport>().is_none() {
+                // Only additional ReceiverReports, for more than 31 ReceptionReports,
+                // are permitted before the CNAME
                 return Err(Error::PacketBeforeCname.into());
             }
         }
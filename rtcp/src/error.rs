@@ -85,6 +85,9 @@ pub enum Error {
     /// Delta exceeds limit.
     #[error("Delta exceed limit")]
     DeltaExceedLimit,
+    /// Sequence number isn't after the previous one.
+    #[error("Sequence number out of order")]
+    SequenceNumberOutOfOrder,
     /// Packet status chunk is not 2 bytes.
     #[error("Packet status chunk must be 2 bytes")]
     PacketStatusChunkLength,
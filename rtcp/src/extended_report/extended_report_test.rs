@@ -182,3 +182,63 @@ fn test_decode() -> Result<()> {
     assert_eq!(actual.to_string(), expected.to_string());
     Ok(())
 }
+
+#[test]
+fn test_decode_padded_dlrr() -> Result<()> {
+    let mut encoded = Bytes::from_static(&[
+        // RTP Header, padding set
+        0xA0, 0xCF, 0x00, 0x06, // byte 0 - 3
+        // Sender SSRC
+        0x01, 0x02, 0x03, 0x04, // byte 4 - 7
+        // DLRR Report
+        0x05, 0x00, 0x00, 0x03, // byte 8 - 11
+        // SSRC 1
+        0x88, 0x88, 0x88, 0x88, // byte 12 - 15
+        // LRR 1
+        0x12, 0x34, 0x56, 0x78, // byte 16 - 19
+        // DLRR 1
+        0x99, 0x99, 0x99, 0x99, // byte 20 - 23
+        // Padding
+        0x00, 0x00, 0x00, 0x04, // byte 24 - 27
+    ]);
+
+    let actual = ExtendedReport::unmarshal(&mut encoded)?;
+    let expected = ExtendedReport {
+        sender_ssrc: 0x01020304,
+        reports: vec![Box::new(DLRRReportBlock {
+            reports: vec![DLRRReport {
+                ssrc: 0x88888888,
+                last_rr: 0x12345678,
+                dlrr: 0x99999999,
+            }],
+        })],
+    };
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_decode_invalid() {
+    let tests = vec![
+        (
+            "length beyond the buffer",
+            Bytes::from_static(&[
+                0x80, 0xCF, 0x00, 0x04, 0x01, 0x02, 0x03, 0x04, 0x05, 0x00, 0x00, 0x03, 0x88, 0x88,
+                0x88, 0x88,
+            ]),
+            error::Error::PacketTooShort,
+        ),
+        (
+            "padding larger than the packet",
+            Bytes::from_static(&[
+                0xA0, 0xCF, 0x00, 0x02, 0x01, 0x02, 0x03, 0x04, 0x00, 0x00, 0x00, 0x09,
+            ]),
+            error::Error::WrongPadding,
+        ),
+    ];
+
+    for (name, mut data, want) in tests {
+        let err = ExtendedReport::unmarshal(&mut data).unwrap_err();
+        assert_eq!(want, err, "Unmarshal {name}: err = {err:?}, want {want:?}");
+    }
+}
@@ -261,33 +261,47 @@ impl Unmarshal for ExtendedReport {
             return Err(error::Error::WrongType.into());
         }
 
-        let sender_ssrc = raw_packet.get_u32();
+        let packet_len = (header.length as usize + 1) * 4;
+        if packet_len < HEADER_LENGTH + SSRC_LENGTH || packet_len > raw_packet_len {
+            return Err(error::Error::PacketTooShort.into());
+        }
+
+        // Report blocks run up to the padding, if any, rather than to the end of the buffer.
+        let mut body = raw_packet.copy_to_bytes(packet_len - HEADER_LENGTH);
+        if header.padding {
+            let padding_len = body[body.len() - 1] as usize;
+            if padding_len == 0 || padding_len > body.len() - SSRC_LENGTH {
+                return Err(error::Error::WrongPadding.into());
+            }
+            body.truncate(body.len() - padding_len);
+        }
+        let body_len = body.len() + HEADER_LENGTH;
+
+        let sender_ssrc = body.get_u32();
 
         let mut offset = HEADER_LENGTH + SSRC_LENGTH;
         let mut reports = vec![];
-        while raw_packet.remaining() > 0 {
-            if offset + XR_HEADER_LENGTH > raw_packet_len {
+        while body.remaining() > 0 {
+            if offset + XR_HEADER_LENGTH > body_len {
                 return Err(error::Error::PacketTooShort.into());
             }
 
-            let block_type: BlockType = raw_packet.chunk()[0].into();
+            let block_type: BlockType = body[0].into();
             let report: Box<dyn Packet + Send + Sync> = match block_type {
-                BlockType::LossRLE => Box::new(LossRLEReportBlock::unmarshal(raw_packet)?),
-                BlockType::DuplicateRLE => {
-                    Box::new(DuplicateRLEReportBlock::unmarshal(raw_packet)?)
-                }
+                BlockType::LossRLE => Box::new(LossRLEReportBlock::unmarshal(&mut body)?),
+                BlockType::DuplicateRLE => Box::new(DuplicateRLEReportBlock::unmarshal(&mut body)?),
                 BlockType::PacketReceiptTimes => {
-                    Box::new(PacketReceiptTimesReportBlock::unmarshal(raw_packet)?)
+                    Box::new(PacketReceiptTimesReportBlock::unmarshal(&mut body)?)
                 }
                 BlockType::ReceiverReferenceTime => {
-                    Box::new(ReceiverReferenceTimeReportBlock::unmarshal(raw_packet)?)
+                    Box::new(ReceiverReferenceTimeReportBlock::unmarshal(&mut body)?)
                 }
-                BlockType::DLRR => Box::new(DLRRReportBlock::unmarshal(raw_packet)?),
+                BlockType::DLRR => Box::new(DLRRReportBlock::unmarshal(&mut body)?),
                 BlockType::StatisticsSummary => {
-                    Box::new(StatisticsSummaryReportBlock::unmarshal(raw_packet)?)
+                    Box::new(StatisticsSummaryReportBlock::unmarshal(&mut body)?)
                 }
-                BlockType::VoIPMetrics => Box::new(VoIPMetricsReportBlock::unmarshal(raw_packet)?),
-                _ => Box::new(UnknownReportBlock::unmarshal(raw_packet)?),
+                BlockType::VoIPMetrics => Box::new(VoIPMetricsReportBlock::unmarshal(&mut body)?),
+                _ => Box::new(UnknownReportBlock::unmarshal(&mut body)?),
             };
 
             offset += report.marshal_size();
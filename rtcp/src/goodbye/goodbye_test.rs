@@ -223,3 +223,86 @@ fn test_goodbye_round_trip() {
         }
     }
 }
+
+#[test]
+fn test_goodbye_reason_padding() -> Result<()> {
+    for (sources, reason) in [
+        (vec![0x902f9e2e], &b"FOO"[..]),
+        (vec![], &b"FOO"[..]),
+        (vec![0x902f9e2e], &b"F"[..]),
+        (vec![0x01020304, 0x05060708], &b"because"[..]),
+    ] {
+        let bye = Goodbye {
+            sources,
+            reason: Bytes::copy_from_slice(reason),
+        };
+        let data = bye.marshal()?;
+        assert_eq!(data.len() % 4, 0, "{bye:?} is not 32-bit aligned");
+
+        let header = Header::unmarshal(&mut data.clone())?;
+        assert!(
+            !header.padding,
+            "{bye:?} must pad the reason with null octets"
+        );
+        assert_eq!((header.length as usize + 1) * 4, data.len());
+        let reason_end = HEADER_LENGTH + bye.sources.len() * SSRC_LENGTH + 1 + reason.len();
+        assert!(data[reason_end..].iter().all(|b| *b == 0));
+
+        // A packet following the BYE in a compound packet must still be found.
+        let pli = crate::payload_feedbacks::picture_loss_indication::PictureLossIndication {
+            sender_ssrc: 1,
+            media_ssrc: 2,
+        };
+        let compound = [data, pli.marshal()?].concat();
+        let packets = crate::packet::unmarshal(&mut compound.as_slice())?;
+        assert_eq!(packets.len(), 2);
+        assert!(packets[0].equal(&bye));
+        assert!(packets[1].equal(&pli));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_goodbye_multiple_sources_with_reason() -> Result<()> {
+    let bye = Goodbye {
+        sources: vec![0x01020304, 0x05060708],
+        reason: Bytes::from_static(b"teardown"),
+    };
+    let expected = Bytes::from_static(&[
+        0x82, 0xcb, 0x00, 0x05, // v=2, sc=2, pt=BYE, len=5
+        0x01, 0x02, 0x03, 0x04, // ssrc
+        0x05, 0x06, 0x07, 0x08, // ssrc
+        0x08, b't', b'e', b'a', // len=8, reason
+        b'r', b'd', b'o', b'w', //
+        b'n', 0x00, 0x00, 0x00, // reason, null padding
+    ]);
+
+    let data = bye.marshal()?;
+    assert_eq!(data, expected);
+    assert_eq!(bye.destination_ssrc(), vec![0x01020304, 0x05060708]);
+
+    let decoded = Goodbye::unmarshal(&mut data.clone())?;
+    assert_eq!(decoded, bye);
+
+    // A reason length byte of zero is an empty reason.
+    let mut zero_reason = Bytes::from_static(&[
+        0x81, 0xcb, 0x00, 0x02, // v=2, sc=1, pt=BYE, len=2
+        0x01, 0x02, 0x03, 0x04, // ssrc
+        0x00, 0x00, 0x00, 0x00, // len=0, null padding
+    ]);
+    let decoded = Goodbye::unmarshal(&mut zero_reason)?;
+    assert_eq!(decoded.sources, vec![0x01020304]);
+    assert!(decoded.reason.is_empty());
+
+    // The reason length must not run past the end of the packet.
+    let mut overrun = Bytes::from_static(&[
+        0x81, 0xcb, 0x00, 0x02, // v=2, sc=1, pt=BYE, len=2
+        0x01, 0x02, 0x03, 0x04, // ssrc
+        0x08, b't', b'e', b'a', // len=8, but only 3 octets follow
+    ]);
+    let err = Goodbye::unmarshal(&mut overrun).unwrap_err();
+    assert_eq!(Error::PacketTooShort, err);
+
+    Ok(())
+}
@@ -38,8 +38,10 @@ impl fmt::Display for Goodbye {
 impl Packet for Goodbye {
     /// Header returns the Header associated with this packet.
     fn header(&self) -> Header {
+        // The reason is padded with null octets rather than RTCP padding (RFC 3550 section 6.6),
+        // so the padding bit is never set.
         Header {
-            padding: get_padding_size(self.raw_size()) != 0,
+            padding: false,
             count: self.sources.len() as u8,
             packet_type: PacketType::Goodbye,
             length: ((self.marshal_size() / 4) - 1) as u16,
@@ -124,8 +126,8 @@ impl Marshal for Goodbye {
             buf.put(self.reason.clone());
         }
 
-        if h.padding {
-            put_padding(buf, self.raw_size());
+        for _ in 0..get_padding_size(self.raw_size()) {
+            buf.put_u8(0);
         }
 
         Ok(self.marshal_size())
@@ -0,0 +1,131 @@
+use super::*;
+
+const SAMPLES: usize = 1000;
+
+fn assert_randomized_within(t: f64, members: usize, senders: usize, we_sent: bool, initial: bool) {
+    let (lo, hi) = (t * 0.5 / COMPENSATION, t * 1.5 / COMPENSATION);
+    let (mut min, mut max) = (f64::MAX, 0f64);
+    for _ in 0..SAMPLES {
+        let interval = compute_rtcp_interval(members, senders, we_sent, 128.0, 400.0, initial);
+        let secs = interval.as_secs_f64();
+        assert!(
+            secs >= lo && secs <= hi,
+            "interval {secs}s out of [{lo}s, {hi}s] for members={members} senders={senders} we_sent={we_sent}"
+        );
+        min = min.min(secs);
+        max = max.max(secs);
+    }
+
+    // The samples should be spread over the range, not stuck at one value.
+    let third = (hi - lo) / 3.0;
+    assert!(min < lo + third, "min {min}s should be near {lo}s");
+    assert!(max > hi - third, "max {max}s should be near {hi}s");
+}
+
+#[test]
+fn test_rtcp_interval_small_session() {
+    // Two members at 64 kb/s with 5% RTCP bandwidth: reports are bound by the minimum interval.
+    assert_eq!(
+        deterministic_rtcp_interval(2, 1, true, 128.0, 400.0, false),
+        5.0
+    );
+    assert_eq!(
+        deterministic_rtcp_interval(2, 1, false, 128.0, 400.0, false),
+        5.0
+    );
+
+    // The minimum is randomized too, to 2.5s..7.5s before compensation (about 2.05s..6.16s).
+    assert_randomized_within(5.0, 2, 1, true, false);
+}
+
+#[test]
+fn test_rtcp_interval_large_session() {
+    // 1000 members, 10 of which are senders: senders share a quarter of the bandwidth ...
+    let sender = deterministic_rtcp_interval(1000, 10, true, 128.0, 400.0, false);
+    assert!((sender - 10.0 * 128.0 / 100.0).abs() < 1e-9, "{sender}");
+    assert_randomized_within(sender, 1000, 10, true, false);
+
+    // ... and the 990 receivers share the rest.
+    let receiver = deterministic_rtcp_interval(1000, 10, false, 128.0, 400.0, false);
+    assert!(
+        (receiver - 990.0 * 128.0 / 300.0).abs() < 1e-9,
+        "{receiver}"
+    );
+    assert_randomized_within(receiver, 1000, 10, false, false);
+}
+
+#[test]
+fn test_rtcp_interval_many_senders() {
+    // With more than a quarter of members sending, everyone shares the whole bandwidth.
+    let t = deterministic_rtcp_interval(100, 50, true, 128.0, 400.0, false);
+    assert!((t - 100.0 * 128.0 / 400.0).abs() < 1e-9, "{t}");
+    assert_eq!(
+        t,
+        deterministic_rtcp_interval(100, 50, false, 128.0, 400.0, false)
+    );
+    assert_randomized_within(t, 100, 50, false, false);
+}
+
+#[test]
+fn test_rtcp_interval_no_bandwidth() {
+    // No RTCP bandwidth means never sending, rather than panicking.
+    assert_eq!(
+        compute_rtcp_interval(2, 1, true, 128.0, 0.0, false),
+        Duration::MAX
+    );
+    // An empty session falls back to the minimum.
+    assert_eq!(
+        deterministic_rtcp_interval(0, 0, false, 128.0, 0.0, false),
+        5.0
+    );
+}
+
+#[test]
+fn test_rtcp_interval_initial() {
+    // Until the first packet is sent the minimum is halved, so a new participant reports early.
+    assert_eq!(
+        deterministic_rtcp_interval(2, 1, true, 128.0, 400.0, true),
+        2.5
+    );
+    assert_randomized_within(2.5, 2, 1, true, true);
+
+    // The halved minimum doesn't shorten an interval that is above it anyway.
+    let t = deterministic_rtcp_interval(1000, 10, false, 128.0, 400.0, true);
+    assert_eq!(
+        t,
+        deterministic_rtcp_interval(1000, 10, false, 128.0, 400.0, false)
+    );
+}
+
+#[test]
+fn test_rtcp_timer_reconsideration() {
+    let tp = Instant::now();
+
+    // The timer was armed for 3s, the membership grew meanwhile and the interval is now 5s:
+    // the packet is held back until tn = tp + 5s.
+    let tc = tp + Duration::from_secs(3);
+    assert_eq!(
+        reconsider_rtcp_timer(tp, tc, Duration::from_secs(5)),
+        RtcpTimerAction::Reschedule(Duration::from_secs(2))
+    );
+
+    // When the timer fires again at tn the packet is due.
+    let tc = tp + Duration::from_secs(5);
+    assert_eq!(
+        reconsider_rtcp_timer(tp, tc, Duration::from_secs(5)),
+        RtcpTimerAction::Send
+    );
+    // So it is if the membership shrank instead.
+    let tc = tp + Duration::from_secs(3);
+    assert_eq!(
+        reconsider_rtcp_timer(tp, tc, Duration::from_secs(2)),
+        RtcpTimerAction::Send
+    );
+
+    // Without RTCP bandwidth nothing is ever due.
+    let interval = compute_rtcp_interval(2, 1, true, 128.0, 0.0, false);
+    assert_eq!(
+        reconsider_rtcp_timer(tp, tc, interval),
+        RtcpTimerAction::Reschedule(Duration::MAX - Duration::from_secs(3))
+    );
+}
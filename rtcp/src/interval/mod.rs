@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod interval_test;
+
+use std::time::{Duration, Instant};
+
+/// Minimum average time between RTCP packets from a participant, RFC 3550 section 6.2.
+pub const RTCP_MIN_TIME: Duration = Duration::from_secs(5);
+
+/// Fraction of the RTCP bandwidth reserved for active senders, if they are no more than this
+/// fraction of the members.
+const RTCP_SENDER_BW_FRACTION: f64 = 0.25;
+const RTCP_RCVR_BW_FRACTION: f64 = 1.0 - RTCP_SENDER_BW_FRACTION;
+
+/// To compensate for "timer reconsideration" converging to a value below the intended average.
+const COMPENSATION: f64 = std::f64::consts::E - 1.5;
+
+/// compute_rtcp_interval returns the time until the next compound RTCP packet should be sent,
+/// following the randomized RTCP timer of RFC 3550 appendix A.7.
+///
+/// `members` is the number of participants in the session, including us, of which `senders`
+/// have sent RTP recently. `we_sent` tells whether we are one of them. `avg_rtcp_size` is the
+/// average size in octets of the compound RTCP packets sent and received, including the UDP and
+/// IP headers, and `bandwidth` is the total RTCP bandwidth of the session in octets per second,
+/// usually 5% of the session bandwidth. `initial` is set until we have sent our first compound
+/// RTCP packet, which halves the minimum interval (RFC 3550 section 6.2).
+///
+/// The interval is randomized between 0.5 and 1.5 times the deterministic one and divided by
+/// e-3/2, so it is meant to be recomputed with the current membership when the timer fires and
+/// passed to [`reconsider_rtcp_timer`] ("timer reconsideration", RFC 3550 section 6.3.6).
+pub fn compute_rtcp_interval(
+    members: usize,
+    senders: usize,
+    we_sent: bool,
+    avg_rtcp_size: f64,
+    bandwidth: f64,
+    initial: bool,
+) -> Duration {
+    let t =
+        deterministic_rtcp_interval(members, senders, we_sent, avg_rtcp_size, bandwidth, initial);
+    let t = t * (rand::random::<f64>() + 0.5) / COMPENSATION;
+
+    Duration::try_from_secs_f64(t).unwrap_or(Duration::MAX)
+}
+
+/// RtcpTimerAction tells what to do when the RTCP transmission timer fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtcpTimerAction {
+    /// The packet is due: send it, take the current time as the last transmission time and
+    /// schedule the next packet one interval from now.
+    Send,
+    /// The packet isn't due yet with the current membership: rearm the timer to fire after this
+    /// delay, at the next transmission time.
+    Reschedule(Duration),
+}
+
+/// reconsider_rtcp_timer implements the timer reconsideration of RFC 3550 section 6.3.6 and
+/// appendix A.7 (`OnExpire`).
+///
+/// `tp` is the time the last compound RTCP packet was sent, `tc` the current time and `interval`
+/// the one returned by [`compute_rtcp_interval`] for the current membership. The packet is
+/// sent only if the next transmission time `tn = tp + interval` is not after `tc`, so the timer
+/// backs off when the session grew since it was armed.
+pub fn reconsider_rtcp_timer(tp: Instant, tc: Instant, interval: Duration) -> RtcpTimerAction {
+    let elapsed = tc.saturating_duration_since(tp);
+    if interval <= elapsed {
+        RtcpTimerAction::Send
+    } else {
+        RtcpTimerAction::Reschedule(interval - elapsed)
+    }
+}
+
+/// Returns the interval in seconds before randomization, which is never below RTCP_MIN_TIME, or
+/// half of it for the initial interval.
+fn deterministic_rtcp_interval(
+    members: usize,
+    senders: usize,
+    we_sent: bool,
+    avg_rtcp_size: f64,
+    bandwidth: f64,
+    initial: bool,
+) -> f64 {
+    let rtcp_min_time = if initial {
+        RTCP_MIN_TIME.as_secs_f64() / 2.0
+    } else {
+        RTCP_MIN_TIME.as_secs_f64()
+    };
+    let mut rtcp_bw = bandwidth;
+    let mut n = members;
+
+    // Dedicate a share of the bandwidth to senders, so their reports, which carry the
+    // information needed for synchronization, go out often enough in large sessions.
+    if senders as f64 <= members as f64 * RTCP_SENDER_BW_FRACTION {
+        if we_sent {
+            rtcp_bw *= RTCP_SENDER_BW_FRACTION;
+            n = senders;
+        } else {
+            rtcp_bw *= RTCP_RCVR_BW_FRACTION;
+            n -= senders;
+        }
+    }
+
+    let t = avg_rtcp_size * n as f64 / rtcp_bw;
+    if t.is_nan() {
+        return rtcp_min_time;
+    }
+
+    t.max(rtcp_min_time)
+}
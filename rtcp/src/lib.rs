@@ -46,6 +46,7 @@ mod error;
 pub mod extended_report;
 pub mod goodbye;
 pub mod header;
+pub mod interval;
 pub mod packet;
 pub mod payload_feedbacks;
 pub mod raw_packet;
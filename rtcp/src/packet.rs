@@ -79,6 +79,43 @@ where
     }
 }
 
+/// unmarshal_iter lazily unmarshals the packets of an entire udp datagram, yielding each
+/// typed RTCP packet in turn without splitting the datagram up front.
+pub fn unmarshal_iter<B>(raw_data: B) -> PacketIter<B>
+where
+    B: Buf,
+{
+    PacketIter {
+        raw_data,
+        failed: false,
+    }
+}
+
+/// PacketIter yields the RTCP packets of a datagram, see [`unmarshal_iter`]. A malformed
+/// packet is yielded as an error and ends the iteration, as the packets following it can't
+/// be located.
+pub struct PacketIter<B> {
+    raw_data: B,
+    failed: bool,
+}
+
+impl<B> Iterator for PacketIter<B>
+where
+    B: Buf,
+{
+    type Item = Result<Box<dyn Packet + Send + Sync>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || !self.raw_data.has_remaining() {
+            return None;
+        }
+
+        let p = unmarshaller(&mut self.raw_data);
+        self.failed = p.is_err();
+        Some(p)
+    }
+}
+
 /// unmarshaller is a factory which pulls the first RTCP packet from a bytestream,
 /// and returns it's parsed representation, and the amount of data that was processed.
 pub(crate) fn unmarshaller<B>(raw_data: &mut B) -> Result<Box<dyn Packet + Send + Sync>>
@@ -120,6 +157,67 @@ where
     Ok(p)
 }
 
+/// rewrite_for_forwarding prepares a feedback packet received from a subscriber for
+/// forwarding to the publisher of the stream it refers to, as done by an SFU.
+///
+/// The sender SSRC is replaced with `sender_ssrc`, the SSRC the forwarder uses towards the
+/// publisher, and every media SSRC is translated with `map_media_ssrc` from the SSRC the
+/// subscriber sees to the SSRC of the publisher. NACK, PLI, FIR, RRR and RR packets are
+/// rewritten; any other packet is terminated by the forwarder and `None` is returned.
+pub fn rewrite_for_forwarding<F>(
+    packet: &(dyn Packet + Send + Sync),
+    sender_ssrc: u32,
+    map_media_ssrc: F,
+) -> Option<Box<dyn Packet + Send + Sync>>
+where
+    F: Fn(u32) -> u32,
+{
+    let packet = packet.as_any();
+
+    if let Some(p) = packet.downcast_ref::<TransportLayerNack>() {
+        Some(Box::new(TransportLayerNack {
+            sender_ssrc,
+            media_ssrc: map_media_ssrc(p.media_ssrc),
+            nacks: p.nacks.clone(),
+        }))
+    } else if let Some(p) = packet.downcast_ref::<PictureLossIndication>() {
+        Some(Box::new(PictureLossIndication {
+            sender_ssrc,
+            media_ssrc: map_media_ssrc(p.media_ssrc),
+        }))
+    } else if let Some(p) = packet.downcast_ref::<RapidResynchronizationRequest>() {
+        Some(Box::new(RapidResynchronizationRequest {
+            sender_ssrc,
+            media_ssrc: map_media_ssrc(p.media_ssrc),
+        }))
+    } else if let Some(p) = packet.downcast_ref::<FullIntraRequest>() {
+        Some(Box::new(FullIntraRequest {
+            sender_ssrc,
+            media_ssrc: map_media_ssrc(p.media_ssrc),
+            fir: p
+                .fir
+                .iter()
+                .map(|entry| FirEntry {
+                    ssrc: map_media_ssrc(entry.ssrc),
+                    sequence_number: entry.sequence_number,
+                })
+                .collect(),
+        }))
+    } else if let Some(p) = packet.downcast_ref::<ReceiverReport>() {
+        let mut reports = p.reports.clone();
+        for report in &mut reports {
+            report.ssrc = map_media_ssrc(report.ssrc);
+        }
+        Some(Box::new(ReceiverReport {
+            ssrc: sender_ssrc,
+            reports,
+            profile_extensions: p.profile_extensions.clone(),
+        }))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
     use bytes::Bytes;
@@ -211,6 +309,80 @@ mod test {
         assert!(packet == expected, "Invalid packets");
     }
 
+    #[test]
+    fn test_packet_unmarshal_iter() -> Result<()> {
+        let sr = SenderReport {
+            ssrc: 0x902f9e2e,
+            ntp_time: 0xda8bd1fcdddda05a,
+            rtp_time: 0xaaf4edd5,
+            packet_count: 1,
+            octet_count: 2,
+            reports: vec![ReceptionReport {
+                ssrc: 0xbc5e9a40,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let sdes = SourceDescription {
+            chunks: vec![SourceDescriptionChunk {
+                source: 0x902f9e2e,
+                items: vec![SourceDescriptionItem {
+                    sdes_type: SdesType::SdesCname,
+                    text: Bytes::from_static(b"cname"),
+                }],
+            }],
+        };
+        let nack = TransportLayerNack {
+            sender_ssrc: 0x902f9e2e,
+            media_ssrc: 0xbc5e9a40,
+            nacks: vec![NackPair {
+                packet_id: 42,
+                lost_packets: 0,
+            }],
+        };
+        let pli = PictureLossIndication {
+            sender_ssrc: 0x902f9e2e,
+            media_ssrc: 0xbc5e9a40,
+        };
+        let packets: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::new(sr),
+            Box::new(ReceiverReport::default()),
+            Box::new(sdes),
+            Box::new(nack),
+            Box::new(pli),
+        ];
+        let data = marshal(&packets)?;
+
+        let mut got = vec![];
+        for p in unmarshal_iter(data.clone()) {
+            let p = p?;
+            got.push((p.header().packet_type, p.header().count));
+        }
+        assert_eq!(
+            got,
+            vec![
+                (PacketType::SenderReport, 1),
+                (PacketType::ReceiverReport, 0),
+                (PacketType::SourceDescription, 1),
+                (PacketType::TransportSpecificFeedback, FORMAT_TLN),
+                (PacketType::PayloadSpecificFeedback, FORMAT_PLI),
+            ]
+        );
+
+        let decoded: Vec<_> = unmarshal_iter(data.clone()).collect::<Result<_>>()?;
+        assert!(decoded == packets, "Invalid packets");
+
+        // Iteration ends with an error at the first truncated packet.
+        let mut iter = unmarshal_iter(data.slice(..data.len() - 4));
+        for _ in 0..4 {
+            assert!(iter.next().unwrap().is_ok());
+        }
+        assert_eq!(iter.next().unwrap().err(), Some(Error::PacketTooShort));
+        assert!(iter.next().is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_packet_unmarshal_empty() -> Result<()> {
         let result = unmarshal(&mut Bytes::new());
@@ -245,6 +417,78 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_rewrite_for_forwarding() {
+        const SUBSCRIBER_SSRC: u32 = 0x11111111;
+        const FORWARDED_SSRC: u32 = 0x22222222;
+        const PUBLISHER_SSRC: u32 = 0x33333333;
+        const SFU_SSRC: u32 = 0x44444444;
+
+        let map_media_ssrc = |ssrc| {
+            if ssrc == FORWARDED_SSRC {
+                PUBLISHER_SSRC
+            } else {
+                ssrc
+            }
+        };
+
+        let pli = PictureLossIndication {
+            sender_ssrc: SUBSCRIBER_SSRC,
+            media_ssrc: FORWARDED_SSRC,
+        };
+        let rewritten = rewrite_for_forwarding(&pli, SFU_SSRC, map_media_ssrc)
+            .expect("PLI should be forwarded");
+        assert_eq!(
+            rewritten
+                .as_any()
+                .downcast_ref::<PictureLossIndication>()
+                .expect("rewritten packet should be a PLI"),
+            &PictureLossIndication {
+                sender_ssrc: SFU_SSRC,
+                media_ssrc: PUBLISHER_SSRC,
+            }
+        );
+        assert_eq!(rewritten.destination_ssrc(), vec![PUBLISHER_SSRC]);
+
+        let fir = FullIntraRequest {
+            sender_ssrc: SUBSCRIBER_SSRC,
+            media_ssrc: 0,
+            fir: vec![FirEntry {
+                ssrc: FORWARDED_SSRC,
+                sequence_number: 42,
+            }],
+        };
+        let rewritten = rewrite_for_forwarding(&fir, SFU_SSRC, map_media_ssrc)
+            .expect("FIR should be forwarded");
+        assert_eq!(rewritten.destination_ssrc(), vec![PUBLISHER_SSRC]);
+
+        let rr = ReceiverReport {
+            ssrc: SUBSCRIBER_SSRC,
+            reports: vec![ReceptionReport {
+                ssrc: FORWARDED_SSRC,
+                jitter: 273,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let rewritten =
+            rewrite_for_forwarding(&rr, SFU_SSRC, map_media_ssrc).expect("RR should be forwarded");
+        let rewritten = rewritten
+            .as_any()
+            .downcast_ref::<ReceiverReport>()
+            .expect("rewritten packet should be a RR");
+        assert_eq!(rewritten.ssrc, SFU_SSRC);
+        assert_eq!(rewritten.reports[0].ssrc, PUBLISHER_SSRC);
+        assert_eq!(rewritten.reports[0].jitter, 273);
+
+        let bye = Goodbye {
+            sources: vec![SUBSCRIBER_SSRC],
+            ..Default::default()
+        };
+        assert!(rewrite_for_forwarding(&bye, SFU_SSRC, map_media_ssrc).is_none());
+    }
+
     #[test]
     fn test_packet_unmarshal_firefox() -> Result<()> {
         // issue report from https://github.com/webrtc-rs/srtp/issues/7
@@ -8,7 +8,7 @@ fn test_full_intra_request_unmarshal() {
         (
             "valid",
             Bytes::from_static(&[
-                0x84, 0xce, 0x00, 0x03, // v=2, p=0, FMT=4, PSFB, len=3
+                0x84, 0xce, 0x00, 0x04, // v=2, p=0, FMT=4, PSFB, len=4
                 0x00, 0x00, 0x00, 0x00, // ssrc=0x0
                 0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
                 0x12, 0x34, 0x56, 0x78, // ssrc=0x12345678
@@ -27,7 +27,7 @@ fn test_full_intra_request_unmarshal() {
         (
             "also valid",
             Bytes::from_static(&[
-                0x84, 0xce, 0x00, 0x05, // v=2, p=0, FMT=4, PSFB, len=3
+                0x84, 0xce, 0x00, 0x06, // v=2, p=0, FMT=4, PSFB, len=6
                 0x00, 0x00, 0x00, 0x00, // ssrc=0x0
                 0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
                 0x12, 0x34, 0x56, 0x78, // ssrc=0x12345678
@@ -90,6 +90,40 @@ fn test_full_intra_request_unmarshal() {
             FullIntraRequest::default(),
             Some(Error::WrongType),
         ),
+        (
+            "length too short for ssrcs",
+            Bytes::from_static(&[
+                0x84, 0xce, 0x00, 0x01, // v=2, p=0, FMT=4, PSFB, len=1
+                0x00, 0x00, 0x00, 0x00, // ssrc=0x0
+                0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
+            ]),
+            FullIntraRequest::default(),
+            Some(Error::PacketTooShort),
+        ),
+        (
+            "partial entry",
+            Bytes::from_static(&[
+                0x84, 0xce, 0x00, 0x04, // v=2, p=0, FMT=4, PSFB, len=4
+                0x00, 0x00, 0x00, 0x00, // ssrc=0x0
+                0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
+                0x12, 0x34, 0x56, 0x78, // ssrc=0x12345678, Seqno missing
+            ]),
+            FullIntraRequest::default(),
+            Some(Error::PacketTooShort),
+        ),
+        (
+            "length not a whole number of entries",
+            Bytes::from_static(&[
+                0x84, 0xce, 0x00, 0x05, // v=2, p=0, FMT=4, PSFB, len=5
+                0x00, 0x00, 0x00, 0x00, // ssrc=0x0
+                0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
+                0x12, 0x34, 0x56, 0x78, // ssrc=0x12345678
+                0x42, 0x00, 0x00, 0x00, // Seqno=0x42
+                0x98, 0x76, 0x54, 0x32, // half of an entry
+            ]),
+            FullIntraRequest::default(),
+            Some(Error::WrongMarshalSize),
+        ),
     ];
 
     for (name, mut data, want, want_error) in tests {
@@ -32,6 +32,7 @@ pub struct FullIntraRequest {
 }
 
 const FIR_OFFSET: usize = 8;
+const FIR_ENTRY_LENGTH: usize = 8;
 
 impl fmt::Display for FullIntraRequest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -63,7 +64,7 @@ impl Packet for FullIntraRequest {
     }
 
     fn raw_size(&self) -> usize {
-        HEADER_LENGTH + FIR_OFFSET + self.fir.len() * 8
+        HEADER_LENGTH + FIR_OFFSET + self.fir.len() * FIR_ENTRY_LENGTH
     }
 
     fn as_any(&self) -> &(dyn Any + Send + Sync) {
@@ -141,12 +142,21 @@ impl Unmarshal for FullIntraRequest {
             return Err(Error::WrongType.into());
         }
 
+        // (length+1)*4 must cover the header, both SSRCs and whole FCI entries.
+        let length = h.length as usize * 4;
+        if length < FIR_OFFSET {
+            return Err(Error::PacketTooShort.into());
+        }
+        if (length - FIR_OFFSET) % FIR_ENTRY_LENGTH != 0 {
+            return Err(Error::WrongMarshalSize.into());
+        }
+
         let sender_ssrc = raw_packet.get_u32();
         let media_ssrc = raw_packet.get_u32();
 
         let mut i = HEADER_LENGTH + FIR_OFFSET;
         let mut fir = vec![];
-        while i < HEADER_LENGTH + (h.length * 4) as usize {
+        while i < HEADER_LENGTH + length {
             fir.push(FirEntry {
                 ssrc: raw_packet.get_u32(),
                 sequence_number: raw_packet.get_u8(),
@@ -154,7 +164,7 @@ impl Unmarshal for FullIntraRequest {
             raw_packet.get_u8();
             raw_packet.get_u16();
 
-            i += 8;
+            i += FIR_ENTRY_LENGTH;
         }
 
         if
@@ -123,6 +123,10 @@ impl Unmarshal for PictureLossIndication {
         if h.packet_type != PacketType::PayloadSpecificFeedback || h.count != FORMAT_PLI {
             return Err(Error::WrongType.into());
         }
+        // A PLI carries nothing but the two SSRCs.
+        if h.length as usize != PLI_LENGTH {
+            return Err(Error::InvalidHeader.into());
+        }
 
         let sender_ssrc = raw_packet.get_u32();
         let media_ssrc = raw_packet.get_u32();
@@ -52,6 +52,27 @@ fn test_picture_loss_indication_unmarshal() {
             PictureLossIndication::default(),
             Some(Error::WrongType),
         ),
+        (
+            "wrong fmt for psfb",
+            Bytes::from_static(&[
+                0x82, 0xce, 0x00, 0x02, // v=2, p=0, FMT=2, PSFB, len=1
+                0x00, 0x00, 0x00, 0x00, // ssrc=0x0
+                0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
+            ]),
+            PictureLossIndication::default(),
+            Some(Error::WrongType),
+        ),
+        (
+            "wrong length",
+            Bytes::from_static(&[
+                0x81, 0xce, 0x00, 0x03, // v=2, p=0, FMT=1, PSFB, len=2
+                0x00, 0x00, 0x00, 0x00, // ssrc=0x0
+                0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
+                0x00, 0x00, 0x00, 0x00, // unexpected FCI
+            ]),
+            PictureLossIndication::default(),
+            Some(Error::InvalidHeader),
+        ),
     ];
 
     for (name, mut data, want, want_error) in tests {
@@ -160,3 +181,27 @@ fn test_picture_loss_indication_unmarshal_header() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_picture_loss_indication_chrome_roundtrip() -> Result<()> {
+    // PLI sent by Chrome to request a keyframe
+    let chrome_pli = Bytes::from_static(&[
+        0x81, 0xce, 0x00, 0x02, // v=2, p=0, FMT=1, PSFB, len=2
+        0x00, 0x00, 0x00, 0x01, // sender ssrc=0x1
+        0x5c, 0x2e, 0x1b, 0x83, // media ssrc=0x5c2e1b83
+    ]);
+
+    let pli = PictureLossIndication::unmarshal(&mut chrome_pli.clone())?;
+    assert_eq!(
+        pli,
+        PictureLossIndication {
+            sender_ssrc: 0x1,
+            media_ssrc: 0x5c2e1b83,
+        }
+    );
+    assert_eq!(pli.destination_ssrc(), vec![0x5c2e1b83]);
+    assert_eq!(pli.marshal_size(), chrome_pli.len());
+    assert_eq!(pli.marshal()?, chrome_pli);
+
+    Ok(())
+}
@@ -243,6 +243,9 @@ impl Unmarshal for ReceiverEstimatedMaximumBitrate {
 
         // The next byte is the number of SSRC entries at the end.
         let ssrcs_len = raw_packet.get_u8() as usize;
+        if raw_packet_len < 20 + ssrcs_len * 4 {
+            return Err(Error::PacketTooShort.into());
+        }
 
         // Get the 6-bit exponent value.
         let b17 = raw_packet.get_u8();
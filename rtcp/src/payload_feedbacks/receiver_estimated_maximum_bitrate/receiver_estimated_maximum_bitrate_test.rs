@@ -38,6 +38,39 @@ fn test_receiver_estimated_maximum_bitrate_unmarshal() {
     assert_eq!(packet, expected);
 }
 
+#[test]
+fn test_receiver_estimated_maximum_bitrate_round_trip() {
+    // 1_000_000 = 250000 * 2^2, 6_000_000 = 187500 * 2^5: both fit the 18-bit mantissa exactly
+    for (bitrate, exp, mantissa) in [(1_000_000.0, 2u8, 250_000u32), (6_000_000.0, 5, 187_500)] {
+        let packet = ReceiverEstimatedMaximumBitrate {
+            sender_ssrc: 0x902f9e2e,
+            bitrate,
+            ssrcs: vec![0x12345678, 0x9abcdef0],
+        };
+
+        let mut output = packet.marshal().unwrap();
+        assert_eq!(&output[12..16], b"REMB");
+        assert_eq!(output[16], 2, "num ssrc");
+        assert_eq!(output[17] >> 2, exp, "exponent for {bitrate}");
+        let m = ((output[17] & 3) as u32) << 16 | (output[18] as u32) << 8 | output[19] as u32;
+        assert_eq!(m, mantissa, "mantissa for {bitrate}");
+
+        let decoded = ReceiverEstimatedMaximumBitrate::unmarshal(&mut output).unwrap();
+        assert_eq!(decoded, packet);
+    }
+}
+
+#[test]
+fn test_receiver_estimated_maximum_bitrate_unmarshal_missing_ssrcs() {
+    // Num SSRC claims two entries but only one follows
+    let mut input = Bytes::from_static(&[
+        143, 206, 0, 5, 0, 0, 0, 1, 0, 0, 0, 0, 82, 69, 77, 66, 2, 26, 32, 223, 72, 116, 237, 22,
+    ]);
+
+    let err = ReceiverEstimatedMaximumBitrate::unmarshal(&mut input).unwrap_err();
+    assert_eq!(Error::PacketTooShort, err);
+}
+
 #[test]
 fn test_receiver_estimated_maximum_bitrate_truncate() {
     let input = Bytes::from_static(&[
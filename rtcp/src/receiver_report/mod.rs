@@ -202,6 +202,16 @@ impl Unmarshal for ReceiverReport {
             return Err(Error::WrongType.into());
         }
 
+        // The length in the header must cover exactly the packet, so that the profile
+        // extensions are 32-bit aligned and end where the padding starts.
+        let packet_len = (header.length as usize + 1) * 4;
+        if raw_packet_len < packet_len {
+            return Err(Error::PacketTooShort.into());
+        }
+        if raw_packet_len > packet_len {
+            return Err(Error::InvalidHeader.into());
+        }
+
         let ssrc = raw_packet.get_u32();
 
         let mut offset = RR_REPORT_OFFSET;
@@ -214,12 +224,16 @@ impl Unmarshal for ReceiverReport {
             reports.push(reception_report);
             offset += RECEPTION_REPORT_LENGTH;
         }
-        let profile_extensions = raw_packet.copy_to_bytes(raw_packet.remaining());
-        /*
-        if header.padding && raw_packet.has_remaining() {
-            raw_packet.advance(raw_packet.remaining());
+        let mut profile_extensions = raw_packet.copy_to_bytes(raw_packet.remaining());
+        if header.padding {
+            // The last octet counts the padding octets, itself included, which must not
+            // reach back into the reception reports.
+            let padding_len = profile_extensions.last().map_or(0, |&b| b as usize);
+            if padding_len == 0 || padding_len > profile_extensions.len() {
+                return Err(Error::InvalidHeader.into());
+            }
+            profile_extensions.truncate(profile_extensions.len() - padding_len);
         }
-         */
 
         Ok(ReceiverReport {
             ssrc,
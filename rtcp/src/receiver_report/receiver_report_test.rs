@@ -104,6 +104,66 @@ fn test_receiver_report_unmarshal() {
             ReceiverReport::default(),
             Some(Error::PacketTooShort),
         ),
+        (
+            "valid with padding",
+            Bytes::from_static(&[
+                0xa1, 0xc9, 0x0, 0x8, // v=2, p=1, count=1, RR, len=8
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0xbc, 0x5e, 0x9a, 0x40, // ssrc=0xbc5e9a40
+                0x0, 0x0, 0x0, 0x0, // fracLost=0, totalLost=0
+                0x0, 0x0, 0x46, 0xe1, // lastSeq=0x46e1
+                0x0, 0x0, 0x1, 0x11, // jitter=273
+                0x9, 0xf3, 0x64, 0x32, // lsr=0x9f36432
+                0x0, 0x2, 0x4a, 0x79, // delay=150137
+                0x54, 0x45, 0x53, // profile-specific extension data
+                0x01, // padding
+            ]),
+            ReceiverReport {
+                ssrc: 0x902f9e2e,
+                reports: vec![ReceptionReport {
+                    ssrc: 0xbc5e9a40,
+                    fraction_lost: 0,
+                    total_lost: 0,
+                    last_sequence_number: 0x46e1,
+                    jitter: 273,
+                    last_sender_report: 0x9f36432,
+                    delay: 150137,
+                }],
+                profile_extensions: Bytes::from_static(&[0x54, 0x45, 0x53]),
+            },
+            None,
+        ),
+        (
+            "extension data beyond length",
+            Bytes::from_static(&[
+                0x81, 0xc9, 0x0, 0x7, // v=2, p=0, count=1, RR, len=7
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0xbc, 0x5e, 0x9a, 0x40, // ssrc=0xbc5e9a40
+                0x0, 0x0, 0x0, 0x0, // fracLost=0, totalLost=0
+                0x0, 0x0, 0x46, 0xe1, // lastSeq=0x46e1
+                0x0, 0x0, 0x1, 0x11, // jitter=273
+                0x9, 0xf3, 0x64, 0x32, // lsr=0x9f36432
+                0x0, 0x2, 0x4a, 0x79, // delay=150137
+                0x54, 0x45, 0x53, // unaligned profile-specific extension data
+            ]),
+            ReceiverReport::default(),
+            Some(Error::InvalidHeader),
+        ),
+        (
+            "padding overlaps report",
+            Bytes::from_static(&[
+                0xa1, 0xc9, 0x0, 0x7, // v=2, p=1, count=1, RR, len=7
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0xbc, 0x5e, 0x9a, 0x40, // ssrc=0xbc5e9a40
+                0x0, 0x0, 0x0, 0x0, // fracLost=0, totalLost=0
+                0x0, 0x0, 0x46, 0xe1, // lastSeq=0x46e1
+                0x0, 0x0, 0x1, 0x11, // jitter=273
+                0x9, 0xf3, 0x64, 0x32, // lsr=0x9f36432
+                0x0, 0x2, 0x4a, 0x04, // delay, whose last octet reads as padding
+            ]),
+            ReceiverReport::default(),
+            Some(Error::InvalidHeader),
+        ),
         (
             "nil",
             Bytes::from_static(&[]),
@@ -0,0 +1,187 @@
+use super::*;
+
+const MAX_RUN_LENGTH_CAP: usize = 0x1fff; // 13 bits
+const MAX_ONE_BIT_CAP: usize = 14; // symbols
+const MAX_TWO_BIT_CAP: usize = 7; // symbols
+
+/// TransportLayerCcBuilder builds a TransportLayerCc feedback packet from the packets a receiver
+/// has seen, picking the most compact chunk encoding for their statuses.
+///
+/// Packets must be added in sequence number order. Sequence numbers that are skipped are reported
+/// as not received.
+#[derive(Debug, Clone)]
+pub struct TransportLayerCcBuilder {
+    packet: TransportLayerCc,
+    next_sequence_number: u16,
+    last_timestamp_us: i64,
+    chunk: ChunkBuilder,
+}
+
+impl TransportLayerCcBuilder {
+    /// new creates a builder for the feedback packet starting at base_sequence_number.
+    /// reference_time_us is rounded down to the 64ms resolution of the reference time, and the
+    /// arrival time of the first packet is relative to it.
+    pub fn new(
+        sender_ssrc: u32,
+        media_ssrc: u32,
+        fb_pkt_count: u8,
+        base_sequence_number: u16,
+        reference_time_us: i64,
+    ) -> Self {
+        let reference_time64ms = reference_time_us.div_euclid(64000);
+
+        TransportLayerCcBuilder {
+            packet: TransportLayerCc {
+                sender_ssrc,
+                media_ssrc,
+                fb_pkt_count,
+                base_sequence_number,
+                reference_time: (reference_time64ms & 0xFFFFFF) as u32,
+                ..Default::default()
+            },
+            next_sequence_number: base_sequence_number,
+            last_timestamp_us: reference_time64ms * 64000,
+            chunk: ChunkBuilder::default(),
+        }
+    }
+
+    /// add_received_packet records that sequence_number arrived at arrival_time_us. It fails,
+    /// leaving the builder untouched, if the packet comes before the last one added or if its
+    /// arrival time is too far from the previous one to fit a recv delta; the caller should then
+    /// build this packet and start a new one.
+    pub fn add_received_packet(
+        &mut self,
+        sequence_number: u16,
+        arrival_time_us: i64,
+    ) -> Result<()> {
+        if (sequence_number.wrapping_sub(self.next_sequence_number) as i16) < 0 {
+            return Err(Error::SequenceNumberOutOfOrder.into());
+        }
+
+        let delta = (arrival_time_us - self.last_timestamp_us) / TYPE_TCC_DELTA_SCALE_FACTOR;
+        if delta < i16::MIN as i64 || delta > i16::MAX as i64 {
+            return Err(Error::DeltaExceedLimit.into());
+        }
+        let lost = sequence_number.wrapping_sub(self.next_sequence_number);
+        if self.packet.packet_status_count as usize + lost as usize + 1 > u16::MAX as usize {
+            return Err(Error::TooManyChunks.into());
+        }
+
+        for _ in 0..lost {
+            self.add_symbol(SymbolTypeTcc::PacketNotReceived);
+        }
+
+        let type_tcc_packet = if (0..=u8::MAX as i64).contains(&delta) {
+            SymbolTypeTcc::PacketReceivedSmallDelta
+        } else {
+            SymbolTypeTcc::PacketReceivedLargeDelta
+        };
+        self.add_symbol(type_tcc_packet);
+
+        // Deltas are sent in multiples of 250us, so keep the arrival times on that grid to
+        // avoid accumulating rounding errors.
+        let delta = delta * TYPE_TCC_DELTA_SCALE_FACTOR;
+        self.packet.recv_deltas.push(RecvDelta {
+            type_tcc_packet,
+            delta,
+        });
+        self.last_timestamp_us += delta;
+        self.next_sequence_number = sequence_number.wrapping_add(1);
+
+        Ok(())
+    }
+
+    /// build returns the feedback packet with the packets added so far.
+    pub fn build(mut self) -> TransportLayerCc {
+        while !self.chunk.symbols.is_empty() {
+            let chunk = self.chunk.encode();
+            self.packet.packet_chunks.push(chunk);
+        }
+
+        self.packet
+    }
+
+    fn add_symbol(&mut self, symbol: SymbolTypeTcc) {
+        if !self.chunk.can_add(symbol) {
+            let chunk = self.chunk.encode();
+            self.packet.packet_chunks.push(chunk);
+        }
+        self.chunk.add(symbol);
+        self.packet.packet_status_count += 1;
+    }
+}
+
+/// ChunkBuilder collects packet statuses until they no longer fit a single chunk.
+#[derive(Debug, Default, Clone)]
+struct ChunkBuilder {
+    has_large_delta: bool,
+    has_different_types: bool,
+    symbols: Vec<SymbolTypeTcc>,
+}
+
+impl ChunkBuilder {
+    fn can_add(&self, symbol: SymbolTypeTcc) -> bool {
+        if self.symbols.len() < MAX_TWO_BIT_CAP {
+            return true;
+        }
+        if self.symbols.len() < MAX_ONE_BIT_CAP
+            && !self.has_large_delta
+            && symbol != SymbolTypeTcc::PacketReceivedLargeDelta
+        {
+            return true;
+        }
+        self.symbols.len() < MAX_RUN_LENGTH_CAP
+            && !self.has_different_types
+            && symbol == self.symbols[0]
+    }
+
+    fn add(&mut self, symbol: SymbolTypeTcc) {
+        self.symbols.push(symbol);
+        self.has_large_delta |= symbol == SymbolTypeTcc::PacketReceivedLargeDelta;
+        self.has_different_types |= symbol != self.symbols[0];
+    }
+
+    /// encode takes the symbols that fit in one chunk, favouring a run length chunk, then a
+    /// one bit status vector, then a two bit status vector.
+    fn encode(&mut self) -> PacketStatusChunk {
+        if !self.has_different_types {
+            let chunk = PacketStatusChunk::RunLengthChunk(RunLengthChunk {
+                type_tcc: StatusChunkTypeTcc::RunLengthChunk,
+                packet_status_symbol: self.symbols[0],
+                run_length: self.symbols.len() as u16,
+            });
+            self.symbols.clear();
+            self.has_large_delta = false;
+            return chunk;
+        }
+
+        if self.symbols.len() == MAX_ONE_BIT_CAP && !self.has_large_delta {
+            let chunk = PacketStatusChunk::StatusVectorChunk(StatusVectorChunk {
+                type_tcc: StatusChunkTypeTcc::StatusVectorChunk,
+                symbol_size: SymbolSizeTypeTcc::OneBit,
+                symbol_list: std::mem::take(&mut self.symbols),
+            });
+            self.has_different_types = false;
+            return chunk;
+        }
+
+        // A status vector always carries 7 two bit symbols on the wire, the ones past the
+        // packet status count being "not received"
+        let n = self.symbols.len().min(MAX_TWO_BIT_CAP);
+        let mut symbol_list: Vec<SymbolTypeTcc> = self.symbols.drain(..n).collect();
+        symbol_list.resize(MAX_TWO_BIT_CAP, SymbolTypeTcc::PacketNotReceived);
+        let chunk = PacketStatusChunk::StatusVectorChunk(StatusVectorChunk {
+            type_tcc: StatusChunkTypeTcc::StatusVectorChunk,
+            symbol_size: SymbolSizeTypeTcc::TwoBit,
+            symbol_list,
+        });
+
+        // The leftover symbols start a new chunk
+        self.has_large_delta = self
+            .symbols
+            .contains(&SymbolTypeTcc::PacketReceivedLargeDelta);
+        self.has_different_types = self.symbols.iter().any(|s| *s != self.symbols[0]);
+
+        chunk
+    }
+}
@@ -1,9 +1,12 @@
 #[cfg(test)]
 mod transport_layer_cc_test;
 
+pub mod builder;
+
 use std::any::Any;
 use std::fmt;
 
+pub use builder::TransportLayerCcBuilder;
 use bytes::{Buf, BufMut};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
@@ -159,16 +162,19 @@ impl MarshalSize for RunLengthChunk {
 impl Marshal for RunLengthChunk {
     /// Marshal ..
     fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
+        let mut dst = [0u8; PACKET_STATUS_CHUNK_LENGTH];
+        let mut w = BitWriter::new(&mut dst);
+
         // append 1 bit '0'
-        let mut dst = set_nbits_of_uint16(0, 1, 0, 0)?;
+        w.write(1, 0)?;
 
         // append 2 bit packet_status_symbol
-        dst = set_nbits_of_uint16(dst, 2, 1, self.packet_status_symbol as u16)?;
+        w.write(2, self.packet_status_symbol as u32)?;
 
         // append 13 bit run_length
-        dst = set_nbits_of_uint16(dst, 13, 3, self.run_length)?;
+        w.write(13, self.run_length as u32)?;
 
-        buf.put_u16(dst);
+        buf.put_slice(&dst);
 
         Ok(PACKET_STATUS_CHUNK_LENGTH)
     }
@@ -189,14 +195,15 @@ impl Unmarshal for RunLengthChunk {
         // record type
         let type_tcc = StatusChunkTypeTcc::RunLengthChunk;
 
-        let b0 = raw_packet.get_u8();
-        let b1 = raw_packet.get_u8();
+        let chunk = [raw_packet.get_u8(), raw_packet.get_u8()];
+        let mut r = BitReader::new(&chunk);
+        r.read(1)?;
 
         // get PacketStatusSymbol
-        let packet_status_symbol = get_nbits_from_byte(b0, 1, 2).into();
+        let packet_status_symbol = (r.read(2)? as u16).into();
 
         // get RunLength
-        let run_length = ((get_nbits_from_byte(b0, 3, 5) as usize) << 8) as u16 + (b1 as u16);
+        let run_length = r.read(13)? as u16;
 
         Ok(RunLengthChunk {
             type_tcc,
@@ -236,20 +243,22 @@ impl MarshalSize for StatusVectorChunk {
 impl Marshal for StatusVectorChunk {
     /// Marshal ..
     fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
+        let mut dst = [0u8; PACKET_STATUS_CHUNK_LENGTH];
+        let mut w = BitWriter::new(&mut dst);
+
         // set first bit '1'
-        let mut dst = set_nbits_of_uint16(0, 1, 0, 1)?;
+        w.write(1, 1)?;
 
         // set second bit symbol_size
-        dst = set_nbits_of_uint16(dst, 1, 1, self.symbol_size as u16)?;
+        w.write(1, self.symbol_size as u32)?;
 
-        let num_of_bits = NUM_OF_BITS_OF_SYMBOL_SIZE[self.symbol_size as usize];
+        let num_of_bits = NUM_OF_BITS_OF_SYMBOL_SIZE[self.symbol_size as usize] as usize;
         // append 14 bit symbol_list
-        for (i, s) in self.symbol_list.iter().enumerate() {
-            let index = num_of_bits * (i as u16) + 2;
-            dst = set_nbits_of_uint16(dst, num_of_bits, index, *s as u16)?;
+        for s in &self.symbol_list {
+            w.write(num_of_bits, *s as u32)?;
         }
 
-        buf.put_u16(dst);
+        buf.put_slice(&dst);
 
         Ok(PACKET_STATUS_CHUNK_LENGTH)
     }
@@ -269,32 +278,17 @@ impl Unmarshal for StatusVectorChunk {
 
         let type_tcc = StatusChunkTypeTcc::StatusVectorChunk;
 
-        let b0 = raw_packet.get_u8();
-        let b1 = raw_packet.get_u8();
+        let chunk = [raw_packet.get_u8(), raw_packet.get_u8()];
+        let mut r = BitReader::new(&chunk);
+        r.read(1)?;
 
-        let symbol_size = get_nbits_from_byte(b0, 1, 1).into();
+        let symbol_size: SymbolSizeTypeTcc = (r.read(1)? as u16).into();
 
+        // 14*1bit or 7*2bit symbols fill the rest of the chunk
+        let num_of_bits = NUM_OF_BITS_OF_SYMBOL_SIZE[symbol_size as usize] as usize;
         let mut symbol_list: Vec<SymbolTypeTcc> = vec![];
-        match symbol_size {
-            SymbolSizeTypeTcc::OneBit => {
-                for i in 0..6u16 {
-                    symbol_list.push(get_nbits_from_byte(b0, 2 + i, 1).into());
-                }
-
-                for i in 0..8u16 {
-                    symbol_list.push(get_nbits_from_byte(b1, i, 1).into())
-                }
-            }
-
-            SymbolSizeTypeTcc::TwoBit => {
-                for i in 0..3u16 {
-                    symbol_list.push(get_nbits_from_byte(b0, 2 + i * 2, 2).into());
-                }
-
-                for i in 0..4u16 {
-                    symbol_list.push(get_nbits_from_byte(b1, i * 2, 2).into());
-                }
-            }
+        for _ in 0..14 / num_of_bits {
+            symbol_list.push((r.read(num_of_bits)? as u16).into());
         }
 
         Ok(StatusVectorChunk {
@@ -440,7 +434,8 @@ pub struct TransportLayerCc {
     pub base_sequence_number: u16,
     /// packet_status_count
     pub packet_status_count: u16,
-    /// reference_time
+    /// reference_time, in multiples of 64ms. Only the low 24 bits are sent, so it wraps
+    /// around after 0xFFFFFF.
     pub reference_time: u32,
     /// fb_pkt_count
     pub fb_pkt_count: u8,
@@ -450,6 +445,62 @@ pub struct TransportLayerCc {
     pub recv_deltas: Vec<RecvDelta>,
 }
 
+/// PacketStatus is the feedback for one transport-wide sequence number, with the
+/// status symbol matched up to its recv delta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketStatus {
+    pub sequence_number: u16,
+    pub symbol: SymbolTypeTcc,
+    /// us, relative to the previous received packet (or to reference_time for the first one)
+    pub delta: Option<i64>,
+}
+
+impl TransportLayerCc {
+    /// packet_statuses expands packet_chunks into one entry per sequence number starting at
+    /// base_sequence_number, pairing each received packet with its entry in recv_deltas.
+    /// Padding symbols past packet_status_count are dropped.
+    pub fn packet_statuses(&self) -> Vec<PacketStatus> {
+        let count = self.packet_status_count as usize;
+        let mut statuses = Vec::with_capacity(count);
+        let mut deltas = self.recv_deltas.iter();
+
+        let mut push = |symbol: SymbolTypeTcc| {
+            if statuses.len() >= count {
+                return;
+            }
+            let delta = match symbol {
+                SymbolTypeTcc::PacketReceivedSmallDelta
+                | SymbolTypeTcc::PacketReceivedLargeDelta => deltas.next().map(|d| d.delta),
+                _ => None,
+            };
+            statuses.push(PacketStatus {
+                sequence_number: self
+                    .base_sequence_number
+                    .wrapping_add(statuses.len() as u16),
+                symbol,
+                delta,
+            });
+        };
+
+        for chunk in &self.packet_chunks {
+            match chunk {
+                PacketStatusChunk::RunLengthChunk(c) => {
+                    for _ in 0..c.run_length {
+                        push(c.packet_status_symbol);
+                    }
+                }
+                PacketStatusChunk::StatusVectorChunk(c) => {
+                    for symbol in &c.symbol_list {
+                        push(*symbol);
+                    }
+                }
+            }
+        }
+
+        statuses
+    }
+}
+
 impl fmt::Display for TransportLayerCc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = String::new();
@@ -537,11 +588,12 @@ impl Marshal for TransportLayerCc {
         buf.put_u16(self.base_sequence_number);
         buf.put_u16(self.packet_status_count);
 
-        let reference_time_and_fb_pkt_count = append_nbits_to_uint32(0, 24, self.reference_time);
-        let reference_time_and_fb_pkt_count =
-            append_nbits_to_uint32(reference_time_and_fb_pkt_count, 8, self.fb_pkt_count as u32);
+        let mut reference_time_and_fb_pkt_count = [0u8; 4];
+        let mut w = BitWriter::new(&mut reference_time_and_fb_pkt_count);
+        w.write(24, self.reference_time)?;
+        w.write(8, self.fb_pkt_count as u32)?;
 
-        buf.put_u32(reference_time_and_fb_pkt_count);
+        buf.put_slice(&reference_time_and_fb_pkt_count);
 
         for chunk in &self.packet_chunks {
             let n = chunk.marshal_to(buf)?;
@@ -596,12 +648,10 @@ impl Unmarshal for TransportLayerCc {
         let base_sequence_number = raw_packet.get_u16();
         let packet_status_count = raw_packet.get_u16();
 
-        let mut buf = vec![0u8; 3];
-        buf[0] = raw_packet.get_u8();
-        buf[1] = raw_packet.get_u8();
-        buf[2] = raw_packet.get_u8();
-        let reference_time = get_24bits_from_bytes(&buf);
-        let fb_pkt_count = raw_packet.get_u8();
+        let reference_time_and_fb_pkt_count = raw_packet.get_u32().to_be_bytes();
+        let mut r = BitReader::new(&reference_time_and_fb_pkt_count);
+        let reference_time = r.read(24)?;
+        let fb_pkt_count = r.read(8)? as u8;
         let mut packet_chunks = vec![];
         let mut recv_deltas = vec![];
 
@@ -613,9 +663,7 @@ impl Unmarshal for TransportLayerCc {
             }
 
             let mut chunk_reader = raw_packet.copy_to_bytes(PACKET_STATUS_CHUNK_LENGTH);
-            let b0 = chunk_reader[0];
-
-            let typ = get_nbits_from_byte(b0, 0, 1);
+            let typ = BitReader::new(&chunk_reader).read(1)? as u16;
             let initial_packet_status: PacketStatusChunk;
             match typ.into() {
                 StatusChunkTypeTcc::RunLengthChunk => {
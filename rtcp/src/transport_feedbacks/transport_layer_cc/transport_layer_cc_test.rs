@@ -925,3 +925,165 @@ fn test_transport_layer_cc_marshal() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_transport_layer_cc_reference_time_24bit_boundary() -> Result<()> {
+    let packet = TransportLayerCc {
+        sender_ssrc: 0x01020304,
+        media_ssrc: 0x05060708,
+        base_sequence_number: 1,
+        packet_status_count: 1,
+        reference_time: 0xFFFFFF,
+        fb_pkt_count: 0xFF,
+        packet_chunks: vec![PacketStatusChunk::RunLengthChunk(RunLengthChunk {
+            type_tcc: StatusChunkTypeTcc::RunLengthChunk,
+            packet_status_symbol: SymbolTypeTcc::PacketReceivedSmallDelta,
+            run_length: 1,
+        })],
+        recv_deltas: vec![RecvDelta {
+            type_tcc_packet: SymbolTypeTcc::PacketReceivedSmallDelta,
+            delta: 250,
+        }],
+    };
+
+    let mut raw = packet.marshal()?;
+    assert_eq!(&raw[16..20], &[0xFF, 0xFF, 0xFF, 0xFF]);
+    let decoded = TransportLayerCc::unmarshal(&mut raw)?;
+    assert_eq!(decoded, packet);
+
+    // One past the 24-bit max wraps to zero without spilling into fb_pkt_count
+    let wrapped = TransportLayerCc {
+        reference_time: 0x1000000,
+        fb_pkt_count: 0x5A,
+        ..packet
+    };
+    let mut raw = wrapped.marshal()?;
+    assert_eq!(&raw[16..20], &[0x00, 0x00, 0x00, 0x5A]);
+    let decoded = TransportLayerCc::unmarshal(&mut raw)?;
+    assert_eq!(decoded.reference_time, 0);
+    assert_eq!(decoded.fb_pkt_count, 0x5A);
+
+    Ok(())
+}
+
+#[test]
+fn test_transport_layer_cc_builder() -> Result<()> {
+    // The reference time rounds down to 960ms
+    let mut builder = TransportLayerCcBuilder::new(0x11, 0x22, 3, 100, 1_000_000);
+    builder.add_received_packet(100, 961_000)?;
+    builder.add_received_packet(101, 962_000)?;
+    // 102 to 104 are lost, and 105 needs a large delta
+    builder.add_received_packet(105, 1_062_100)?;
+    for (i, sequence_number) in (106..=124).enumerate() {
+        builder.add_received_packet(sequence_number, 1_063_000 + i as i64 * 1000)?;
+    }
+
+    // Rejected packets leave the builder untouched
+    let err = builder.add_received_packet(110, 1_100_000).unwrap_err();
+    assert_eq!(Error::SequenceNumberOutOfOrder, err);
+    let err = builder.add_received_packet(125, 20_000_000).unwrap_err();
+    assert_eq!(Error::DeltaExceedLimit, err);
+
+    let packet = builder.build();
+    assert_eq!(packet.base_sequence_number, 100);
+    assert_eq!(packet.packet_status_count, 25);
+    assert_eq!(packet.reference_time, 15);
+    assert_eq!(packet.fb_pkt_count, 3);
+    assert_eq!(
+        packet.packet_chunks,
+        vec![
+            PacketStatusChunk::StatusVectorChunk(StatusVectorChunk {
+                type_tcc: StatusChunkTypeTcc::StatusVectorChunk,
+                symbol_size: SymbolSizeTypeTcc::TwoBit,
+                symbol_list: vec![
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                    SymbolTypeTcc::PacketNotReceived,
+                    SymbolTypeTcc::PacketNotReceived,
+                    SymbolTypeTcc::PacketNotReceived,
+                    SymbolTypeTcc::PacketReceivedLargeDelta,
+                    SymbolTypeTcc::PacketReceivedSmallDelta,
+                ],
+            }),
+            PacketStatusChunk::RunLengthChunk(RunLengthChunk {
+                type_tcc: StatusChunkTypeTcc::RunLengthChunk,
+                packet_status_symbol: SymbolTypeTcc::PacketReceivedSmallDelta,
+                run_length: 18,
+            }),
+        ]
+    );
+    assert_eq!(packet.recv_deltas.len(), 22);
+    assert_eq!(
+        packet.recv_deltas[2],
+        RecvDelta {
+            type_tcc_packet: SymbolTypeTcc::PacketReceivedLargeDelta,
+            delta: 100_000,
+        }
+    );
+
+    let mut raw = packet.marshal()?;
+    let decoded = TransportLayerCc::unmarshal(&mut raw)?;
+    assert_eq!(decoded, packet);
+
+    Ok(())
+}
+
+#[test]
+fn test_transport_layer_cc_builder_one_bit_vector() -> Result<()> {
+    // Every other packet is lost, ending with a partial two bit vector
+    let mut builder = TransportLayerCcBuilder::new(0x11, 0x22, 0, 65530, 0);
+    for i in 0..10u16 {
+        builder.add_received_packet(65530u16.wrapping_add(i * 2), i as i64 * 500)?;
+    }
+
+    let packet = builder.build();
+    assert_eq!(packet.packet_status_count, 19);
+    assert_eq!(packet.packet_chunks.len(), 2);
+    match &packet.packet_chunks[0] {
+        PacketStatusChunk::StatusVectorChunk(c) => {
+            assert_eq!(c.symbol_size, SymbolSizeTypeTcc::OneBit);
+            assert_eq!(c.symbol_list.len(), 14);
+        }
+        c => panic!("expected a status vector chunk, got {c:?}"),
+    }
+
+    let mut raw = packet.marshal()?;
+    let decoded = TransportLayerCc::unmarshal(&mut raw)?;
+    assert_eq!(decoded, packet);
+
+    Ok(())
+}
+
+#[test]
+fn test_transport_layer_cc_packet_statuses() -> Result<()> {
+    let mut builder = TransportLayerCcBuilder::new(0x11, 0x22, 0, 65535, 0);
+    builder.add_received_packet(65535, 1000)?;
+    // 0 is lost, and 1 is received 100ms later
+    builder.add_received_packet(1, 101_000)?;
+    let packet = builder.build();
+
+    let mut raw = packet.marshal()?;
+    let decoded = TransportLayerCc::unmarshal(&mut raw)?;
+    assert_eq!(
+        decoded.packet_statuses(),
+        vec![
+            PacketStatus {
+                sequence_number: 65535,
+                symbol: SymbolTypeTcc::PacketReceivedSmallDelta,
+                delta: Some(1000),
+            },
+            PacketStatus {
+                sequence_number: 0,
+                symbol: SymbolTypeTcc::PacketNotReceived,
+                delta: None,
+            },
+            PacketStatus {
+                sequence_number: 1,
+                symbol: SymbolTypeTcc::PacketReceivedLargeDelta,
+                delta: Some(100_000),
+            },
+        ]
+    );
+
+    Ok(())
+}
@@ -22,38 +22,80 @@ pub(crate) fn put_padding(mut buf: &mut [u8], len: usize) {
     }
 }
 
-// set_nbits_of_uint16 will truncate the value to size, left-shift to start_index position and set
-pub(crate) fn set_nbits_of_uint16(
-    src: u16,
-    size: u16,
-    start_index: u16,
-    mut val: u16,
-) -> Result<u16> {
-    if start_index + size > 16 {
-        return Err(Error::InvalidSizeOrStartIndex);
+/// BitWriter packs big-endian (MSB first) bit fields into a byte slice. Fields may cross byte
+/// boundaries; bits that are never written keep their value in the slice.
+pub(crate) struct BitWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> BitWriter<'a> {
+    pub(crate) fn new(buf: &'a mut [u8]) -> Self {
+        BitWriter { buf, pos: 0 }
     }
 
-    // truncate val to size bits
-    val &= (1 << size) - 1;
+    /// write truncates val to n bits and appends them, failing if they don't fit in the slice.
+    pub(crate) fn write(&mut self, n: usize, val: u32) -> Result<()> {
+        if n > 32 {
+            return Err(Error::InvalidSizeOrStartIndex);
+        }
+        if self.pos + n > self.buf.len() * 8 {
+            return Err(Error::BufferTooShort);
+        }
 
-    Ok(src | (val << (16 - size - start_index)))
-}
+        let mut left = n;
+        while left > 0 {
+            // the part of the field that fits in the rest of the current byte
+            let free = 8 - self.pos % 8;
+            let take = free.min(left);
+            let shift = free - take;
+            let mask = (((1u16 << take) - 1) << shift) as u8;
+            let bits = ((val >> (left - take)) as u8) << shift;
+            self.buf[self.pos / 8] = (self.buf[self.pos / 8] & !mask) | (bits & mask);
+            self.pos += take;
+            left -= take;
+        }
 
-// appendBit32 will left-shift and append n bits of val
-pub(crate) fn append_nbits_to_uint32(src: u32, n: u32, val: u32) -> u32 {
-    (src << n) | (val & (0xFFFFFFFF >> (32 - n)))
+        Ok(())
+    }
 }
 
-// getNBit get n bits from 1 byte, begin with a position
-pub(crate) fn get_nbits_from_byte(b: u8, begin: u16, n: u16) -> u16 {
-    let end_shift = 8 - (begin + n);
-    let mask = (0xFF >> begin) & (0xFF << end_shift) as u8;
-    (b & mask) as u16 >> end_shift
+/// BitReader reads big-endian (MSB first) bit fields from a byte slice. Fields may cross byte
+/// boundaries.
+pub(crate) struct BitReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
 }
 
-// get24BitFromBytes get 24bits from `[3]byte` slice
-pub(crate) fn get_24bits_from_bytes(b: &[u8]) -> u32 {
-    ((b[0] as u32) << 16) + ((b[1] as u32) << 8) + (b[2] as u32)
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        BitReader { buf, pos: 0 }
+    }
+
+    /// read consumes the next n bits, failing if fewer are left in the slice.
+    pub(crate) fn read(&mut self, n: usize) -> Result<u32> {
+        if n > 32 {
+            return Err(Error::InvalidSizeOrStartIndex);
+        }
+        if self.pos + n > self.buf.len() * 8 {
+            return Err(Error::PacketTooShort);
+        }
+
+        let mut val = 0u32;
+        let mut left = n;
+        while left > 0 {
+            // the part of the field left in the current byte
+            let free = 8 - self.pos % 8;
+            let take = free.min(left);
+            let mask = ((1u16 << take) - 1) as u8;
+            let bits = (self.buf[self.pos / 8] >> (free - take)) & mask;
+            val = (val << take) | bits as u32;
+            self.pos += take;
+            left -= take;
+        }
+
+        Ok(val)
+    }
 }
 
 #[cfg(test)]
@@ -76,40 +118,82 @@ mod test {
     }
 
     #[test]
-    fn test_set_nbits_of_uint16() -> Result<()> {
-        let tests = vec![
-            ("setOneBit", 0, 1, 8, 1, 128, None),
-            ("setStatusVectorBit", 0, 1, 0, 1, 32768, None),
-            ("setStatusVectorSecondBit", 32768, 1, 1, 1, 49152, None),
-            (
-                "setStatusVectorInnerBitsAndCutValue",
-                49152,
-                2,
-                6,
-                11111,
-                49920,
-                None,
-            ),
-            ("setRunLengthSecondTwoBit", 32768, 2, 1, 1, 40960, None),
-            (
-                "setOneBitOutOfBounds",
-                32768,
-                2,
-                15,
-                1,
-                0,
-                Some("invalid size or startIndex"),
-            ),
-        ];
-
-        for (name, source, size, index, value, result, err) in tests {
-            let res = set_nbits_of_uint16(source, size, index, value);
-            if err.is_some() {
-                assert!(res.is_err(), "setNBitsOfUint16 {name} : should be error");
-            } else if let Ok(got) = res {
-                assert_eq!(got, result, "setNBitsOfUint16 {name}");
-            } else {
-                panic!("setNBitsOfUint16 {name} :unexpected error result");
+    fn test_bit_writer() -> Result<()> {
+        let mut buf = [0u8; 4];
+        let mut w = BitWriter::new(&mut buf);
+        w.write(1, 1)?;
+        w.write(2, 0b10)?;
+        // crosses the first byte boundary
+        w.write(13, 0x1ABC)?;
+        // a 24-bit field does not fit in the 16 bits left
+        assert_eq!(w.write(24, 0xFFFFFF), Err(Error::BufferTooShort));
+        w.write(16, 0x1_2345)?;
+        assert_eq!(w.write(1, 1), Err(Error::BufferTooShort));
+        assert_eq!(buf, [0xDA, 0xBC, 0x23, 0x45]);
+
+        // reference time and feedback packet count of a TCC packet
+        let mut buf = [0u8; 4];
+        let mut w = BitWriter::new(&mut buf);
+        w.write(24, 0x1FFFFFF)?;
+        w.write(8, 0x5A)?;
+        assert_eq!(buf, [0xFF, 0xFF, 0xFF, 0x5A]);
+
+        let mut buf = [0xFFu8; 2];
+        let mut w = BitWriter::new(&mut buf);
+        w.write(0, 1)?;
+        w.write(3, 0)?;
+        assert_eq!(buf, [0x1F, 0xFF], "unwritten bits are left as is");
+
+        let mut buf = [0u8; 8];
+        let mut w = BitWriter::new(&mut buf);
+        assert_eq!(w.write(33, 0), Err(Error::InvalidSizeOrStartIndex));
+        w.write(32, 0xDEADBEEF)?;
+        assert_eq!(buf[..4], [0xDE, 0xAD, 0xBE, 0xEF]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bit_reader() -> Result<()> {
+        let buf = [0xDA, 0xBC, 0x23, 0x45];
+        let mut r = BitReader::new(&buf);
+        assert_eq!(r.read(1)?, 1);
+        assert_eq!(r.read(2)?, 0b10);
+        // crosses the first byte boundary
+        assert_eq!(r.read(13)?, 0x1ABC);
+        assert_eq!(r.read(24), Err(Error::PacketTooShort));
+        assert_eq!(r.read(12)?, 0x234);
+        assert_eq!(r.read(4)?, 0x5);
+        assert_eq!(r.read(1), Err(Error::PacketTooShort));
+        assert_eq!(r.read(0)?, 0);
+
+        let buf = [0xFF, 0xFF, 0xFF, 0x5A];
+        let mut r = BitReader::new(&buf);
+        assert_eq!(r.read(24)?, 0xFFFFFF);
+        assert_eq!(r.read(8)?, 0x5A);
+
+        let buf = [0xDE, 0xAD, 0xBE, 0xEF, 0x00];
+        let mut r = BitReader::new(&buf);
+        assert_eq!(r.read(33), Err(Error::InvalidSizeOrStartIndex));
+        assert_eq!(r.read(32)?, 0xDEADBEEF);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bit_round_trip() -> Result<()> {
+        // Fields of every width at every alignment
+        for offset in 0..8 {
+            for n in 1..=24 {
+                let val = 0xA5A5A5A5 & (u32::MAX >> (32 - n));
+                let mut buf = [0u8; 5];
+                let mut w = BitWriter::new(&mut buf);
+                w.write(offset, 0)?;
+                w.write(n, val)?;
+
+                let mut r = BitReader::new(&buf);
+                r.read(offset)?;
+                assert_eq!(r.read(n)?, val, "offset {offset}, {n} bits");
             }
         }
 
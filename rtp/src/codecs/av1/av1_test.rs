@@ -452,3 +452,139 @@ fn test_split_two_obus_into_two_packets() -> Result<()> {
     );
     Ok(())
 }
+
+fn depacketize_all(packets: &[Bytes]) -> Result<Bytes> {
+    let mut depacketizer = Av1Packet::default();
+    let mut out = vec![];
+    for packet in packets {
+        out.extend_from_slice(&depacketizer.depacketize(packet)?);
+    }
+    Ok(Bytes::from(out))
+}
+
+#[test]
+fn test_depacketize_single_obu() -> Result<()> {
+    let frame = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_FRAME).with_payload(vec![1, 2, 3, 4, 5, 6, 7])
+    ]);
+    let mut payloader = Av1Payloader {};
+    let packets = payloader.payload(1200, &frame)?;
+    assert_eq!(packets.len(), 1);
+
+    let mut depacketizer = Av1Packet::default();
+    assert!(depacketizer.is_partition_head(&packets[0]));
+    assert_eq!(depacketizer.depacketize(&packets[0])?, frame);
+    assert!(!depacketizer.z);
+    assert!(!depacketizer.y);
+    assert_eq!(depacketizer.w, 1);
+    assert!(!depacketizer.n);
+
+    // OBUs sent without a size field are restored with one
+    let frame_without_size = build_av1_frame(&vec![Av1Obu::new(OBU_TYPE_FRAME)
+        .with_extension(OBU_EXTENSION_S1T1)
+        .without_size()
+        .with_payload(vec![1, 2, 3])]);
+    let packets = payloader.payload(1200, &frame_without_size)?;
+    assert_eq!(
+        depacketize_all(&packets)?,
+        build_av1_frame(&vec![Av1Obu::new(OBU_TYPE_FRAME)
+            .with_extension(OBU_EXTENSION_S1T1)
+            .with_payload(vec![1, 2, 3])])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_temporal_unit() -> Result<()> {
+    let mut payloader = Av1Payloader {};
+
+    // Three OBUs: the size of the last element is omitted
+    let frame = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_SEQUENCE_HEADER).with_payload(vec![1, 2, 3]),
+        Av1Obu::new(OBU_TYPE_FRAME_HEADER).with_payload(vec![4, 5]),
+        Av1Obu::new(OBU_TYPE_TILE_GROUP).with_payload(vec![6, 7, 8, 9]),
+    ]);
+    let packets = payloader.payload(1200, &frame)?;
+    assert_eq!(packets.len(), 1);
+    let mut depacketizer = Av1Packet::default();
+    assert_eq!(depacketizer.depacketize(&packets[0])?, frame);
+    assert_eq!(depacketizer.w, 3);
+    assert!(depacketizer.n);
+
+    // Four OBUs: every element is preceded by its size
+    let frame = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_METADATA).with_payload(vec![1]),
+        Av1Obu::new(OBU_TYPE_FRAME_HEADER)
+            .with_extension(OBU_EXTENSION_S1T1)
+            .with_payload(vec![2, 3]),
+        Av1Obu::new(OBU_TYPE_TILE_GROUP).with_payload(vec![4, 5, 6]),
+        Av1Obu::new(OBU_TYPE_TILE_GROUP).with_payload(vec![7, 8, 9, 10]),
+    ]);
+    let packets = payloader.payload(1200, &frame)?;
+    assert_eq!(packets.len(), 1);
+    let mut depacketizer = Av1Packet::default();
+    assert_eq!(depacketizer.depacketize(&packets[0])?, frame);
+    assert_eq!(depacketizer.w, 0);
+    assert!(!depacketizer.n);
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_obu_fragmented_across_three_packets() -> Result<()> {
+    let frame = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_FRAME).with_payload((0..250).collect())
+    ]);
+    let mut payloader = Av1Payloader {};
+    let packets = payloader.payload(100, &frame)?;
+    assert_eq!(packets.len(), 3);
+
+    let mut depacketizer = Av1Packet::default();
+    assert!(depacketizer.is_partition_head(&packets[0]));
+    assert!(depacketizer.depacketize(&packets[0])?.is_empty());
+    assert!(!depacketizer.z);
+    assert!(depacketizer.y);
+
+    assert!(!depacketizer.is_partition_head(&packets[1]));
+    assert!(depacketizer.depacketize(&packets[1])?.is_empty());
+    assert!(depacketizer.z);
+    assert!(depacketizer.y);
+
+    assert!(!depacketizer.is_partition_head(&packets[2]));
+    assert_eq!(depacketizer.depacketize(&packets[2])?, frame);
+    assert!(depacketizer.z);
+    assert!(!depacketizer.y);
+
+    // The tail of an OBU whose head was lost is dropped
+    let mut depacketizer = Av1Packet::default();
+    assert!(depacketizer.depacketize(&packets[1])?.is_empty());
+    assert!(depacketizer.depacketize(&packets[2])?.is_empty());
+
+    // and a fragment that is never continued does not leak into the next OBU
+    let next = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_FRAME).with_payload(vec![1, 2, 3])
+    ]);
+    let mut depacketizer = Av1Packet::default();
+    assert!(depacketizer.depacketize(&packets[0])?.is_empty());
+    assert_eq!(
+        depacketizer.depacketize(&payloader.payload(100, &next)?[0])?,
+        next
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_short_packet() {
+    let mut depacketizer = Av1Packet::default();
+    assert_eq!(
+        depacketizer.depacketize(&Bytes::new()),
+        Err(Error::ErrShortPacket)
+    );
+    // OBU element size runs past the end of the packet
+    assert_eq!(
+        depacketizer.depacketize(&Bytes::from_static(&[0b0000_0000, 5, OBU_TYPE_FRAME << 3])),
+        Err(Error::ErrShortPacket)
+    );
+}
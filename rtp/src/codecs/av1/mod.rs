@@ -1,11 +1,12 @@
 use bytes::{BufMut, Bytes, BytesMut};
 
-use crate::codecs::av1::leb128::BytesMutExt;
-use crate::codecs::av1::obu::{obu_has_extension, parse_obus, OBU_HAS_SIZE_BIT};
+use crate::codecs::av1::leb128::{read_leb128, BytesMutExt};
+use crate::codecs::av1::obu::{obu_has_extension, obu_has_size, parse_obus, OBU_HAS_SIZE_BIT};
 use crate::codecs::av1::packetizer::{
     get_aggregation_header, packetize, AGGREGATION_HEADER_SIZE, MAX_NUM_OBUS_TO_OMIT_SIZE,
 };
-use crate::packetizer::Payloader;
+use crate::error::{Error, Result};
+use crate::packetizer::{Depacketizer, Payloader};
 
 #[cfg(test)]
 mod av1_test;
@@ -19,7 +20,7 @@ pub struct Av1Payloader {}
 impl Payloader for Av1Payloader {
     /// Based on https://chromium.googlesource.com/external/webrtc/+/4e513346ec56c829b3a6010664998469fc237b35/modules/rtp_rtcp/source/rtp_packetizer_av1.cc
     /// Reference: https://aomediacodec.github.io/av1-rtp-spec/#45-payload-structure
-    fn payload(&mut self, mtu: usize, payload: &Bytes) -> crate::error::Result<Vec<Bytes>> {
+    fn payload(&mut self, mtu: usize, payload: &Bytes) -> Result<Vec<Bytes>> {
         // 0                   1                   2                   3
         // 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
         // +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
@@ -120,3 +121,124 @@ impl Payloader for Av1Payloader {
         Box::new(self.clone())
     }
 }
+
+/// Av1Packet represents the AV1 aggregation header of an RTP packet and reassembles the
+/// OBU elements it carries. Depacketized payloads are in the low overhead bitstream format,
+/// i.e. every OBU has its obu_has_size_field set and is followed by its size.
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct Av1Packet {
+    /// The first OBU element is the continuation of an OBU fragment from the previous packet
+    pub z: bool,
+    /// The last OBU element is continued in the next packet
+    pub y: bool,
+    /// Number of OBU elements in the packet, 0 if every element is preceded by its size
+    pub w: u8,
+    /// The packet is the first packet of a coded video sequence
+    pub n: bool,
+
+    /// OBU fragment carried over from the previous packet
+    fragment: BytesMut,
+}
+
+impl Depacketizer for Av1Packet {
+    /// depacketize parses the passed byte slice and returns the complete OBUs it carries.
+    /// A trailing OBU fragment is kept until the packets continuing it are depacketized.
+    fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
+        if packet.is_empty() {
+            return Err(Error::ErrShortPacket);
+        }
+
+        let b = packet[0];
+        self.z = (b & 0x80) != 0;
+        self.y = (b & 0x40) != 0;
+        self.w = (b & 0x30) >> 4;
+        self.n = (b & 0x08) != 0;
+
+        // A fragment that is not continued by this packet can never be completed.
+        if !self.z {
+            self.fragment.clear();
+        }
+
+        let mut out = BytesMut::new();
+        let mut index = AGGREGATION_HEADER_SIZE;
+        let mut element = 0;
+        while index < packet.len() {
+            element += 1;
+
+            let size = if self.w != 0 && element == self.w as usize {
+                packet.len() - index
+            } else {
+                let (size, n) = read_leb128(&packet.slice(index..));
+                if n == 0 {
+                    return Err(Error::ErrShortPacket);
+                }
+                index += n;
+                size as usize
+            };
+            if index + size > packet.len() {
+                return Err(Error::ErrShortPacket);
+            }
+
+            let data = packet.slice(index..index + size);
+            index += size;
+
+            let obu = if element == 1 && self.z {
+                if self.fragment.is_empty() {
+                    // The head of this OBU was lost, drop the rest of it.
+                    continue;
+                }
+                self.fragment.extend_from_slice(&data);
+                self.fragment.split().freeze()
+            } else {
+                data
+            };
+
+            if index == packet.len() && self.y {
+                self.fragment.extend_from_slice(&obu);
+                break;
+            }
+
+            Av1Packet::put_obu(&mut out, &obu)?;
+        }
+
+        Ok(out.freeze())
+    }
+
+    fn is_partition_head(&self, payload: &Bytes) -> bool {
+        if payload.is_empty() {
+            false
+        } else {
+            (payload[0] & 0x80) == 0
+        }
+    }
+
+    fn is_partition_tail(&self, marker: bool, _payload: &Bytes) -> bool {
+        marker
+    }
+}
+
+impl Av1Packet {
+    /// Writes the OBU to `out`, adding the obu_size field if the OBU does not carry one.
+    fn put_obu(out: &mut BytesMut, obu: &Bytes) -> Result<()> {
+        if obu.is_empty() {
+            return Ok(());
+        }
+
+        let header = obu[0];
+        if obu_has_size(header) {
+            out.put_slice(obu);
+            return Ok(());
+        }
+
+        let header_size = if obu_has_extension(header) { 2 } else { 1 };
+        if obu.len() < header_size {
+            return Err(Error::ErrShortPacket);
+        }
+        out.put_u8(header | OBU_HAS_SIZE_BIT);
+        out.put_slice(&obu[1..header_size]);
+        out.put_leb128((obu.len() - header_size) as u32);
+        out.put_slice(&obu[header_size..]);
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ mod opus_test;
 
 use bytes::Bytes;
 
-use crate::error::{Error, Result};
+use crate::error::Result;
 use crate::packetizer::{Depacketizer, Payloader};
 
 #[derive(Default, Debug, Copy, Clone)]
@@ -27,13 +27,20 @@ impl Payloader for OpusPayloader {
 #[derive(PartialEq, Eq, Debug, Default, Clone)]
 pub struct OpusPacket;
 
+impl OpusPacket {
+    /// is_dtx reports whether the payload is a DTX (discontinuous transmission) frame, sent
+    /// in place of audio during silence. Such frames carry at most the TOC byte and a frame
+    /// count byte, and no audio data.
+    pub fn is_dtx(payload: &Bytes) -> bool {
+        payload.len() <= 2
+    }
+}
+
 impl Depacketizer for OpusPacket {
+    /// depacketize returns the Opus frame as is. An empty payload is a DTX frame and yields
+    /// an empty frame rather than an error.
     fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
-        if packet.is_empty() {
-            Err(Error::ErrShortPacket)
-        } else {
-            Ok(packet.clone())
-        }
+        Ok(packet.clone())
     }
 
     fn is_partition_head(&self, _payload: &Bytes) -> bool {
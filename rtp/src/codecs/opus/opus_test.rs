@@ -4,10 +4,10 @@ use super::*;
 fn test_opus_unmarshal() -> Result<()> {
     let mut pck = OpusPacket;
 
-    // Empty packet
+    // Empty packet, sent during DTX
     let empty_bytes = Bytes::from_static(&[]);
-    let result = pck.depacketize(&empty_bytes);
-    assert!(result.is_err(), "Result should be err in case of error");
+    let payload = pck.depacketize(&empty_bytes)?;
+    assert!(payload.is_empty(), "DTX payload must be empty");
 
     // Normal packet
     let raw_bytes = Bytes::from_static(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x90]);
@@ -49,3 +49,21 @@ fn test_opus_is_partition_head() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_opus_dtx() -> Result<()> {
+    let mut pck = OpusPacket;
+
+    // A 20ms SILK wideband frame (config 9, mono, code 0)
+    let frame = Bytes::from_static(&[0x48, 0x0b, 0xe4, 0xc1, 0x36, 0xec, 0xc5, 0x80]);
+    assert_eq!(pck.depacketize(&frame)?, frame);
+    assert!(!OpusPacket::is_dtx(&frame));
+
+    // DTX frames carry no audio, only the TOC byte or nothing at all
+    for dtx in [Bytes::new(), Bytes::from_static(&[0x48])] {
+        assert_eq!(pck.depacketize(&dtx)?, dtx);
+        assert!(OpusPacket::is_dtx(&dtx));
+    }
+
+    Ok(())
+}
@@ -11,6 +11,8 @@ use crate::packetizer::{Depacketizer, Payloader};
 
 /// Flexible mode 15 bit picture ID
 const VP9HEADER_SIZE: usize = 3;
+/// Flexible mode 7 bit picture ID, used while the picture ID fits in 7 bits
+const VP9HEADER_SIZE_SHORT_PICTURE_ID: usize = 2;
 const MAX_SPATIAL_LAYERS: u8 = 5;
 const MAX_VP9REF_PICS: usize = 3;
 
@@ -93,7 +95,12 @@ impl Payloader for Vp9Payloader {
             self.initialized = true;
         }
 
-        let max_fragment_size = mtu as isize - VP9HEADER_SIZE as isize;
+        let header_size = if self.picture_id < 0x80 {
+            VP9HEADER_SIZE_SHORT_PICTURE_ID
+        } else {
+            VP9HEADER_SIZE
+        };
+        let max_fragment_size = mtu as isize - header_size as isize;
         let mut payloads = vec![];
         let mut payload_data_remaining = payload.len();
         let mut payload_data_index = 0;
@@ -105,7 +112,7 @@ impl Payloader for Vp9Payloader {
         while payload_data_remaining > 0 {
             let current_fragment_size =
                 std::cmp::min(max_fragment_size as usize, payload_data_remaining);
-            let mut out = BytesMut::with_capacity(VP9HEADER_SIZE + current_fragment_size);
+            let mut out = BytesMut::with_capacity(header_size + current_fragment_size);
             let mut buf = [0u8; VP9HEADER_SIZE];
             buf[0] = 0x90; // F=1 I=1
             if payload_data_index == 0 {
@@ -114,10 +121,14 @@ impl Payloader for Vp9Payloader {
             if payload_data_remaining == current_fragment_size {
                 buf[0] |= 0x04; // E=1
             }
-            buf[1] = (self.picture_id >> 8) as u8 | 0x80;
-            buf[2] = (self.picture_id & 0xFF) as u8;
+            if header_size == VP9HEADER_SIZE {
+                buf[1] = (self.picture_id >> 8) as u8 | 0x80; // M=1
+                buf[2] = (self.picture_id & 0xFF) as u8;
+            } else {
+                buf[1] = self.picture_id as u8; // M=0
+            }
 
-            out.put(&buf[..]);
+            out.put(&buf[..header_size]);
 
             out.put(
                 &*payload.slice(payload_data_index..payload_data_index + current_fragment_size),
@@ -254,6 +265,12 @@ impl Depacketizer for Vp9Packet {
 }
 
 impl Vp9Packet {
+    /// is_keyframe reports whether the last depacketized packet starts a key frame:
+    /// the start of a frame in the base spatial layer that is not inter-picture predicted.
+    pub fn is_keyframe(&self) -> bool {
+        self.b && !self.p && self.sid == 0
+    }
+
     // Picture ID:
     //
     //      +-+-+-+-+-+-+-+-+
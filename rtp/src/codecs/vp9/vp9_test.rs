@@ -306,6 +306,28 @@ fn test_vp9_payloader_payload() -> Result<()> {
         assert_eq!(actual, expected, "{name}: Payloaded packet");
     }
 
+    //"ShortPictureID"
+    {
+        let mut pck = Vp9Payloader {
+            initial_picture_id_fn: Some(Arc::new(|| -> u16 { 0x7F })),
+            ..Default::default()
+        };
+        let frame = Bytes::from_static(&[0x01, 0x02]);
+        assert_eq!(
+            pck.payload(3, &frame)?,
+            vec![
+                Bytes::from_static(&[0x98, 0x7F, 0x01]),
+                Bytes::from_static(&[0x94, 0x7F, 0x02]),
+            ],
+            "7-bit picture ID while it fits"
+        );
+        assert_eq!(
+            pck.payload(10, &frame)?,
+            vec![Bytes::from_static(&[0x9C, 0x80, 0x80, 0x01, 0x02])],
+            "15-bit picture ID once it no longer fits"
+        );
+    }
+
     //"PictureIDOverflow"
     {
         let mut pck = Vp9Payloader {
@@ -362,3 +384,54 @@ fn test_vp9_partition_head_checker_is_partition_head() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_vp9_payloader_depacketizer_round_trip() -> Result<()> {
+    let frame: Bytes = (0..4096).map(|i| i as u8).collect::<Vec<u8>>().into();
+    let mut pck = Vp9Payloader {
+        initial_picture_id_fn: Some(Arc::new(|| -> u16 { 0x7FFF })),
+        ..Default::default()
+    };
+
+    let payloads = pck.payload(1200, &frame)?;
+    assert_eq!(payloads.len(), 4);
+    assert!(payloads.iter().all(|p| p.len() <= 1200));
+
+    let mut reassembled = BytesMut::new();
+    for (i, payload) in payloads.iter().enumerate() {
+        let mut pkt = Vp9Packet::default();
+        reassembled.put(pkt.depacketize(payload)?);
+
+        assert!(pkt.f, "flexible mode");
+        assert_eq!(pkt.b, i == 0, "B is only set on the first packet");
+        assert_eq!(
+            pkt.e,
+            i == payloads.len() - 1,
+            "E is only set on the last packet"
+        );
+        assert_eq!(pkt.picture_id, 0x7FFF, "15-bit picture ID");
+        assert_eq!(pkt.is_keyframe(), i == 0);
+        assert_eq!(pkt.is_partition_head(payload), i == 0);
+    }
+    assert_eq!(reassembled.freeze(), frame);
+
+    // The picture ID wraps within 15 bits and then fits the 7-bit form
+    let payloads = pck.payload(1200, &Bytes::from_static(&[0x01, 0x02]))?;
+    assert_eq!(
+        payloads,
+        vec![Bytes::from_static(&[0x9C, 0x00, 0x01, 0x02])]
+    );
+    let mut pkt = Vp9Packet::default();
+    pkt.depacketize(&payloads[0])?;
+    assert_eq!(pkt.picture_id, 0);
+    assert!(pkt.b && pkt.e);
+
+    // A 7-bit picture ID and a predicted frame
+    let mut pkt = Vp9Packet::default();
+    let payload = pkt.depacketize(&Bytes::from_static(&[0xDC, 0x05, 0x0A, 0xAA]))?;
+    assert_eq!(pkt.picture_id, 5);
+    assert!(!pkt.is_keyframe());
+    assert_eq!(payload, Bytes::from_static(&[0xAA]));
+
+    Ok(())
+}
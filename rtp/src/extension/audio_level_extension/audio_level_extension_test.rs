@@ -64,3 +64,24 @@ fn test_audio_level_extension_level_overflow() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_audio_level_extension_round_trip() -> Result<()> {
+    for (a, raw) in [
+        // muted
+        (AudioLevelExtension::new(127, false), 0x7F),
+        // loud speech
+        (AudioLevelExtension::new(10, true), 0x8A),
+    ] {
+        let buf = a.marshal()?;
+        assert_eq!(buf, Bytes::from(vec![raw]));
+
+        let mut header = crate::header::Header::default();
+        header.set_extension(1, buf)?;
+        let header = crate::header::Header::unmarshal(&mut header.marshal()?)?;
+        let payload = header.get_extension(1).expect("extension must be present");
+        assert_eq!(AudioLevelExtension::unmarshal(&mut payload.clone())?, a);
+    }
+
+    Ok(())
+}
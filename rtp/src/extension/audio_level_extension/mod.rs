@@ -78,3 +78,11 @@ impl Marshal for AudioLevelExtension {
         Ok(AUDIO_LEVEL_EXTENSION_SIZE)
     }
 }
+
+impl AudioLevelExtension {
+    /// Makes a new AudioLevelExtension from a level in -dBov (0 is the loudest, 127 silence)
+    /// and the voice activity flag. Levels above 127 are rejected when marshaling.
+    pub fn new(level: u8, voice: bool) -> Self {
+        AudioLevelExtension { level, voice }
+    }
+}
@@ -42,3 +42,35 @@ fn test_transport_cc_extension_extra_bytes() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_transport_cc_extension_marshal_too_small() {
+    let t = TransportCcExtension {
+        transport_sequence: 2,
+    };
+    let mut dst = [0u8; TRANSPORT_CC_EXTENSION_SIZE - 1];
+    let err = t.marshal_to(&mut dst).unwrap_err();
+    assert_eq!(Error::ErrBufferTooSmall, err);
+}
+
+#[test]
+fn test_transport_cc_extension_header_round_trip() -> Result<()> {
+    // The highest sequence number, right before it wraps around.
+    let t = TransportCcExtension {
+        transport_sequence: 0xFFFF,
+    };
+
+    let mut header = crate::header::Header::default();
+    header.set_extension(5, t.marshal()?)?;
+    let raw = header.marshal()?;
+    assert_eq!(
+        &raw[12..20],
+        &[0xBE, 0xDE, 0x00, 0x01, 0x51, 0xFF, 0xFF, 0x00]
+    );
+
+    let header = crate::header::Header::unmarshal(&mut raw.clone())?;
+    let payload = header.get_extension(5).expect("extension must be present");
+    assert_eq!(TransportCcExtension::unmarshal(&mut payload.clone())?, t);
+
+    Ok(())
+}
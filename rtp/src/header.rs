@@ -12,6 +12,8 @@ pub const EXTENSION_SHIFT: u8 = 4;
 pub const EXTENSION_MASK: u8 = 0x1;
 pub const EXTENSION_PROFILE_ONE_BYTE: u16 = 0xBEDE;
 pub const EXTENSION_PROFILE_TWO_BYTE: u16 = 0x1000;
+/// The low 4 bits of the two-byte profile are "appbits", available for application use.
+pub const EXTENSION_PROFILE_TWO_BYTE_MASK: u16 = 0xFFF0;
 pub const EXTENSION_ID_RESERVED: u8 = 0xF;
 pub const CC_MASK: u8 = 0xF;
 pub const MARKER_SHIFT: u8 = 7;
@@ -144,7 +146,7 @@ impl Unmarshal for Header {
                     }
                 }
                 // RFC 8285 RTP Two Byte Header Extension
-                EXTENSION_PROFILE_TWO_BYTE => {
+                profile if is_two_byte_profile(profile) => {
                     let end = curr_offset + extension_length;
                     while curr_offset < end {
                         let b = raw_packet.get_u8();
@@ -268,9 +270,7 @@ impl Marshal for Header {
 
             // calculate extensions size and round to 4 bytes boundaries
             let extension_payload_len = self.get_extension_payload_len();
-            if self.extension_profile != EXTENSION_PROFILE_ONE_BYTE
-                && self.extension_profile != EXTENSION_PROFILE_TWO_BYTE
-                && extension_payload_len % 4 != 0
+            if extension_profile_len(self.extension_profile) == 0 && extension_payload_len % 4 != 0
             {
                 //the payload must be in 32-bit words.
                 return Err(Error::HeaderExtensionPayloadNot32BitWords.into());
@@ -287,7 +287,7 @@ impl Marshal for Header {
                     }
                 }
                 // RFC 8285 RTP Two Byte Header Extension
-                EXTENSION_PROFILE_TWO_BYTE => {
+                profile if is_two_byte_profile(profile) => {
                     for extension in &self.extensions {
                         buf.put_u8(extension.id);
                         buf.put_u8(extension.payload.len() as u8);
@@ -329,103 +329,109 @@ impl Header {
             .map(|extension| extension.payload.len())
             .sum();
 
-        let profile_len = self.extensions.len()
-            * match self.extension_profile {
-                EXTENSION_PROFILE_ONE_BYTE => 1,
-                EXTENSION_PROFILE_TWO_BYTE => 2,
-                _ => 0,
-            };
+        let profile_len = self.extensions.len() * extension_profile_len(self.extension_profile);
 
         payload_len + profile_len
     }
 
-    /// SetExtension sets an RTP header extension
+    /// SetExtension sets an RTP header extension. When extensions are not enabled yet, the
+    /// two-byte form is only used if the payload is longer than 16 bytes. Otherwise the
+    /// extension must fit the form already in use; see `set_extension_allow_mixed` to switch
+    /// to the two-byte form instead.
     pub fn set_extension(&mut self, id: u8, payload: Bytes) -> Result<(), Error> {
         let payload_len = payload.len() as isize;
-        if self.extension {
-            let extension_profile_len = match self.extension_profile {
-                EXTENSION_PROFILE_ONE_BYTE => {
-                    if !(1..=14).contains(&id) {
-                        return Err(Error::ErrRfc8285oneByteHeaderIdrange);
-                    }
-                    if payload_len > 16 {
-                        return Err(Error::ErrRfc8285oneByteHeaderSize);
-                    }
-                    1
-                }
-                EXTENSION_PROFILE_TWO_BYTE => {
-                    if id < 1 {
-                        return Err(Error::ErrRfc8285twoByteHeaderIdrange);
-                    }
-                    if payload_len > 255 {
-                        return Err(Error::ErrRfc8285twoByteHeaderSize);
-                    }
-                    2
+        let extension_profile = if self.extension {
+            self.extension_profile
+        } else if payload_len > 16 {
+            EXTENSION_PROFILE_TWO_BYTE
+        } else {
+            EXTENSION_PROFILE_ONE_BYTE
+        };
+
+        let extension_profile_len = match extension_profile {
+            EXTENSION_PROFILE_ONE_BYTE => {
+                if !(1..=14).contains(&id) {
+                    return Err(Error::ErrRfc8285oneByteHeaderIdrange);
                 }
-                _ => {
-                    if id != 0 {
-                        return Err(Error::ErrRfc3550headerIdrange);
-                    }
-                    0
+                if !(1..=16).contains(&payload_len) {
+                    return Err(Error::ErrRfc8285oneByteHeaderSize);
                 }
-            };
-
-            let delta;
-            // Update existing if it exists else add new extension
-            if let Some(extension) = self
-                .extensions
-                .iter_mut()
-                .find(|extension| extension.id == id)
-            {
-                delta = payload_len - extension.payload.len() as isize;
-                extension.payload = payload;
-            } else {
-                delta = payload_len + extension_profile_len;
-                self.extensions.push(Extension { id, payload });
+                1
             }
-
-            match delta.cmp(&0) {
-                std::cmp::Ordering::Less => {
-                    self.extensions_padding =
-                        ((self.extensions_padding as isize - delta) % 4) as usize;
+            profile if is_two_byte_profile(profile) => {
+                if id < 1 {
+                    return Err(Error::ErrRfc8285twoByteHeaderIdrange);
                 }
-                std::cmp::Ordering::Greater => {
-                    let extension_padding = (delta % 4) as usize;
-                    if self.extensions_padding < extension_padding {
-                        self.extensions_padding = (self.extensions_padding + 4) - extension_padding;
-                    } else {
-                        self.extensions_padding -= extension_padding
-                    }
+                if payload_len > 255 {
+                    return Err(Error::ErrRfc8285twoByteHeaderSize);
                 }
-                _ => {}
+                2
             }
-        } else {
+            _ => {
+                if id != 0 {
+                    return Err(Error::ErrRfc3550headerIdrange);
+                }
+                0
+            }
+        };
+
+        if !self.extension {
             // No existing header extensions
             self.extension = true;
-            let mut extension_profile_len = 0;
-            self.extension_profile = match payload_len {
-                0..=16 => {
-                    extension_profile_len = 1;
-                    EXTENSION_PROFILE_ONE_BYTE
-                }
-                17..=255 => {
-                    extension_profile_len = 2;
-                    EXTENSION_PROFILE_TWO_BYTE
-                }
-                _ => self.extension_profile,
-            };
+            self.extension_profile = extension_profile;
+        }
 
-            let extension_padding = (payload.len() + extension_profile_len) % 4;
-            if self.extensions_padding < extension_padding {
-                self.extensions_padding = self.extensions_padding + 4 - extension_padding;
-            } else {
-                self.extensions_padding -= extension_padding
-            }
+        let delta;
+        // Update existing if it exists else add new extension
+        if let Some(extension) = self
+            .extensions
+            .iter_mut()
+            .find(|extension| extension.id == id)
+        {
+            delta = payload_len - extension.payload.len() as isize;
+            extension.payload = payload;
+        } else {
+            delta = payload_len + extension_profile_len;
             self.extensions.push(Extension { id, payload });
         }
+
+        match delta.cmp(&0) {
+            std::cmp::Ordering::Less => {
+                self.extensions_padding = ((self.extensions_padding as isize - delta) % 4) as usize;
+            }
+            std::cmp::Ordering::Greater => {
+                let extension_padding = (delta % 4) as usize;
+                if self.extensions_padding < extension_padding {
+                    self.extensions_padding = (self.extensions_padding + 4) - extension_padding;
+                } else {
+                    self.extensions_padding -= extension_padding
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
 
+    /// set_extension_allow_mixed sets an RTP header extension like `set_extension`, but switches
+    /// the header to the two-byte form when the extension can't be carried in the one-byte form,
+    /// i.e. its id is above 14 or its payload is empty or longer than 16 bytes. Existing
+    /// extensions move to the two-byte form too. This must only be used when the remote peer
+    /// signaled `a=extmap-allow-mixed`.
+    pub fn set_extension_allow_mixed(&mut self, id: u8, payload: Bytes) -> Result<(), Error> {
+        if needs_two_byte(id, payload.len())
+            && (!self.extension || self.extension_profile == EXTENSION_PROFILE_ONE_BYTE)
+        {
+            if !self.extension {
+                self.extension = true;
+                self.extensions.clear();
+            }
+            self.extension_profile = EXTENSION_PROFILE_TWO_BYTE;
+            self.extensions_padding = (4 - self.get_extension_payload_len() % 4) % 4;
+        }
+
+        self.set_extension(id, payload)
+    }
+
     /// returns an extension id array
     pub fn get_extension_ids(&self) -> Vec<u8> {
         if self.extension {
@@ -457,13 +463,8 @@ impl Header {
             {
                 let extension = self.extensions.remove(index);
 
-                let extension_profile_len = match self.extension_profile {
-                    EXTENSION_PROFILE_ONE_BYTE => 1,
-                    EXTENSION_PROFILE_TWO_BYTE => 2,
-                    _ => 0,
-                };
-
-                let extension_padding = (extension.payload.len() + extension_profile_len) % 4;
+                let extension_padding =
+                    (extension.payload.len() + extension_profile_len(self.extension_profile)) % 4;
                 self.extensions_padding = (self.extensions_padding + extension_padding) % 4;
 
                 Ok(())
@@ -475,3 +476,23 @@ impl Header {
         }
     }
 }
+
+/// Reports whether an extension can only be carried in the RFC 8285 two-byte header form, whose
+/// ids go up to 255 and whose payloads may be empty or up to 255 bytes long.
+fn needs_two_byte(id: u8, payload_len: usize) -> bool {
+    id > 14 || !(1..=16).contains(&payload_len)
+}
+
+/// Reports whether the profile is the RFC 8285 two-byte header form, with any appbits.
+fn is_two_byte_profile(profile: u16) -> bool {
+    profile & EXTENSION_PROFILE_TWO_BYTE_MASK == EXTENSION_PROFILE_TWO_BYTE
+}
+
+/// Returns the size of the per-extension header for the profile, 0 for RFC 3550 extensions.
+fn extension_profile_len(profile: u16) -> usize {
+    match profile {
+        EXTENSION_PROFILE_ONE_BYTE => 1,
+        profile if is_two_byte_profile(profile) => 2,
+        _ => 0,
+    }
+}
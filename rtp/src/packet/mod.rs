@@ -33,6 +33,22 @@ impl fmt::Display for Packet {
     }
 }
 
+impl Packet {
+    /// Returns a copy of the packet with a new sequence number, timestamp and SSRC, as when
+    /// forwarding it to another stream. The payload is shared with this packet, not copied.
+    pub fn clone_with(&self, sequence_number: u16, timestamp: u32, ssrc: u32) -> Self {
+        Packet {
+            header: Header {
+                sequence_number,
+                timestamp,
+                ssrc,
+                ..self.header.clone()
+            },
+            payload: self.payload.clone(),
+        }
+    }
+}
+
 impl Unmarshal for Packet {
     /// Unmarshal parses the passed byte slice and stores the result in the Header this method is called upon
     fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
@@ -1241,3 +1241,224 @@ fn test_round_trip() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_rfc8285_set_extension_allow_mixed() -> Result<()> {
+    // Only extensions that fit stay in the one-byte form.
+    let mut p = Packet {
+        header: Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36]),
+    };
+    p.header
+        .set_extension_allow_mixed(1, Bytes::from_static(&[0xAA]))?;
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_ONE_BYTE);
+
+    // An id above 14 switches the packet to the two-byte form.
+    p.header
+        .set_extension_allow_mixed(15, Bytes::from_static(&[0xBB, 0xCC]))?;
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+
+    let raw = p.marshal()?;
+    let q = Packet::unmarshal(&mut raw.clone())?;
+    assert_eq!(q.header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+    assert_eq!(q.header.get_extension(1), Some(Bytes::from_static(&[0xAA])));
+    assert_eq!(
+        q.header.get_extension(15),
+        Some(Bytes::from_static(&[0xBB, 0xCC]))
+    );
+    assert_eq!(q.payload, p.payload);
+
+    // Without mixing allowed, the one-byte form rejects the id.
+    let mut h = Header {
+        extension: true,
+        extension_profile: EXTENSION_PROFILE_ONE_BYTE,
+        ..Default::default()
+    };
+    assert!(h.set_extension(15, Bytes::from_static(&[0xBB])).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_rfc8285_set_extension_allow_mixed_empty_payload() -> Result<()> {
+    let mut p = Packet {
+        header: Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36]),
+    };
+    p.header
+        .set_extension_allow_mixed(1, Bytes::from_static(&[0xAA]))?;
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_ONE_BYTE);
+
+    // The one-byte form can't carry an empty payload, the two-byte form can.
+    let mut h = p.header.clone();
+    assert_eq!(
+        h.set_extension(2, Bytes::new()),
+        Err(Error::ErrRfc8285oneByteHeaderSize)
+    );
+    assert_eq!(h.extension_profile, EXTENSION_PROFILE_ONE_BYTE);
+
+    p.header.set_extension_allow_mixed(2, Bytes::new())?;
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+
+    let raw = p.marshal()?;
+    // 2 + 1 and 2 + 0 bytes of extensions padded to 8 bytes, i.e. 2 words.
+    assert_eq!(&raw[12..16], &[0x10, 0x00, 0x00, 0x02]);
+    assert_eq!(&raw[16..21], &[0x01, 0x01, 0xAA, 0x02, 0x00]);
+    let q = Packet::unmarshal(&mut raw.clone())?;
+    assert_eq!(q.header.get_extension(1), Some(Bytes::from_static(&[0xAA])));
+    assert_eq!(q.header.get_extension(2), Some(Bytes::new()));
+    assert_eq!(q.payload, p.payload);
+
+    Ok(())
+}
+
+#[test]
+fn test_rfc8285_set_extension_allow_mixed_upgrades_existing_extensions() -> Result<()> {
+    let extension = Bytes::from_static(&[0xDD; 17]);
+    let mut p = Packet {
+        header: Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36]),
+    };
+    p.header
+        .set_extension_allow_mixed(1, Bytes::from_static(&[0xAA]))?;
+    p.header
+        .set_extension_allow_mixed(2, Bytes::from_static(&[0xBB, 0xCC]))?;
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_ONE_BYTE);
+
+    // A payload longer than 16 bytes switches the packet to the two-byte form, keeping the
+    // existing extensions.
+    p.header.set_extension_allow_mixed(3, extension.clone())?;
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+
+    let raw = p.marshal()?;
+    // 2 + 1, 2 + 2 and 2 + 17 bytes of extensions padded to 28 bytes, i.e. 7 words.
+    assert_eq!(&raw[12..16], &[0x10, 0x00, 0x00, 0x07]);
+    assert_eq!(&raw[16..23], &[0x01, 0x01, 0xAA, 0x02, 0x02, 0xBB, 0xCC]);
+    assert_eq!(&raw[23..25], &[0x03, 17]);
+    let q = Packet::unmarshal(&mut raw.clone())?;
+    assert_eq!(q.header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+    assert_eq!(q.header.get_extension(1), Some(Bytes::from_static(&[0xAA])));
+    assert_eq!(
+        q.header.get_extension(2),
+        Some(Bytes::from_static(&[0xBB, 0xCC]))
+    );
+    assert_eq!(q.header.get_extension(3), Some(extension));
+    assert_eq!(q.payload, p.payload);
+    assert_eq!(q.marshal()?, raw);
+
+    // Without mixing allowed, the one-byte form rejects the payload.
+    let mut h = p.header.clone();
+    h.extension_profile = EXTENSION_PROFILE_ONE_BYTE;
+    h.extensions.truncate(2);
+    assert_eq!(
+        h.set_extension(3, Bytes::from_static(&[0xDD; 17])),
+        Err(Error::ErrRfc8285oneByteHeaderSize)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rfc8285_set_extension_picks_two_byte_form() -> Result<()> {
+    // A 20-byte extension doesn't fit in the one-byte form.
+    let extension = Bytes::from_static(&[0xAA; 20]);
+    let mut p = Packet {
+        header: Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36]),
+    };
+    p.header.set_extension(1, extension.clone())?;
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+
+    let raw = p.marshal()?;
+    assert_eq!(&raw[12..16], &[0x10, 0x00, 0x00, 0x06]);
+    assert_eq!(&raw[16..18], &[0x01, 20]);
+    let q = Packet::unmarshal(&mut raw.clone())?;
+    assert_eq!(q.header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+    assert_eq!(q.header.get_extension(1), Some(extension));
+    assert_eq!(q.payload, p.payload);
+    assert_eq!(q.marshal()?, raw);
+
+    // Neither do ids above 14 nor empty payloads, but set_extension only picks the two-byte
+    // form for long payloads and leaves the others to set_extension_allow_mixed.
+    for (id, payload, err) in [
+        (
+            15,
+            Bytes::from_static(&[0xBB]),
+            Error::ErrRfc8285oneByteHeaderIdrange,
+        ),
+        (1, Bytes::new(), Error::ErrRfc8285oneByteHeaderSize),
+    ] {
+        let mut h = Header::default();
+        assert_eq!(h.set_extension(id, payload.clone()), Err(err));
+        assert!(!h.extension);
+
+        h.set_extension_allow_mixed(id, payload.clone())?;
+        assert_eq!(h.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+        let q = Header::unmarshal(&mut h.marshal()?)?;
+        assert_eq!(q.get_extension(id), Some(payload));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rfc8285_two_byte_extension_with_appbits() -> Result<()> {
+    let raw_pkt = Bytes::from_static(&[
+        0x90, 0x60, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, 0x1c, 0x64, 0x27, 0x82, 0x10, 0x05, 0x00,
+        0x01, 0x1f, 0x01, 0xAA, 0x00, 0x98, 0x36,
+    ]);
+    let p = Packet::unmarshal(&mut raw_pkt.clone())?;
+    assert_eq!(p.header.extension_profile, 0x1005);
+    assert_eq!(
+        p.header.get_extension(0x1f),
+        Some(Bytes::from_static(&[0xAA]))
+    );
+    assert_eq!(p.payload, Bytes::from_static(&[0x98, 0x36]));
+    assert_eq!(p.marshal()?, raw_pkt);
+
+    Ok(())
+}
+
+#[test]
+fn test_clone_with_shares_payload() {
+    let pkt = Packet {
+        header: Header {
+            version: 2,
+            marker: true,
+            payload_type: 96,
+            sequence_number: 27023,
+            timestamp: 3653407706,
+            ssrc: 476325762,
+            csrc: vec![1, 2],
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36, 0xbe, 0x88, 0x9e]),
+    };
+
+    let cloned = pkt.clone_with(100, 90000, 0xdeadbeef);
+
+    assert_eq!(cloned.header.sequence_number, 100);
+    assert_eq!(cloned.header.timestamp, 90000);
+    assert_eq!(cloned.header.ssrc, 0xdeadbeef);
+    assert_eq!(cloned.header.marker, pkt.header.marker);
+    assert_eq!(cloned.header.payload_type, pkt.header.payload_type);
+    assert_eq!(cloned.header.csrc, pkt.header.csrc);
+    assert_eq!(
+        cloned.payload.as_ptr(),
+        pkt.payload.as_ptr(),
+        "payload should be shared, not copied"
+    );
+    assert_eq!(pkt.header.sequence_number, 27023, "original left untouched");
+}
@@ -29,6 +29,9 @@ impl Clone for Box<dyn Payloader + Send + Sync> {
 /// Packetizer packetizes a payload
 pub trait Packetizer: fmt::Debug {
     fn enable_abs_send_time(&mut self, value: u8);
+    /// set_mtu changes the largest packet produced by subsequent calls to packetize. Packetizers
+    /// that can't change their MTU ignore it.
+    fn set_mtu(&mut self, _mtu: usize) {}
     fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>>;
     fn skip_samples(&mut self, skipped_samples: u32);
     fn clone_to(&self) -> Box<dyn Packetizer + Send + Sync>;
@@ -111,6 +114,10 @@ impl Packetizer for PacketizerImpl {
         self.abs_send_time = value
     }
 
+    fn set_mtu(&mut self, mtu: usize) {
+        self.mtu = mtu
+    }
+
     fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>> {
         let payloads = self.payloader.payload(self.mtu - 12, payload)?;
         let payloads_len = payloads.len();
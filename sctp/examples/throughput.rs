@@ -63,12 +63,7 @@ fn main() -> Result<(), Error> {
                 ));
                 println!("listening {}...", conn.local_addr().unwrap());
 
-                let config = Config {
-                    net_conn: Arc::new(conn),
-                    max_receive_buffer_size: 0,
-                    max_message_size: 0,
-                    name: "recver".to_owned(),
-                };
+                let config = Config::new(Arc::new(conn), "recver".to_owned());
                 let a = Association::server(config).await?;
                 println!("created a server");
 
@@ -109,12 +104,7 @@ fn main() -> Result<(), Error> {
                 conn.connect(format!("127.0.0.1:{port2}")).await.unwrap();
                 println!("connecting 127.0.0.1:{port2}..");
 
-                let config = Config {
-                    net_conn: conn,
-                    max_receive_buffer_size: 0,
-                    max_message_size: 0,
-                    name: "sender".to_owned(),
-                };
+                let config = Config::new(conn, "sender".to_owned());
                 let a = Association::client(config).await.unwrap();
                 println!("created a client");
 
@@ -36,8 +36,8 @@ pub struct AssociationInternal {
     reconfig_requests: HashMap<u32, ParamOutgoingResetRequest>,
 
     // Non-RFC internal data
-    source_port: u16,
-    destination_port: u16,
+    pub(crate) source_port: u16,
+    pub(crate) destination_port: u16,
     pub(crate) my_max_num_inbound_streams: u16,
     pub(crate) my_max_num_outbound_streams: u16,
     my_cookie: Option<ParamStateCookie>,
@@ -68,6 +68,13 @@ pub struct AssociationInternal {
     pub(crate) t3rtx: Option<RtxTimer<AssociationInternal>>,
     pub(crate) treconfig: Option<RtxTimer<AssociationInternal>>,
     pub(crate) ack_timer: Option<AckTimer<AssociationInternal>>,
+    pub(crate) heartbeat_timer: Option<HeartbeatTimer<AssociationInternal>>,
+
+    // Heartbeat
+    pub(crate) heartbeat_interval: Duration,
+    heartbeat_nonce: u64,
+    heartbeat_sent_at: Option<SystemTime>, // set while a HEARTBEAT awaits its ACK
+    heartbeats_unacked: usize,
 
     // Chunks stored for retransmission
     pub(crate) stored_init: Option<ChunkInit>,
@@ -117,8 +124,19 @@ impl AssociationInternal {
         if tsn == 0 {
             tsn += 1;
         }
+        let port_or_default = |port| {
+            if port == 0 {
+                DEFAULT_SCTP_PORT
+            } else {
+                port
+            }
+        };
+
         let mut a = AssociationInternal {
             name: config.name,
+            source_port: port_or_default(config.local_port),
+            destination_port: port_or_default(config.remote_port),
+            heartbeat_interval: config.heartbeat_interval,
             max_receive_buffer_size,
             max_message_size: Arc::new(AtomicU32::new(max_message_size)),
 
@@ -176,9 +194,6 @@ impl AssociationInternal {
         if let Some(stored_init) = self.stored_init.clone() {
             log::debug!("[{}] sending INIT", self.name);
 
-            self.source_port = 5000; // Spec??
-            self.destination_port = 5000; // Spec??
-
             let outbound = Packet {
                 source_port: self.source_port,
                 destination_port: self.destination_port,
@@ -286,6 +301,9 @@ impl AssociationInternal {
         if let Some(ack_timer) = &mut self.ack_timer {
             ack_timer.stop();
         }
+        if let Some(heartbeat_timer) = &mut self.heartbeat_timer {
+            heartbeat_timer.stop();
+        }
     }
 
     fn awake_write_loop(&self) {
@@ -295,6 +313,12 @@ impl AssociationInternal {
         }
     }
 
+    /// has_stream tells whether a stream is registered for the identifier.
+    #[cfg(test)]
+    pub(crate) fn has_stream(&self, stream_identifier: u16) -> bool {
+        self.streams.contains_key(&stream_identifier)
+    }
+
     /// unregister_stream un-registers a stream from the association
     /// The caller should hold the association write lock.
     fn unregister_stream(&mut self, stream_identifier: u16) {
@@ -375,12 +399,12 @@ impl AssociationInternal {
 
             if !sis_to_reset.is_empty() {
                 let rsn = self.generate_next_rsn();
-                let tsn = self.my_next_tsn - 1;
+                let tsn = self.my_next_tsn.wrapping_sub(1);
                 log::debug!(
                     "[{}] sending RECONFIG: rsn={} tsn={} streams={:?}",
                     self.name,
                     rsn,
-                    self.my_next_tsn - 1,
+                    tsn,
                     sis_to_reset
                 );
 
@@ -819,6 +843,56 @@ impl AssociationInternal {
         Ok(vec![])
     }
 
+    fn handle_heartbeat_ack(&mut self, c: &ChunkHeartbeatAck) -> Result<Vec<Packet>> {
+        log::trace!("[{}] chunkHeartbeatAck", self.name);
+        let hbi = match c
+            .params
+            .first()
+            .and_then(|p| p.as_any().downcast_ref::<ParamHeartbeatInfo>())
+        {
+            Some(hbi) => hbi,
+            None => return Ok(vec![]),
+        };
+
+        // Only the ACK of the latest HEARTBEAT is trusted for measuring RTT, as it's the
+        // only one whose send time is known.
+        if hbi.heartbeat_information[..] != self.heartbeat_nonce.to_be_bytes() {
+            return Ok(vec![]);
+        }
+        if let Some(sent_at) = self.heartbeat_sent_at.take() {
+            self.heartbeats_unacked = 0;
+            self.stats.inc_heartbeat_acks();
+
+            // RFC 4960 sec 8.3
+            //  Upon the receipt of the HEARTBEAT ACK, the sender of the HEARTBEAT
+            //  should clear the error counter of the destination transport address
+            //  to which the HEARTBEAT was sent, and mark the destination transport
+            //  address as active if it is not so marked.  The endpoint may
+            //  optionally report to the upper layer when an inactive destination
+            //  address is marked as active due to the reception of the latest
+            //  HEARTBEAT ACK.  The receiver of the HEARTBEAT ACK must also
+            //  clear the association overall error count as well.
+            //
+            //  The receiver of the HEARTBEAT ACK should also perform an RTT
+            //  measurement for that destination transport address using the time
+            //  value carried in the HEARTBEAT ACK chunk.
+            let rtt = match SystemTime::now().duration_since(sent_at) {
+                Ok(rtt) => rtt,
+                Err(_) => return Err(Error::ErrInvalidSystemTime),
+            };
+            let srtt = self.rto_mgr.set_new_rtt(rtt.as_millis() as u64);
+            log::trace!(
+                "[{}] HEARTBEAT-ACK: measured-rtt={} srtt={} new-rto={}",
+                self.name,
+                rtt.as_millis(),
+                srtt,
+                self.rto_mgr.get_rto()
+            );
+        }
+
+        Ok(vec![])
+    }
+
     async fn handle_cookie_echo(&mut self, c: &ChunkCookieEcho) -> Result<Vec<Packet>> {
         let state = self.get_state();
         log::debug!("[{}] COOKIE-ECHO received in state '{}'", self.name, state);
@@ -848,6 +922,9 @@ impl AssociationInternal {
                     self.stored_cookie_echo = None;
 
                     self.set_state(AssociationState::Established);
+                    if let Some(heartbeat_timer) = &mut self.heartbeat_timer {
+                        heartbeat_timer.start();
+                    }
                     if let Some(handshake_completed_ch) = &self.handshake_completed_ch_tx {
                         let _ = handshake_completed_ch.send(None).await;
                     }
@@ -884,6 +961,9 @@ impl AssociationInternal {
         self.stored_cookie_echo = None;
 
         self.set_state(AssociationState::Established);
+        if let Some(heartbeat_timer) = &mut self.heartbeat_timer {
+            heartbeat_timer.start();
+        }
         if let Some(handshake_completed_ch) = &self.handshake_completed_ch_tx {
             let _ = handshake_completed_ch.send(None).await;
         }
@@ -960,8 +1040,12 @@ impl AssociationInternal {
         // Meaning, if peer_last_tsn+1 points to a chunk that is received,
         // advance peer_last_tsn until peer_last_tsn+1 points to unreceived chunk.
         log::debug!("[{}] peer_last_tsn = {}", self.name, self.peer_last_tsn);
-        while self.payload_queue.pop(self.peer_last_tsn + 1).is_some() {
-            self.peer_last_tsn += 1;
+        while self
+            .payload_queue
+            .pop(self.peer_last_tsn.wrapping_add(1))
+            .is_some()
+        {
+            self.peer_last_tsn = self.peer_last_tsn.wrapping_add(1);
             log::debug!("[{}] peer_last_tsn = {}", self.name, self.peer_last_tsn);
 
             let rst_reqs: Vec<ParamOutgoingResetRequest> =
@@ -1443,13 +1527,13 @@ impl AssociationInternal {
             }
 
             // RFC 3758 Sec 3.5 C2
-            let mut i = self.advanced_peer_tsn_ack_point + 1;
+            let mut i = self.advanced_peer_tsn_ack_point.wrapping_add(1);
             while let Some(c) = self.inflight_queue.get(i) {
                 if !c.abandoned() {
                     break;
                 }
                 self.advanced_peer_tsn_ack_point = i;
-                i += 1;
+                i = i.wrapping_add(1);
             }
 
             // RFC 3758 Sec 3.5 C3
@@ -1561,7 +1645,7 @@ impl AssociationInternal {
     fn create_forward_tsn(&self) -> ChunkForwardTsn {
         // RFC 3758 Sec 3.5 C4
         let mut stream_map: HashMap<u16, u16> = HashMap::new(); // to report only once per SI
-        let mut i = self.cumulative_tsn_ack_point + 1;
+        let mut i = self.cumulative_tsn_ack_point.wrapping_add(1);
         while sna32lte(i, self.advanced_peer_tsn_ack_point) {
             if let Some(c) = self.inflight_queue.get(i) {
                 if let Some(ssn) = stream_map.get(&c.stream_identifier) {
@@ -1576,7 +1660,7 @@ impl AssociationInternal {
                 break;
             }
 
-            i += 1;
+            i = i.wrapping_add(1);
         }
 
         let mut fwd_tsn = ChunkForwardTsn {
@@ -1685,8 +1769,8 @@ impl AssociationInternal {
 
         // Advance peer_last_tsn
         while sna32lt(self.peer_last_tsn, c.new_cumulative_tsn) {
-            self.payload_queue.pop(self.peer_last_tsn + 1); // may not exist
-            self.peer_last_tsn += 1;
+            self.payload_queue.pop(self.peer_last_tsn.wrapping_add(1)); // may not exist
+            self.peer_last_tsn = self.peer_last_tsn.wrapping_add(1);
         }
 
         // Report new peer_last_tsn value and abandoned largest SSN value to
@@ -1798,7 +1882,7 @@ impl AssociationInternal {
         // reconfig_response_sequence_number.
         if !sis_to_reset.is_empty() {
             let rsn = self.generate_next_rsn();
-            let tsn = self.my_next_tsn - 1;
+            let tsn = self.my_next_tsn.wrapping_sub(1);
 
             let c = ChunkReconfig {
                 param_a: Some(Box::new(ParamOutgoingResetRequest {
@@ -1998,7 +2082,9 @@ impl AssociationInternal {
 
             if reliability_type == ReliabilityType::Rexmit {
                 if c.nsent >= reliability_value {
-                    c.set_abandoned(true);
+                    if c.abandon() {
+                        s.notify_message_abandoned();
+                    }
                     log::trace!(
                         "[{}] marked as abandoned: tsn={} ppi={} (remix: {})",
                         self.name,
@@ -2010,7 +2096,9 @@ impl AssociationInternal {
             } else if reliability_type == ReliabilityType::Timed {
                 if let Ok(elapsed) = SystemTime::now().duration_since(c.since) {
                     if elapsed.as_millis() as u32 >= reliability_value {
-                        c.set_abandoned(true);
+                        if c.abandon() {
+                            s.notify_message_abandoned();
+                        }
                         log::trace!(
                             "[{}] marked as abandoned: tsn={} ppi={} (timed: {:?})",
                             self.name,
@@ -2081,14 +2169,14 @@ impl AssociationInternal {
     /// generate_next_tsn returns the my_next_tsn and increases it. The caller should hold the lock.
     fn generate_next_tsn(&mut self) -> u32 {
         let tsn = self.my_next_tsn;
-        self.my_next_tsn += 1;
+        self.my_next_tsn = self.my_next_tsn.wrapping_add(1);
         tsn
     }
 
     /// generate_next_rsn returns the my_next_rsn and increases it. The caller should hold the lock.
     fn generate_next_rsn(&mut self) -> u32 {
         let rsn = self.my_next_rsn;
-        self.my_next_rsn += 1;
+        self.my_next_rsn = self.my_next_rsn.wrapping_add(1);
         rsn
     }
 
@@ -2146,6 +2234,8 @@ impl AssociationInternal {
             return Err(Error::ErrChunk);
         } else if let Some(c) = chunk_any.downcast_ref::<ChunkHeartbeat>() {
             self.handle_heartbeat(c).await?
+        } else if let Some(c) = chunk_any.downcast_ref::<ChunkHeartbeatAck>() {
+            self.handle_heartbeat_ack(c)?
         } else if let Some(c) = chunk_any.downcast_ref::<ChunkCookieEcho>() {
             self.handle_cookie_echo(c).await?
         } else if chunk_any.downcast_ref::<ChunkCookieAck>().is_some() {
@@ -2259,6 +2349,54 @@ impl AckTimerObserver for AssociationInternal {
     }
 }
 
+#[async_trait]
+impl HeartbeatTimerObserver for AssociationInternal {
+    async fn on_heartbeat_timeout(&mut self) {
+        // RFC 4960 sec 8.3
+        //  An endpoint should send a HEARTBEAT chunk to an idle destination to
+        //  monitor its reachability.  Data in flight already measures the path
+        //  through its SACKs, so only an idle association needs probing.
+        if self.get_state() != AssociationState::Established
+            || self.inflight_queue.get_num_bytes() > 0
+        {
+            return;
+        }
+
+        if self.heartbeat_sent_at.is_some() {
+            // The previous HEARTBEAT went unanswered. Like T3-rtx, this doesn't fail the
+            // association: ICE notices if connectivity is lost.
+            self.heartbeats_unacked += 1;
+            if self.heartbeats_unacked == PATH_MAX_RETRANS {
+                log::warn!(
+                    "[{}] {} consecutive heartbeats went unacknowledged",
+                    self.name,
+                    self.heartbeats_unacked
+                );
+            }
+        }
+
+        self.heartbeat_nonce = random::<u64>();
+        self.heartbeat_sent_at = Some(SystemTime::now());
+        self.stats.inc_heartbeats();
+        log::trace!("[{}] sending HEARTBEAT", self.name);
+
+        let outbound = Packet {
+            source_port: self.source_port,
+            destination_port: self.destination_port,
+            verification_tag: self.peer_verification_tag,
+            chunks: vec![Box::new(ChunkHeartbeat {
+                params: vec![Box::new(ParamHeartbeatInfo {
+                    heartbeat_information: Bytes::copy_from_slice(
+                        &self.heartbeat_nonce.to_be_bytes(),
+                    ),
+                })],
+            })],
+        };
+        self.control_queue.push_back(outbound);
+        self.awake_write_loop();
+    }
+}
+
 #[async_trait]
 impl RtxTimerObserver for AssociationInternal {
     async fn on_retransmission_timeout(&mut self, id: RtxTimerId, n_rtos: usize) {
@@ -2334,13 +2472,13 @@ impl RtxTimerObserver for AssociationInternal {
                 //  the procedures outlined in C2 - C5.
                 if self.use_forward_tsn {
                     // RFC 3758 Sec 3.5 C2
-                    let mut i = self.advanced_peer_tsn_ack_point + 1;
+                    let mut i = self.advanced_peer_tsn_ack_point.wrapping_add(1);
                     while let Some(c) = self.inflight_queue.get(i) {
                         if !c.abandoned() {
                             break;
                         }
                         self.advanced_peer_tsn_ack_point = i;
-                        i += 1;
+                        i = i.wrapping_add(1);
                     }
 
                     // RFC 3758 Sec 3.5 C3
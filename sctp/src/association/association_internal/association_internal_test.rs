@@ -159,6 +159,38 @@ fn test_create_forward_tsn_forward_two_abandoned_with_the_same_si() -> Result<()
     Ok(())
 }
 
+#[test]
+fn test_create_forward_tsn_across_tsn_wraparound() -> Result<()> {
+    let mut a = AssociationInternal {
+        cumulative_tsn_ack_point: u32::MAX - 1,
+        ..Default::default()
+    };
+
+    a.advanced_peer_tsn_ack_point = 0;
+    for (tsn, ssn) in [(u32::MAX, 2), (0, 3)] {
+        a.inflight_queue.push_no_check(ChunkPayloadData {
+            beginning_fragment: true,
+            ending_fragment: true,
+            tsn,
+            stream_identifier: 1,
+            stream_sequence_number: ssn,
+            user_data: Bytes::from_static(b"ABC"),
+            nsent: 1,
+            abandoned: Arc::new(AtomicBool::new(true)),
+            ..Default::default()
+        });
+    }
+
+    let fwdtsn = a.create_forward_tsn();
+
+    assert_eq!(fwdtsn.new_cumulative_tsn, 0);
+    assert_eq!(fwdtsn.streams.len(), 1, "there should be one stream");
+    assert_eq!(fwdtsn.streams[0].identifier, 1, "si should be 1");
+    assert_eq!(fwdtsn.streams[0].sequence, 3, "ssn should be 3");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_handle_forward_tsn_forward_3unreceived_chunks() -> Result<()> {
     let mut a = AssociationInternal {
@@ -245,6 +277,47 @@ async fn test_handle_forward_tsn_forward_1for1_missing() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_handle_forward_tsn_across_tsn_wraparound() -> Result<()> {
+    let mut a = AssociationInternal {
+        use_forward_tsn: true,
+        peer_last_tsn: u32::MAX - 1,
+        ..Default::default()
+    };
+
+    // this chunk is blocked by the abandoned chunks at tsn=u32::MAX and tsn=0
+    a.payload_queue.push(
+        ChunkPayloadData {
+            beginning_fragment: true,
+            ending_fragment: true,
+            tsn: 1,
+            stream_identifier: 0,
+            stream_sequence_number: 2,
+            user_data: Bytes::from_static(b"ABC"),
+            ..Default::default()
+        },
+        a.peer_last_tsn,
+    );
+
+    let fwdtsn = ChunkForwardTsn {
+        new_cumulative_tsn: 0,
+        streams: vec![ChunkForwardTsnStream {
+            identifier: 0,
+            sequence: 1,
+        }],
+    };
+
+    let p = a.handle_forward_tsn(&fwdtsn).await?;
+
+    assert_eq!(
+        a.peer_last_tsn, 1,
+        "peerLastTSN should advance past the wrap"
+    );
+    assert!(p.is_empty(), "should return empty");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_handle_forward_tsn_forward_1for2_missing() -> Result<()> {
     let mut a = AssociationInternal {
@@ -364,6 +437,9 @@ async fn handle_init_test(name: &str, initial_state: AssociationState, expect_er
         max_receive_buffer_size: 0,
         max_message_size: 0,
         name: "client".to_owned(),
+        heartbeat_interval: Duration::ZERO,
+        local_port: 0,
+        remote_port: 0,
     });
     a.set_state(initial_state);
     let pkt = Packet {
@@ -454,6 +530,9 @@ async fn test_assoc_max_message_size_default() -> Result<()> {
         max_receive_buffer_size: 0,
         max_message_size: 0,
         name: "client".to_owned(),
+        heartbeat_interval: Duration::ZERO,
+        local_port: 0,
+        remote_port: 0,
     });
     assert_eq!(
         a.max_message_size.load(Ordering::SeqCst),
@@ -499,6 +578,9 @@ async fn test_assoc_max_message_size_explicit() -> Result<()> {
         max_receive_buffer_size: 0,
         max_message_size: 30000,
         name: "client".to_owned(),
+        heartbeat_interval: Duration::ZERO,
+        local_port: 0,
+        remote_port: 0,
     });
 
     assert_eq!(
@@ -537,3 +619,121 @@ async fn test_assoc_max_message_size_explicit() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_assoc_configured_ports() -> Result<()> {
+    let mut a = create_association_internal(Config {
+        net_conn: Arc::new(DumbConn {}),
+        max_receive_buffer_size: 0,
+        max_message_size: 0,
+        name: "client".to_owned(),
+        heartbeat_interval: Duration::ZERO,
+        local_port: 5001,
+        remote_port: 5002,
+    });
+
+    a.stored_init = Some(ChunkInit::default());
+    a.send_init()?;
+
+    let init = a.control_queue.pop_front().expect("an INIT packet");
+    assert_eq!(init.source_port, 5001);
+    assert_eq!(init.destination_port, 5002);
+
+    let a = create_association_internal(Config {
+        net_conn: Arc::new(DumbConn {}),
+        max_receive_buffer_size: 0,
+        max_message_size: 0,
+        name: "client".to_owned(),
+        heartbeat_interval: Duration::ZERO,
+        local_port: 0,
+        remote_port: 0,
+    });
+    assert_eq!(a.source_port, DEFAULT_SCTP_PORT);
+    assert_eq!(a.destination_port, DEFAULT_SCTP_PORT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_handle_reconfig_resets_stream() -> Result<()> {
+    let mut a = AssociationInternal::default();
+    let s = a.create_stream(1, false).expect("a stream");
+
+    // The reply's sender last TSN wraps around with the initial TSN
+    assert_eq!(a.my_next_tsn, 0);
+
+    let p = a
+        .handle_reconfig(&ChunkReconfig {
+            param_a: Some(Box::new(ParamOutgoingResetRequest {
+                reconfig_request_sequence_number: 10,
+                sender_last_tsn: a.peer_last_tsn,
+                stream_identifiers: vec![1],
+                ..Default::default()
+            })),
+            param_b: None,
+        })
+        .await?;
+
+    assert!(!a.has_stream(1), "should be unregistered");
+    assert!(s.read_shutdown.load(Ordering::SeqCst));
+    assert!(s.write_shutdown.load(Ordering::SeqCst));
+    assert!(a.reconfig_requests.is_empty());
+
+    // The peer's stream is reset in turn, then the request is acknowledged.
+    assert_eq!(p.len(), 2);
+    let reset = p[0].chunks[0]
+        .as_any()
+        .downcast_ref::<ChunkReconfig>()
+        .and_then(|c| c.param_a.as_ref())
+        .and_then(|p| p.as_any().downcast_ref::<ParamOutgoingResetRequest>())
+        .expect("an outgoing reset request");
+    assert_eq!(reset.reconfig_response_sequence_number, 10);
+    assert_eq!(reset.sender_last_tsn, u32::MAX);
+    assert_eq!(reset.stream_identifiers, vec![1]);
+    assert!(a
+        .reconfigs
+        .contains_key(&reset.reconfig_request_sequence_number));
+
+    let response = p[1].chunks[0]
+        .as_any()
+        .downcast_ref::<ChunkReconfig>()
+        .and_then(|c| c.param_a.as_ref())
+        .and_then(|p| p.as_any().downcast_ref::<ParamReconfigResponse>())
+        .expect("a reconfig response");
+    assert_eq!(response.reconfig_response_sequence_number, 10);
+    assert_eq!(response.result, ReconfigResult::SuccessPerformed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_handle_reconfig_waits_for_sender_last_tsn() -> Result<()> {
+    let mut a = AssociationInternal::default();
+    a.create_stream(1, false).expect("a stream");
+
+    let p = a
+        .handle_reconfig(&ChunkReconfig {
+            param_a: Some(Box::new(ParamOutgoingResetRequest {
+                reconfig_request_sequence_number: 10,
+                sender_last_tsn: a.peer_last_tsn + 1,
+                stream_identifiers: vec![1],
+                ..Default::default()
+            })),
+            param_b: None,
+        })
+        .await?;
+
+    // Data up to the sender last TSN is still due, so the stream stays until it arrives.
+    assert!(a.has_stream(1), "should still be registered");
+    assert!(a.reconfig_requests.contains_key(&10));
+    assert_eq!(p.len(), 1);
+    let response = p[0].chunks[0]
+        .as_any()
+        .downcast_ref::<ChunkReconfig>()
+        .and_then(|c| c.param_a.as_ref())
+        .and_then(|p| p.as_any().downcast_ref::<ParamReconfigResponse>())
+        .expect("a reconfig response");
+    assert_eq!(response.result, ReconfigResult::InProgress);
+
+    Ok(())
+}
@@ -1,5 +1,30 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 
+/// AssociationStatsSnapshot holds the counters of an Association at one point in time.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssociationStatsSnapshot {
+    /// The number of bytes written to the underlying conn.
+    pub bytes_sent: usize,
+    /// The number of bytes read from the underlying conn.
+    pub bytes_received: usize,
+    /// The amount of user data queued for sending or not yet acknowledged by the peer.
+    pub buffered_amount: usize,
+    /// The number of DATA chunks received.
+    pub n_datas: u64,
+    /// The number of SACK chunks received.
+    pub n_sacks: u64,
+    /// The number of T3-rtx timer expirations.
+    pub n_t3timeouts: u64,
+    /// The number of delayed ack timer expirations.
+    pub n_ack_timeouts: u64,
+    /// The number of fast retransmissions.
+    pub n_fast_retrans: u64,
+    /// The number of HEARTBEAT chunks sent.
+    pub n_heartbeats: u64,
+    /// The number of HEARTBEAT ACK chunks received.
+    pub n_heartbeat_acks: u64,
+}
+
 #[derive(Default, Debug)]
 pub(crate) struct AssociationStats {
     n_datas: AtomicU64,
@@ -7,6 +32,8 @@ pub(crate) struct AssociationStats {
     n_t3timeouts: AtomicU64,
     n_ack_timeouts: AtomicU64,
     n_fast_retrans: AtomicU64,
+    n_heartbeats: AtomicU64,
+    n_heartbeat_acks: AtomicU64,
 }
 
 impl AssociationStats {
@@ -50,11 +77,29 @@ impl AssociationStats {
         self.n_fast_retrans.load(Ordering::SeqCst)
     }
 
+    pub(crate) fn inc_heartbeats(&self) {
+        self.n_heartbeats.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn get_num_heartbeats(&self) -> u64 {
+        self.n_heartbeats.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn inc_heartbeat_acks(&self) {
+        self.n_heartbeat_acks.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn get_num_heartbeat_acks(&self) -> u64 {
+        self.n_heartbeat_acks.load(Ordering::SeqCst)
+    }
+
     pub(crate) fn reset(&self) {
         self.n_datas.store(0, Ordering::SeqCst);
         self.n_sacks.store(0, Ordering::SeqCst);
         self.n_t3timeouts.store(0, Ordering::SeqCst);
         self.n_ack_timeouts.store(0, Ordering::SeqCst);
         self.n_fast_retrans.store(0, Ordering::SeqCst);
+        self.n_heartbeats.store(0, Ordering::SeqCst);
+        self.n_heartbeat_acks.store(0, Ordering::SeqCst);
     }
 }
@@ -15,6 +15,8 @@ use tokio::net::UdpSocket;
 use util::conn::conn_bridge::*;
 use util::conn::conn_pipe::pipe;
 use util::conn::*;
+use util::vnet::net::{Net, NetConfig};
+use util::vnet::router::{Router, RouterConfig};
 
 async fn create_new_association_pair(
     br: &Arc<Bridge>,
@@ -35,6 +37,9 @@ async fn create_new_association_pair(
             max_receive_buffer_size: recv_buf_size,
             max_message_size: 0,
             name: "client".to_owned(),
+            heartbeat_interval: Duration::ZERO,
+            local_port: 0,
+            remote_port: 0,
         })
         .await;
 
@@ -51,6 +56,9 @@ async fn create_new_association_pair(
             max_receive_buffer_size: recv_buf_size,
             max_message_size: 0,
             name: "server".to_owned(),
+            heartbeat_interval: Duration::ZERO,
+            local_port: 0,
+            remote_port: 0,
         })
         .await;
 
@@ -287,6 +295,53 @@ async fn test_assoc_reliable_simple() -> Result<()> {
     Ok(())
 }
 
+#[cfg(not(target_os = "windows"))] // this times out in CI on windows.
+#[tokio::test]
+async fn test_assoc_stats() -> Result<()> {
+    const SI: u16 = 1;
+    static MSG: Bytes = Bytes::from_static(b"0123456789");
+
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, mut a1) =
+        create_new_association_pair(&br, Arc::new(ca), Arc::new(cb), AckMode::NoDelay, 0).await?;
+
+    let (s0, s1) = establish_session_pair(&br, &a0, &mut a1, SI).await?;
+
+    let before0 = a0.stats().await;
+    let before1 = a1.stats().await;
+    assert_eq!(before0.buffered_amount, 0, "incorrect bufferedAmount");
+
+    s0.write_sctp(&MSG, PayloadProtocolIdentifier::Binary)
+        .await?;
+    assert_eq!(
+        a0.buffered_amount().await,
+        MSG.len(),
+        "incorrect bufferedAmount"
+    );
+
+    flush_buffers(&br, &a0, &a1).await;
+
+    let mut buf = vec![0u8; 32];
+    let (n, _) = s1.read_sctp(&mut buf).await?;
+    assert_eq!(n, MSG.len(), "unexpected length of received data");
+
+    let after0 = a0.stats().await;
+    let after1 = a1.stats().await;
+    assert_eq!(after0.buffered_amount, 0, "incorrect bufferedAmount");
+    assert_eq!(after0.bytes_sent, a0.bytes_sent());
+    assert_eq!(after1.bytes_received, a1.bytes_received());
+    // The DATA chunk carries the payload plus the common and chunk headers.
+    assert!(after0.bytes_sent - before0.bytes_sent >= MSG.len() + 28);
+    assert!(after1.bytes_received - before1.bytes_received >= MSG.len() + 28);
+    assert_eq!(after1.n_datas - before1.n_datas, 1);
+    assert!(after0.n_sacks > before0.n_sacks);
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
 //use std::io::Write;
 
 // NB: This is ignored on Windows due to flakiness with timing/IO interactions.
@@ -1207,6 +1262,59 @@ async fn test_assoc_unreliable_rexmit_timed_ordered() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_assoc_unreliable_timed_message_abandoned() -> Result<()> {
+    const SI: u16 = 3;
+    let sbuf = vec![0u8; 1000];
+
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, mut a1) =
+        create_new_association_pair(&br, Arc::new(ca), Arc::new(cb), AckMode::NoDelay, 0).await?;
+
+    let (s0, s1) = establish_session_pair(&br, &a0, &mut a1, SI).await?;
+
+    // A lifetime of 0ms expires every message as soon as it is sent
+    s0.set_reliability_params(false, ReliabilityType::Timed, 0);
+    s1.set_reliability_params(false, ReliabilityType::Timed, 0); // doesn't matter
+
+    let (abandoned_tx, mut abandoned_rx) = mpsc::channel::<()>(10);
+    s0.on_message_abandoned(Box::new(move || {
+        let abandoned_tx = abandoned_tx.clone();
+        Box::pin(async move {
+            let _ = abandoned_tx.send(()).await;
+        })
+    }));
+
+    br.drop_next_nwrites(0, 1); // drop the first packet
+
+    for _ in 0..2 {
+        s0.write_sctp(
+            &Bytes::from(sbuf.clone()),
+            PayloadProtocolIdentifier::Binary,
+        )
+        .await?;
+    }
+
+    flush_buffers(&br, &a0, &a1).await;
+
+    // Each message is reported once, however many times it was looked at
+    for _ in 0..2 {
+        tokio::time::timeout(Duration::from_secs(1), abandoned_rx.recv())
+            .await
+            .expect("message abandoned hook not called");
+    }
+    br.process().await;
+    assert!(
+        abandoned_rx.try_recv().is_err(),
+        "too many abandoned messages"
+    );
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
 //use std::io::Write;
 
 #[tokio::test]
@@ -2036,6 +2144,46 @@ async fn test_assoc_reset_close_both_ways() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_assoc_reset_unregisters_stream_on_both_sides() -> Result<()> {
+    const SI: u16 = 1;
+
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, mut a1) =
+        create_new_association_pair(&br, Arc::new(ca), Arc::new(cb), AckMode::NoDelay, 0).await?;
+
+    let (s0, _s1) = establish_session_pair(&br, &a0, &mut a1, SI).await?;
+
+    log::debug!("s0.shutdown");
+    s0.shutdown(Shutdown::Both).await?; // send reset
+
+    // The peer resets its side of the stream in turn, so both sides drop it.
+    let mut unregistered = false;
+    for _ in 0..100 {
+        flush_buffers(&br, &a0, &a1).await;
+
+        {
+            let (ai0, ai1) = (
+                a0.association_internal.lock().await,
+                a1.association_internal.lock().await,
+            );
+            if !ai0.has_stream(SI) && !ai1.has_stream(SI) {
+                unregistered = true;
+            }
+        }
+        if unregistered {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(unregistered, "stream should be unregistered on both sides");
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
 //use std::io::Write;
 
 #[tokio::test]
@@ -2209,6 +2357,9 @@ async fn test_stats() -> Result<()> {
         max_receive_buffer_size: 0,
         max_message_size: 0,
         name: "client".to_owned(),
+        heartbeat_interval: Duration::ZERO,
+        local_port: 0,
+        remote_port: 0,
     })
     .await?;
 
@@ -2225,6 +2376,100 @@ async fn test_stats() -> Result<()> {
     Ok(())
 }
 
+/// Binds port 5000 of `ip` on a new vnet attached to `wan` and connects it to port 5000 of `remote_ip`.
+async fn create_vnet_conn(
+    wan: &Arc<Mutex<Router>>,
+    ip: &str,
+    remote_ip: &str,
+) -> Arc<dyn Conn + Send + Sync> {
+    let net = Net::new(Some(NetConfig {
+        static_ips: vec![ip.to_owned()],
+        ..Default::default()
+    }));
+    let nic = net.get_nic().unwrap();
+    {
+        let mut w = wan.lock().await;
+        w.add_net(Arc::clone(&nic)).await.unwrap();
+    }
+    {
+        let n = nic.lock().await;
+        n.set_router(Arc::clone(wan)).await.unwrap();
+    }
+
+    let conn = net
+        .bind(SocketAddr::from_str(&format!("{ip}:5000")).unwrap())
+        .await
+        .unwrap();
+    conn.connect(SocketAddr::from_str(&format!("{remote_ip}:5000")).unwrap())
+        .await
+        .unwrap();
+    conn
+}
+
+#[tokio::test]
+async fn test_assoc_heartbeat_on_idle_association() -> Result<()> {
+    // Delay packets by 10 msec each way, so that round trips take long enough to measure.
+    let wan = Arc::new(Mutex::new(
+        Router::new(RouterConfig {
+            cidr: "1.2.3.0/24".to_owned(),
+            min_delay: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .unwrap(),
+    ));
+    let ca = create_vnet_conn(&wan, "1.2.3.4", "1.2.3.5").await;
+    let cb = create_vnet_conn(&wan, "1.2.3.5", "1.2.3.4").await;
+    {
+        let mut w = wan.lock().await;
+        w.start().await.unwrap();
+    }
+
+    let client = tokio::spawn(Association::client(
+        Config::new(ca, "client".to_owned()).with_heartbeat_interval(Duration::from_millis(50)),
+    ));
+    // The server doesn't send heartbeats of its own, but still answers the client's.
+    let server = tokio::spawn(Association::server(Config::new(cb, "server".to_owned())));
+    let client = client.await.unwrap()?;
+    let server = server.await.unwrap()?;
+
+    {
+        let ai = client.association_internal.lock().await;
+        assert_eq!(ai.rto_mgr.srtt, 0, "no RTT should be measured yet");
+    }
+
+    // Leave the association idle for a while.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    {
+        let ai = client.association_internal.lock().await;
+        let n_heartbeats = ai.stats.get_num_heartbeats();
+        let n_heartbeat_acks = ai.stats.get_num_heartbeat_acks();
+        assert!(n_heartbeats >= 3, "client sent {n_heartbeats} heartbeats");
+        assert!(
+            n_heartbeat_acks >= 2,
+            "client received {n_heartbeat_acks} heartbeat acks"
+        );
+        assert!(
+            ai.rto_mgr.srtt >= 20,
+            "heartbeats should have measured the RTT, got srtt={}",
+            ai.rto_mgr.srtt
+        );
+    }
+    {
+        let ai = server.association_internal.lock().await;
+        assert_eq!(ai.stats.get_num_heartbeats(), 0);
+    }
+
+    client.close().await?;
+    server.close().await?;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await.unwrap();
+    }
+
+    Ok(())
+}
+
 async fn create_assocs() -> Result<(Association, Association)> {
     let addr1 = SocketAddr::from_str("0.0.0.0:0").unwrap();
     let addr2 = SocketAddr::from_str("0.0.0.0:0").unwrap();
@@ -2244,6 +2489,9 @@ async fn create_assocs() -> Result<(Association, Association)> {
             max_receive_buffer_size: 0,
             max_message_size: 0,
             name: "client".to_owned(),
+            heartbeat_interval: Duration::ZERO,
+            local_port: 0,
+            remote_port: 0,
         })
         .await?;
 
@@ -2258,6 +2506,9 @@ async fn create_assocs() -> Result<(Association, Association)> {
             max_receive_buffer_size: 0,
             max_message_size: 0,
             name: "server".to_owned(),
+            heartbeat_interval: Duration::ZERO,
+            local_port: 0,
+            remote_port: 0,
         })
         .await?;
 
@@ -2602,6 +2853,9 @@ async fn test_association_handle_packet_before_init() -> Result<()> {
                 max_message_size: 0,
                 max_receive_buffer_size: 0,
                 name: "client".to_owned(),
+                heartbeat_interval: Duration::ZERO,
+                local_port: 0,
+                remote_port: 0,
             },
             true,
         )
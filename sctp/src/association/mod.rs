@@ -34,10 +34,12 @@ use crate::queue::payload_queue::PayloadQueue;
 use crate::queue::pending_queue::PendingQueue;
 use crate::stream::*;
 use crate::timer::ack_timer::*;
+use crate::timer::heartbeat_timer::*;
 use crate::timer::rtx_timer::*;
 use crate::util::*;
 
 use association_internal::*;
+pub use association_stats::AssociationStatsSnapshot;
 use association_stats::*;
 
 use bytes::{Bytes, BytesMut};
@@ -46,7 +48,7 @@ use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use tokio::sync::{broadcast, mpsc, Mutex, Semaphore};
 use util::Conn;
 
@@ -58,6 +60,9 @@ pub(crate) const INITIAL_RECV_BUF_SIZE: u32 = 1024 * 1024;
 pub(crate) const COMMON_HEADER_SIZE: u32 = 12;
 pub(crate) const DATA_CHUNK_HEADER_SIZE: u32 = 16;
 pub(crate) const DEFAULT_MAX_MESSAGE_SIZE: u32 = 65536;
+/// The SCTP port used when none is configured, as for WebRTC data channels.
+/// <https://www.rfc-editor.org/rfc/rfc8841#section-5.2>
+pub const DEFAULT_SCTP_PORT: u16 = 5000;
 
 /// other constants
 pub(crate) const ACCEPT_CH_SIZE: usize = 16;
@@ -185,12 +190,67 @@ impl fmt::Display for AckState {
 }
 
 /// Config collects the arguments to create_association construction into
-/// a single structure
+/// a single structure. Build it with [`Config::new`] and the `with_` methods.
+#[non_exhaustive]
 pub struct Config {
     pub net_conn: Arc<dyn Conn + Send + Sync>,
     pub max_receive_buffer_size: u32,
     pub max_message_size: u32,
     pub name: String,
+    /// How often an idle association sends a HEARTBEAT to keep the path alive and
+    /// measure its round-trip time (RFC 4960 Sec 8.3 suggests 30 seconds).
+    /// Zero disables heartbeats; the peer's ones are answered regardless.
+    pub heartbeat_interval: Duration,
+    /// The local and remote SCTP ports of the association, [`DEFAULT_SCTP_PORT`] when zero.
+    local_port: u16,
+    remote_port: u16,
+}
+
+impl Config {
+    /// Creates a config for an association over `net_conn` with every other setting left at
+    /// its default.
+    pub fn new(net_conn: Arc<dyn Conn + Send + Sync>, name: String) -> Self {
+        Config {
+            net_conn,
+            max_receive_buffer_size: 0,
+            max_message_size: 0,
+            name,
+            heartbeat_interval: Duration::ZERO,
+            local_port: 0,
+            remote_port: 0,
+        }
+    }
+
+    /// with_max_receive_buffer_size sets the receive buffer size, the default when zero.
+    pub fn with_max_receive_buffer_size(mut self, max_receive_buffer_size: u32) -> Self {
+        self.max_receive_buffer_size = max_receive_buffer_size;
+        self
+    }
+
+    /// with_max_message_size sets the largest message accepted, the default when zero.
+    pub fn with_max_message_size(mut self, max_message_size: u32) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// with_heartbeat_interval sets how often an idle association sends a HEARTBEAT, zero
+    /// disables them.
+    pub fn with_heartbeat_interval(mut self, heartbeat_interval: Duration) -> Self {
+        self.heartbeat_interval = heartbeat_interval;
+        self
+    }
+
+    /// with_local_port sets the local SCTP port, [`DEFAULT_SCTP_PORT`] when zero.
+    pub fn with_local_port(mut self, local_port: u16) -> Self {
+        self.local_port = local_port;
+        self
+    }
+
+    /// with_remote_port sets the remote SCTP port, [`DEFAULT_SCTP_PORT`] when zero.
+    pub fn with_remote_port(mut self, remote_port: u16) -> Self {
+        self.remote_port = remote_port;
+        self
+    }
 }
 
 ///Association represents an SCTP association
@@ -222,6 +282,7 @@ pub struct Association {
     net_conn: Arc<dyn Conn + Send + Sync>,
     bytes_received: Arc<AtomicUsize>,
     bytes_sent: Arc<AtomicUsize>,
+    stats: Arc<AssociationStats>,
 
     pub(crate) association_internal: Arc<Mutex<AssociationInternal>>,
 }
@@ -322,6 +383,7 @@ impl Association {
         let max_message_size = Arc::clone(&ai.max_message_size);
         let inflight_queue_length = Arc::clone(&ai.inflight_queue_length);
         let will_send_shutdown = Arc::clone(&ai.will_send_shutdown);
+        let stats = Arc::clone(&ai.stats);
 
         let mut init = ChunkInit {
             initial_tsn: ai.my_next_tsn,
@@ -379,6 +441,12 @@ impl Association {
                 Arc::downgrade(&association_internal3),
                 ACK_INTERVAL,
             ));
+            if !ai.heartbeat_interval.is_zero() {
+                ai.heartbeat_timer = Some(HeartbeatTimer::new(
+                    Arc::downgrade(&association_internal3),
+                    ai.heartbeat_interval,
+                ));
+            }
         }
 
         tokio::spawn(async move {
@@ -428,6 +496,7 @@ impl Association {
                 net_conn,
                 bytes_received,
                 bytes_sent,
+                stats,
                 association_internal,
             },
             handshake_completed_ch_rx,
@@ -581,6 +650,29 @@ impl Association {
         self.bytes_received.load(Ordering::SeqCst)
     }
 
+    /// buffered_amount returns the amount of user data (in bytes) queued for sending or not yet
+    /// acknowledged by the peer, over all streams.
+    pub async fn buffered_amount(&self) -> usize {
+        let ai = self.association_internal.lock().await;
+        ai.buffered_amount()
+    }
+
+    /// stats returns a snapshot of the association's counters.
+    pub async fn stats(&self) -> AssociationStatsSnapshot {
+        AssociationStatsSnapshot {
+            bytes_sent: self.bytes_sent(),
+            bytes_received: self.bytes_received(),
+            buffered_amount: self.buffered_amount().await,
+            n_datas: self.stats.get_num_datas(),
+            n_sacks: self.stats.get_num_sacks(),
+            n_t3timeouts: self.stats.get_num_t3timeouts(),
+            n_ack_timeouts: self.stats.get_num_ack_timeouts(),
+            n_fast_retrans: self.stats.get_num_fast_retrans(),
+            n_heartbeats: self.stats.get_num_heartbeats(),
+            n_heartbeat_acks: self.stats.get_num_heartbeat_acks(),
+        }
+    }
+
     /// open_stream opens a stream
     pub async fn open_stream(
         &self,
@@ -597,6 +689,18 @@ impl Association {
         accept_ch_rx.recv().await
     }
 
+    /// local_port returns the SCTP port of this end of the association.
+    pub async fn local_port(&self) -> u16 {
+        let ai = self.association_internal.lock().await;
+        ai.source_port
+    }
+
+    /// remote_port returns the SCTP port of the peer of the association.
+    pub async fn remote_port(&self) -> u16 {
+        let ai = self.association_internal.lock().await;
+        ai.destination_port
+    }
+
     /// max_message_size returns the maximum message size you can send.
     pub fn max_message_size(&self) -> u32 {
         self.max_message_size.load(Ordering::SeqCst)
@@ -261,8 +261,10 @@ impl ChunkPayloadData {
         abandoned && all_inflight
     }
 
-    pub(crate) fn set_abandoned(&self, abandoned: bool) {
-        self.abandoned.store(abandoned, Ordering::SeqCst);
+    /// Marks the message this chunk belongs to as abandoned, returning true if it was not
+    /// abandoned yet. All fragments of a message share the flag.
+    pub(crate) fn abandon(&self) -> bool {
+        !self.abandoned.swap(true, Ordering::SeqCst)
     }
 
     pub(crate) fn set_all_inflight(&mut self) {
@@ -65,6 +65,9 @@ impl From<u8> for ReliabilityType {
 pub type OnBufferedAmountLowFn =
     Box<dyn (FnMut() -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>) + Send + Sync>;
 
+pub type OnMessageAbandonedFn =
+    Box<dyn (FnMut() -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>) + Send + Sync>;
+
 // TODO: benchmark performance between multiple Atomic+Mutex vs one Mutex<StreamInternal>
 
 /// Stream represents an SCTP stream
@@ -89,6 +92,7 @@ pub struct Stream {
     pub(crate) buffered_amount: AtomicUsize,
     pub(crate) buffered_amount_low: AtomicUsize,
     pub(crate) on_buffered_amount_low: ArcSwapOption<Mutex<OnBufferedAmountLowFn>>,
+    pub(crate) on_message_abandoned: ArcSwapOption<Mutex<OnMessageAbandonedFn>>,
     pub(crate) name: String,
 }
 
@@ -145,6 +149,7 @@ impl Stream {
             buffered_amount: AtomicUsize::new(0),
             buffered_amount_low: AtomicUsize::new(0),
             on_buffered_amount_low: ArcSwapOption::empty(),
+            on_message_abandoned: ArcSwapOption::empty(),
             name,
         }
     }
@@ -430,6 +435,26 @@ impl Stream {
             .store(Some(Arc::new(Mutex::new(f))));
     }
 
+    /// on_message_abandoned sets the callback handler which would be called when partial
+    /// reliability gives up on sending a message, once its retransmission limit or lifetime is
+    /// exceeded. The message may still reach the peer if it was sent before being abandoned.
+    pub fn on_message_abandoned(&self, f: OnMessageAbandonedFn) {
+        self.on_message_abandoned
+            .store(Some(Arc::new(Mutex::new(f))));
+    }
+
+    /// Called by the association when a message of this stream is abandoned. The handler
+    /// runs on its own task since the association lock is held here.
+    pub(crate) fn notify_message_abandoned(&self) {
+        if let Some(handler) = &*self.on_message_abandoned.load() {
+            let handler = Arc::clone(handler);
+            tokio::spawn(async move {
+                let mut f = handler.lock().await;
+                f().await;
+            });
+        }
+    }
+
     /// This method is called by association's read_loop (go-)routine to notify this stream
     /// of the specified amount of outgoing data has been delivered to the peer.
     pub(crate) async fn on_buffer_released(&self, n_bytes_released: i64) {
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use std::sync::Weak;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::Duration;
+
+/// heartbeatTimerObserver is the interface to a heartbeat timer observer.
+#[async_trait]
+pub(crate) trait HeartbeatTimerObserver {
+    async fn on_heartbeat_timeout(&mut self);
+}
+
+/// heartbeatTimer periodically asks its observer to probe the path to the peer,
+/// as described in RFC 4960 Sec 8.3.
+#[derive(Default, Debug)]
+pub(crate) struct HeartbeatTimer<T: 'static + HeartbeatTimerObserver + Send> {
+    pub(crate) timeout_observer: Weak<Mutex<T>>,
+    pub(crate) interval: Duration,
+    pub(crate) close_tx: Option<mpsc::Sender<()>>,
+}
+
+impl<T: 'static + HeartbeatTimerObserver + Send> HeartbeatTimer<T> {
+    /// newHeartbeatTimer creates a new heartbeat timer firing every interval.
+    pub(crate) fn new(timeout_observer: Weak<Mutex<T>>, interval: Duration) -> Self {
+        HeartbeatTimer {
+            timeout_observer,
+            interval,
+            close_tx: None,
+        }
+    }
+
+    /// start starts the timer. It keeps firing until stop() is called or the
+    /// observer is gone.
+    pub(crate) fn start(&mut self) -> bool {
+        // this timer is already running
+        if self.close_tx.is_some() {
+            return false;
+        }
+
+        let (close_tx, mut close_rx) = mpsc::channel(1);
+        let interval = self.interval;
+        let timeout_observer = self.timeout_observer.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let timer = tokio::time::sleep(interval);
+                tokio::pin!(timer);
+
+                tokio::select! {
+                    _ = timer.as_mut() => {
+                        if let Some(observer) = timeout_observer.upgrade() {
+                            let mut observer = observer.lock().await;
+                            observer.on_heartbeat_timeout().await;
+                        } else {
+                            break;
+                        }
+                    }
+                    _ = close_rx.recv() => break,
+                }
+            }
+        });
+
+        self.close_tx = Some(close_tx);
+        true
+    }
+
+    /// stop stops the timer.
+    pub(crate) fn stop(&mut self) {
+        self.close_tx.take();
+    }
+
+    /// isRunning tests if the timer is running.
+    /// Debug purpose only
+    pub(crate) fn is_running(&self) -> bool {
+        self.close_tx.is_some()
+    }
+}
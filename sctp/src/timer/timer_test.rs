@@ -71,6 +71,61 @@ mod test_ack_timer {
     }
 }
 
+///////////////////////////////////////////////////////////////////
+//heartbeat_timer_test
+///////////////////////////////////////////////////////////////////
+use super::heartbeat_timer::*;
+
+mod test_heartbeat_timer {
+    use crate::error::Result;
+
+    use super::*;
+
+    struct TestHeartbeatTimerObserver {
+        ncbs: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl HeartbeatTimerObserver for TestHeartbeatTimerObserver {
+        async fn on_heartbeat_timeout(&mut self) {
+            log::trace!("heartbeat timed out");
+            self.ncbs.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_timer_fires_until_stopped() -> Result<()> {
+        let ncbs = Arc::new(AtomicU32::new(0));
+        let obs = Arc::new(Mutex::new(TestHeartbeatTimerObserver {
+            ncbs: ncbs.clone(),
+        }));
+
+        let mut ht = HeartbeatTimer::new(Arc::downgrade(&obs), Duration::from_millis(20));
+
+        let ok = ht.start();
+        assert!(ok, "start() should succeed");
+        assert!(!ht.start(), "start() should fail while running");
+
+        sleep(Duration::from_millis(110)).await;
+        let n = ncbs.load(Ordering::SeqCst);
+        assert!(n >= 3, "should have fired repeatedly (actual: {n})");
+
+        ht.stop();
+        assert!(!ht.is_running(), "should not be running");
+        sleep(Duration::from_millis(30)).await;
+        let n = ncbs.load(Ordering::SeqCst);
+
+        sleep(Duration::from_millis(60)).await;
+        assert_eq!(
+            ncbs.load(Ordering::SeqCst),
+            n,
+            "should not fire after stop()"
+        );
+
+        Ok(())
+    }
+}
+
 ///////////////////////////////////////////////////////////////////
 //rtx_timer_test
 ///////////////////////////////////////////////////////////////////
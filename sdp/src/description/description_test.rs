@@ -605,3 +605,175 @@ fn test_unmarshal_non_nil_address() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_try_unmarshal_malformed() -> Result<()> {
+    const HEADER: &str = "v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\ns=-\r\nt=0 0\r\n";
+
+    let tests = vec![
+        (
+            format!("{HEADER}a=recvonly\r\nbogus\r\n"),
+            Error::SdpMissingEquals("bogus".to_owned()),
+        ),
+        (
+            "v0\r\no=- 0 0 IN IP4 127.0.0.1\r\n".to_owned(),
+            Error::SdpMissingEquals("v0".to_owned()),
+        ),
+        (
+            format!("{HEADER}m=audio 9\r\n"),
+            Error::SdpInvalidMediaLine("`m=audio 9`".to_owned()),
+        ),
+        (
+            format!("{HEADER}m=audio port RTP/AVP 0\r\n"),
+            Error::SdpInvalidPort("port".to_owned()),
+        ),
+        (
+            format!("{HEADER}m=audio 99999 RTP/AVP 0\r\n"),
+            Error::SdpInvalidPort("99999".to_owned()),
+        ),
+        (
+            format!("{HEADER}m=audio 9/x RTP/AVP 0\r\n"),
+            Error::SdpInvalidPort("9/x".to_owned()),
+        ),
+    ];
+
+    for (sdp, expected) in tests {
+        let actual = SessionDescription::try_unmarshal(sdp.as_bytes());
+        assert_eq!(actual.err(), Some(expected), "{sdp:?}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_try_unmarshal_garbage() {
+    // Every prefix of a valid description must either parse or fail gracefully.
+    for end in 0..=CANONICAL_UNMARSHAL_SDP.len() {
+        let _ = SessionDescription::try_unmarshal(&CANONICAL_UNMARSHAL_SDP.as_bytes()[..end]);
+    }
+
+    let garbage: &[&[u8]] = &[
+        b"",
+        b"=",
+        b"==",
+        b"\r\n\r\n",
+        b"v=",
+        b"v=0\r\no=",
+        b"v=0\r\no=- 0 0 IN IP4\r\n",
+        b"v=0\r\no=- x y IN IP4 127.0.0.1\r\ns=-\r\n",
+        b"v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\ns=-\r\nt=0 0\r\nm=\r\n",
+        b"v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\ns=-\r\nt=0 0\r\nz=1\r\n",
+        b"v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\ns=-\r\nt=0 0\r\nr=d d d\r\n",
+        b"v=0\r\n\xff\xfe=\x80\r\n",
+        b"\x00\x01\x02\x03",
+    ];
+    for data in garbage {
+        assert!(
+            SessionDescription::try_unmarshal(data).is_err(),
+            "{:?} should fail to parse",
+            String::from_utf8_lossy(data)
+        );
+    }
+}
+
+#[test]
+fn test_extmap_allow_mixed() -> Result<()> {
+    const INPUT: &str = "v=0\r\n\
+        o=- 0 0 IN IP4 127.0.0.1\r\n\
+        s=-\r\n\
+        t=0 0\r\n\
+        a=extmap-allow-mixed\r\n\
+        m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+        a=extmap:15 urn:ietf:params:rtp-hdrext:sdes:mid\r\n";
+
+    let mut reader = Cursor::new(INPUT.as_bytes());
+    let sdp = SessionDescription::unmarshal(&mut reader)?;
+    assert!(sdp.has_extmap_allow_mixed());
+    assert_eq!(sdp.marshal(), INPUT);
+
+    let mut sdp = SessionDescription::new_jsep_session_description(false);
+    assert!(!sdp.has_extmap_allow_mixed());
+    sdp = sdp.with_extmap_allow_mixed();
+    assert!(sdp.has_extmap_allow_mixed());
+    assert!(sdp.marshal().contains("a=extmap-allow-mixed\r\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_port_round_trip() -> Result<()> {
+    const INPUT: &str = "v=0\r\n\
+        o=- 0 0 IN IP4 127.0.0.1\r\n\
+        s=-\r\n\
+        t=0 0\r\n\
+        m=audio 49170 RTP/AVP 0\r\n\
+        a=rtcp:53020 IN IP4 126.16.64.4\r\n";
+
+    let mut reader = Cursor::new(INPUT.as_bytes());
+    let sdp = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(sdp.media_descriptions[0].rtcp_port(), Some(53020));
+    assert_eq!(sdp.marshal(), INPUT);
+
+    let media = MediaDescription::new_jsep_media_description("audio".to_owned(), vec![])
+        .with_codec(0, "PCMU".to_owned(), 8000, 0, String::new());
+    assert_eq!(media.rtcp_port(), None);
+    let media = media.with_rtcp_port(49171);
+    assert_eq!(media.rtcp_port(), Some(49171));
+
+    let mut sdp = SessionDescription::new_jsep_session_description(false);
+    sdp = sdp.with_media(media);
+    let marshaled = sdp.marshal();
+    assert!(marshaled.contains("a=rtcp:49171\r\n"));
+
+    let mut reader = Cursor::new(marshaled.as_bytes());
+    let parsed = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(parsed.media_descriptions[0].rtcp_port(), Some(49171));
+
+    Ok(())
+}
+
+#[test]
+fn test_fmtp_parameters() -> Result<()> {
+    const INPUT: &str = "v=0\r\n\
+        o=- 0 0 IN IP4 127.0.0.1\r\n\
+        s=-\r\n\
+        t=0 0\r\n\
+        m=audio 9 UDP/TLS/RTP/SAVPF 111 0\r\n\
+        a=rtpmap:111 opus/48000/2\r\n\
+        a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+        a=rtpmap:0 PCMU/8000\r\n\
+        m=video 9 UDP/TLS/RTP/SAVPF 102\r\n\
+        a=rtpmap:102 H264/90000\r\n\
+        a=fmtp:102 profile-level-id=42e01f;packetization-mode=1\r\n";
+
+    let mut reader = Cursor::new(INPUT.as_bytes());
+    let sdp = SessionDescription::unmarshal(&mut reader)?;
+
+    let audio = &sdp.media_descriptions[0];
+    let opus = audio.fmtp_parameters(111).expect("an fmtp line for opus");
+    assert_eq!(opus.len(), 2);
+    assert_eq!(opus["minptime"], "10");
+    assert_eq!(opus["useinbandfec"], "1");
+    assert_eq!(audio.fmtp_parameters(0), None);
+
+    let video = &sdp.media_descriptions[1];
+    let h264 = video.fmtp_parameters(102).expect("an fmtp line for h264");
+    assert_eq!(h264.len(), 2);
+    assert_eq!(h264["profile-level-id"], "42e01f");
+    assert_eq!(h264["packetization-mode"], "1");
+    assert_eq!(video.fmtp_parameters(111), None);
+
+    let media = MediaDescription::new_jsep_media_description("video".to_owned(), vec![])
+        .with_codec(
+            96,
+            "H264".to_owned(),
+            90000,
+            0,
+            "level-asymmetry-allowed=1;profile-level-id=640032".to_owned(),
+        );
+    let params = media.fmtp_parameters(96).expect("an fmtp line");
+    assert_eq!(params["profile-level-id"], "640032");
+    assert_eq!(params["level-asymmetry-allowed"], "1");
+
+    Ok(())
+}
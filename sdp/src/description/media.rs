@@ -4,7 +4,12 @@ use std::fmt;
 use url::Url;
 
 use crate::description::common::*;
+use crate::description::session::{ATTR_KEY_RTCP, ATTR_KEY_SSRCGROUP};
 use crate::extmap::*;
+use crate::imageattr::*;
+use crate::simulcast::*;
+use crate::ssrc_group::*;
+use crate::util::parse_fmtp_parameters;
 
 /// Constants for extmap key
 pub const EXT_MAP_VALUE_TRANSPORT_CC_KEY: isize = 3;
@@ -150,6 +155,19 @@ impl MediaDescription {
         }
     }
 
+    /// fmtp_parameters returns the format specific parameters of the 'a=fmtp' attribute
+    /// for `payload_type`, see [`parse_fmtp_parameters`], or None if there is none
+    pub fn fmtp_parameters(&self, payload_type: u8) -> Option<HashMap<String, String>> {
+        let payload_type = payload_type.to_string();
+        self.attributes
+            .iter()
+            .filter(|a| a.key == "fmtp")
+            .filter_map(|a| a.value.as_deref())
+            .filter_map(|v| v.trim().split_once(' '))
+            .find(|(pt, _)| *pt == payload_type)
+            .map(|(_, parameters)| parse_fmtp_parameters(parameters))
+    }
+
     /// with_media_source adds media source information to the media description
     pub fn with_media_source(
         self,
@@ -166,6 +184,22 @@ impl MediaDescription {
         // Deprecated but not phased out?
     }
 
+    /// with_ssrc_group adds an 'a=ssrc-group' to the media description
+    pub fn with_ssrc_group(self, ssrc_group: SsrcGroup) -> Self {
+        self.with_value_attribute(ATTR_KEY_SSRCGROUP.to_owned(), ssrc_group.to_string())
+    }
+
+    /// ssrc_groups returns the ssrc-group attributes of the media description, skipping any
+    /// that fail to parse
+    pub fn ssrc_groups(&self) -> Vec<SsrcGroup> {
+        self.attributes
+            .iter()
+            .filter(|a| a.key == ATTR_KEY_SSRCGROUP)
+            .filter_map(|a| a.value.as_deref())
+            .filter_map(|v| SsrcGroup::parse_value(v).ok())
+            .collect()
+    }
+
     /// with_candidate adds an ICE candidate to the media description
     /// Deprecated: use WithICECandidate instead
     pub fn with_candidate(self, value: String) -> Self {
@@ -176,6 +210,68 @@ impl MediaDescription {
         self.with_property_attribute(e.marshal())
     }
 
+    /// with_image_attr adds an imageattr to the media description
+    pub fn with_image_attr(self, image_attr: ImageAttr) -> Self {
+        self.with_value_attribute(ATTR_KEY_IMAGE_ATTR.to_owned(), image_attr.to_string())
+    }
+
+    /// image_attrs returns the imageattr attributes of the media description, skipping
+    /// any that fail to parse
+    pub fn image_attrs(&self) -> Vec<ImageAttr> {
+        self.attributes
+            .iter()
+            .filter(|a| a.key == ATTR_KEY_IMAGE_ATTR)
+            .filter_map(|a| a.value.as_deref())
+            .filter_map(|v| ImageAttr::parse_value(v).ok())
+            .collect()
+    }
+
+    /// with_rid adds an 'a=rid' to the media description
+    pub fn with_rid(self, rid: Rid) -> Self {
+        self.with_value_attribute(ATTR_KEY_RID.to_owned(), rid.to_string())
+    }
+
+    /// rids returns the rid attributes of the media description, skipping any that fail to
+    /// parse
+    pub fn rids(&self) -> Vec<Rid> {
+        self.attributes
+            .iter()
+            .filter(|a| a.key == ATTR_KEY_RID)
+            .filter_map(|a| a.value.as_deref())
+            .filter_map(|v| Rid::parse_value(v).ok())
+            .collect()
+    }
+
+    /// with_simulcast adds an 'a=simulcast' to the media description
+    pub fn with_simulcast(self, simulcast: Simulcast) -> Self {
+        self.with_value_attribute(ATTR_KEY_SIMULCAST.to_owned(), simulcast.to_string())
+    }
+
+    /// simulcast returns the simulcast attribute of the media description, if present and
+    /// valid
+    pub fn simulcast(&self) -> Option<Simulcast> {
+        self.attribute(ATTR_KEY_SIMULCAST)
+            .flatten()
+            .and_then(|v| Simulcast::parse_value(v).ok())
+    }
+
+    /// with_rtcp_port adds 'a=rtcp:<port>' to the media description, the port RTCP is
+    /// received on when it isn't multiplexed with RTP. The webrtc crate always multiplexes
+    /// RTCP, so it doesn't emit this attribute itself.
+    ///
+    /// <https://tools.ietf.org/html/rfc3605>
+    pub fn with_rtcp_port(self, port: u16) -> Self {
+        self.with_value_attribute(ATTR_KEY_RTCP.to_owned(), port.to_string())
+    }
+
+    /// rtcp_port returns the port of the 'a=rtcp' attribute, if present and valid
+    pub fn rtcp_port(&self) -> Option<u16> {
+        self.attribute(ATTR_KEY_RTCP)
+            .flatten()
+            .and_then(|v| v.split_whitespace().next())
+            .and_then(|port| port.parse().ok())
+    }
+
     /// with_transport_cc_extmap adds an extmap to the media description
     pub fn with_transport_cc_extmap(self) -> Self {
         let uri = {
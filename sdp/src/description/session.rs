@@ -8,6 +8,7 @@ use url::Url;
 use super::common::*;
 use super::media::*;
 use crate::error::{Error, Result};
+use crate::group::Group;
 use crate::lexer::*;
 use crate::util::*;
 
@@ -23,6 +24,7 @@ pub const ATTR_KEY_MSID_SEMANTIC: &str = "msid-semantic";
 pub const ATTR_KEY_CONNECTION_SETUP: &str = "setup";
 pub const ATTR_KEY_MID: &str = "mid";
 pub const ATTR_KEY_ICELITE: &str = "ice-lite";
+pub const ATTR_KEY_RTCP: &str = "rtcp";
 pub const ATTR_KEY_RTCPMUX: &str = "rtcp-mux";
 pub const ATTR_KEY_RTCPRSIZE: &str = "rtcp-rsize";
 pub const ATTR_KEY_INACTIVE: &str = "inactive";
@@ -30,11 +32,15 @@ pub const ATTR_KEY_RECV_ONLY: &str = "recvonly";
 pub const ATTR_KEY_SEND_ONLY: &str = "sendonly";
 pub const ATTR_KEY_SEND_RECV: &str = "sendrecv";
 pub const ATTR_KEY_EXT_MAP: &str = "extmap";
+pub const ATTR_KEY_EXTMAP_ALLOW_MIXED: &str = "extmap-allow-mixed";
+pub const ATTR_KEY_MAX_MESSAGE_SIZE: &str = "max-message-size";
 
 /// Constants for semantic tokens used in JSEP
+pub const SEMANTIC_TOKEN_BUNDLE: &str = "BUNDLE";
 pub const SEMANTIC_TOKEN_LIP_SYNCHRONIZATION: &str = "LS";
 pub const SEMANTIC_TOKEN_FLOW_IDENTIFICATION: &str = "FID";
 pub const SEMANTIC_TOKEN_FORWARD_ERROR_CORRECTION: &str = "FEC";
+pub const SEMANTIC_TOKEN_SIMULCAST: &str = "SIM";
 pub const SEMANTIC_TOKEN_WEBRTC_MEDIA_STREAMS: &str = "WMS";
 
 /// Version describes the value provided by the "v=" field which gives
@@ -355,6 +361,47 @@ impl SessionDescription {
         Err(Error::CodecNotFound)
     }
 
+    /// with_extmap_allow_mixed adds 'a=extmap-allow-mixed' to the session description,
+    /// allowing one-byte and two-byte RTP header extensions to be mixed in a stream
+    pub fn with_extmap_allow_mixed(self) -> Self {
+        self.with_property_attribute(ATTR_KEY_EXTMAP_ALLOW_MIXED.to_owned())
+    }
+
+    /// has_extmap_allow_mixed returns whether 'a=extmap-allow-mixed' is present at the session level
+    pub fn has_extmap_allow_mixed(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|a| a.key == ATTR_KEY_EXTMAP_ALLOW_MIXED)
+    }
+
+    /// with_group adds an 'a=group' to the session description
+    pub fn with_group(self, group: Group) -> Self {
+        self.with_value_attribute(ATTR_KEY_GROUP.to_owned(), group.to_string())
+    }
+
+    /// groups returns the group attributes of the session description, in order, skipping any
+    /// that fail to parse
+    pub fn groups(&self) -> Vec<Group> {
+        self.attributes
+            .iter()
+            .filter(|a| a.key == ATTR_KEY_GROUP)
+            .filter_map(|a| a.value.as_deref())
+            .filter_map(|v| Group::parse_value(v).ok())
+            .collect()
+    }
+
+    /// bundle_mids returns the mids of the first BUNDLE group, in order, or an empty list if
+    /// nothing is bundled
+    ///
+    /// <https://tools.ietf.org/html/rfc8843#section-7.1>
+    pub fn bundle_mids(&self) -> Vec<String> {
+        self.groups()
+            .into_iter()
+            .find(|g| g.semantics == SEMANTIC_TOKEN_BUNDLE)
+            .map(|g| g.mids)
+            .unwrap_or_default()
+    }
+
     /// Attribute returns the value of an attribute and if it exists
     pub fn attribute(&self, key: &str) -> Option<&String> {
         for a in &self.attributes {
@@ -562,6 +609,13 @@ impl SessionDescription {
 
         Ok(lexer.desc)
     }
+
+    /// try_unmarshal parses a session description from arbitrary bytes, e.g. as received
+    /// from a remote peer. Malformed or truncated input results in an error rather than a panic.
+    pub fn try_unmarshal(data: &[u8]) -> Result<Self> {
+        let mut reader = io::Cursor::new(data);
+        SessionDescription::unmarshal(&mut reader)
+    }
 }
 
 impl From<SessionDescription> for String {
@@ -573,9 +627,7 @@ impl From<SessionDescription> for String {
 impl TryFrom<String> for SessionDescription {
     type Error = Error;
     fn try_from(sdp_string: String) -> Result<Self> {
-        let mut reader = io::Cursor::new(sdp_string.as_bytes());
-        let session_description = SessionDescription::unmarshal(&mut reader)?;
-        Ok(session_description)
+        SessionDescription::try_unmarshal(sdp_string.as_bytes())
     }
 }
 
@@ -1200,7 +1252,7 @@ fn unmarshal_media_description<'a, R: io::BufRead + io::Seek>(
 
     let fields: Vec<&str> = value.split_whitespace().collect();
     if fields.len() < 4 {
-        return Err(Error::SdpInvalidSyntax(format!("`m={value}`")));
+        return Err(Error::SdpInvalidMediaLine(format!("`m={value}`")));
     }
 
     // <media>
@@ -1218,9 +1270,15 @@ fn unmarshal_media_description<'a, R: io::BufRead + io::Seek>(
 
     // <port>
     let parts: Vec<&str> = fields[1].split('/').collect();
-    let port_value = parts[0].parse::<u16>()? as isize;
+    let port_value = parts[0]
+        .parse::<u16>()
+        .map_err(|_| Error::SdpInvalidPort(fields[1].to_owned()))? as isize;
     let port_range = if parts.len() > 1 {
-        Some(parts[1].parse::<i32>()? as isize)
+        Some(
+            parts[1]
+                .parse::<i32>()
+                .map_err(|_| Error::SdpInvalidPort(fields[1].to_owned()))? as isize,
+        )
     } else {
         None
     };
@@ -28,12 +28,28 @@ pub enum Error {
     SdpInvalidValue(String),
     #[error("sdp: empty time_descriptions")]
     SdpEmptyTimeDescription,
+    #[error("sdp: missing `=` in line: {0}")]
+    SdpMissingEquals(String),
+    #[error("sdp: invalid media line: {0}")]
+    SdpInvalidMediaLine(String),
+    #[error("sdp: invalid port: {0}")]
+    SdpInvalidPort(String),
     #[error("parse int: {0}")]
     ParseInt(#[from] ParseIntError),
     #[error("parse url: {0}")]
     ParseUrl(#[from] url::ParseError),
     #[error("parse extmap: {0}")]
     ParseExtMap(String),
+    #[error("parse group: {0}")]
+    ParseGroup(String),
+    #[error("parse imageattr: {0}")]
+    ParseImageAttr(String),
+    #[error("parse rid: {0}")]
+    ParseRid(String),
+    #[error("parse simulcast: {0}")]
+    ParseSimulcast(String),
+    #[error("parse ssrc-group: {0}")]
+    ParseSsrcGroup(String),
     #[error("{} --> {} <-- {}", .s.substring(0,*.p), .s.substring(*.p, *.p+1), .s.substring(*.p+1, .s.len()))]
     SyntaxError { s: String, p: usize },
 }
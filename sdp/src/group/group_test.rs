@@ -0,0 +1,76 @@
+use std::io::{BufReader, Cursor};
+
+use super::*;
+use crate::description::session::{SessionDescription, SEMANTIC_TOKEN_BUNDLE};
+
+const EXAMPLE_ATTR_GROUP1: &str = "group:BUNDLE 0 1 2";
+const EXAMPLE_ATTR_GROUP2: &str = "group:LS audio video";
+const EXAMPLE_ATTR_GROUP3: &str = "group:BUNDLE";
+const FAILING_ATTR_GROUP1: &str = "group:";
+const FAILING_ATTR_GROUP2: &str = "grp:BUNDLE 0";
+
+const BUNDLE_SDP: &str = "v=0\r\n\
+o=- 4596489990601351948 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:LS audio video\r\n\
+a=group:BUNDLE audio video data\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:audio\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+m=video 0 UDP/TLS/RTP/SAVPF 96\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:video\r\n\
+a=bundle-only\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+m=application 0 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:data\r\n\
+a=bundle-only\r\n\
+a=sctp-port:5000\r\n";
+
+#[test]
+fn test_group() -> Result<()> {
+    let passingtests = [
+        EXAMPLE_ATTR_GROUP1,
+        EXAMPLE_ATTR_GROUP2,
+        EXAMPLE_ATTR_GROUP3,
+    ];
+    let failingtests = [FAILING_ATTR_GROUP1, FAILING_ATTR_GROUP2];
+
+    for (i, u) in passingtests.iter().enumerate() {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = Group::unmarshal(&mut reader)?;
+        assert_eq!(actual.marshal(), *u, "{}: {} vs {}", i, u, actual.marshal());
+    }
+
+    for u in failingtests {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = Group::unmarshal(&mut reader);
+        assert!(actual.is_err(), "{u} should fail to parse");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bundle_mids() -> Result<()> {
+    let mut reader = Cursor::new(BUNDLE_SDP.as_bytes());
+    let parsed = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(parsed.marshal(), BUNDLE_SDP);
+
+    assert_eq!(parsed.groups().len(), 2);
+    assert_eq!(parsed.bundle_mids(), vec!["audio", "video", "data"]);
+
+    let built = SessionDescription::default().with_group(Group {
+        semantics: SEMANTIC_TOKEN_BUNDLE.to_owned(),
+        mids: vec!["2".to_owned(), "0".to_owned(), "1".to_owned()],
+    });
+    assert_eq!(built.attribute("group"), Some(&"BUNDLE 2 0 1".to_owned()));
+    assert_eq!(built.bundle_mids(), vec!["2", "0", "1"]);
+
+    assert!(SessionDescription::default().bundle_mids().is_empty());
+
+    Ok(())
+}
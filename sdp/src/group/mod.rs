@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod group_test;
+
+use std::{fmt, io};
+
+use super::error::{Error, Result};
+use crate::description::common::*;
+use crate::description::session::ATTR_KEY_GROUP;
+
+/// Group represents the `a=group` attribute, which relates the media descriptions identified
+/// by `mids`, e.g. `BUNDLE` ones share a single transport.
+/// <https://tools.ietf.org/html/rfc5888#section-5>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub semantics: String,
+    pub mids: Vec<String>,
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.semantics)?;
+        for mid in &self.mids {
+            write!(f, " {mid}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Group {
+    /// converts this object to an Attribute
+    pub fn convert(&self) -> Attribute {
+        Attribute {
+            key: ATTR_KEY_GROUP.to_string(),
+            value: Some(self.to_string()),
+        }
+    }
+
+    /// unmarshal creates a Group from a string
+    pub fn unmarshal<R: io::BufRead>(reader: &mut R) -> Result<Self> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let parts: Vec<&str> = line.trim().splitn(2, ':').collect();
+        if parts.len() != 2 || parts[0] != ATTR_KEY_GROUP {
+            return Err(Error::ParseGroup(line));
+        }
+
+        Group::parse_value(parts[1])
+    }
+
+    /// parse_value creates a Group from the value of a `group` attribute. A group may list no
+    /// mids, e.g. a BUNDLE group of an offer without media.
+    pub fn parse_value(value: &str) -> Result<Self> {
+        let mut fields = value.split_whitespace();
+        let semantics = match fields.next() {
+            Some(semantics) => semantics.to_owned(),
+            None => return Err(Error::ParseGroup(value.to_owned())),
+        };
+
+        Ok(Group {
+            semantics,
+            mids: fields.map(|mid| mid.to_owned()).collect(),
+        })
+    }
+
+    /// marshal creates a string from a Group
+    pub fn marshal(&self) -> String {
+        ATTR_KEY_GROUP.to_string() + ":" + self.to_string().as_str()
+    }
+}
@@ -0,0 +1,136 @@
+use std::io::{BufReader, Cursor};
+
+use super::*;
+use crate::description::media::MediaDescription;
+use crate::description::session::SessionDescription;
+
+// From RFC 6236 section 3.1.2.
+const EXAMPLE_ATTR_IMAGEATTR1: &str =
+    "imageattr:97 send [x=800,y=640,sar=1.1,q=0.6] [x=480,y=320] recv [x=330,y=250]";
+const EXAMPLE_ATTR_IMAGEATTR2: &str =
+    "imageattr:* send [x=[320:16:640],y=[240:16:480],par=[1.2-1.3]] recv *";
+const EXAMPLE_ATTR_IMAGEATTR3: &str = "imageattr:100 recv [x=[176,352,704],y=[144:576]]";
+const FAILING_ATTR_IMAGEATTR1: &str = "imageattr:97 send [y=640]";
+const FAILING_ATTR_IMAGEATTR2: &str = "imageattr:97 sendrecv [x=800,y=640]";
+const FAILING_ATTR_IMAGEATTR3: &str = "imageattr:97";
+const FAILING_ATTR_IMAGEATTR4: &str = "imageattr:97 send [x=[1:2:3:4],y=640]";
+
+#[test]
+fn test_imageattr() -> Result<()> {
+    let passingtests = [
+        EXAMPLE_ATTR_IMAGEATTR1,
+        EXAMPLE_ATTR_IMAGEATTR2,
+        EXAMPLE_ATTR_IMAGEATTR3,
+    ];
+    let failingtests = [
+        FAILING_ATTR_IMAGEATTR1,
+        FAILING_ATTR_IMAGEATTR2,
+        FAILING_ATTR_IMAGEATTR3,
+        FAILING_ATTR_IMAGEATTR4,
+    ];
+
+    for (i, u) in passingtests.iter().enumerate() {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = ImageAttr::unmarshal(&mut reader)?;
+        assert_eq!(actual.marshal(), *u, "{}: {} vs {}", i, u, actual.marshal());
+    }
+
+    for u in failingtests {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = ImageAttr::unmarshal(&mut reader);
+        assert!(actual.is_err(), "{u} should fail to parse");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_imageattr_typed_values() -> Result<()> {
+    let mut reader = BufReader::new(EXAMPLE_ATTR_IMAGEATTR1.as_bytes());
+    let actual = ImageAttr::unmarshal(&mut reader)?;
+
+    assert_eq!(actual.payload_type, Some(97));
+    let send = match &actual.send {
+        Some(ImageAttrList::Sets(sets)) => sets,
+        other => panic!("unexpected send list {other:?}"),
+    };
+    assert_eq!(send.len(), 2);
+    assert_eq!(send[0].max_width(), 800);
+    assert_eq!(send[0].max_height(), 640);
+    assert_eq!(send[0].sar.as_deref(), Some("1.1"));
+    assert_eq!(send[0].q, Some(0.6));
+    assert_eq!(send[1].min_width(), 480);
+    assert_eq!(send[1].min_height(), 320);
+
+    let mut reader = BufReader::new(EXAMPLE_ATTR_IMAGEATTR2.as_bytes());
+    let actual = ImageAttr::unmarshal(&mut reader)?;
+
+    assert_eq!(actual.payload_type, None);
+    assert_eq!(actual.recv, Some(ImageAttrList::Any));
+    let send = match &actual.send {
+        Some(ImageAttrList::Sets(sets)) => sets,
+        other => panic!("unexpected send list {other:?}"),
+    };
+    assert_eq!(
+        send[0].x,
+        ImageAttrRange::Range {
+            min: 320,
+            step: Some(16),
+            max: 640
+        }
+    );
+    assert_eq!(
+        (send[0].min_width(), send[0].max_width()),
+        (320, 640),
+        "width range"
+    );
+    assert_eq!(
+        (send[0].min_height(), send[0].max_height()),
+        (240, 480),
+        "height range"
+    );
+
+    let mut reader = BufReader::new(EXAMPLE_ATTR_IMAGEATTR3.as_bytes());
+    let actual = ImageAttr::unmarshal(&mut reader)?;
+
+    assert!(actual.send.is_none());
+    let recv = match &actual.recv {
+        Some(ImageAttrList::Sets(sets)) => sets,
+        other => panic!("unexpected recv list {other:?}"),
+    };
+    assert_eq!(recv[0].x, ImageAttrRange::List(vec![176, 352, 704]));
+    assert_eq!((recv[0].min_width(), recv[0].max_width()), (176, 704));
+    assert_eq!((recv[0].min_height(), recv[0].max_height()), (144, 576));
+
+    Ok(())
+}
+
+#[test]
+fn test_imageattr_media_description() -> Result<()> {
+    let sdp = "v=0\r\n\
+o=- 4596489990601351948 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 97\r\n\
+a=rtpmap:97 H264/90000\r\n\
+a=imageattr:97 send [x=800,y=640,sar=1.1,q=0.6] [x=480,y=320] recv [x=330,y=250]\r\n";
+
+    let mut reader = Cursor::new(sdp.as_bytes());
+    let parsed = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(parsed.marshal(), sdp);
+    let image_attrs = parsed.media_descriptions[0].image_attrs();
+    assert_eq!(image_attrs.len(), 1);
+    assert_eq!(image_attrs[0].payload_type, Some(97));
+
+    let media = MediaDescription::new_jsep_media_description("video".to_owned(), vec![])
+        .with_image_attr(image_attrs[0].clone());
+    assert_eq!(media.image_attrs(), image_attrs);
+    assert_eq!(
+        media.attribute(ATTR_KEY_IMAGE_ATTR),
+        Some(Some(
+            "97 send [x=800,y=640,sar=1.1,q=0.6] [x=480,y=320] recv [x=330,y=250]"
+        ))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,341 @@
+#[cfg(test)]
+mod imageattr_test;
+
+use std::{fmt, io};
+
+use super::error::{Error, Result};
+use crate::description::common::*;
+
+pub const ATTR_KEY_IMAGE_ATTR: &str = "imageattr";
+
+/// ImageAttrRange is the set of values allowed for a single image dimension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageAttrRange {
+    /// A single value, e.g. `640`.
+    Value(u32),
+    /// A range with an optional step, e.g. `[320:640]` or `[320:16:640]`.
+    Range {
+        min: u32,
+        step: Option<u32>,
+        max: u32,
+    },
+    /// A list of discrete values, e.g. `[320,480,640]`.
+    List(Vec<u32>),
+}
+
+impl Default for ImageAttrRange {
+    fn default() -> Self {
+        ImageAttrRange::Value(0)
+    }
+}
+
+impl fmt::Display for ImageAttrRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageAttrRange::Value(v) => write!(f, "{v}"),
+            ImageAttrRange::Range {
+                min,
+                step: Some(step),
+                max,
+            } => write!(f, "[{min}:{step}:{max}]"),
+            ImageAttrRange::Range {
+                min,
+                step: None,
+                max,
+            } => write!(f, "[{min}:{max}]"),
+            ImageAttrRange::List(values) => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", values.join(","))
+            }
+        }
+    }
+}
+
+impl ImageAttrRange {
+    /// min returns the smallest value allowed by the range.
+    pub fn min(&self) -> u32 {
+        match self {
+            ImageAttrRange::Value(v) => *v,
+            ImageAttrRange::Range { min, .. } => *min,
+            ImageAttrRange::List(values) => values.iter().copied().min().unwrap_or(0),
+        }
+    }
+
+    /// max returns the largest value allowed by the range.
+    pub fn max(&self) -> u32 {
+        match self {
+            ImageAttrRange::Value(v) => *v,
+            ImageAttrRange::Range { max, .. } => *max,
+            ImageAttrRange::List(values) => values.iter().copied().max().unwrap_or(0),
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        let inner = match s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            Some(inner) => inner,
+            None => return Ok(ImageAttrRange::Value(s.parse()?)),
+        };
+
+        if inner.contains(':') {
+            let values = inner
+                .split(':')
+                .map(|v| v.parse::<u32>())
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            match values[..] {
+                [min, max] => Ok(ImageAttrRange::Range {
+                    min,
+                    step: None,
+                    max,
+                }),
+                [min, step, max] => Ok(ImageAttrRange::Range {
+                    min,
+                    step: Some(step),
+                    max,
+                }),
+                _ => Err(Error::ParseImageAttr(format!("invalid range {s}"))),
+            }
+        } else {
+            let values = inner
+                .split(',')
+                .map(|v| v.parse::<u32>())
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            if values.len() < 2 {
+                return Err(Error::ParseImageAttr(format!("invalid list {s}")));
+            }
+            Ok(ImageAttrRange::List(values))
+        }
+    }
+}
+
+/// ImageAttrSet is a single `[x=...,y=...]` set of acceptable image sizes.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImageAttrSet {
+    /// Allowed widths in pixels.
+    pub x: ImageAttrRange,
+    /// Allowed heights in pixels.
+    pub y: ImageAttrRange,
+    /// Sample aspect ratio, kept as written.
+    pub sar: Option<String>,
+    /// Picture aspect ratio range, kept as written.
+    pub par: Option<String>,
+    /// Preference of this set relative to the others, between 0.0 and 1.0.
+    pub q: Option<f32>,
+}
+
+impl fmt::Display for ImageAttrSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[x={},y={}", self.x, self.y)?;
+        if let Some(sar) = &self.sar {
+            write!(f, ",sar={sar}")?;
+        }
+        if let Some(par) = &self.par {
+            write!(f, ",par={par}")?;
+        }
+        if let Some(q) = &self.q {
+            write!(f, ",q={q}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl ImageAttrSet {
+    pub fn min_width(&self) -> u32 {
+        self.x.min()
+    }
+
+    pub fn max_width(&self) -> u32 {
+        self.x.max()
+    }
+
+    pub fn min_height(&self) -> u32 {
+        self.y.min()
+    }
+
+    pub fn max_height(&self) -> u32 {
+        self.y.max()
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        let inner = s
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .ok_or_else(|| Error::ParseImageAttr(format!("invalid set {s}")))?;
+
+        let mut set = ImageAttrSet::default();
+        let (mut has_x, mut has_y) = (false, false);
+        for param in split_top_level(inner) {
+            let (key, value) = param
+                .split_once('=')
+                .ok_or_else(|| Error::ParseImageAttr(format!("invalid parameter {param}")))?;
+            match key {
+                "x" => {
+                    set.x = ImageAttrRange::parse(value)?;
+                    has_x = true;
+                }
+                "y" => {
+                    set.y = ImageAttrRange::parse(value)?;
+                    has_y = true;
+                }
+                "sar" => set.sar = Some(value.to_owned()),
+                "par" => set.par = Some(value.to_owned()),
+                "q" => {
+                    set.q =
+                        Some(value.parse().map_err(|_| {
+                            Error::ParseImageAttr(format!("invalid q value {value}"))
+                        })?)
+                }
+                _ => return Err(Error::ParseImageAttr(format!("unknown parameter {param}"))),
+            }
+        }
+
+        if !has_x || !has_y {
+            return Err(Error::ParseImageAttr(format!("missing x or y in {s}")));
+        }
+
+        Ok(set)
+    }
+}
+
+/// ImageAttrList is the list of acceptable image sizes for one direction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageAttrList {
+    /// `*`, any image size is acceptable.
+    Any,
+    Sets(Vec<ImageAttrSet>),
+}
+
+impl fmt::Display for ImageAttrList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageAttrList::Any => write!(f, "*"),
+            ImageAttrList::Sets(sets) => {
+                let sets: Vec<String> = sets.iter().map(|s| s.to_string()).collect();
+                write!(f, "{}", sets.join(" "))
+            }
+        }
+    }
+}
+
+/// ImageAttr represents the `a=imageattr` attribute used to negotiate image sizes.
+/// <https://tools.ietf.org/html/rfc6236#section-3.1>
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImageAttr {
+    /// The payload type the attribute applies to, [`None`] for `*`, i.e. all payload types.
+    pub payload_type: Option<u8>,
+    /// Image sizes the sender of the description is able to send.
+    pub send: Option<ImageAttrList>,
+    /// Image sizes the sender of the description wants to receive.
+    pub recv: Option<ImageAttrList>,
+}
+
+impl fmt::Display for ImageAttr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.payload_type {
+            Some(pt) => write!(f, "{pt}")?,
+            None => write!(f, "*")?,
+        }
+        if let Some(send) = &self.send {
+            write!(f, " send {send}")?;
+        }
+        if let Some(recv) = &self.recv {
+            write!(f, " recv {recv}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ImageAttr {
+    /// converts this object to an Attribute
+    pub fn convert(&self) -> Attribute {
+        Attribute {
+            key: ATTR_KEY_IMAGE_ATTR.to_string(),
+            value: Some(self.to_string()),
+        }
+    }
+
+    /// unmarshal creates an ImageAttr from a string
+    pub fn unmarshal<R: io::BufRead>(reader: &mut R) -> Result<Self> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let parts: Vec<&str> = line.trim().splitn(2, ':').collect();
+        if parts.len() != 2 || parts[0] != ATTR_KEY_IMAGE_ATTR {
+            return Err(Error::ParseImageAttr(line));
+        }
+
+        ImageAttr::parse_value(parts[1])
+    }
+
+    /// parse_value creates an ImageAttr from the value of an `imageattr` attribute
+    pub fn parse_value(value: &str) -> Result<Self> {
+        let mut fields = value.split_whitespace().peekable();
+
+        let payload_type = match fields.next() {
+            Some("*") => None,
+            Some(pt) => Some(pt.parse::<u8>()?),
+            None => return Err(Error::ParseImageAttr(value.to_owned())),
+        };
+
+        let mut image_attr = ImageAttr {
+            payload_type,
+            ..Default::default()
+        };
+
+        while let Some(direction) = fields.next() {
+            let mut sets = vec![];
+            let mut any = false;
+            while let Some(field) = fields.next_if(|f| *f != "send" && *f != "recv") {
+                if field == "*" {
+                    any = true;
+                } else {
+                    sets.push(ImageAttrSet::parse(field)?);
+                }
+            }
+
+            let list = match (any, sets.is_empty()) {
+                (true, true) => ImageAttrList::Any,
+                (false, false) => ImageAttrList::Sets(sets),
+                _ => return Err(Error::ParseImageAttr(value.to_owned())),
+            };
+
+            let slot = match direction {
+                "send" => &mut image_attr.send,
+                "recv" => &mut image_attr.recv,
+                _ => return Err(Error::ParseImageAttr(value.to_owned())),
+            };
+            if slot.replace(list).is_some() {
+                return Err(Error::ParseImageAttr(value.to_owned()));
+            }
+        }
+
+        if image_attr.send.is_none() && image_attr.recv.is_none() {
+            return Err(Error::ParseImageAttr(value.to_owned()));
+        }
+
+        Ok(image_attr)
+    }
+
+    /// marshal creates a string from an ImageAttr
+    pub fn marshal(&self) -> String {
+        ATTR_KEY_IMAGE_ATTR.to_string() + ":" + self.to_string().as_str()
+    }
+}
+
+/// split_top_level splits `s` on commas that aren't enclosed in brackets.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
@@ -35,6 +35,16 @@ pub fn read_type<R: io::BufRead + io::Seek>(reader: &mut R) -> Result<(Vec<u8>,
         if num_bytes == 0 {
             return Ok((b"".to_vec(), num_bytes));
         }
+        // The type must be followed by `=` before the end of the line
+        if buf.last() != Some(&b'=') || buf.contains(&b'\n') {
+            let line = buf
+                .split(|b| *b == b'\r' || *b == b'\n')
+                .next()
+                .unwrap_or_default();
+            return Err(Error::SdpMissingEquals(
+                String::from_utf8_lossy(line).into_owned(),
+            ));
+        }
         match buf.len() {
             2 => return Ok((buf, num_bytes)),
             _ => return Err(Error::SdpInvalidSyntax(String::from_utf8(buf)?)),
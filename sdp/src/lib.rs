@@ -4,6 +4,10 @@
 pub mod description;
 pub mod direction;
 pub mod extmap;
+pub mod group;
+pub mod imageattr;
+pub mod simulcast;
+pub mod ssrc_group;
 pub mod util;
 
 mod error;
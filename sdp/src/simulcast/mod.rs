@@ -0,0 +1,281 @@
+#[cfg(test)]
+mod simulcast_test;
+
+use std::{fmt, io};
+
+use super::error::{Error, Result};
+use crate::description::common::*;
+
+pub const ATTR_KEY_RID: &str = "rid";
+pub const ATTR_KEY_SIMULCAST: &str = "simulcast";
+
+/// RidDirection is the direction of the RTP stream a rid applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RidDirection {
+    Send,
+    Recv,
+}
+
+impl fmt::Display for RidDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RidDirection::Send => write!(f, "send"),
+            RidDirection::Recv => write!(f, "recv"),
+        }
+    }
+}
+
+impl RidDirection {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "send" => Some(RidDirection::Send),
+            "recv" => Some(RidDirection::Recv),
+            _ => None,
+        }
+    }
+}
+
+/// Rid represents the `a=rid` attribute identifying an RTP stream and its restrictions.
+/// <https://tools.ietf.org/html/rfc8851#section-4>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rid {
+    pub id: String,
+    pub direction: RidDirection,
+    /// The payload types the stream may use, empty if not restricted.
+    pub payload_types: Vec<u8>,
+    /// The restrictions in the order they appear, e.g. `("max-width", Some("1280"))`.
+    pub restrictions: Vec<(String, Option<String>)>,
+}
+
+impl fmt::Display for Rid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.id, self.direction)?;
+
+        let mut params = vec![];
+        if !self.payload_types.is_empty() {
+            let payload_types: Vec<String> =
+                self.payload_types.iter().map(|pt| pt.to_string()).collect();
+            params.push(format!("pt={}", payload_types.join(",")));
+        }
+        for (key, value) in &self.restrictions {
+            match value {
+                Some(value) => params.push(format!("{key}={value}")),
+                None => params.push(key.clone()),
+            }
+        }
+        if !params.is_empty() {
+            write!(f, " {}", params.join(";"))?;
+        }
+        Ok(())
+    }
+}
+
+impl Rid {
+    /// converts this object to an Attribute
+    pub fn convert(&self) -> Attribute {
+        Attribute {
+            key: ATTR_KEY_RID.to_string(),
+            value: Some(self.to_string()),
+        }
+    }
+
+    /// restriction returns the value of the restriction `key`, `Some("")` if it has no value
+    pub fn restriction(&self, key: &str) -> Option<&str> {
+        self.restrictions
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_deref().unwrap_or(""))
+    }
+
+    /// max_width returns the `max-width` restriction, in pixels
+    pub fn max_width(&self) -> Option<u32> {
+        self.restriction("max-width")?.parse().ok()
+    }
+
+    /// max_height returns the `max-height` restriction, in pixels
+    pub fn max_height(&self) -> Option<u32> {
+        self.restriction("max-height")?.parse().ok()
+    }
+
+    /// unmarshal creates a Rid from a string
+    pub fn unmarshal<R: io::BufRead>(reader: &mut R) -> Result<Self> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let parts: Vec<&str> = line.trim().splitn(2, ':').collect();
+        if parts.len() != 2 || parts[0] != ATTR_KEY_RID {
+            return Err(Error::ParseRid(line));
+        }
+
+        Rid::parse_value(parts[1])
+    }
+
+    /// parse_value creates a Rid from the value of a `rid` attribute
+    pub fn parse_value(value: &str) -> Result<Self> {
+        let mut fields = value.split_whitespace();
+
+        let (id, direction) = match (fields.next(), fields.next().and_then(RidDirection::parse)) {
+            (Some(id), Some(direction)) => (id.to_owned(), direction),
+            _ => return Err(Error::ParseRid(value.to_owned())),
+        };
+
+        let mut rid = Rid {
+            id,
+            direction,
+            payload_types: vec![],
+            restrictions: vec![],
+        };
+
+        if let Some(params) = fields.next() {
+            for (i, param) in params.split(';').enumerate() {
+                match param.split_once('=') {
+                    // The payload types can only come first.
+                    Some(("pt", pts)) if i == 0 => {
+                        rid.payload_types = pts
+                            .split(',')
+                            .map(|pt| pt.parse::<u8>())
+                            .collect::<std::result::Result<Vec<_>, _>>()?;
+                    }
+                    Some(("pt", _)) => return Err(Error::ParseRid(value.to_owned())),
+                    Some((key, v)) => rid.restrictions.push((key.to_owned(), Some(v.to_owned()))),
+                    None if !param.is_empty() => rid.restrictions.push((param.to_owned(), None)),
+                    None => return Err(Error::ParseRid(value.to_owned())),
+                }
+            }
+        }
+
+        if fields.next().is_some() {
+            return Err(Error::ParseRid(value.to_owned()));
+        }
+
+        Ok(rid)
+    }
+
+    /// marshal creates a string from a Rid
+    pub fn marshal(&self) -> String {
+        ATTR_KEY_RID.to_string() + ":" + self.to_string().as_str()
+    }
+}
+
+/// SimulcastRid is a rid listed in an `a=simulcast` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulcastRid {
+    pub id: String,
+    /// Whether the stream is paused, written as a `~` prefix.
+    pub paused: bool,
+}
+
+impl fmt::Display for SimulcastRid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.paused {
+            write!(f, "~")?;
+        }
+        write!(f, "{}", self.id)
+    }
+}
+
+/// Simulcast represents the `a=simulcast` attribute listing the simulcast streams of a media
+/// description. Each direction lists the streams, and for each stream its alternative rids, in
+/// order of preference.
+/// <https://tools.ietf.org/html/rfc8853#section-5.1>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Simulcast {
+    pub send: Vec<Vec<SimulcastRid>>,
+    pub recv: Vec<Vec<SimulcastRid>>,
+}
+
+impl fmt::Display for Simulcast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |streams: &[Vec<SimulcastRid>]| {
+            let streams: Vec<String> = streams
+                .iter()
+                .map(|alternatives| {
+                    let alternatives: Vec<String> =
+                        alternatives.iter().map(|rid| rid.to_string()).collect();
+                    alternatives.join(",")
+                })
+                .collect();
+            streams.join(";")
+        };
+
+        let mut directions = vec![];
+        if !self.send.is_empty() {
+            directions.push(format!("send {}", list(&self.send)));
+        }
+        if !self.recv.is_empty() {
+            directions.push(format!("recv {}", list(&self.recv)));
+        }
+        write!(f, "{}", directions.join(" "))
+    }
+}
+
+impl Simulcast {
+    /// converts this object to an Attribute
+    pub fn convert(&self) -> Attribute {
+        Attribute {
+            key: ATTR_KEY_SIMULCAST.to_string(),
+            value: Some(self.to_string()),
+        }
+    }
+
+    /// unmarshal creates a Simulcast from a string
+    pub fn unmarshal<R: io::BufRead>(reader: &mut R) -> Result<Self> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let parts: Vec<&str> = line.trim().splitn(2, ':').collect();
+        if parts.len() != 2 || parts[0] != ATTR_KEY_SIMULCAST {
+            return Err(Error::ParseSimulcast(line));
+        }
+
+        Simulcast::parse_value(parts[1])
+    }
+
+    /// parse_value creates a Simulcast from the value of a `simulcast` attribute. The send and
+    /// recv lists may come in either order.
+    pub fn parse_value(value: &str) -> Result<Self> {
+        let mut fields = value.split_whitespace();
+        let mut simulcast = Simulcast::default();
+
+        while let Some(direction) = fields.next() {
+            let list = match direction {
+                "send" => &mut simulcast.send,
+                "recv" => &mut simulcast.recv,
+                _ => return Err(Error::ParseSimulcast(value.to_owned())),
+            };
+            let streams = match fields.next() {
+                Some(streams) if list.is_empty() => streams,
+                _ => return Err(Error::ParseSimulcast(value.to_owned())),
+            };
+
+            for alternatives in streams.split(';') {
+                let alternatives = alternatives
+                    .split(',')
+                    .map(|id| {
+                        let (id, paused) = match id.strip_prefix('~') {
+                            Some(id) => (id, true),
+                            None => (id, false),
+                        };
+                        if id.is_empty() {
+                            return Err(Error::ParseSimulcast(value.to_owned()));
+                        }
+                        Ok(SimulcastRid {
+                            id: id.to_owned(),
+                            paused,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                list.push(alternatives);
+            }
+        }
+
+        if simulcast.send.is_empty() && simulcast.recv.is_empty() {
+            return Err(Error::ParseSimulcast(value.to_owned()));
+        }
+
+        Ok(simulcast)
+    }
+
+    /// marshal creates a string from a Simulcast
+    pub fn marshal(&self) -> String {
+        ATTR_KEY_SIMULCAST.to_string() + ":" + self.to_string().as_str()
+    }
+}
@@ -0,0 +1,210 @@
+use std::io::{BufReader, Cursor};
+
+use super::*;
+use crate::description::media::MediaDescription;
+use crate::description::session::SessionDescription;
+
+const EXAMPLE_ATTR_RID1: &str = "rid:f send";
+// From RFC 8851 section 10.
+const EXAMPLE_ATTR_RID2: &str = "rid:1 send pt=97,98;max-width=1280;max-height=720;max-fps=30";
+const EXAMPLE_ATTR_RID3: &str = "rid:5 recv pt=99;depend=1";
+const EXAMPLE_ATTR_RID4: &str = "rid:2 send max-br=64000;custom";
+const FAILING_ATTR_RID1: &str = "rid:1";
+const FAILING_ATTR_RID2: &str = "rid:1 sendrecv";
+const FAILING_ATTR_RID3: &str = "rid:1 send max-width=1280;pt=97";
+const FAILING_ATTR_RID4: &str = "rid:1 send pt=x";
+
+// From RFC 8853 section 5.1.
+const EXAMPLE_ATTR_SIMULCAST1: &str = "simulcast:send 1;2,3 recv 4";
+const EXAMPLE_ATTR_SIMULCAST2: &str = "simulcast:send ~q;h;f";
+const EXAMPLE_ATTR_SIMULCAST3: &str = "simulcast:recv 1,~4;2;3";
+const FAILING_ATTR_SIMULCAST1: &str = "simulcast:";
+const FAILING_ATTR_SIMULCAST2: &str = "simulcast:sendrecv 1;2";
+const FAILING_ATTR_SIMULCAST3: &str = "simulcast:send 1;;2";
+const FAILING_ATTR_SIMULCAST4: &str = "simulcast:send 1 send 2";
+const FAILING_ATTR_SIMULCAST5: &str = "simulcast:send";
+
+// A video section as offered by Chrome with three simulcast layers.
+const CHROME_SIMULCAST_OFFER: &str = "v=0\r\n\
+o=- 3840232462471583827 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0\r\n\
+a=msid-semantic: WMS\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Jx4A\r\n\
+a=ice-pwd:3ZKgO3R5ncD8WvK9mMt2T1y+\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 B9:B2:86:10:8C:14:AE:07:3F:4C:1A:99:6D:0B:8E:1B:3E:8B:62:1A:6C:20:16:7C:5E:24:85:0B:2D:D4:6A:48\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:10 urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id\r\n\
+a=extmap:11 urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id\r\n\
+a=sendonly\r\n\
+a=msid:- 8a6b7d4c-2f0b-4d5a-9b8e-3c1f6e0d2a71\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 goog-remb\r\n\
+a=rtcp-fb:96 transport-cc\r\n\
+a=rtcp-fb:96 ccm fir\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=rid:q send\r\n\
+a=rid:h send\r\n\
+a=rid:f send\r\n\
+a=simulcast:send q;h;f\r\n";
+
+#[test]
+fn test_rid() -> Result<()> {
+    let passingtests = [
+        EXAMPLE_ATTR_RID1,
+        EXAMPLE_ATTR_RID2,
+        EXAMPLE_ATTR_RID3,
+        EXAMPLE_ATTR_RID4,
+    ];
+    let failingtests = [
+        FAILING_ATTR_RID1,
+        FAILING_ATTR_RID2,
+        FAILING_ATTR_RID3,
+        FAILING_ATTR_RID4,
+    ];
+
+    for (i, u) in passingtests.iter().enumerate() {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = Rid::unmarshal(&mut reader)?;
+        assert_eq!(actual.marshal(), *u, "{}: {} vs {}", i, u, actual.marshal());
+    }
+
+    for u in failingtests {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = Rid::unmarshal(&mut reader);
+        assert!(actual.is_err(), "{u} should fail to parse");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rid_typed_values() -> Result<()> {
+    let mut reader = BufReader::new(EXAMPLE_ATTR_RID2.as_bytes());
+    let actual = Rid::unmarshal(&mut reader)?;
+
+    assert_eq!(actual.id, "1");
+    assert_eq!(actual.direction, RidDirection::Send);
+    assert_eq!(actual.payload_types, vec![97, 98]);
+    assert_eq!(actual.max_width(), Some(1280));
+    assert_eq!(actual.max_height(), Some(720));
+    assert_eq!(actual.restriction("max-fps"), Some("30"));
+    assert_eq!(actual.restriction("max-br"), None);
+
+    let mut reader = BufReader::new(EXAMPLE_ATTR_RID4.as_bytes());
+    let actual = Rid::unmarshal(&mut reader)?;
+
+    assert!(actual.payload_types.is_empty());
+    assert_eq!(actual.restriction("custom"), Some(""));
+    assert_eq!(actual.max_width(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_simulcast() -> Result<()> {
+    let passingtests = [
+        EXAMPLE_ATTR_SIMULCAST1,
+        EXAMPLE_ATTR_SIMULCAST2,
+        EXAMPLE_ATTR_SIMULCAST3,
+    ];
+    let failingtests = [
+        FAILING_ATTR_SIMULCAST1,
+        FAILING_ATTR_SIMULCAST2,
+        FAILING_ATTR_SIMULCAST3,
+        FAILING_ATTR_SIMULCAST4,
+        FAILING_ATTR_SIMULCAST5,
+    ];
+
+    for (i, u) in passingtests.iter().enumerate() {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = Simulcast::unmarshal(&mut reader)?;
+        assert_eq!(actual.marshal(), *u, "{}: {} vs {}", i, u, actual.marshal());
+    }
+
+    for u in failingtests {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = Simulcast::unmarshal(&mut reader);
+        assert!(actual.is_err(), "{u} should fail to parse");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_simulcast_typed_values() -> Result<()> {
+    let rid = |id: &str, paused: bool| SimulcastRid {
+        id: id.to_owned(),
+        paused,
+    };
+
+    let actual = Simulcast::parse_value("send 1;2,3 recv 4")?;
+    assert_eq!(
+        actual.send,
+        vec![
+            vec![rid("1", false)],
+            vec![rid("2", false), rid("3", false)]
+        ]
+    );
+    assert_eq!(actual.recv, vec![vec![rid("4", false)]]);
+
+    // The directions may come in either order.
+    let reversed = Simulcast::parse_value("recv 4 send 1;2,3")?;
+    assert_eq!(reversed, actual);
+
+    let actual = Simulcast::parse_value("recv 1,~4;~2")?;
+    assert!(actual.send.is_empty());
+    assert_eq!(
+        actual.recv,
+        vec![vec![rid("1", false), rid("4", true)], vec![rid("2", true)]]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_simulcast_media_description() -> Result<()> {
+    let mut reader = Cursor::new(CHROME_SIMULCAST_OFFER.as_bytes());
+    let parsed = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(parsed.marshal(), CHROME_SIMULCAST_OFFER);
+
+    let media = &parsed.media_descriptions[0];
+    let rids = media.rids();
+    let ids: Vec<&str> = rids.iter().map(|rid| rid.id.as_str()).collect();
+    assert_eq!(ids, vec!["q", "h", "f"]);
+    assert!(rids.iter().all(|rid| rid.direction == RidDirection::Send));
+
+    let simulcast = media.simulcast().expect("a simulcast attribute");
+    assert!(simulcast.recv.is_empty());
+    let send: Vec<&str> = simulcast
+        .send
+        .iter()
+        .map(|alternatives| alternatives[0].id.as_str())
+        .collect();
+    assert_eq!(send, ids);
+
+    let media = MediaDescription::new_jsep_media_description("video".to_owned(), vec![])
+        .with_rid(rids[0].clone())
+        .with_simulcast(simulcast.clone());
+    assert_eq!(media.rids(), vec![rids[0].clone()]);
+    assert_eq!(media.simulcast(), Some(simulcast));
+    assert_eq!(media.attribute(ATTR_KEY_RID), Some(Some("q send")));
+    assert_eq!(
+        media.attribute(ATTR_KEY_SIMULCAST),
+        Some(Some("send q;h;f"))
+    );
+
+    Ok(())
+}
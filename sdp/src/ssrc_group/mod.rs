@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod ssrc_group_test;
+
+use std::{fmt, io};
+
+use super::error::{Error, Result};
+use crate::description::common::*;
+use crate::description::session::ATTR_KEY_SSRCGROUP;
+
+/// SsrcGroup represents the `a=ssrc-group` attribute, which relates the SSRCs of a media
+/// description, e.g. `FID` pairs a stream with its retransmission stream and `SIM` lists the
+/// streams of a legacy simulcast, from lowest to highest quality.
+/// <https://tools.ietf.org/html/rfc5576#section-4.2>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SsrcGroup {
+    pub semantics: String,
+    pub ssrcs: Vec<u32>,
+}
+
+impl fmt::Display for SsrcGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.semantics)?;
+        for ssrc in &self.ssrcs {
+            write!(f, " {ssrc}")?;
+        }
+        Ok(())
+    }
+}
+
+impl SsrcGroup {
+    /// converts this object to an Attribute
+    pub fn convert(&self) -> Attribute {
+        Attribute {
+            key: ATTR_KEY_SSRCGROUP.to_string(),
+            value: Some(self.to_string()),
+        }
+    }
+
+    /// unmarshal creates a SsrcGroup from a string
+    pub fn unmarshal<R: io::BufRead>(reader: &mut R) -> Result<Self> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let parts: Vec<&str> = line.trim().splitn(2, ':').collect();
+        if parts.len() != 2 || parts[0] != ATTR_KEY_SSRCGROUP {
+            return Err(Error::ParseSsrcGroup(line));
+        }
+
+        SsrcGroup::parse_value(parts[1])
+    }
+
+    /// parse_value creates a SsrcGroup from the value of a `ssrc-group` attribute
+    pub fn parse_value(value: &str) -> Result<Self> {
+        let mut fields = value.split_whitespace();
+        let semantics = match fields.next() {
+            Some(semantics) => semantics.to_owned(),
+            None => return Err(Error::ParseSsrcGroup(value.to_owned())),
+        };
+
+        let ssrcs = fields
+            .map(|ssrc| ssrc.parse::<u32>())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if ssrcs.is_empty() {
+            return Err(Error::ParseSsrcGroup(value.to_owned()));
+        }
+
+        Ok(SsrcGroup { semantics, ssrcs })
+    }
+
+    /// marshal creates a string from a SsrcGroup
+    pub fn marshal(&self) -> String {
+        ATTR_KEY_SSRCGROUP.to_string() + ":" + self.to_string().as_str()
+    }
+}
@@ -0,0 +1,88 @@
+use std::io::{BufReader, Cursor};
+
+use super::*;
+use crate::description::media::MediaDescription;
+use crate::description::session::{
+    SessionDescription, SEMANTIC_TOKEN_FLOW_IDENTIFICATION, SEMANTIC_TOKEN_SIMULCAST,
+};
+
+const EXAMPLE_ATTR_SSRC_GROUP1: &str = "ssrc-group:FID 2231627014 632943048";
+const EXAMPLE_ATTR_SSRC_GROUP2: &str = "ssrc-group:SIM 1111 2222 3333";
+const EXAMPLE_ATTR_SSRC_GROUP3: &str = "ssrc-group:FEC-FR 1111";
+const FAILING_ATTR_SSRC_GROUP1: &str = "ssrc-group:";
+const FAILING_ATTR_SSRC_GROUP2: &str = "ssrc-group:FID";
+const FAILING_ATTR_SSRC_GROUP3: &str = "ssrc-group:FID 1111 rtx";
+const FAILING_ATTR_SSRC_GROUP4: &str = "ssrc:FID 1111 2222";
+
+const FID_MEDIA_SECTION: &str = "v=0\r\n\
+o=- 4596489990601351948 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:0\r\n\
+a=sendrecv\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=ssrc-group:FID 2231627014 632943048\r\n\
+a=ssrc:2231627014 cname:4TOk42mSjXCkVIa6\r\n\
+a=ssrc:2231627014 msid:stream track\r\n\
+a=ssrc:632943048 cname:4TOk42mSjXCkVIa6\r\n\
+a=ssrc:632943048 msid:stream track\r\n";
+
+#[test]
+fn test_ssrc_group() -> Result<()> {
+    let passingtests = [
+        EXAMPLE_ATTR_SSRC_GROUP1,
+        EXAMPLE_ATTR_SSRC_GROUP2,
+        EXAMPLE_ATTR_SSRC_GROUP3,
+    ];
+    let failingtests = [
+        FAILING_ATTR_SSRC_GROUP1,
+        FAILING_ATTR_SSRC_GROUP2,
+        FAILING_ATTR_SSRC_GROUP3,
+        FAILING_ATTR_SSRC_GROUP4,
+    ];
+
+    for (i, u) in passingtests.iter().enumerate() {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = SsrcGroup::unmarshal(&mut reader)?;
+        assert_eq!(actual.marshal(), *u, "{}: {} vs {}", i, u, actual.marshal());
+    }
+
+    for u in failingtests {
+        let mut reader = BufReader::new(u.as_bytes());
+        let actual = SsrcGroup::unmarshal(&mut reader);
+        assert!(actual.is_err(), "{u} should fail to parse");
+    }
+
+    let actual = SsrcGroup::parse_value("SIM 1111 2222 3333")?;
+    assert_eq!(actual.semantics, SEMANTIC_TOKEN_SIMULCAST);
+    assert_eq!(actual.ssrcs, vec![1111, 2222, 3333]);
+
+    Ok(())
+}
+
+#[test]
+fn test_ssrc_group_media_description() -> Result<()> {
+    let mut reader = Cursor::new(FID_MEDIA_SECTION.as_bytes());
+    let parsed = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(parsed.marshal(), FID_MEDIA_SECTION);
+
+    let fid = SsrcGroup {
+        semantics: SEMANTIC_TOKEN_FLOW_IDENTIFICATION.to_owned(),
+        ssrcs: vec![2231627014, 632943048],
+    };
+    assert_eq!(
+        parsed.media_descriptions[0].ssrc_groups(),
+        vec![fid.clone()]
+    );
+
+    let media = MediaDescription::default()
+        .with_ssrc_group(fid.clone())
+        .with_value_attribute("ssrc-group".to_owned(), "FID nope".to_owned());
+    assert_eq!(media.ssrc_groups(), vec![fid]);
+
+    Ok(())
+}
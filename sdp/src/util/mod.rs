@@ -162,6 +162,22 @@ pub(crate) fn parse_fmtp(fmtp: &str) -> Result<Codec> {
     })
 }
 
+/// parse_fmtp_parameters parses the format specific parameters of an 'a=fmtp' line,
+/// e.g. `profile-level-id=42e01f;packetization-mode=1`, into a map. Keys are lowercased. A
+/// parameter without a value, such as the bare `0-15` of telephone-event, maps to an empty
+/// string.
+pub fn parse_fmtp_parameters(parameters: &str) -> HashMap<String, String> {
+    parameters
+        .split(';')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| match p.split_once('=') {
+            Some((key, value)) => (key.trim().to_lowercase(), value.trim().to_owned()),
+            None => (p.to_lowercase(), String::new()),
+        })
+        .collect()
+}
+
 pub(crate) fn parse_rtcp_fb(rtcp_fb: &str) -> Result<Codec> {
     // a=ftcp-fb:<payload type> <RTCP feedback type> [<RTCP feedback parameter>]
     let split: Vec<&str> = rtcp_fb.splitn(2, ' ').collect();
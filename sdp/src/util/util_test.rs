@@ -175,3 +175,27 @@ fn test_new_session_id() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_parse_fmtp_parameters() {
+    let params = parse_fmtp_parameters("profile-level-id=42e01f;packetization-mode=1");
+    assert_eq!(params.len(), 2);
+    assert_eq!(params["profile-level-id"], "42e01f");
+    assert_eq!(params["packetization-mode"], "1");
+
+    let params = parse_fmtp_parameters("minptime=10; useinbandfec=1");
+    assert_eq!(params.len(), 2);
+    assert_eq!(params["minptime"], "10");
+    assert_eq!(params["useinbandfec"], "1");
+
+    // Keys are case-insensitive, values are kept as is.
+    let params = parse_fmtp_parameters("Profile-Level-Id=42E01F;level-asymmetry-allowed");
+    assert_eq!(params["profile-level-id"], "42E01F");
+    assert_eq!(params["level-asymmetry-allowed"], "");
+
+    let params = parse_fmtp_parameters("0-15");
+    assert_eq!(params.len(), 1);
+    assert_eq!(params["0-15"], "");
+
+    assert!(parse_fmtp_parameters("").is_empty());
+}
@@ -91,7 +91,7 @@ impl CipherInner {
     /// - Authenticated portion of the packet is everything BEFORE MKI
     /// - k_a is the session message authentication key
     /// - n_tag is the bit-length of the output authentication tag
-    fn generate_srtp_auth_tag(&self, buf: &[u8], roc: u32) -> [u8; 20] {
+    pub(crate) fn generate_srtp_auth_tag(&self, buf: &[u8], roc: u32) -> [u8; 20] {
         let mut signer = self.srtp_session_auth.clone();
 
         signer.update(buf);
@@ -113,7 +113,7 @@ impl CipherInner {
     /// - Authenticated portion of the packet is everything BEFORE MKI
     /// - k_a is the session message authentication key
     /// - n_tag is the bit-length of the output authentication tag
-    fn generate_srtcp_auth_tag(&self, buf: &[u8]) -> [u8; 20] {
+    pub(crate) fn generate_srtcp_auth_tag(&self, buf: &[u8]) -> [u8; 20] {
         let mut signer = self.srtcp_session_auth.clone();
 
         signer.update(buf);
@@ -121,11 +121,11 @@ impl CipherInner {
         signer.finalize().into_bytes().into()
     }
 
-    fn auth_tag_len(&self) -> usize {
+    pub(crate) fn auth_tag_len(&self) -> usize {
         CIPHER_AES_CM_HMAC_SHA1AUTH_TAG_LEN
     }
 
-    fn get_rtcp_index(&self, input: &[u8]) -> usize {
+    pub(crate) fn get_rtcp_index(&self, input: &[u8]) -> usize {
         let tail_offset = input.len() - (self.auth_tag_len() + SRTCP_INDEX_SIZE);
         (BigEndian::read_u32(&input[tail_offset..tail_offset + SRTCP_INDEX_SIZE]) & !(1 << 31))
             as usize
@@ -0,0 +1,109 @@
+use bytes::{BufMut, Bytes};
+use subtle::ConstantTimeEq;
+
+use super::cipher_aes_cm_hmac_sha1::CipherInner;
+use super::Cipher;
+use crate::error::{Error, Result};
+use crate::key_derivation::*;
+
+/// NULL cipher with HMAC-SHA1 authentication, https://tools.ietf.org/html/rfc3711#section-4.1.3
+///
+/// Packets are authenticated exactly like with AES_CM_128_HMAC_SHA1_80, but their payloads are
+/// sent as they are, so they can be inspected on the wire. Never use it in production.
+pub(crate) struct CipherNullHmacSha1 {
+    inner: CipherInner,
+}
+
+impl CipherNullHmacSha1 {
+    pub fn new(master_key: &[u8], master_salt: &[u8]) -> Result<Self> {
+        Ok(CipherNullHmacSha1 {
+            inner: CipherInner::new(master_key, master_salt)?,
+        })
+    }
+}
+
+impl Cipher for CipherNullHmacSha1 {
+    fn auth_tag_len(&self) -> usize {
+        self.inner.auth_tag_len()
+    }
+
+    fn get_rtcp_index(&self, input: &[u8]) -> usize {
+        self.inner.get_rtcp_index(input)
+    }
+
+    fn encrypt_rtp(
+        &mut self,
+        plaintext: &[u8],
+        _header: &rtp::header::Header,
+        roc: u32,
+    ) -> Result<Bytes> {
+        let mut writer = Vec::with_capacity(plaintext.len() + self.auth_tag_len());
+        writer.extend_from_slice(plaintext);
+
+        let auth_tag = &self.inner.generate_srtp_auth_tag(&writer, roc)[..self.auth_tag_len()];
+        writer.extend(auth_tag);
+
+        Ok(Bytes::from(writer))
+    }
+
+    fn decrypt_rtp(
+        &mut self,
+        encrypted: &[u8],
+        _header: &rtp::header::Header,
+        roc: u32,
+    ) -> Result<Bytes> {
+        let encrypted_len = encrypted.len();
+        if encrypted_len < self.auth_tag_len() {
+            return Err(Error::SrtpTooSmall(encrypted_len, self.auth_tag_len()));
+        }
+
+        let actual_tag = &encrypted[encrypted_len - self.auth_tag_len()..];
+        let plaintext = &encrypted[..encrypted_len - self.auth_tag_len()];
+
+        let expected_tag =
+            &self.inner.generate_srtp_auth_tag(plaintext, roc)[..self.auth_tag_len()];
+        if actual_tag.ct_eq(expected_tag).unwrap_u8() != 1 {
+            return Err(Error::RtpFailedToVerifyAuthTag);
+        }
+
+        Ok(Bytes::copy_from_slice(plaintext))
+    }
+
+    fn encrypt_rtcp(&mut self, decrypted: &[u8], srtcp_index: usize, _ssrc: u32) -> Result<Bytes> {
+        let mut writer =
+            Vec::with_capacity(decrypted.len() + SRTCP_INDEX_SIZE + self.auth_tag_len());
+        writer.extend_from_slice(decrypted);
+
+        // Add SRTCP index, leaving the Encryption bit unset
+        writer.put_u32(srtcp_index as u32 & !(1u32 << 31));
+
+        let auth_tag = &self.inner.generate_srtcp_auth_tag(&writer)[..self.auth_tag_len()];
+        writer.extend(auth_tag);
+
+        Ok(Bytes::from(writer))
+    }
+
+    fn decrypt_rtcp(&mut self, encrypted: &[u8], _srtcp_index: usize, _ssrc: u32) -> Result<Bytes> {
+        let encrypted_len = encrypted.len();
+        if encrypted_len < self.auth_tag_len() + SRTCP_INDEX_SIZE {
+            return Err(Error::SrtcpTooSmall(
+                encrypted_len,
+                self.auth_tag_len() + SRTCP_INDEX_SIZE,
+            ));
+        }
+
+        let tail_offset = encrypted_len - (self.auth_tag_len() + SRTCP_INDEX_SIZE);
+
+        // Unlike with the AES cipher, the tag is checked even though the Encryption bit is unset.
+        let actual_tag = &encrypted[encrypted_len - self.auth_tag_len()..];
+        let authenticated = &encrypted[..encrypted_len - self.auth_tag_len()];
+
+        let expected_tag =
+            &self.inner.generate_srtcp_auth_tag(authenticated)[..self.auth_tag_len()];
+        if actual_tag.ct_eq(expected_tag).unwrap_u8() != 1 {
+            return Err(Error::RtcpFailedToVerifyAuthTag);
+        }
+
+        Ok(Bytes::copy_from_slice(&encrypted[..tail_offset]))
+    }
+}
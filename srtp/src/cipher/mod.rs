@@ -1,5 +1,6 @@
 pub mod cipher_aead_aes_gcm;
 pub mod cipher_aes_cm_hmac_sha1;
+pub mod cipher_null_hmac_sha1;
 
 use bytes::Bytes;
 
@@ -11,6 +11,7 @@ use util::replay_detector::*;
 
 use crate::cipher::cipher_aead_aes_gcm::*;
 use crate::cipher::cipher_aes_cm_hmac_sha1::*;
+use crate::cipher::cipher_null_hmac_sha1::*;
 use crate::cipher::*;
 use crate::error::{Error, Result};
 use crate::option::*;
@@ -137,6 +138,10 @@ impl Context {
             ProtectionProfile::AeadAes128Gcm => {
                 Box::new(CipherAeadAesGcm::new(master_key, master_salt)?)
             }
+
+            ProtectionProfile::NullHmacSha1_80 => {
+                Box::new(CipherNullHmacSha1::new(master_key, master_salt)?)
+            }
         };
 
         let srtp_ctx_opt = if let Some(ctx_opt) = srtp_ctx_opt {
@@ -128,6 +128,46 @@ fn test_rtcp_lifecycle() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_rtcp_null_cipher() -> Result<()> {
+    let mut encrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::NullHmacSha1_80,
+        None,
+        None,
+    )?;
+    let mut decrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::NullHmacSha1_80,
+        None,
+        None,
+    )?;
+    let auth_tag_len = ProtectionProfile::NullHmacSha1_80.auth_tag_len();
+
+    let decrypted = &RTCP_TEST_CASES[0].decrypted;
+    let encrypted = encrypt_context.encrypt_rtcp(decrypted)?;
+    assert_eq!(
+        encrypted.len(),
+        decrypted.len() + SRTCP_INDEX_SIZE + auth_tag_len
+    );
+    assert_eq!(encrypted[..decrypted.len()], decrypted[..]);
+    // The Encryption bit is unset
+    assert_eq!(encrypted[decrypted.len()] >> 7, 0);
+
+    let actual_decrypted = decrypt_context.decrypt_rtcp(&encrypted)?;
+    assert_eq!(&actual_decrypted, decrypted);
+
+    // The auth tag is still checked
+    let mut tampered = encrypt_context.encrypt_rtcp(decrypted)?.to_vec();
+    tampered[rtcp::header::HEADER_LENGTH + rtcp::header::SSRC_LENGTH] ^= 0xff;
+    let result = decrypt_context.decrypt_rtcp(&tampered);
+    assert_eq!(result, Err(Error::RtcpFailedToVerifyAuthTag));
+
+    Ok(())
+}
+
 #[test]
 fn test_rtcp_invalid_auth_tag() -> Result<()> {
     let auth_tag_len = ProtectionProfile::Aes128CmHmacSha1_80.auth_tag_len();
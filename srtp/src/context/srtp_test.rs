@@ -167,6 +167,71 @@ fn test_rtp_lifecycle() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_rtp_null_cipher() -> Result<()> {
+    let master_key = [0x0d; 16];
+    let master_salt = [0x62; 14];
+    let mut encrypt_context = Context::new(
+        &master_key,
+        &master_salt,
+        ProtectionProfile::NullHmacSha1_80,
+        None,
+        None,
+    )?;
+    let mut decrypt_context = Context::new(
+        &master_key,
+        &master_salt,
+        ProtectionProfile::NullHmacSha1_80,
+        None,
+        None,
+    )?;
+    let auth_tag_len = ProtectionProfile::NullHmacSha1_80.auth_tag_len();
+
+    for test_case in RTP_TEST_CASES.iter() {
+        let decrypted_raw = rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number: test_case.sequence_number,
+                ..Default::default()
+            },
+            payload: RTP_TEST_CASE_DECRYPTED.clone(),
+        }
+        .marshal()?;
+
+        // The packet goes out as it is, followed by the auth tag
+        let encrypted_raw = encrypt_context.encrypt_rtp(&decrypted_raw)?;
+        assert_eq!(encrypted_raw.len(), decrypted_raw.len() + auth_tag_len);
+        assert_eq!(
+            encrypted_raw[..decrypted_raw.len()],
+            decrypted_raw[..],
+            "RTP packet with SeqNum encrypted by the null cipher: {}",
+            test_case.sequence_number
+        );
+
+        let actual_decrypted = decrypt_context.decrypt_rtp(&encrypted_raw)?;
+        assert_eq!(actual_decrypted, decrypted_raw);
+    }
+
+    // The auth tag is still checked
+    let mut tampered = encrypt_context
+        .encrypt_rtp(
+            &rtp::packet::Packet {
+                header: rtp::header::Header {
+                    sequence_number: 6000,
+                    ..Default::default()
+                },
+                payload: RTP_TEST_CASE_DECRYPTED.clone(),
+            }
+            .marshal()?,
+        )?
+        .to_vec();
+    let last_payload_byte = tampered.len() - auth_tag_len - 1;
+    tampered[last_payload_byte] ^= 0xff;
+    let result = decrypt_context.decrypt_rtp(&tampered);
+    assert_eq!(result, Err(Error::RtpFailedToVerifyAuthTag));
+
+    Ok(())
+}
+
 //TODO: BenchmarkEncryptRTP
 //TODO: BenchmarkEncryptRTPInPlace
 //TODO: BenchmarkDecryptRTP
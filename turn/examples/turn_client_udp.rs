@@ -78,17 +78,11 @@ async fn main() -> Result<(), Error> {
 
     let turn_server_addr = format!("{host}:{port}");
 
-    let cfg = ClientConfig {
-        stun_serv_addr: turn_server_addr.clone(),
-        turn_serv_addr: turn_server_addr,
-        username: cred[0].to_string(),
-        password: cred[1].to_string(),
-        realm: realm.to_string(),
-        software: String::new(),
-        rto_in_ms: 0,
-        conn: Arc::new(conn),
-        vnet: None,
-    };
+    let cfg = ClientConfig::new(Arc::new(conn))
+        .with_stun_serv_addr(turn_server_addr.clone())
+        .with_turn_serv_addr(turn_server_addr)
+        .with_credentials(cred[0].to_string(), cred[1].to_string())
+        .with_realm(realm.to_string());
 
     let client = Client::new(cfg).await?;
 
@@ -441,6 +441,8 @@ async fn create_client(username: String, server_port: u16) -> Result<Client> {
         rto_in_ms: 0,
         conn,
         vnet: None,
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await
 }
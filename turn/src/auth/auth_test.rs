@@ -89,6 +89,8 @@ async fn test_new_long_term_auth_handler() -> Result<()> {
         rto_in_ms: 0,
         conn,
         vnet: None,
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await?;
 
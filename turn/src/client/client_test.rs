@@ -23,6 +23,8 @@ async fn create_listening_test_client(rto_in_ms: u16) -> Result<Client> {
         rto_in_ms,
         conn: Arc::new(conn),
         vnet: None,
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await?;
 
@@ -44,6 +46,8 @@ async fn create_listening_test_client_with_stun_serv() -> Result<Client> {
         rto_in_ms: 0,
         conn: Arc::new(conn),
         vnet: None,
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await?;
 
@@ -167,6 +171,8 @@ async fn test_client_nonce_expiration() -> Result<()> {
         rto_in_ms: 0,
         conn,
         vnet: None,
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await?;
 
@@ -189,3 +195,179 @@ async fn test_client_nonce_expiration() -> Result<()> {
 
     Ok(())
 }
+
+async fn capture_allocate_request(disable_fingerprint: bool) -> Result<Message> {
+    let server = UdpSocket::bind("127.0.0.1:0").await?;
+    let server_addr = server.local_addr()?;
+
+    let client = Arc::new(
+        Client::new(
+            ClientConfig::new(Arc::new(UdpSocket::bind("127.0.0.1:0").await?))
+                .with_turn_serv_addr(server_addr.to_string())
+                .with_credentials("foo".to_owned(), "pass".to_owned())
+                .with_disable_fingerprint(disable_fingerprint),
+        )
+        .await?,
+    );
+    client.listen().await?;
+
+    let c = Arc::clone(&client);
+    let handle = tokio::spawn(async move {
+        let _ = c.allocate().await;
+    });
+
+    let mut buf = vec![0u8; 1500];
+    let (n, _) = tokio::time::timeout(Duration::from_secs(5), server.recv_from(&mut buf))
+        .await
+        .expect("allocate request not received")?;
+    handle.abort();
+
+    let mut msg = Message::new();
+    msg.raw = buf[..n].to_vec();
+    msg.decode()?;
+
+    Ok(msg)
+}
+
+#[tokio::test]
+async fn test_client_disable_fingerprint() -> Result<()> {
+    let msg = capture_allocate_request(false).await?;
+    assert_eq!(msg.typ, MessageType::new(METHOD_ALLOCATE, CLASS_REQUEST));
+    assert!(msg.contains(ATTR_FINGERPRINT));
+
+    let msg = capture_allocate_request(true).await?;
+    assert_eq!(msg.typ, MessageType::new(METHOD_ALLOCATE, CLASS_REQUEST));
+    assert!(
+        !msg.contains(ATTR_FINGERPRINT),
+        "FINGERPRINT must be omitted when disabled"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_allocate_forbidden() -> Result<()> {
+    let server = UdpSocket::bind("127.0.0.1:0").await?;
+    let server_addr = server.local_addr()?;
+
+    let client = Client::new(ClientConfig {
+        stun_serv_addr: String::new(),
+        turn_serv_addr: server_addr.to_string(),
+        username: "foo".to_owned(),
+        password: "pass".to_owned(),
+        realm: String::new(),
+        software: String::new(),
+        rto_in_ms: 0,
+        conn: Arc::new(UdpSocket::bind("127.0.0.1:0").await?),
+        vnet: None,
+        disable_fingerprint: false,
+        relay_rate_limit: None,
+    })
+    .await?;
+    client.listen().await?;
+
+    tokio::spawn(async move {
+        let mut buf = vec![0u8; 1500];
+        for code in [CODE_UNAUTHORIZED, CODE_FORBIDDEN] {
+            let (n, from) = server.recv_from(&mut buf).await?;
+            let mut req = Message::new();
+            req.raw = buf[..n].to_vec();
+            req.decode()?;
+
+            let mut res = Message::new();
+            res.build(&[
+                Box::new(req.transaction_id),
+                Box::new(MessageType::new(METHOD_ALLOCATE, CLASS_ERROR_RESPONSE)),
+                Box::new(ErrorCodeAttribute {
+                    code,
+                    reason: b"Forbidden by policy".to_vec(),
+                }),
+                Box::new(Realm::new(ATTR_REALM, "webrtc.rs".to_owned())),
+                Box::new(Nonce::new(ATTR_NONCE, "nonce".to_owned())),
+            ])?;
+            server.send_to(&res.raw, from).await?;
+        }
+        Result::<()>::Ok(())
+    });
+
+    let err = client.allocate().await.err().expect("allocate must fail");
+    assert_eq!(
+        Error::Turn(TurnError::Forbidden("Forbidden by policy".to_owned())),
+        err
+    );
+    if let Error::Turn(turn_err) = &err {
+        assert_eq!(403, turn_err.code());
+        assert_eq!("Forbidden by policy", turn_err.reason());
+    }
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_relay_rate_limit() -> Result<()> {
+    let conn = Arc::new(UdpSocket::bind("0.0.0.0:0").await?);
+    let server_port = conn.local_addr()?.port();
+
+    let server = Server::new(ServerConfig {
+        conn_configs: vec![ConnConfig {
+            conn,
+            relay_addr_generator: Box::new(RelayAddressGeneratorStatic {
+                relay_address: IpAddr::from_str("127.0.0.1")?,
+                address: "0.0.0.0".to_owned(),
+                net: Arc::new(Net::new(None)),
+            }),
+        }],
+        realm: "webrtc.rs".to_owned(),
+        auth_handler: Arc::new(TestAuthHandler {}),
+        channel_bind_timeout: Duration::from_secs(0),
+        alloc_close_notify: None,
+    })
+    .await?;
+
+    let client = Client::new(
+        ClientConfig::new(Arc::new(UdpSocket::bind("0.0.0.0:0").await?))
+            .with_stun_serv_addr(format!("127.0.0.1:{server_port}"))
+            .with_turn_serv_addr(format!("127.0.0.1:{server_port}"))
+            .with_credentials("foo".to_owned(), "pass".to_owned())
+            .with_relay_rate_limit(1000),
+    )
+    .await?;
+
+    client.listen().await?;
+
+    let allocation = client.allocate().await?;
+    let peer = SocketAddr::from_str("127.0.0.1:8080")?;
+
+    // The first second's worth of data goes through, the rest is throttled
+    allocation.send_to(&[0u8; 600], peer).await?;
+    allocation.send_to(&[0u8; 400], peer).await?;
+    let err = allocation.send_to(&[0u8; 400], peer).await.unwrap_err();
+    assert_eq!(
+        err,
+        std::io::Error::new(
+            std::io::ErrorKind::WouldBlock,
+            Error::ErrRelayRateLimitExceeded.to_string()
+        )
+        .into()
+    );
+
+    // until the window refills
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    allocation.send_to(&[0u8; 400], peer).await?;
+
+    client.close().await?;
+    server.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_zero_relay_rate_limit() -> Result<()> {
+    let conn = Arc::new(UdpSocket::bind("127.0.0.1:0").await?);
+    let result = Client::new(ClientConfig::new(conn).with_relay_rate_limit(0)).await;
+    assert_eq!(result.err(), Some(Error::ErrZeroRelayRateLimit));
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ mod client_test;
 pub mod binding;
 pub mod periodic_timer;
 pub mod permission;
+pub(crate) mod rate_limiter;
 pub mod relay_conn;
 pub mod transaction;
 
@@ -55,10 +56,12 @@ const MAX_READ_QUEUE_SIZE: usize = 1024;
 // 6: 31500 ms  +32000
 // -: 63500 ms  failed
 
-/// ClientConfig is a bag of config parameters for Client.
+/// ClientConfig is a bag of config parameters for Client. Build it with [`ClientConfig::new`]
+/// and the `with_` methods.
+#[non_exhaustive]
 pub struct ClientConfig {
-    pub stun_serv_addr: String, // STUN server address (e.g. "stun.abc.com:3478")
-    pub turn_serv_addr: String, // TURN server address (e.g. "turn.abc.com:3478")
+    pub stun_serv_addr: String, // STUN server address (e.g. "stun.abc.com:3478", or a resolved "192.0.2.1:3478")
+    pub turn_serv_addr: String, // TURN server address (e.g. "turn.abc.com:3478", or a resolved "192.0.2.1:3478")
     pub username: String,
     pub password: String,
     pub realm: String,
@@ -66,6 +69,87 @@ pub struct ClientConfig {
     pub rto_in_ms: u16,
     pub conn: Arc<dyn Conn + Send + Sync>,
     pub vnet: Option<Arc<Net>>,
+    /// Omits the FINGERPRINT attribute from requests, for legacy servers that reject it.
+    pub disable_fingerprint: bool,
+    /// Caps the data sent through each allocation, in bytes per second. Sends beyond
+    /// the limit fail with `WouldBlock`. `None` disables the limit, a limit of zero is
+    /// rejected by `Client::new`.
+    pub relay_rate_limit: Option<usize>,
+}
+
+impl ClientConfig {
+    /// new creates a config for a client sending over `conn`, with every other setting left
+    /// at its default.
+    pub fn new(conn: Arc<dyn Conn + Send + Sync>) -> Self {
+        ClientConfig {
+            stun_serv_addr: String::new(),
+            turn_serv_addr: String::new(),
+            username: String::new(),
+            password: String::new(),
+            realm: String::new(),
+            software: String::new(),
+            rto_in_ms: 0,
+            conn,
+            vnet: None,
+            disable_fingerprint: false,
+            relay_rate_limit: None,
+        }
+    }
+
+    /// with_stun_serv_addr sets the STUN server address.
+    pub fn with_stun_serv_addr(mut self, stun_serv_addr: String) -> Self {
+        self.stun_serv_addr = stun_serv_addr;
+        self
+    }
+
+    /// with_turn_serv_addr sets the TURN server address.
+    pub fn with_turn_serv_addr(mut self, turn_serv_addr: String) -> Self {
+        self.turn_serv_addr = turn_serv_addr;
+        self
+    }
+
+    /// with_credentials sets the username and password used to authenticate with the TURN server.
+    pub fn with_credentials(mut self, username: String, password: String) -> Self {
+        self.username = username;
+        self.password = password;
+        self
+    }
+
+    /// with_realm sets the realm.
+    pub fn with_realm(mut self, realm: String) -> Self {
+        self.realm = realm;
+        self
+    }
+
+    /// with_software sets the SOFTWARE attribute sent with requests.
+    pub fn with_software(mut self, software: String) -> Self {
+        self.software = software;
+        self
+    }
+
+    /// with_rto_in_ms sets the initial retransmission timeout, the default when zero.
+    pub fn with_rto_in_ms(mut self, rto_in_ms: u16) -> Self {
+        self.rto_in_ms = rto_in_ms;
+        self
+    }
+
+    /// with_vnet sets the virtual network the client runs on.
+    pub fn with_vnet(mut self, vnet: Arc<Net>) -> Self {
+        self.vnet = Some(vnet);
+        self
+    }
+
+    /// with_disable_fingerprint sets whether requests omit the FINGERPRINT attribute.
+    pub fn with_disable_fingerprint(mut self, disable_fingerprint: bool) -> Self {
+        self.disable_fingerprint = disable_fingerprint;
+        self
+    }
+
+    /// with_relay_rate_limit caps the data sent through each allocation, in bytes per second.
+    pub fn with_relay_rate_limit(mut self, bytes_per_sec: usize) -> Self {
+        self.relay_rate_limit = Some(bytes_per_sec);
+        self
+    }
 }
 
 struct ClientInternal {
@@ -77,6 +161,8 @@ struct ClientInternal {
     realm: Realm,
     integrity: MessageIntegrity,
     software: Software,
+    disable_fingerprint: bool,
+    relay_rate_limit: Option<usize>,
     tr_map: Arc<Mutex<TransactionMap>>,
     binding_mgr: Arc<Mutex<BindingManager>>,
     rto_in_ms: u16,
@@ -101,6 +187,11 @@ impl RelayConnObserver for ClientInternal {
         self.realm.clone()
     }
 
+    /// Returns whether requests omit the FINGERPRINT attribute.
+    fn disable_fingerprint(&self) -> bool {
+        self.disable_fingerprint
+    }
+
     /// Sends data to the specified destination using the base socket.
     async fn write_to(&self, data: &[u8], to: &str) -> std::result::Result<usize, util::Error> {
         let n = self.conn.send_to(data, SocketAddr::from_str(to)?).await?;
@@ -161,9 +252,42 @@ impl RelayConnObserver for ClientInternal {
     }
 }
 
+/// OptionalFingerprint adds the FINGERPRINT attribute when enabled, and nothing otherwise.
+pub(crate) struct OptionalFingerprint(pub(crate) bool);
+
+impl Setter for OptionalFingerprint {
+    fn add_to(&self, m: &mut Message) -> std::result::Result<(), stun::Error> {
+        if self.0 {
+            FINGERPRINT.add_to(m)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Resolves a STUN/TURN server address, skipping the lookup when it is already a socket address.
+async fn resolve_serv_addr(
+    net: &Arc<Net>,
+    conn: &Arc<dyn Conn + Send + Sync>,
+    addr: &str,
+) -> Result<SocketAddr> {
+    if let Ok(resolved) = addr.parse::<SocketAddr>() {
+        return Ok(resolved);
+    }
+
+    log::debug!("resolving {}", addr);
+    let local_addr = conn.local_addr()?;
+    Ok(net.resolve_addr(local_addr.is_ipv4(), addr).await?)
+}
+
 impl ClientInternal {
     /// Creates a new [`ClientInternal`].
     async fn new(config: ClientConfig) -> Result<Self> {
+        // An empty bucket never refills, so a zero limit would block the relay for good.
+        if config.relay_rate_limit == Some(0) {
+            return Err(Error::ErrZeroRelayRateLimit);
+        }
+
         let net = if let Some(vnet) = config.vnet {
             if vnet.is_virtual() {
                 log::warn!("vnet is enabled");
@@ -176,11 +300,7 @@ impl ClientInternal {
         let stun_serv_addr = if config.stun_serv_addr.is_empty() {
             String::new()
         } else {
-            log::debug!("resolving {}", config.stun_serv_addr);
-            let local_addr = config.conn.local_addr()?;
-            let stun_serv = net
-                .resolve_addr(local_addr.is_ipv4(), &config.stun_serv_addr)
-                .await?;
+            let stun_serv = resolve_serv_addr(&net, &config.conn, &config.stun_serv_addr).await?;
             log::debug!("stunServ: {}", stun_serv);
             stun_serv.to_string()
         };
@@ -188,11 +308,7 @@ impl ClientInternal {
         let turn_serv_addr = if config.turn_serv_addr.is_empty() {
             String::new()
         } else {
-            log::debug!("resolving {}", config.turn_serv_addr);
-            let local_addr = config.conn.local_addr()?;
-            let turn_serv = net
-                .resolve_addr(local_addr.is_ipv4(), &config.turn_serv_addr)
-                .await?;
+            let turn_serv = resolve_serv_addr(&net, &config.conn, &config.turn_serv_addr).await?;
             log::debug!("turnServ: {}", turn_serv);
             turn_serv.to_string()
         };
@@ -213,6 +329,8 @@ impl ClientInternal {
                 DEFAULT_RTO_IN_MS
             },
             integrity: MessageIntegrity::new_short_term_integrity(String::new()),
+            disable_fingerprint: config.disable_fingerprint,
+            relay_rate_limit: config.relay_rate_limit,
             read_ch_tx: Arc::new(Mutex::new(None)),
             close_notify: CancellationToken::new(),
         })
@@ -528,7 +646,7 @@ impl ClientInternal {
             Box::new(RequestedTransport {
                 protocol: PROTO_UDP,
             }),
-            Box::new(FINGERPRINT),
+            Box::new(OptionalFingerprint(!self.disable_fingerprint)),
         ])?;
 
         log::debug!("client.Allocate call PerformTransaction 1");
@@ -537,6 +655,14 @@ impl ClientInternal {
             .await?;
         let res = tr_res.msg;
 
+        // Anything other than 401 means the server refused to authenticate us at all.
+        if res.typ.class == CLASS_ERROR_RESPONSE {
+            let mut code = ErrorCodeAttribute::default();
+            if code.get_from(&res).is_ok() && code.code != CODE_UNAUTHORIZED {
+                return Err(TurnError::from(&code).into());
+            }
+        }
+
         // Anonymous allocate failed, trying to authenticate.
         let nonce = Nonce::get_from_as(&res, ATTR_NONCE)?;
         self.realm = Realm::get_from_as(&res, ATTR_REALM)?;
@@ -558,7 +684,7 @@ impl ClientInternal {
             Box::new(self.realm.clone()),
             Box::new(nonce.clone()),
             Box::new(self.integrity.clone()),
-            Box::new(FINGERPRINT),
+            Box::new(OptionalFingerprint(!self.disable_fingerprint)),
         ])?;
 
         log::debug!("client.Allocate call PerformTransaction 2");
@@ -573,7 +699,7 @@ impl ClientInternal {
             if result.is_err() {
                 return Err(Error::Other(format!("{}", res.typ)));
             } else {
-                return Err(Error::Other(format!("{} (error {})", res.typ, code)));
+                return Err(TurnError::from(&code).into());
             }
         }
 
@@ -600,6 +726,7 @@ impl ClientInternal {
             lifetime: lifetime.0,
             binding_mgr: Arc::clone(&self.binding_mgr),
             read_ch_rx: Arc::new(Mutex::new(read_ch_rx)),
+            rate_limit: self.relay_rate_limit,
         })
     }
 }
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod rate_limiter_test;
+
+use tokio::time::Instant;
+
+/// `RateLimiter` is a token bucket holding up to one second worth of bytes.
+/// A packet larger than the bucket is let through once the bucket is full, and
+/// the overdraft is paid back before anything else is sent.
+pub(crate) struct RateLimiter {
+    bytes_per_sec: usize,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(bytes_per_sec: usize, now: Instant) -> Self {
+        RateLimiter {
+            bytes_per_sec,
+            tokens: bytes_per_sec as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Returns whether `n` bytes may be sent at `now`.
+    pub(crate) fn check(&mut self, n: usize, now: Instant) -> bool {
+        let capacity = self.bytes_per_sec as f64;
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * capacity).min(capacity);
+        self.last_refill = now;
+
+        self.tokens >= n as f64 || self.tokens >= capacity
+    }
+
+    /// Charges `n` sent bytes against the bucket.
+    pub(crate) fn consume(&mut self, n: usize) {
+        self.tokens -= n as f64;
+    }
+}
@@ -0,0 +1,42 @@
+use tokio::time::Duration;
+
+use super::*;
+
+#[test]
+fn test_rate_limiter() {
+    let start = Instant::now();
+    let mut rl = RateLimiter::new(1000, start);
+
+    // The first second's worth of bytes goes out at once
+    assert!(rl.check(600, start));
+    rl.consume(600);
+    assert!(rl.check(400, start));
+    rl.consume(400);
+    assert!(!rl.check(1, start));
+
+    // and then it refills at the configured rate
+    let t = start + Duration::from_millis(300);
+    assert!(!rl.check(400, t));
+    assert!(rl.check(300, t));
+    rl.consume(300);
+
+    // Idle time does not build up more than one second of credit
+    let t = t + Duration::from_secs(10);
+    assert!(rl.check(1000, t));
+    rl.consume(1000);
+    assert!(!rl.check(1, t));
+}
+
+#[test]
+fn test_rate_limiter_oversized_packet() {
+    let start = Instant::now();
+    let mut rl = RateLimiter::new(1000, start);
+
+    // A packet larger than the bucket goes out once the bucket is full
+    assert!(rl.check(1500, start));
+    rl.consume(1500);
+
+    // and the overdraft has to be paid back first
+    assert!(!rl.check(1, start + Duration::from_millis(500)));
+    assert!(rl.check(500, start + Duration::from_secs(1)));
+}
@@ -10,7 +10,6 @@ use async_trait::async_trait;
 use stun::agent::*;
 use stun::attributes::*;
 use stun::error_code::*;
-use stun::fingerprint::*;
 use stun::integrity::*;
 use stun::message::*;
 use stun::textattrs::*;
@@ -21,7 +20,10 @@ use util::Conn;
 use super::binding::*;
 use super::periodic_timer::*;
 use super::permission::*;
+use super::rate_limiter::RateLimiter;
 use super::transaction::*;
+use super::OptionalFingerprint;
+use crate::error::TurnError;
 use crate::{proto, Error};
 
 const PERM_REFRESH_INTERVAL: Duration = Duration::from_secs(120);
@@ -38,6 +40,9 @@ pub trait RelayConnObserver {
     fn turn_server_addr(&self) -> String;
     fn username(&self) -> Username;
     fn realm(&self) -> Realm;
+    fn disable_fingerprint(&self) -> bool {
+        false
+    }
     async fn write_to(&self, data: &[u8], to: &str) -> Result<usize, util::Error>;
     async fn perform_transaction(
         &mut self,
@@ -55,6 +60,7 @@ pub(crate) struct RelayConnConfig {
     pub(crate) lifetime: Duration,
     pub(crate) binding_mgr: Arc<Mutex<BindingManager>>,
     pub(crate) read_ch_rx: Arc<Mutex<mpsc::Receiver<InboundData>>>,
+    pub(crate) rate_limit: Option<usize>,
 }
 
 pub struct RelayConnInternal<T: 'static + RelayConnObserver + Send + Sync> {
@@ -65,6 +71,7 @@ pub struct RelayConnInternal<T: 'static + RelayConnObserver + Send + Sync> {
     integrity: MessageIntegrity,
     nonce: Nonce,
     lifetime: Duration,
+    rate_limiter: Option<RateLimiter>,
 }
 
 /// `RelayConn` is the implementation of the Conn interfaces for UDP Relayed network connections.
@@ -159,6 +166,11 @@ impl<T: RelayConnObserver + Send + Sync> Conn for RelayConn<T> {
         let mut relay_conn = self.relay_conn.lock().await;
         match relay_conn.send_to(p, addr).await {
             Ok(n) => Ok(n),
+            Err(Error::ErrRelayRateLimitExceeded) => Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                Error::ErrRelayRateLimitExceeded.to_string(),
+            )
+            .into()),
             Err(err) => Err(io::Error::new(io::ErrorKind::Other, err.to_string()).into()),
         }
     }
@@ -199,6 +211,9 @@ impl<T: RelayConnObserver + Send + Sync> RelayConnInternal<T> {
             integrity: config.integrity,
             nonce: config.nonce,
             lifetime: config.lifetime,
+            rate_limiter: config
+                .rate_limit
+                .map(|bytes_per_sec| RateLimiter::new(bytes_per_sec, Instant::now())),
         }
     }
 
@@ -208,6 +223,22 @@ impl<T: RelayConnObserver + Send + Sync> RelayConnInternal<T> {
     /// see SetDeadline and SetWriteDeadline.
     /// On packet-oriented connections, write timeouts are rare.
     async fn send_to(&mut self, p: &[u8], addr: SocketAddr) -> Result<usize, Error> {
+        if let Some(rate_limiter) = &mut self.rate_limiter {
+            if !rate_limiter.check(p.len(), Instant::now()) {
+                return Err(Error::ErrRelayRateLimitExceeded);
+            }
+        }
+
+        let n = self.send_to_unlimited(p, addr).await?;
+        if let Some(rate_limiter) = &mut self.rate_limiter {
+            rate_limiter.consume(p.len());
+        }
+
+        Ok(n)
+    }
+
+    /// Same as [`Self::send_to()`], without the rate limit.
+    async fn send_to_unlimited(&mut self, p: &[u8], addr: SocketAddr) -> Result<usize, Error> {
         // check if we have a permission for the destination IP addr
         let perm = if let Some(perm) = self.perm_map.find(&addr) {
             Arc::clone(perm)
@@ -289,17 +320,17 @@ impl<T: RelayConnObserver + Send + Sync> RelayConnInternal<T> {
 
                 // send data using SendIndication
                 let peer_addr = socket_addr2peer_address(&addr);
+                let obs = self.obs.lock().await;
                 let mut msg = Message::new();
                 msg.build(&[
                     Box::new(TransactionId::new()),
                     Box::new(MessageType::new(METHOD_SEND, CLASS_INDICATION)),
                     Box::new(proto::data::Data(p.to_vec())),
                     Box::new(peer_addr),
-                    Box::new(FINGERPRINT),
+                    Box::new(OptionalFingerprint(!obs.disable_fingerprint())),
                 ])?;
 
                 // indication has no transaction (fire-and-forget)
-                let obs = self.obs.lock().await;
                 let turn_server_addr = obs.turn_server_addr();
                 return Ok(obs.write_to(&msg.raw, &turn_server_addr).await?);
             }
@@ -402,7 +433,7 @@ impl<T: RelayConnObserver + Send + Sync> RelayConnInternal<T> {
                 setters.push(Box::new(obs.realm()));
                 setters.push(Box::new(self.nonce.clone()));
                 setters.push(Box::new(self.integrity.clone()));
-                setters.push(Box::new(FINGERPRINT));
+                setters.push(Box::new(OptionalFingerprint(!obs.disable_fingerprint())));
 
                 let mut msg = Message::new();
                 msg.build(&setters)?;
@@ -429,7 +460,7 @@ impl<T: RelayConnObserver + Send + Sync> RelayConnInternal<T> {
                 self.set_nonce_from_msg(&res);
                 return Err(Error::ErrTryAgain);
             } else {
-                return Err(Error::Other(format!("{} (error {})", res.typ, code)));
+                return Err(TurnError::from(&code).into());
             }
         }
 
@@ -471,7 +502,7 @@ impl<T: RelayConnObserver + Send + Sync> RelayConnInternal<T> {
                 Box::new(obs.realm()),
                 Box::new(self.nonce.clone()),
                 Box::new(self.integrity.clone()),
-                Box::new(FINGERPRINT),
+                Box::new(OptionalFingerprint(!obs.disable_fingerprint())),
             ])?;
 
             log::debug!("send refresh request (dont_wait={})", dont_wait);
@@ -549,7 +580,7 @@ impl<T: RelayConnObserver + Send + Sync> RelayConnInternal<T> {
                 Box::new(obs.realm()),
                 Box::new(nonce),
                 Box::new(integrity),
-                Box::new(FINGERPRINT),
+                Box::new(OptionalFingerprint(!obs.disable_fingerprint())),
             ];
 
             let mut msg = Message::new();
@@ -54,6 +54,7 @@ async fn test_relay_conn() -> Result<()> {
         lifetime: Duration::from_secs(0),
         binding_mgr: Arc::new(Mutex::new(BindingManager::new())),
         read_ch_rx: Arc::new(Mutex::new(read_ch_rx)),
+        rate_limit: None,
     };
 
     let rc = RelayConn::new(Arc::new(Mutex::new(obs)), config).await;
@@ -2,6 +2,7 @@ use std::num::ParseIntError;
 use std::time::SystemTimeError;
 use std::{io, net};
 
+use stun::error_code::*;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -155,6 +156,10 @@ pub enum Error {
     ErrNoSuchChannelBind,
     #[error("failed writing to socket")]
     ErrFailedWriteSocket,
+    #[error("relay send rate limit exceeded")]
+    ErrRelayRateLimitExceeded,
+    #[error("relay send rate limit must be greater than zero")]
+    ErrZeroRelayRateLimit,
     #[error("parse int: {0}")]
     ParseInt(#[from] ParseIntError),
     #[error("parse addr: {0}")]
@@ -166,9 +171,95 @@ pub enum Error {
     #[error("{0}")]
     Stun(#[from] stun::Error),
     #[error("{0}")]
+    Turn(#[from] TurnError),
+    #[error("{0}")]
     Other(String),
 }
 
+/// TurnError is an ERROR-CODE carried by a TURN error response, with the
+/// reason phrase supplied by the server.
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum TurnError {
+    #[error("error 400: {0}")]
+    BadRequest(String),
+    #[error("error 401: {0}")]
+    Unauthorized(String),
+    #[error("error 403: {0}")]
+    Forbidden(String),
+    #[error("error 437: {0}")]
+    AllocationMismatch(String),
+    #[error("error 438: {0}")]
+    StaleNonce(String),
+    #[error("error 441: {0}")]
+    WrongCredentials(String),
+    #[error("error 442: {0}")]
+    UnsupportedTransportProtocol(String),
+    #[error("error 443: {0}")]
+    PeerAddressFamilyMismatch(String),
+    #[error("error 486: {0}")]
+    AllocationQuotaReached(String),
+    #[error("error 508: {0}")]
+    InsufficientCapacity(String),
+    #[error("error {code}: {reason}")]
+    Other { code: u16, reason: String },
+}
+
+impl TurnError {
+    /// Returns the numeric error code, e.g. 403.
+    pub fn code(&self) -> u16 {
+        match self {
+            TurnError::BadRequest(_) => CODE_BAD_REQUEST.0,
+            TurnError::Unauthorized(_) => CODE_UNAUTHORIZED.0,
+            TurnError::Forbidden(_) => CODE_FORBIDDEN.0,
+            TurnError::AllocationMismatch(_) => CODE_ALLOC_MISMATCH.0,
+            TurnError::StaleNonce(_) => CODE_STALE_NONCE.0,
+            TurnError::WrongCredentials(_) => CODE_WRONG_CREDENTIALS.0,
+            TurnError::UnsupportedTransportProtocol(_) => CODE_UNSUPPORTED_TRANS_PROTO.0,
+            TurnError::PeerAddressFamilyMismatch(_) => CODE_PEER_ADDR_FAMILY_MISMATCH.0,
+            TurnError::AllocationQuotaReached(_) => CODE_ALLOC_QUOTA_REACHED.0,
+            TurnError::InsufficientCapacity(_) => CODE_INSUFFICIENT_CAPACITY.0,
+            TurnError::Other { code, .. } => *code,
+        }
+    }
+
+    /// Returns the reason phrase sent by the server.
+    pub fn reason(&self) -> &str {
+        match self {
+            TurnError::BadRequest(reason)
+            | TurnError::Unauthorized(reason)
+            | TurnError::Forbidden(reason)
+            | TurnError::AllocationMismatch(reason)
+            | TurnError::StaleNonce(reason)
+            | TurnError::WrongCredentials(reason)
+            | TurnError::UnsupportedTransportProtocol(reason)
+            | TurnError::PeerAddressFamilyMismatch(reason)
+            | TurnError::AllocationQuotaReached(reason)
+            | TurnError::InsufficientCapacity(reason)
+            | TurnError::Other { reason, .. } => reason,
+        }
+    }
+}
+
+impl From<&ErrorCodeAttribute> for TurnError {
+    fn from(attr: &ErrorCodeAttribute) -> Self {
+        let reason = String::from_utf8_lossy(&attr.reason).into_owned();
+        match attr.code {
+            CODE_BAD_REQUEST => TurnError::BadRequest(reason),
+            CODE_UNAUTHORIZED => TurnError::Unauthorized(reason),
+            CODE_FORBIDDEN => TurnError::Forbidden(reason),
+            CODE_ALLOC_MISMATCH => TurnError::AllocationMismatch(reason),
+            CODE_STALE_NONCE => TurnError::StaleNonce(reason),
+            CODE_WRONG_CREDENTIALS => TurnError::WrongCredentials(reason),
+            CODE_UNSUPPORTED_TRANS_PROTO => TurnError::UnsupportedTransportProtocol(reason),
+            CODE_PEER_ADDR_FAMILY_MISMATCH => TurnError::PeerAddressFamilyMismatch(reason),
+            CODE_ALLOC_QUOTA_REACHED => TurnError::AllocationQuotaReached(reason),
+            CODE_INSUFFICIENT_CAPACITY => TurnError::InsufficientCapacity(reason),
+            ErrorCode(code) => TurnError::Other { code, reason },
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("io error: {0}")]
 pub struct IoError(#[from] pub io::Error);
@@ -10,4 +10,4 @@ pub mod proto;
 pub mod relay;
 pub mod server;
 
-pub use error::Error;
+pub use error::{Error, TurnError};
@@ -81,6 +81,8 @@ async fn test_server_simple() -> Result<()> {
         rto_in_ms: 0,
         conn,
         vnet: None,
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await?;
 
@@ -235,6 +237,8 @@ async fn test_server_vnet_send_binding_request() -> Result<()> {
         rto_in_ms: 0,
         conn: lconn,
         vnet: Some(Arc::clone(&v.netl0)),
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await?;
 
@@ -272,6 +276,8 @@ async fn test_server_vnet_echo_via_relay() -> Result<()> {
         rto_in_ms: 0,
         conn: lconn,
         vnet: Some(Arc::clone(&v.netl0)),
+        disable_fingerprint: false,
+        relay_rate_limit: None,
     })
     .await?;
 
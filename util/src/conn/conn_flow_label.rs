@@ -0,0 +1,195 @@
+use std::net::{Ipv6Addr, SocketAddrV6};
+
+use tokio::net::UdpSocket;
+
+use super::*;
+use crate::error::Error;
+
+/// Flow labels are 20 bits. Labels with the top bit set are reserved by Linux for stateless
+/// (automatic) labels, so only the lower half of the space can be leased.
+pub const FLOW_LABEL_MAX: u32 = 0x7FFFF;
+
+/// FlowLabelConn is a UDP socket that marks the IPv6 datagrams it sends with a fixed flow label,
+/// for networks that use it for QoS classification. Datagrams sent to IPv4 destinations are left
+/// untouched.
+///
+/// Only Linux supports setting the flow label of a socket; elsewhere [`FlowLabelConn::new`]
+/// fails with `ErrFlowLabelUnsupported`.
+pub struct FlowLabelConn {
+    socket: UdpSocket,
+    flow_label: u32,
+}
+
+impl FlowLabelConn {
+    /// Leases `flow_label` for `socket` and enables sending it.
+    pub fn new(socket: UdpSocket, flow_label: u32) -> Result<Self> {
+        if flow_label == 0 || flow_label > FLOW_LABEL_MAX {
+            return Err(Error::ErrInvalidFlowLabel);
+        }
+
+        sys::set_flow_label(&socket, flow_label)?;
+
+        Ok(FlowLabelConn { socket, flow_label })
+    }
+
+    /// Returns the flow label of the datagrams sent on this conn.
+    pub fn flow_label(&self) -> u32 {
+        self.flow_label
+    }
+
+    /// Returns the underlying socket.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    fn with_flow_label(&self, addr: SocketAddr) -> SocketAddr {
+        match addr {
+            SocketAddr::V6(a) => SocketAddr::V6(SocketAddrV6::new(
+                *a.ip(),
+                a.port(),
+                // sin6_flowinfo is in network byte order
+                self.flow_label.to_be(),
+                a.scope_id(),
+            )),
+            SocketAddr::V4(_) => addr,
+        }
+    }
+}
+
+#[async_trait]
+impl Conn for FlowLabelConn {
+    async fn connect(&self, addr: SocketAddr) -> Result<()> {
+        Ok(self.socket.connect(self.with_flow_label(addr)).await?)
+    }
+
+    async fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        Ok(self.socket.recv(buf).await?)
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        Ok(self.socket.recv_from(buf).await?)
+    }
+
+    async fn send(&self, buf: &[u8]) -> Result<usize> {
+        Ok(self.socket.send(buf).await?)
+    }
+
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> Result<usize> {
+        Ok(self
+            .socket
+            .send_to(buf, self.with_flow_label(target))
+            .await?)
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) mod sys {
+    use std::io;
+    use std::mem;
+    use std::os::unix::io::AsRawFd;
+
+    use super::*;
+
+    const IPV6_FL_A_GET: u8 = 0;
+    const IPV6_FL_S_USER: u8 = 3;
+    const IPV6_FL_F_CREATE: u16 = 1;
+
+    /// struct in6_flowlabel_req from linux/in6.h
+    #[repr(C)]
+    struct In6FlowlabelReq {
+        flr_dst: libc::in6_addr,
+        flr_label: u32,
+        flr_action: u8,
+        flr_share: u8,
+        flr_flags: u16,
+        flr_expires: u16,
+        flr_linger: u16,
+        flr_pad: u32,
+    }
+
+    fn setsockopt<T>(socket: &UdpSocket, name: libc::c_int, value: &T) -> io::Result<()> {
+        // SAFETY: the descriptor is owned by `socket`, which outlives the call, and the kernel
+        // reads at most `size_of::<T>()` bytes from `value`, which is borrowed for the call.
+        let res = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::IPPROTO_IPV6,
+                name,
+                value as *const T as *const libc::c_void,
+                mem::size_of::<T>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Leases the flow label, shared by all the sockets of this user, then lets the label in
+    /// the destination address of each datagram through.
+    pub(crate) fn set_flow_label(socket: &UdpSocket, flow_label: u32) -> Result<()> {
+        // A lease must name a destination, but sending doesn't check it. The peers of a socket
+        // aren't known yet, and every lease of a label must agree on it, so use loopback.
+        let req = In6FlowlabelReq {
+            flr_dst: libc::in6_addr {
+                s6_addr: Ipv6Addr::LOCALHOST.octets(),
+            },
+            flr_label: flow_label.to_be(),
+            flr_action: IPV6_FL_A_GET,
+            flr_share: IPV6_FL_S_USER,
+            flr_flags: IPV6_FL_F_CREATE,
+            flr_expires: 0,
+            flr_linger: 0,
+            flr_pad: 0,
+        };
+        setsockopt(socket, libc::IPV6_FLOWLABEL_MGR, &req)?;
+        setsockopt(socket, libc::IPV6_FLOWINFO_SEND, &(1 as libc::c_int))?;
+
+        Ok(())
+    }
+
+    /// Returns whether the socket sends the flow label of destination addresses.
+    #[cfg(test)]
+    pub(crate) fn is_flow_label_enabled(socket: &UdpSocket) -> Result<bool> {
+        let mut value: libc::c_int = 0;
+        let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+        // SAFETY: the descriptor is owned by `socket`, which outlives the call, and `len` tells
+        // the kernel it may write at most the size of `value`, an exclusively borrowed c_int.
+        let res = unsafe {
+            libc::getsockopt(
+                socket.as_raw_fd(),
+                libc::IPPROTO_IPV6,
+                libc::IPV6_FLOWINFO_SEND,
+                &mut value as *mut libc::c_int as *mut libc::c_void,
+                &mut len,
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        Ok(value != 0)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) mod sys {
+    use super::*;
+
+    pub(crate) fn set_flow_label(_socket: &UdpSocket, _flow_label: u32) -> Result<()> {
+        Err(Error::ErrFlowLabelUnsupported)
+    }
+}
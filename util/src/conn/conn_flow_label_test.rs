@@ -0,0 +1,56 @@
+use tokio::net::UdpSocket;
+
+use super::conn_flow_label::*;
+use super::*;
+use crate::error::Error;
+use crate::vnet::net::Net;
+
+#[tokio::test]
+async fn test_flow_label_conn() -> Result<()> {
+    let receiver = UdpSocket::bind("[::1]:0").await?;
+    let conn = FlowLabelConn::new(UdpSocket::bind("[::1]:0").await?, 0x12345)?;
+    assert_eq!(conn.flow_label(), 0x12345);
+    assert!(sys::is_flow_label_enabled(conn.socket())?);
+
+    // The kernel rejects datagrams carrying a label that wasn't leased
+    let n = conn.send_to(b"hello", receiver.local_addr()?).await?;
+    assert_eq!(n, 5);
+    let mut buf = [0u8; 8];
+    let (n, from) = receiver.recv_from(&mut buf).await?;
+    assert_eq!(&buf[..n], b"hello");
+    assert_eq!(from, conn.local_addr()?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flow_label_conn_invalid_label() -> Result<()> {
+    for flow_label in [0, 0x80000] {
+        let result = FlowLabelConn::new(UdpSocket::bind("[::1]:0").await?, flow_label);
+        assert_eq!(result.err(), Some(Error::ErrInvalidFlowLabel));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_net_bind_with_flow_label() -> Result<()> {
+    let net = Net::new(None);
+
+    // IPv4 has no flow label, so a plain socket is bound
+    let conn = net
+        .bind_with_flow_label("127.0.0.1:0".parse().unwrap(), 0x12345)
+        .await?;
+    assert!(conn.local_addr()?.is_ipv4());
+
+    let conn = net
+        .bind_with_flow_label("[::1]:0".parse().unwrap(), 0x12345)
+        .await?;
+    let receiver = UdpSocket::bind("[::1]:0").await?;
+    conn.send_to(b"hello", receiver.local_addr()?).await?;
+    let mut buf = [0u8; 8];
+    let n = receiver.recv(&mut buf).await?;
+    assert_eq!(&buf[..n], b"hello");
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::io::{Error, ErrorKind};
+
+use tokio::sync::{mpsc, watch, Mutex};
+
+use super::*;
+use crate::sync::RwLock;
+
+/// A datagram together with the remote address it was received from or is destined to.
+pub type Datagram = (Vec<u8>, SocketAddr);
+
+const INJECT_CHANNEL_SIZE: usize = 64;
+
+/// InjectConn is a packet oriented `Conn` that isn't backed by a socket, so that a custom
+/// network stack can carry the traffic instead. Datagrams received by that stack are fed in
+/// through a [`PacketInjector`], and datagrams written to the conn are handed out on the
+/// receiver returned by [`InjectConn::new`].
+///
+/// It can be used as the underlying conn of a UDP mux to run ICE without real sockets.
+pub struct InjectConn {
+    local_addr: SocketAddr,
+    remote_addr: RwLock<Option<SocketAddr>>,
+    inbound_tx: mpsc::Sender<Datagram>,
+    inbound_rx: Mutex<mpsc::Receiver<Datagram>>,
+    outbound_tx: mpsc::Sender<Datagram>,
+    closed_tx: watch::Sender<bool>,
+}
+
+impl InjectConn {
+    /// Creates a conn reporting `local_addr` as its address, and the receiver of all the
+    /// datagrams sent on it.
+    pub fn new(local_addr: SocketAddr) -> (Self, mpsc::Receiver<Datagram>) {
+        let (inbound_tx, inbound_rx) = mpsc::channel(INJECT_CHANNEL_SIZE);
+        let (outbound_tx, outbound_rx) = mpsc::channel(INJECT_CHANNEL_SIZE);
+        let (closed_tx, _) = watch::channel(false);
+
+        (
+            InjectConn {
+                local_addr,
+                remote_addr: RwLock::new(None),
+                inbound_tx,
+                inbound_rx: Mutex::new(inbound_rx),
+                outbound_tx,
+                closed_tx,
+            },
+            outbound_rx,
+        )
+    }
+
+    /// Returns a handle to feed received datagrams into this conn.
+    pub fn injector(&self) -> PacketInjector {
+        PacketInjector {
+            inbound_tx: self.inbound_tx.clone(),
+            closed_rx: self.closed_tx.subscribe(),
+        }
+    }
+}
+
+/// PacketInjector feeds externally received datagrams into an [`InjectConn`].
+#[derive(Clone)]
+pub struct PacketInjector {
+    inbound_tx: mpsc::Sender<Datagram>,
+    closed_rx: watch::Receiver<bool>,
+}
+
+impl PacketInjector {
+    /// handle_inbound hands a datagram received from `from` to the conn, waiting if its
+    /// receive queue is full.
+    pub async fn handle_inbound(&self, data: &[u8], from: SocketAddr) -> Result<()> {
+        if *self.closed_rx.borrow() {
+            return Err(crate::Error::ErrUseClosedNetworkConn);
+        }
+        self.inbound_tx
+            .send((data.to_vec(), from))
+            .await
+            .map_err(|_| crate::Error::ErrUseClosedNetworkConn)
+    }
+}
+
+#[async_trait]
+impl Conn for InjectConn {
+    async fn connect(&self, addr: SocketAddr) -> Result<()> {
+        *self.remote_addr.write() = Some(addr);
+        Ok(())
+    }
+
+    async fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        let (n, _) = self.recv_from(buf).await?;
+        Ok(n)
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        let mut closed_rx = self.closed_tx.subscribe();
+        if *closed_rx.borrow_and_update() {
+            return Err(crate::Error::ErrUseClosedNetworkConn);
+        }
+
+        let mut inbound_rx = self.inbound_rx.lock().await;
+        tokio::select! {
+            datagram = inbound_rx.recv() => match datagram {
+                Some((data, from)) => {
+                    let n = std::cmp::min(data.len(), buf.len());
+                    buf[..n].copy_from_slice(&data[..n]);
+                    Ok((n, from))
+                }
+                None => Err(crate::Error::ErrUseClosedNetworkConn),
+            },
+            _ = closed_rx.changed() => Err(crate::Error::ErrUseClosedNetworkConn),
+        }
+    }
+
+    async fn send(&self, buf: &[u8]) -> Result<usize> {
+        let target = match *self.remote_addr.read() {
+            Some(target) => target,
+            None => return Err(Error::new(ErrorKind::NotConnected, "Not connected").into()),
+        };
+        self.send_to(buf, target).await
+    }
+
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> Result<usize> {
+        if *self.closed_tx.borrow() {
+            return Err(crate::Error::ErrUseClosedNetworkConn);
+        }
+        self.outbound_tx
+            .send((buf.to_vec(), target))
+            .await
+            .map_err(|_| crate::Error::ErrUseClosedNetworkConn)?;
+        Ok(buf.len())
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        *self.remote_addr.read()
+    }
+
+    async fn close(&self) -> Result<()> {
+        if self.closed_tx.send_replace(true) {
+            return Err(crate::Error::ErrAlreadyClosed);
+        }
+        Ok(())
+    }
+}
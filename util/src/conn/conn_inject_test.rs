@@ -0,0 +1,47 @@
+use super::conn_inject::*;
+use super::*;
+
+#[tokio::test]
+async fn test_inject_conn() -> Result<()> {
+    let local: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+    let remote: SocketAddr = "10.0.0.2:6000".parse().unwrap();
+
+    let (conn, mut outbound_rx) = InjectConn::new(local);
+    let injector = conn.injector();
+    assert_eq!(conn.local_addr()?, local);
+
+    injector.handle_inbound(&[1, 2, 3], remote).await?;
+    let mut buf = vec![0u8; 16];
+    let (n, from) = conn.recv_from(&mut buf).await?;
+    assert_eq!(&buf[..n], &[1, 2, 3]);
+    assert_eq!(from, remote);
+
+    let n = conn.send_to(&[4, 5], remote).await?;
+    assert_eq!(n, 2);
+    assert_eq!(outbound_rx.recv().await, Some((vec![4, 5], remote)));
+
+    conn.close().await?;
+    assert!(conn.recv_from(&mut buf).await.is_err());
+    assert!(conn.send_to(&[6], remote).await.is_err());
+    assert!(injector.handle_inbound(&[7], remote).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_inject_conn_close_unblocks_recv() -> Result<()> {
+    let (conn, _outbound_rx) = InjectConn::new("10.0.0.1:5000".parse().unwrap());
+    let conn = Arc::new(conn);
+
+    let reader = Arc::clone(&conn);
+    let handle = tokio::spawn(async move {
+        let mut buf = vec![0u8; 16];
+        reader.recv_from(&mut buf).await
+    });
+
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    conn.close().await?;
+    assert!(handle.await.unwrap().is_err());
+
+    Ok(())
+}
@@ -1,11 +1,18 @@
 pub mod conn_bridge;
 pub mod conn_disconnected_packet;
+pub mod conn_flow_label;
+pub mod conn_inject;
 pub mod conn_pipe;
 pub mod conn_udp;
 pub mod conn_udp_listener;
 
 #[cfg(test)]
 mod conn_bridge_test;
+#[cfg(target_os = "linux")]
+#[cfg(test)]
+mod conn_flow_label_test;
+#[cfg(test)]
+mod conn_inject_test;
 #[cfg(test)]
 mod conn_pipe_test;
 #[cfg(test)]
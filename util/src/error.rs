@@ -105,6 +105,10 @@ pub enum Error {
     ErrNoIpaddrEth0,
     #[error("Invalid mask")]
     ErrInvalidMask,
+    #[error("flow label must be between 1 and 0x7FFFF")]
+    ErrInvalidFlowLabel,
+    #[error("IPv6 flow labels are not supported on this platform")]
+    ErrFlowLabelUnsupported,
     #[error("parse ipnet: {0}")]
     ParseIpnet(#[from] ipnet::AddrParseError),
     #[error("parse ip: {0}")]
@@ -14,6 +14,7 @@ use tokio::sync::Mutex;
 
 use super::conn_map::*;
 use super::interface::*;
+use crate::conn::conn_flow_label::FlowLabelConn;
 use crate::error::*;
 use crate::vnet::chunk::Chunk;
 use crate::vnet::conn::{ConnObserver, UdpConn};
@@ -530,6 +531,22 @@ impl Net {
         }
     }
 
+    /// bind_with_flow_label is bind, with the IPv6 datagrams sent on the conn marked with
+    /// `flow_label`. IPv4 addresses and the virtual network have no flow labels and ignore it.
+    pub async fn bind_with_flow_label(
+        &self,
+        addr: SocketAddr,
+        flow_label: u32,
+    ) -> Result<Arc<dyn Conn + Send + Sync>> {
+        match self {
+            Net::Ifs(_) if addr.is_ipv6() => Ok(Arc::new(FlowLabelConn::new(
+                UdpSocket::bind(addr).await?,
+                flow_label,
+            )?)),
+            _ => self.bind(addr).await,
+        }
+    }
+
     pub async fn dail(
         &self,
         use_ipv4: bool,
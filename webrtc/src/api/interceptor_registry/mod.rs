@@ -4,6 +4,7 @@ mod interceptor_registry_test;
 use interceptor::nack::generator::Generator;
 use interceptor::nack::responder::Responder;
 use interceptor::registry::Registry;
+use interceptor::remb::Remb;
 use interceptor::report::receiver::ReceiverReport;
 use interceptor::report::sender::SenderReport;
 use interceptor::twcc::receiver::Receiver;
@@ -12,7 +13,7 @@ use interceptor::twcc::sender::Sender;
 use crate::api::media_engine::MediaEngine;
 use crate::error::Result;
 use crate::rtp_transceiver::rtp_codec::{RTCRtpHeaderExtensionCapability, RTPCodecType};
-use crate::rtp_transceiver::{RTCPFeedback, TYPE_RTCP_FB_TRANSPORT_CC};
+use crate::rtp_transceiver::{RTCPFeedback, TYPE_RTCP_FB_GOOG_REMB, TYPE_RTCP_FB_TRANSPORT_CC};
 
 /// register_default_interceptors will register some useful interceptors.
 /// If you want to customize which interceptors are loaded, you should copy the
@@ -63,6 +64,27 @@ pub fn configure_nack(mut registry: Registry, media_engine: &mut MediaEngine) ->
     registry
 }
 
+/// configure_remb will setup everything necessary for advertising `bitrate`, in bits per second,
+/// as the maximum bitrate with REMB feedback to the senders of the remote video streams that
+/// negotiated goog-remb. The bitrate is fixed, no bandwidth estimation adjusts it.
+pub fn configure_remb(
+    mut registry: Registry,
+    media_engine: &mut MediaEngine,
+    bitrate: f32,
+) -> Registry {
+    media_engine.register_feedback(
+        RTCPFeedback {
+            typ: TYPE_RTCP_FB_GOOG_REMB.to_owned(),
+            ..Default::default()
+        },
+        RTPCodecType::Video,
+    );
+
+    let remb = Box::new(Remb::builder(bitrate));
+    registry.add(remb);
+    registry
+}
+
 /// configure_twcc will setup everything necessary for adding
 /// a TWCC header extension to outgoing RTP packets and generating TWCC reports.
 pub fn configure_twcc(mut registry: Registry, media_engine: &mut MediaEngine) -> Result<Registry> {
@@ -105,10 +127,19 @@ pub fn configure_twcc(mut registry: Registry, media_engine: &mut MediaEngine) ->
 
 /// configure_twcc_sender will setup everything necessary for adding
 /// a TWCC header extension to outgoing RTP packets. This will allow the remote peer to generate TWCC reports.
+/// The transport-cc feedback is registered as well, as the header extension is only added
+/// to streams that negotiated it.
 pub fn configure_twcc_sender_only(
     mut registry: Registry,
     media_engine: &mut MediaEngine,
 ) -> Result<Registry> {
+    media_engine.register_feedback(
+        RTCPFeedback {
+            typ: TYPE_RTCP_FB_TRANSPORT_CC.to_owned(),
+            ..Default::default()
+        },
+        RTPCodecType::Video,
+    );
     media_engine.register_header_extension(
         RTCRtpHeaderExtensionCapability {
             uri: sdp::extmap::TRANSPORT_CC_URI.to_owned(),
@@ -117,6 +148,13 @@ pub fn configure_twcc_sender_only(
         None,
     )?;
 
+    media_engine.register_feedback(
+        RTCPFeedback {
+            typ: TYPE_RTCP_FB_TRANSPORT_CC.to_owned(),
+            ..Default::default()
+        },
+        RTPCodecType::Audio,
+    );
     media_engine.register_header_extension(
         RTCRtpHeaderExtensionCapability {
             uri: sdp::extmap::TRANSPORT_CC_URI.to_owned(),
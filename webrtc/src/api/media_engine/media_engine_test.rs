@@ -778,3 +778,144 @@ a=rtpmap:111 opus/48000/2
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_h264_packetization_mode_negotiation() -> Result<()> {
+    let must_parse = |raw: &str| -> Result<SessionDescription> {
+        let mut reader = Cursor::new(raw.as_bytes());
+        Ok(SessionDescription::unmarshal(&mut reader)?)
+    };
+    let h264 = |sdp_fmtp_line: &str, payload_type: u8| RTCRtpCodecParameters {
+        capability: RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_H264.to_owned(),
+            clock_rate: 90000,
+            channels: 0,
+            sdp_fmtp_line: sdp_fmtp_line.to_owned(),
+            rtcp_feedback: vec![],
+        },
+        payload_type,
+        ..Default::default()
+    };
+
+    const MODE_1_OFFER: &str = "v=0
+o=- 4596489990601351948 2 IN IP4 127.0.0.1
+s=-
+t=0 0
+m=video 60323 UDP/TLS/RTP/SAVPF 96
+a=rtpmap:96 H264/90000
+a=fmtp:96 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f
+";
+
+    // A mode 0 registration doesn't negotiate an offered mode 1
+    let mut m = MediaEngine::default();
+    m.register_codec(
+        h264(
+            "level-asymmetry-allowed=1;packetization-mode=0;profile-level-id=42e01f",
+            100,
+        ),
+        RTPCodecType::Video,
+    )?;
+    m.update_from_remote_description(&must_parse(MODE_1_OFFER)?)
+        .await?;
+    assert!(m.get_codec_by_payload(96).await.is_err());
+    assert!(m.get_codecs_by_kind(RTPCodecType::Video).is_empty());
+
+    // With both modes registered, each offered payload type maps to its own mode
+    const BOTH_MODES_OFFER: &str = "v=0
+o=- 4596489990601351948 2 IN IP4 127.0.0.1
+s=-
+t=0 0
+m=video 60323 UDP/TLS/RTP/SAVPF 96 97
+a=rtpmap:96 H264/90000
+a=fmtp:96 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f
+a=rtpmap:97 H264/90000
+a=fmtp:97 level-asymmetry-allowed=1;profile-level-id=42e01f
+";
+    let mut m = MediaEngine::default();
+    m.register_codec(
+        h264(
+            "level-asymmetry-allowed=1;packetization-mode=0;profile-level-id=42e01f",
+            100,
+        ),
+        RTPCodecType::Video,
+    )?;
+    m.register_codec(
+        h264(
+            "level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f",
+            102,
+        ),
+        RTPCodecType::Video,
+    )?;
+    m.update_from_remote_description(&must_parse(BOTH_MODES_OFFER)?)
+        .await?;
+
+    let (mode_1, _) = m.get_codec_by_payload(96).await?;
+    assert_eq!(
+        mode_1.capability.sdp_fmtp_line,
+        "level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f"
+    );
+    let (mode_0, _) = m.get_codec_by_payload(97).await?;
+    assert_eq!(
+        mode_0.capability.sdp_fmtp_line,
+        "level-asymmetry-allowed=1;profile-level-id=42e01f"
+    );
+    assert_eq!(m.get_codecs_by_kind(RTPCodecType::Video).len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_media_engine_get_codecs() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+
+    let audio: Vec<_> = m
+        .get_codecs(RTPCodecType::Audio)
+        .into_iter()
+        .map(|c| (c.capability.mime_type, c.payload_type))
+        .collect();
+    assert_eq!(
+        audio,
+        vec![
+            (MIME_TYPE_OPUS.to_owned(), 111),
+            (MIME_TYPE_G722.to_owned(), 9),
+            (MIME_TYPE_PCMU.to_owned(), 0),
+            (MIME_TYPE_PCMA.to_owned(), 8),
+        ]
+    );
+
+    let video = m.get_codecs(RTPCodecType::Video);
+    assert_eq!(video.len(), m.video_codecs.len());
+    let vp8 = &video[0];
+    assert_eq!(vp8.capability.mime_type, MIME_TYPE_VP8);
+    assert_eq!(vp8.payload_type, 96);
+    assert!(!vp8.capability.rtcp_feedback.is_empty());
+    assert!(video
+        .iter()
+        .any(|c| c.capability.mime_type == "video/ulpfec" && c.payload_type == 116));
+
+    m.register_codec(
+        RTCRtpCodecParameters {
+            capability: RTCRtpCodecCapability {
+                mime_type: "video/rtx".to_owned(),
+                clock_rate: 90000,
+                channels: 0,
+                sdp_fmtp_line: "apt=96".to_owned(),
+                rtcp_feedback: vec![],
+            },
+            payload_type: 97,
+            ..Default::default()
+        },
+        RTPCodecType::Video,
+    )?;
+    let rtx = m
+        .get_codecs(RTPCodecType::Video)
+        .pop()
+        .expect("an RTX codec");
+    assert_eq!(rtx.capability.sdp_fmtp_line, "apt=96");
+    assert_eq!(rtx.payload_type, 97);
+
+    assert!(m.get_codecs(RTPCodecType::Unspecified).is_empty());
+
+    Ok(())
+}
@@ -51,6 +51,12 @@ pub const MIME_TYPE_PCMA: &str = "audio/PCMA";
 /// MIME_TYPE_TELEPHONE_EVENT telephone-event MIME type
 /// Note: Matching should be case insensitive.
 pub const MIME_TYPE_TELEPHONE_EVENT: &str = "audio/telephone-event";
+/// MIME_TYPE_RTX RTX (RFC 4588) MIME type
+/// Note: Matching should be case insensitive.
+pub const MIME_TYPE_RTX: &str = "video/rtx";
+/// MIME_TYPE_AUDIO_RTX RTX (RFC 4588) MIME type for audio codecs
+/// Note: Matching should be case insensitive.
+pub const MIME_TYPE_AUDIO_RTX: &str = "audio/rtx";
 
 const VALID_EXT_IDS: Range<isize> = 1..15;
 
@@ -93,6 +99,8 @@ pub struct MediaEngine {
     header_extensions: Vec<MediaEngineHeaderExtension>,
     proposed_header_extensions: SyncMutex<HashMap<isize, MediaEngineHeaderExtension>>,
     pub(crate) negotiated_header_extensions: SyncMutex<HashMap<isize, MediaEngineHeaderExtension>>,
+    // If the remote peer allows mixing one-byte and two-byte header extensions.
+    negotiated_extmap_allow_mixed: AtomicBool,
 }
 
 impl MediaEngine {
@@ -348,6 +356,16 @@ impl MediaEngine {
         }
     }
 
+    /// get_codecs returns the codecs registered for the given kind, including RTX and FEC entries.
+    /// Unlike the codecs of a PeerConnection, these don't change through negotiation.
+    pub fn get_codecs(&self, typ: RTPCodecType) -> Vec<RTCRtpCodecParameters> {
+        match typ {
+            RTPCodecType::Audio => self.audio_codecs.clone(),
+            RTPCodecType::Video => self.video_codecs.clone(),
+            _ => vec![],
+        }
+    }
+
     /// Adds a header extension to the MediaEngine
     /// To determine the negotiated value use [`get_header_extension_id`] after signaling is complete.
     ///
@@ -606,6 +624,10 @@ impl MediaEngine {
         &self,
         desc: &SessionDescription,
     ) -> Result<()> {
+        let allow_mixed = desc.has_extmap_allow_mixed();
+        self.negotiated_extmap_allow_mixed
+            .store(allow_mixed, Ordering::SeqCst);
+
         for media in &desc.media_descriptions {
             let typ = if !self.negotiated_audio.load(Ordering::SeqCst)
                 && media.media_name.media.to_lowercase() == "audio"
@@ -650,6 +672,16 @@ impl MediaEngine {
             let extensions = rtp_extensions_from_media_description(media)?;
 
             for (extension, id) in extensions {
+                // Ids above 14 need the two-byte form, which may only be used if the
+                // remote peer allows mixing it with the one-byte form.
+                if !allow_mixed && !(1..=14).contains(&id) {
+                    log::debug!(
+                        "Ignoring header extension {} with id {} without extmap-allow-mixed",
+                        extension,
+                        id
+                    );
+                    continue;
+                }
                 self.update_header_extension(id, &extension, typ).await?;
             }
         }
@@ -657,6 +689,12 @@ impl MediaEngine {
         Ok(())
     }
 
+    /// extmap_allow_mixed returns whether the remote peer signaled `a=extmap-allow-mixed`, so
+    /// header extensions may use the two-byte form when needed.
+    pub(crate) fn extmap_allow_mixed(&self) -> bool {
+        self.negotiated_extmap_allow_mixed.load(Ordering::SeqCst)
+    }
+
     pub(crate) fn get_codecs_by_kind(&self, typ: RTPCodecType) -> Vec<RTCRtpCodecParameters> {
         if typ == RTPCodecType::Video {
             if self.negotiated_video.load(Ordering::SeqCst) {
@@ -7,13 +7,17 @@ use dtls::extension::extension_use_srtp::SrtpProtectionProfile;
 use ice::agent::agent_config::{InterfaceFilterFn, IpFilterFn};
 use ice::mdns::MulticastDnsMode;
 use ice::network_type::NetworkType;
+use ice::udp_mux::{UDPMuxDefault, UDPMuxParams};
 use ice::udp_network::UDPNetwork;
+use sctp::association::DEFAULT_SCTP_PORT;
 use tokio::time::Duration;
 use util::vnet::net::*;
+use util::Conn;
 
 use crate::dtls_transport::dtls_role::DTLSRole;
 use crate::error::{Error, Result};
 use crate::ice_transport::ice_candidate_type::RTCIceCandidateType;
+use crate::peer_connection::SIMULCAST_MAX_PROBE_ROUTINES;
 use crate::RECEIVE_MTU;
 
 #[derive(Default, Clone)]
@@ -26,6 +30,7 @@ pub struct Timeout {
     pub ice_disconnected_timeout: Option<Duration>,
     pub ice_failed_timeout: Option<Duration>,
     pub ice_keepalive_interval: Option<Duration>,
+    pub ice_check_interval: Option<Duration>,
     pub ice_host_acceptance_min_wait: Option<Duration>,
     pub ice_srflx_acceptance_min_wait: Option<Duration>,
     pub ice_prflx_acceptance_min_wait: Option<Duration>,
@@ -44,6 +49,7 @@ pub struct Candidates {
     pub multicast_dns_host_name: String,
     pub username_fragment: String,
     pub password: String,
+    pub max_concurrent_gather_probes: usize,
 }
 
 #[derive(Default, Clone)]
@@ -75,7 +81,16 @@ pub struct SettingEngine {
     pub(crate) udp_network: UDPNetwork,
     pub(crate) disable_media_engine_copy: bool,
     pub(crate) srtp_protection_profiles: Vec<SrtpProtectionProfile>,
+    pub(crate) srtp_null_cipher: bool,
     pub(crate) receive_mtu: usize,
+    pub(crate) max_media_sections: usize,
+    pub(crate) sctp_port: u16,
+    pub(crate) sctp_heartbeat_interval: Duration,
+    pub(crate) simulcast_max_probe_routines: u64,
+    pub(crate) unhandled_track_buffer: usize,
+    pub(crate) raw_rtp_tap: bool,
+    pub(crate) path_mtu_probe_timeout: Option<Duration>,
+    pub(crate) ipv6_flow_label: Option<u32>,
     pub(crate) mid_generator: Option<Arc<dyn Fn(isize) -> String + Send + Sync>>,
 }
 
@@ -88,6 +103,27 @@ impl SettingEngine {
             RECEIVE_MTU
         }
     }
+
+    /// get_sctp_port returns the configured local SCTP port. If SettingEngine's port is configured
+    /// to 0 it returns the default
+    pub(crate) fn get_sctp_port(&self) -> u16 {
+        if self.sctp_port != 0 {
+            self.sctp_port
+        } else {
+            DEFAULT_SCTP_PORT
+        }
+    }
+
+    /// get_simulcast_max_probe_routines returns the configured cap on concurrent undeclared SSRC
+    /// probes. If it's configured to 0 it returns the default
+    pub(crate) fn get_simulcast_max_probe_routines(&self) -> u64 {
+        if self.simulcast_max_probe_routines != 0 {
+            self.simulcast_max_probe_routines
+        } else {
+            SIMULCAST_MAX_PROBE_ROUTINES
+        }
+    }
+
     /// detach_data_channels enables detaching data channels. When enabled
     /// data channels have to be detached in the OnOpen callback using the
     /// DataChannel.Detach method.
@@ -116,6 +152,13 @@ impl SettingEngine {
         self.timeout.ice_keepalive_interval = keep_alive_interval;
     }
 
+    /// set_ice_check_interval sets how often the ICE Agent sends connectivity checks while
+    /// connecting (Ta). Lower values connect faster on lossy links, higher values are gentler on
+    /// the network. Default is 400 milliseconds
+    pub fn set_ice_check_interval(&mut self, t: Option<Duration>) {
+        self.timeout.ice_check_interval = t;
+    }
+
     /// set_host_acceptance_min_wait sets the icehost_acceptance_min_wait
     pub fn set_host_acceptance_min_wait(&mut self, t: Option<Duration>) {
         self.timeout.ice_host_acceptance_min_wait = t;
@@ -143,7 +186,19 @@ impl SettingEngine {
         self.udp_network = udp_network;
     }
 
-    /// set_lite configures whether or not the ice agent should be a lite agent
+    /// set_udp_conn routes all ICE traffic through a UDPMux over the given conn instead of
+    /// sockets opened by the ICE agent. Together with `util::conn::conn_inject::InjectConn`
+    /// this lets a custom network stack carry the traffic of a PeerConnection.
+    pub fn set_udp_conn<C>(&mut self, conn: C)
+    where
+        C: Conn + Send + Sync + 'static,
+    {
+        self.udp_network = UDPNetwork::Muxed(UDPMuxDefault::new(UDPMuxParams::new(conn)));
+    }
+
+    /// set_lite configures whether or not the ice agent should be a lite agent.
+    /// A lite agent only gathers host candidates, ignoring any configured ICE servers,
+    /// never initiates connectivity checks and advertises `a=ice-lite` in its descriptions.
     pub fn set_lite(&mut self, lite: bool) {
         self.candidates.ice_lite = lite;
     }
@@ -242,6 +297,13 @@ impl SettingEngine {
         self.candidates.password = password;
     }
 
+    /// set_ice_max_concurrent_gather_probes limits how many STUN and TURN servers are queried at
+    /// once while gathering candidates, so many interfaces and servers don't exhaust sockets.
+    /// 0, the default, means no limit.
+    pub fn set_ice_max_concurrent_gather_probes(&mut self, max_probes: usize) {
+        self.candidates.max_concurrent_gather_probes = max_probes;
+    }
+
     /// disable_certificate_fingerprint_verification disables fingerprint verification after dtls_transport Handshake has finished
     pub fn disable_certificate_fingerprint_verification(&mut self, is_disabled: bool) {
         self.disable_certificate_fingerprint_verification = is_disabled;
@@ -305,12 +367,79 @@ impl SettingEngine {
         self.disable_media_engine_copy = is_disabled;
     }
 
+    /// enable_raw_rtp_tap adds an interceptor to every PeerConnection that hands a copy of each
+    /// RTP packet to the handlers set with `RTCPeerConnection::on_raw_rtp`. It's disabled by
+    /// default so PeerConnections that don't need it don't pay for it.
+    pub fn enable_raw_rtp_tap(&mut self, is_enabled: bool) {
+        self.raw_rtp_tap = is_enabled;
+    }
+
+    /// set_path_mtu_probe makes the ICE transport probe the path MTU of the selected candidate
+    /// pair once connected, waiting up to `probe_timeout` for each probe to be answered. Outgoing
+    /// tracks then fragment their payloads to fit the discovered MTU instead of a fixed one.
+    /// Probing runs in the background and the default MTU is used until it completes. It's
+    /// disabled by default.
+    pub fn set_path_mtu_probe(&mut self, probe_timeout: Option<Duration>) {
+        self.path_mtu_probe_timeout = probe_timeout;
+    }
+
+    /// set_ipv6_flow_label marks the IPv6 datagrams sent by the ICE agent, and so the media, with
+    /// `flow_label`, for networks that classify traffic by flow label. The label must be between
+    /// 1 and 0x7FFFF. It's only supported on Linux, and gathering fails elsewhere.
+    pub fn set_ipv6_flow_label(&mut self, flow_label: Option<u32>) {
+        self.ipv6_flow_label = flow_label;
+    }
+
+    /// enable_srtp_null_cipher allows negotiating the SRTP_NULL_HMAC_SHA1_80 protection profile,
+    /// which authenticates media but doesn't encrypt it, so captured packets can be inspected in
+    /// loopback and integration tests. Unless other profiles are set with
+    /// `set_srtp_protection_profiles`, it becomes the only one offered.
+    ///
+    /// WARNING: media is sent in the clear. Never enable this outside of tests.
+    pub fn enable_srtp_null_cipher(&mut self, is_enabled: bool) {
+        self.srtp_null_cipher = is_enabled;
+    }
+
     /// set_receive_mtu sets the size of read buffer that copies incoming packets. This is optional.
     /// Leave this 0 for the default receive_mtu
     pub fn set_receive_mtu(&mut self, receive_mtu: usize) {
         self.receive_mtu = receive_mtu;
     }
 
+    /// set_max_media_sections caps the number of media sections accepted in a remote description.
+    /// Offers exceeding it are rejected before any transceiver is created. Leave this 0 for no limit.
+    pub fn set_max_media_sections(&mut self, max_media_sections: usize) {
+        self.max_media_sections = max_media_sections;
+    }
+
+    /// set_sctp_port sets the local SCTP port advertised with 'a=sctp-port' and used by the
+    /// association carrying data channels. Leave this 0 for the default of 5000
+    pub fn set_sctp_port(&mut self, port: u16) {
+        self.sctp_port = port;
+    }
+
+    /// set_sctp_heartbeat_interval sets how often an idle SCTP association sends a HEARTBEAT to
+    /// keep the path alive and measure its round-trip time. Leave this 0 to send none, the
+    /// remote's heartbeats are answered regardless.
+    pub fn set_sctp_heartbeat_interval(&mut self, interval: Duration) {
+        self.sctp_heartbeat_interval = interval;
+    }
+
+    /// set_simulcast_max_probe_routines caps how many incoming SSRCs that aren't declared in the
+    /// remote description are probed for their MID/RID at the same time. Packets of further SSRCs
+    /// are dropped until a probe finishes. Leave this 0 for the default of
+    /// [`SIMULCAST_MAX_PROBE_ROUTINES`].
+    pub fn set_simulcast_max_probe_routines(&mut self, max_probe_routines: u64) {
+        self.simulcast_max_probe_routines = max_probe_routines;
+    }
+
+    /// set_unhandled_track_buffer keeps remote tracks that arrive before an on_track handler is
+    /// set, reading up to `packets` of their first RTP packets into a buffer, and hands them to the
+    /// handler once it's set. With the default of 0 such tracks are dropped.
+    pub fn set_unhandled_track_buffer(&mut self, packets: usize) {
+        self.unhandled_track_buffer = packets;
+    }
+
     /// Sets a callback used to generate mid for transceivers created by this side of the RTCPeerconnection.
     /// By having separate "naming schemes" for mids generated by either side of a connection, it's
     /// possible to reduce complexity when handling SDP offers/answers clashing.
@@ -125,6 +125,20 @@ fn test_set_replay_protection() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_set_simulcast_max_probe_routines() -> Result<()> {
+    let mut s = SettingEngine::default();
+    assert_eq!(
+        s.get_simulcast_max_probe_routines(),
+        crate::peer_connection::SIMULCAST_MAX_PROBE_ROUTINES
+    );
+
+    s.set_simulcast_max_probe_routines(3);
+    assert_eq!(s.get_simulcast_max_probe_routines(), 3);
+
+    Ok(())
+}
+
 /*TODO:#[test] fn test_setting_engine_set_ice_tcp_mux() ->Result<()> {
 
     listener, err := net.ListenTCP("tcp", &net.TCPAddr{})
@@ -269,3 +283,35 @@ async fn test_setting_engine_set_disable_media_engine_copy() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_setting_engine_set_max_media_sections() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+
+    let mut s = SettingEngine::default();
+    s.set_max_media_sections(2);
+
+    let api = APIBuilder::new()
+        .with_media_engine(m)
+        .with_setting_engine(s)
+        .build();
+
+    let (offerer, answerer) = new_pair(&api).await?;
+
+    for _ in 0..3 {
+        offerer
+            .add_transceiver_from_kind(RTPCodecType::Video, None)
+            .await?;
+    }
+
+    let offer = offerer.create_offer(None).await?;
+    let result = answerer.set_remote_description(offer).await;
+    assert_eq!(result, Err(Error::ErrPeerConnTooManyMediaSections));
+    assert!(answerer.get_transceivers().await.is_empty());
+    assert!(answerer.remote_description().await.is_none());
+
+    close_pair_now(&offerer, &answerer).await;
+
+    Ok(())
+}
@@ -22,8 +22,10 @@ use crate::ice_transport::ice_parameters::RTCIceParameters;
 use crate::ice_transport::ice_role::RTCIceRole;
 use crate::ice_transport::RTCIceTransport;
 use crate::peer_connection::configuration::RTCConfiguration;
+use crate::peer_connection::peer_connection_state::RTCPeerConnectionState;
 use crate::peer_connection::peer_connection_test::*;
 use crate::peer_connection::RTCPeerConnection;
+use crate::rtp_transceiver::rtp_codec::RTPCodecType;
 use crate::sctp_transport::sctp_transport_capabilities::SCTPTransportCapabilities;
 
 // EXPECTED_LABEL represents the label of the data channel we are trying to test.
@@ -1266,6 +1268,85 @@ async fn test_data_channel_non_standard_session_description() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_data_channel_max_message_size() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    let (offer_pc, answer_pc) = new_pair(&api).await?;
+
+    // Each side tries a message at and one just over its limit once its channel is open.
+    let (result_tx, mut result_rx) = mpsc::channel::<(usize, Vec<Result<usize>>)>(2);
+    let try_send = move |d: Arc<RTCDataChannel>, limit: usize| {
+        let result_tx = result_tx.clone();
+        let d2 = Arc::clone(&d);
+        d.on_open(Box::new(move || {
+            Box::pin(async move {
+                let results = vec![
+                    d2.send(&Bytes::from(vec![0u8; limit])).await,
+                    d2.send(&Bytes::from(vec![0u8; limit + 1])).await,
+                    d2.send_text("a".repeat(limit + 1)).await,
+                ];
+                let _ = result_tx.send((limit, results)).await;
+            })
+        }));
+    };
+
+    let answer_try_send = try_send.clone();
+    answer_pc.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+        answer_try_send(d, 1024);
+        Box::pin(async {})
+    }));
+    let dc = offer_pc.create_data_channel("foo", None).await?;
+    try_send(dc, 65536);
+
+    let offer = offer_pc.create_offer(None).await?;
+    let mut offer_gathering_complete = offer_pc.gathering_complete_promise().await;
+    offer_pc.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+
+    // The offerer only accepts messages of up to 1024 bytes.
+    let mut offer = offer_pc.local_description().await.unwrap();
+    let re = Regex::new(r"(a=sctp-port:.*?\r\n)").unwrap();
+    offer.sdp = re
+        .replace_all(offer.sdp.as_str(), "${1}a=max-message-size:1024\r\n")
+        .to_string();
+    assert!(offer.sdp.contains("a=max-message-size:1024"));
+
+    answer_pc.set_remote_description(offer).await?;
+    let answer = answer_pc.create_answer(None).await?;
+    let mut answer_gathering_complete = answer_pc.gathering_complete_promise().await;
+    answer_pc.set_local_description(answer).await?;
+    let _ = answer_gathering_complete.recv().await;
+    let answer = answer_pc.local_description().await.unwrap();
+    offer_pc.set_remote_description(answer).await?;
+
+    for _ in 0..2 {
+        let (limit, results) = tokio::time::timeout(Duration::from_secs(10), result_rx.recv())
+            .await
+            .expect("the data channel never opened")
+            .unwrap();
+        assert_eq!(results[0], Ok(limit), "limit {limit}");
+        assert_eq!(
+            results[1],
+            Err(Error::ErrDataChannelMessageTooLarge),
+            "limit {limit}"
+        );
+        assert_eq!(
+            results[2],
+            Err(Error::ErrDataChannelMessageTooLarge),
+            "limit {limit}"
+        );
+    }
+    assert_eq!(offer_pc.sctp().max_message_size(), 65536);
+    assert_eq!(answer_pc.sctp().max_message_size(), 1024);
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
 struct TestOrtcStack {
     //api      *API
     gatherer: Arc<RTCIceGatherer>,
@@ -1502,3 +1583,87 @@ async fn test_data_channel_ortc_e2e() -> Result<()> {
 
     Ok(())
 }
+
+async fn negotiate_without_trickle(
+    offer_pc: &RTCPeerConnection,
+    answer_pc: &RTCPeerConnection,
+) -> Result<()> {
+    let offer = offer_pc.create_offer_and_gather(None).await?;
+    answer_pc.set_remote_description(offer).await?;
+
+    let answer = answer_pc.create_answer(None).await?;
+    let mut answer_gathering_complete = answer_pc.gathering_complete_promise().await;
+    answer_pc.set_local_description(answer).await?;
+    let _ = answer_gathering_complete.recv().await;
+
+    offer_pc
+        .set_remote_description(
+            answer_pc
+                .local_description()
+                .await
+                .ok_or(Error::new("non local description".to_owned()))?,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_data_channel_added_by_renegotiation() -> Result<()> {
+    let (mut offer_pc, mut answer_pc, _wan) = create_vnet_pair().await?;
+
+    // Start with a media-only session, without an application media section.
+    offer_pc
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut offer_pc, &wg, RTCPeerConnectionState::Connected).await;
+    until_connection_state(&mut answer_pc, &wg, RTCPeerConnectionState::Connected).await;
+
+    negotiate_without_trickle(&offer_pc, &answer_pc).await?;
+    assert!(!offer_pc
+        .local_description()
+        .await
+        .map(|d| d.sdp.contains("m=application"))
+        .unwrap_or_default());
+
+    wg.wait().await;
+
+    let (answer_open_tx, mut answer_open_rx) = mpsc::channel::<()>(1);
+    let answer_open_tx = Arc::new(answer_open_tx);
+    answer_pc.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+        let answer_open_tx2 = Arc::clone(&answer_open_tx);
+        Box::pin(async move {
+            if d.label() == EXPECTED_LABEL {
+                d.on_open(Box::new(move || {
+                    Box::pin(async move {
+                        let _ = answer_open_tx2.send(()).await;
+                    })
+                }));
+            }
+        })
+    }));
+
+    let (offer_open_tx, mut offer_open_rx) = mpsc::channel::<()>(1);
+    let dc = offer_pc.create_data_channel(EXPECTED_LABEL, None).await?;
+    dc.on_open(Box::new(move || {
+        Box::pin(async move {
+            let _ = offer_open_tx.send(()).await;
+        })
+    }));
+
+    negotiate_without_trickle(&offer_pc, &answer_pc).await?;
+
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    for open_rx in [&mut offer_open_rx, &mut answer_open_rx] {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for data channel to open"),
+            _ = open_rx.recv() => {}
+        }
+    }
+    assert_eq!(dc.ready_state(), RTCDataChannelState::Open);
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
@@ -58,6 +58,8 @@ pub struct RTCDataChannel {
     pub(crate) id: AtomicU16,
     pub(crate) ready_state: Arc<AtomicU8>, // DataChannelState
     pub(crate) buffered_amount_low_threshold: AtomicUsize,
+    // The max_message_size of the SCTPTransport, set once the channel opens.
+    pub(crate) max_message_size: AtomicUsize,
     pub(crate) detach_called: Arc<AtomicBool>,
 
     // The binaryType represents attribute MUST, on getting, return the value to
@@ -120,6 +122,8 @@ impl RTCDataChannel {
                 let mut st = self.sctp_transport.lock().await;
                 if st.is_none() {
                     *st = Some(Arc::downgrade(&sctp_transport));
+                    self.max_message_size
+                        .store(sctp_transport.max_message_size(), Ordering::SeqCst);
                 } else {
                     return Ok(());
                 }
@@ -360,6 +364,7 @@ impl RTCDataChannel {
     /// send sends the binary message to the DataChannel peer
     pub async fn send(&self, data: &Bytes) -> Result<usize> {
         self.ensure_open()?;
+        self.ensure_message_size(data.len())?;
 
         let data_channel = self.data_channel.lock().await;
         if let Some(dc) = &*data_channel {
@@ -372,10 +377,12 @@ impl RTCDataChannel {
     /// send_text sends the text message to the DataChannel peer
     pub async fn send_text(&self, s: impl Into<String>) -> Result<usize> {
         self.ensure_open()?;
+        let s = s.into();
+        self.ensure_message_size(s.len())?;
 
         let data_channel = self.data_channel.lock().await;
         if let Some(dc) = &*data_channel {
-            Ok(dc.write_data_channel(&Bytes::from(s.into()), true).await?)
+            Ok(dc.write_data_channel(&Bytes::from(s), true).await?)
         } else {
             Err(Error::ErrClosedPipe)
         }
@@ -389,6 +396,15 @@ impl RTCDataChannel {
         }
     }
 
+    fn ensure_message_size(&self, len: usize) -> Result<()> {
+        let max_message_size = self.max_message_size.load(Ordering::SeqCst);
+        if max_message_size != 0 && len > max_message_size {
+            Err(Error::ErrDataChannelMessageTooLarge)
+        } else {
+            Ok(())
+        }
+    }
+
     /// detach allows you to detach the underlying datachannel. This provides
     /// an idiomatic API to work with, however it disables the OnMessage callback.
     /// Before calling Detach you have to enable this behavior by calling
@@ -545,8 +561,8 @@ impl RTCDataChannel {
     }
 
     pub(crate) async fn collect_stats(&self, collector: &StatsCollector) {
-        /*let stats = DataChannelStats::from(self).await;
-        collector.insert(self.stats_id.clone(), StatsReportType::DataChannel(stats));*/
+        let stats = DataChannelStats::from(self).await;
+        collector.insert(self.stats_id.clone(), StatsReportType::DataChannel(stats));
     }
 
     pub(crate) fn set_ready_state(&self, r: RTCDataChannelState) {
@@ -50,32 +50,57 @@ impl fmt::Display for DTLSRole {
     }
 }
 
+/// Returns the first setup attribute of a SessionDescription, or None if there is none.
+fn remote_connection_role(session_description: &SessionDescription) -> Option<ConnectionRole> {
+    for media_section in &session_description.media_descriptions {
+        for attribute in &media_section.attributes {
+            if attribute.key == "setup" {
+                return Some(
+                    attribute
+                        .value
+                        .as_deref()
+                        .map(ConnectionRole::from)
+                        .unwrap_or_default(),
+                );
+            }
+        }
+    }
+
+    None
+}
+
 /// Iterate a SessionDescription from a remote to determine if an explicit
 /// role can been determined from it. The decision is made from the first role we we parse.
 /// If no role can be found we return DTLSRoleAuto
 impl From<&SessionDescription> for DTLSRole {
     fn from(session_description: &SessionDescription) -> Self {
-        for media_section in &session_description.media_descriptions {
-            for attribute in &media_section.attributes {
-                if attribute.key == "setup" {
-                    if let Some(value) = &attribute.value {
-                        match value.as_str() {
-                            "active" => return DTLSRole::Client,
-                            "passive" => return DTLSRole::Server,
-                            _ => return DTLSRole::Auto,
-                        };
-                    } else {
-                        return DTLSRole::Auto;
-                    }
-                }
-            }
+        match remote_connection_role(session_description) {
+            Some(ConnectionRole::Active) => DTLSRole::Client,
+            Some(ConnectionRole::Passive) => DTLSRole::Server,
+            _ => DTLSRole::Auto,
         }
-
-        DTLSRole::Auto
     }
 }
 
 impl DTLSRole {
+    /// from_remote_description determines the remote role like `From<&SessionDescription>`,
+    /// but settles the cases where the description has no usable setup attribute by its
+    /// place in the offer/answer exchange, as RFC 4145 section 4 does: a missing attribute
+    /// means the offerer is active (Client) and the answerer passive (Server). holdconn is
+    /// not allowed for DTLS-SRTP (RFC 5763 section 5), so it is treated as missing too.
+    pub(crate) fn from_remote_description(
+        session_description: &SessionDescription,
+        remote_is_offer: bool,
+    ) -> Self {
+        match remote_connection_role(session_description) {
+            Some(ConnectionRole::Active) => DTLSRole::Client,
+            Some(ConnectionRole::Passive) => DTLSRole::Server,
+            None | Some(ConnectionRole::Holdconn) if remote_is_offer => DTLSRole::Client,
+            None | Some(ConnectionRole::Holdconn) => DTLSRole::Server,
+            _ => DTLSRole::Auto,
+        }
+    }
+
     pub(crate) fn to_connection_role(self) -> ConnectionRole {
         match self {
             DTLSRole::Client => ConnectionRole::Active,
@@ -167,4 +192,80 @@ a=setup:";
 
         Ok(())
     }
+
+    #[test]
+    fn test_dtls_role_from_remote_description() -> Result<()> {
+        const MEDIA_NO_SETUP: &str = "v=0
+o=- 4596489990601351948 2 IN IP4 127.0.0.1
+s=-
+t=0 0
+m=application 47299 DTLS/SCTP 5000
+c=IN IP4 192.168.20.129
+";
+
+        const MEDIA_SETUP_DECLARED: &str = "v=0
+o=- 4596489990601351948 2 IN IP4 127.0.0.1
+s=-
+t=0 0
+m=application 47299 DTLS/SCTP 5000
+c=IN IP4 192.168.20.129
+a=setup:";
+
+        let tests = vec![
+            (
+                "no setup in an offer",
+                MEDIA_NO_SETUP.to_owned(),
+                true,
+                DTLSRole::Client,
+            ),
+            (
+                "no setup in an answer",
+                MEDIA_NO_SETUP.to_owned(),
+                false,
+                DTLSRole::Server,
+            ),
+            (
+                "setup:holdconn in an offer",
+                format!("{MEDIA_SETUP_DECLARED}holdconn\n"),
+                true,
+                DTLSRole::Client,
+            ),
+            (
+                "setup:holdconn in an answer",
+                format!("{MEDIA_SETUP_DECLARED}holdconn\n"),
+                false,
+                DTLSRole::Server,
+            ),
+            (
+                "setup:actpass in an offer",
+                format!("{MEDIA_SETUP_DECLARED}actpass\n"),
+                true,
+                DTLSRole::Auto,
+            ),
+            (
+                "setup:active in an answer",
+                format!("{MEDIA_SETUP_DECLARED}active\n"),
+                false,
+                DTLSRole::Client,
+            ),
+            (
+                "setup:passive in an answer",
+                format!("{MEDIA_SETUP_DECLARED}passive\n"),
+                false,
+                DTLSRole::Server,
+            ),
+        ];
+
+        for (name, session_description_str, remote_is_offer, expected_role) in tests {
+            let mut reader = Cursor::new(session_description_str.as_bytes());
+            let session_description = SessionDescription::unmarshal(&mut reader)?;
+            assert_eq!(
+                DTLSRole::from_remote_description(&session_description, remote_is_offer),
+                expected_role,
+                "{name} failed"
+            );
+        }
+
+        Ok(())
+    }
 }
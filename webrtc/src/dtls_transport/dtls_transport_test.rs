@@ -1,5 +1,6 @@
 use ice::mdns::MulticastDnsMode;
 use ice::network_type::NetworkType;
+use rcgen::KeyPair;
 use regex::Regex;
 use tokio::time::Duration;
 use waitgroup::WaitGroup;
@@ -202,3 +203,121 @@ async fn test_peer_connection_dtls_role_setting_engine_client() -> Result<()> {
 
     run_test(DTLSRole::Client).await
 }
+
+#[tokio::test]
+async fn test_dtls_transport_selected_srtp_profile() -> Result<()> {
+    let new_pc = || async {
+        let mut s = SettingEngine::default();
+        s.set_srtp_protection_profiles(vec![SrtpProtectionProfile::Srtp_Aes128_Cm_Hmac_Sha1_80]);
+        s.set_ice_multicast_dns_mode(MulticastDnsMode::Disabled);
+        s.set_network_types(vec![NetworkType::Udp4]);
+        APIBuilder::new()
+            .with_setting_engine(s)
+            .build()
+            .new_peer_connection(RTCConfiguration::default())
+            .await
+    };
+    let mut offer_pc = new_pc().await?;
+    let mut answer_pc = new_pc().await?;
+
+    assert_eq!(
+        offer_pc.dtls_transport().selected_srtp_profile().await,
+        None
+    );
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut offer_pc, &wg, RTCPeerConnectionState::Connected).await;
+    until_connection_state(&mut answer_pc, &wg, RTCPeerConnectionState::Connected).await;
+
+    signal_pair(&mut offer_pc, &mut answer_pc).await?;
+    wg.wait().await;
+
+    for pc in [&offer_pc, &answer_pc] {
+        assert_eq!(
+            pc.dtls_transport().selected_srtp_profile().await,
+            Some(SrtpProtectionProfile::Srtp_Aes128_Cm_Hmac_Sha1_80)
+        );
+    }
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dtls_transport_selected_cipher_suite() -> Result<()> {
+    let api = APIBuilder::new().build();
+    let mut offer_pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+    let mut answer_pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    assert_eq!(
+        offer_pc.dtls_transport().selected_cipher_suite().await,
+        None
+    );
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut offer_pc, &wg, RTCPeerConnectionState::Connected).await;
+    until_connection_state(&mut answer_pc, &wg, RTCPeerConnectionState::Connected).await;
+
+    signal_pair(&mut offer_pc, &mut answer_pc).await?;
+    wg.wait().await;
+
+    let offer_cipher_suite = offer_pc.dtls_transport().selected_cipher_suite().await;
+    assert!(offer_cipher_suite.is_some());
+    assert_eq!(
+        answer_pc.dtls_transport().selected_cipher_suite().await,
+        offer_cipher_suite
+    );
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dtls_transport_get_remote_certificate() -> Result<()> {
+    let kp = KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256)?;
+    let certificate = RTCCertificate::from_key_pair(kp)?;
+    let expected: Vec<Bytes> = certificate
+        .dtls_certificate
+        .certificate
+        .iter()
+        .map(|c| Bytes::from(c.0.clone()))
+        .collect();
+
+    let api = APIBuilder::new().build();
+    let mut offer_pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+    let mut answer_pc = api
+        .new_peer_connection(RTCConfiguration {
+            certificates: vec![certificate],
+            ..Default::default()
+        })
+        .await?;
+
+    assert!(offer_pc
+        .dtls_transport()
+        .get_remote_certificate()
+        .await
+        .is_empty());
+    assert!(offer_pc
+        .dtls_transport()
+        .get_remote_certificate_chain()
+        .await
+        .is_empty());
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut offer_pc, &wg, RTCPeerConnectionState::Connected).await;
+    signal_pair(&mut offer_pc, &mut answer_pc).await?;
+    wg.wait().await;
+
+    let dtls_transport = offer_pc.dtls_transport();
+    assert_eq!(
+        dtls_transport.get_remote_certificate_chain().await,
+        expected
+    );
+    assert_eq!(dtls_transport.get_remote_certificate().await, expected[0]);
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use arc_swap::ArcSwapOption;
 use bytes::Bytes;
+use dtls::cipher_suite::CipherSuiteId;
 use dtls::config::ClientAuthType;
 use dtls::conn::DTLSConn;
 use dtls::extension::extension_use_srtp::SrtpProtectionProfile;
@@ -64,7 +65,7 @@ pub struct RTCDtlsTransport {
     pub(crate) setting_engine: Arc<SettingEngine>,
 
     pub(crate) remote_parameters: Mutex<DTLSParameters>,
-    pub(crate) remote_certificate: Mutex<Bytes>,
+    pub(crate) remote_certificates: Mutex<Vec<Bytes>>,
     pub(crate) state: AtomicU8, //DTLSTransportState,
     pub(crate) srtp_protection_profile: Mutex<ProtectionProfile>,
     pub(crate) on_state_change_handler: ArcSwapOption<Mutex<OnDTLSTransportStateChangeHdlrFn>>,
@@ -165,11 +166,35 @@ impl RTCDtlsTransport {
         })
     }
 
-    /// get_remote_certificate returns the certificate chain in use by the remote side
-    /// returns an empty list prior to selection of the remote certificate
+    /// get_remote_certificate returns the DER encoded certificate in use by the remote side
+    /// returns empty bytes prior to selection of the remote certificate
     pub async fn get_remote_certificate(&self) -> Bytes {
-        let remote_certificate = self.remote_certificate.lock().await;
-        remote_certificate.clone()
+        let remote_certificates = self.remote_certificates.lock().await;
+        remote_certificates.first().cloned().unwrap_or_default()
+    }
+
+    /// get_remote_certificate_chain returns the DER encoded certificate chain presented by the
+    /// remote side during the handshake, its own certificate first. This allows pinning beyond
+    /// the fingerprints in the remote description.
+    /// returns an empty list prior to selection of the remote certificate
+    pub async fn get_remote_certificate_chain(&self) -> Vec<Bytes> {
+        let remote_certificates = self.remote_certificates.lock().await;
+        remote_certificates.clone()
+    }
+
+    /// selected_srtp_profile returns the SRTP protection profile negotiated by the DTLS handshake
+    /// returns None until the handshake has completed successfully
+    pub async fn selected_srtp_profile(&self) -> Option<SrtpProtectionProfile> {
+        self.conn()
+            .await
+            .map(|conn| conn.selected_srtpprotection_profile())
+    }
+
+    /// selected_cipher_suite returns the cipher suite negotiated by the DTLS handshake
+    /// returns None until the handshake has completed successfully
+    pub async fn selected_cipher_suite(&self) -> Option<CipherSuiteId> {
+        let conn = self.conn().await?;
+        conn.selected_cipher_suite().await
     }
 
     pub(crate) async fn start_srtp(&self) -> Result<()> {
@@ -346,15 +371,7 @@ impl RTCDtlsTransport {
             self.role().await,
             dtls::config::Config {
                 certificates: vec![certificate],
-                srtp_protection_profiles: if !self
-                    .setting_engine
-                    .srtp_protection_profiles
-                    .is_empty()
-                {
-                    self.setting_engine.srtp_protection_profiles.clone()
-                } else {
-                    default_srtp_protection_profiles()
-                },
+                srtp_protection_profiles: self.srtp_protection_profiles(),
                 client_auth: ClientAuthType::RequireAnyClientCert,
                 insecure_skip_verify: true,
                 insecure_verification: self.setting_engine.allow_insecure_verification_algorithm,
@@ -363,6 +380,23 @@ impl RTCDtlsTransport {
         ))
     }
 
+    fn srtp_protection_profiles(&self) -> Vec<SrtpProtectionProfile> {
+        let mut profiles = if !self.setting_engine.srtp_protection_profiles.is_empty() {
+            self.setting_engine.srtp_protection_profiles.clone()
+        } else if self.setting_engine.srtp_null_cipher {
+            vec![SrtpProtectionProfile::Srtp_Null_Hmac_Sha1_80]
+        } else {
+            default_srtp_protection_profiles()
+        };
+
+        // The null cipher must be enabled explicitly, whatever the profiles set
+        if !self.setting_engine.srtp_null_cipher {
+            profiles.retain(|p| *p != SrtpProtectionProfile::Srtp_Null_Hmac_Sha1_80);
+        }
+
+        profiles
+    }
+
     /// start DTLS transport negotiation with the parameters of the remote DTLS transport
     pub async fn start(&self, remote_parameters: DTLSParameters) -> Result<()> {
         let dtls_conn_result = if let Some(dtls_endpoint) =
@@ -416,6 +450,12 @@ impl RTCDtlsTransport {
                 dtls::extension::extension_use_srtp::SrtpProtectionProfile::Srtp_Aes128_Cm_Hmac_Sha1_80 => {
                     srtp::protection_profile::ProtectionProfile::Aes128CmHmacSha1_80
                 }
+                dtls::extension::extension_use_srtp::SrtpProtectionProfile::Srtp_Null_Hmac_Sha1_80 => {
+                    log::warn!(
+                        "SRTP null cipher negotiated: media is NOT encrypted. This must only be used for testing"
+                    );
+                    srtp::protection_profile::ProtectionProfile::NullHmacSha1_80
+                }
                 _ => {
                     if let Err(err) = dtls_conn.close().await {
                         log::error!("{}", err);
@@ -439,8 +479,8 @@ impl RTCDtlsTransport {
         }
 
         {
-            let mut remote_certificate = self.remote_certificate.lock().await;
-            *remote_certificate = Bytes::from(remote_certs[0].clone());
+            let mut remote_certificates = self.remote_certificates.lock().await;
+            *remote_certificates = remote_certs.iter().cloned().map(Bytes::from).collect();
         }
 
         if !self
@@ -469,6 +509,53 @@ impl RTCDtlsTransport {
     /// stops and closes the DTLSTransport object.
     pub async fn stop(&self) -> Result<()> {
         // Try closing everything and collect the errors
+        let close_errs = self.close_sessions_and_conn().await;
+
+        self.state_change(RTCDtlsTransportState::Closed).await;
+
+        flatten_errs(close_errs)
+    }
+
+    /// reset closes what a previous start set up and takes the transport back to the new state,
+    /// so that it can be started again with other remote parameters.
+    pub(crate) async fn reset(&self) -> Result<()> {
+        let close_errs = self.close_sessions_and_conn().await;
+
+        {
+            let mut conn = self.conn.lock().await;
+            conn.take();
+        }
+        {
+            let mut srtp_endpoint = self.srtp_endpoint.lock().await;
+            srtp_endpoint.take();
+        }
+        {
+            let mut srtcp_endpoint = self.srtcp_endpoint.lock().await;
+            srtcp_endpoint.take();
+        }
+        {
+            let mut remote_certificates = self.remote_certificates.lock().await;
+            remote_certificates.clear();
+        }
+        {
+            let mut remote_parameters = self.remote_parameters.lock().await;
+            *remote_parameters = DTLSParameters::default();
+        }
+        {
+            let (srtp_ready_tx, srtp_ready_rx) = mpsc::channel(1);
+            let mut tx = self.srtp_ready_tx.lock().await;
+            let mut rx = self.srtp_ready_rx.lock().await;
+            *tx = Some(srtp_ready_tx);
+            *rx = Some(srtp_ready_rx);
+            self.srtp_ready_signal.store(false, Ordering::SeqCst);
+        }
+
+        self.state_change(RTCDtlsTransportState::New).await;
+
+        flatten_errs(close_errs)
+    }
+
+    async fn close_sessions_and_conn(&self) -> Vec<Error> {
         let mut close_errs: Vec<Error> = vec![];
         {
             let srtp_session = {
@@ -531,9 +618,7 @@ impl RTCDtlsTransport {
             }
         }
 
-        self.state_change(RTCDtlsTransportState::Closed).await;
-
-        flatten_errs(close_errs)
+        close_errs
     }
 
     pub(crate) async fn validate_fingerprint(&self, remote_cert: &[u8]) -> Result<()> {
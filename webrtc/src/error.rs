@@ -30,6 +30,11 @@ pub enum Error {
     #[error("data channel not open")]
     ErrDataChannelNotOpen,
 
+    /// ErrDataChannelMessageTooLarge indicates that a message passed to a DataChannel's send
+    /// methods is larger than the max_message_size of its SCTPTransport.
+    #[error("data channel message is larger than the max message size")]
+    ErrDataChannelMessageTooLarge,
+
     /// ErrCertificateExpired indicates that an x509 certificate has expired.
     #[error("x509Cert expired")]
     ErrCertificateExpired,
@@ -194,6 +199,11 @@ pub enum Error {
     #[error("unable to populate media section, RTPSender created with no codecs")]
     ErrSenderWithNoCodecs,
 
+    /// ErrNoCommonCodec indicates that a remote media section offered no codec supported by the MediaEngine,
+    /// the media section is rejected in the answer.
+    #[error("no common codec for media section, rejecting it")]
+    ErrNoCommonCodec,
+
     /// ErrRTPSenderNewTrackHasIncorrectKind indicates that the new track is of a different kind than the previous/original
     #[error("new track must be of the same kind as previous")]
     ErrRTPSenderNewTrackHasIncorrectKind,
@@ -210,6 +220,16 @@ pub enum Error {
     #[error("failed to unbind TrackLocal from PeerConnection")]
     ErrUnbindFailed,
 
+    /// ErrRawRtpTapDisabled indicates that a raw RTP handler was set without enabling the tap
+    /// in the SettingEngine
+    #[error("the raw RTP tap is not enabled in the SettingEngine")]
+    ErrRawRtpTapDisabled,
+
+    /// ErrWouldBlock indicates that a packet couldn't be written without waiting for the
+    /// transport, and was dropped instead
+    #[error("writing the packet would block")]
+    ErrWouldBlock,
+
     /// ErrNoPayloaderForCodec indicates that the requested codec does not have a payloader
     #[error("the requested codec does not have a payloader")]
     ErrNoPayloaderForCodec,
@@ -282,8 +302,16 @@ pub enum Error {
     ErrPeerConnSDPTypeInvalidValueSetLocalDescription,
     #[error("remoteDescription contained media section without mid value")]
     ErrPeerConnRemoteDescriptionWithoutMidValue,
+    #[error("remoteDescription contains more media sections than allowed")]
+    ErrPeerConnTooManyMediaSections,
+    #[error("remote answer must choose a DTLS role, setup:actpass is only valid in an offer")]
+    ErrPeerConnRemoteAnswerDtlsRoleActpass,
+    #[error("remote offer takes the DTLS role the local answer is set to")]
+    ErrPeerConnDtlsRoleConflict,
     #[error("remoteDescription has not been set yet")]
     ErrPeerConnRemoteDescriptionNil,
+    #[error("localDescription has not been set yet")]
+    ErrPeerConnLocalDescriptionNil,
     #[error("single media section has an explicit SSRC")]
     ErrPeerConnSingleMediaSectionHasExplicitSSRC,
     #[error("could not add transceiver for remote SSRC")]
@@ -377,10 +405,18 @@ pub enum Error {
 
     #[error("DataChannel is not opened")]
     ErrClosedPipe,
+    /// ErrTrackClosed indicates that a TrackRemote can't be read from anymore because its
+    /// RTPReceiver or the underlying SRTP stream was closed.
+    #[error("track closed")]
+    ErrTrackClosed,
     #[error("Interceptor is not bind")]
     ErrInterceptorNotBind,
     #[error("excessive retries in CreateOffer")]
     ErrExcessiveRetries,
+    /// ErrCreateCancelled indicates that generating an offer or answer was aborted
+    /// through its RTCCancellationToken.
+    #[error("offer or answer creation was cancelled")]
+    ErrCreateCancelled,
 
     #[error("not long enough to be a RTP Packet")]
     ErrRTPTooShort,
@@ -416,6 +452,8 @@ pub enum Error {
     ParseInt(#[from] ParseIntError),
     #[error("parse url: {0}")]
     ParseUrl(#[from] url::ParseError),
+    #[error("json: {0}")]
+    Json(String),
 
     /// Error parsing a given PEM string.
     #[error("invalid PEM: {0}")]
@@ -429,6 +467,13 @@ pub enum Error {
 pub type OnErrorHdlrFn =
     Box<dyn (FnMut(Error) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>) + Send + Sync>;
 
+// serde_json::Error isn't PartialEq, so keep its message only.
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e.to_string())
+    }
+}
+
 // Because Tokio SendError is parameterized, we sadly lose the backtrace.
 impl<T> From<MpscSendError<T>> for Error {
     fn from(e: MpscSendError<T>) -> Self {
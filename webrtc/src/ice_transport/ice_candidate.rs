@@ -6,7 +6,8 @@ use ice::candidate::candidate_host::CandidateHostConfig;
 use ice::candidate::candidate_peer_reflexive::CandidatePeerReflexiveConfig;
 use ice::candidate::candidate_relay::CandidateRelayConfig;
 use ice::candidate::candidate_server_reflexive::CandidateServerReflexiveConfig;
-use ice::candidate::Candidate;
+use ice::candidate::{Candidate, RelayProtocol};
+use ice::tcp_type::TcpType;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
@@ -27,6 +28,8 @@ pub struct RTCIceCandidate {
     pub related_address: String,
     pub related_port: u16,
     pub tcp_type: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub relay_protocol: String,
 }
 
 /// Conversion for ice_candidates
@@ -56,6 +59,11 @@ impl From<&Arc<dyn Candidate + Send + Sync>> for RTCIceCandidate {
             component: c.component(),
             typ,
             tcp_type: c.tcp_type().to_string(),
+            // Non-relay candidates report an empty relay protocol, as in the ICE stats.
+            relay_protocol: match c.relay_protocol() {
+                RelayProtocol::Unspecified => String::new(),
+                relay_protocol => relay_protocol.to_string(),
+            },
             related_address,
             related_port,
         }
@@ -74,12 +82,11 @@ impl RTCIceCandidate {
                         address: self.address.clone(),
                         port: self.port,
                         component: self.component,
-                        //tcp_type: ice.NewTCPType(c.TCPType),
                         foundation: self.foundation.clone(),
                         priority: self.priority,
                         ..Default::default()
                     },
-                    ..Default::default()
+                    tcp_type: TcpType::from(self.tcp_type.as_str()),
                 };
                 config.new_candidate_host()?
             }
@@ -131,7 +138,9 @@ impl RTCIceCandidate {
                     },
                     rel_addr: self.related_address.clone(),
                     rel_port: self.related_port,
+                    relay_protocol: RelayProtocol::from(self.relay_protocol.as_str()),
                     relay_client: None, //TODO?
+                    ..Default::default()
                 };
                 config.new_candidate_relay()?
             }
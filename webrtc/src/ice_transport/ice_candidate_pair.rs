@@ -1,9 +1,13 @@
 use std::fmt;
 
+use serde::Serialize;
+
+use crate::error::Result;
 use crate::ice_transport::ice_candidate::*;
 
 /// ICECandidatePair represents an ICE Candidate pair
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RTCIceCandidatePair {
     stats_id: String,
     local: RTCIceCandidate,
@@ -31,4 +35,21 @@ impl RTCIceCandidatePair {
             remote,
         }
     }
+
+    /// local returns the local candidate of the pair
+    pub fn local(&self) -> &RTCIceCandidate {
+        &self.local
+    }
+
+    /// remote returns the remote candidate of the pair
+    pub fn remote(&self) -> &RTCIceCandidate {
+        &self.remote
+    }
+
+    /// to_json returns the pair as a JSON object, with its `statsId` and the `local` and
+    /// `remote` candidates. The state and round trip time of the pair are in its stats, see
+    /// `RTCIceTransport::get_selected_candidate_pair_stats`.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
 }
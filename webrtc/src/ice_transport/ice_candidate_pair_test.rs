@@ -0,0 +1,84 @@
+use super::ice_candidate::*;
+use super::ice_candidate_pair::*;
+use crate::error::Result;
+use crate::ice_transport::ice_candidate_type::RTCIceCandidateType;
+use crate::ice_transport::ice_protocol::RTCIceProtocol;
+
+#[test]
+fn test_ice_candidate_pair_to_json() -> Result<()> {
+    let local = RTCIceCandidate {
+        stats_id: "local-id".to_owned(),
+        foundation: "1052353102".to_owned(),
+        priority: 2130706431,
+        address: "192.168.1.2".to_owned(),
+        protocol: RTCIceProtocol::Udp,
+        port: 50000,
+        typ: RTCIceCandidateType::Host,
+        component: 1,
+        ..Default::default()
+    };
+    let remote = RTCIceCandidate {
+        stats_id: "remote-id".to_owned(),
+        foundation: "4207374051".to_owned(),
+        priority: 1694498815,
+        address: "203.0.113.7".to_owned(),
+        protocol: RTCIceProtocol::Udp,
+        port: 60000,
+        typ: RTCIceCandidateType::Srflx,
+        component: 1,
+        related_address: "10.0.0.7".to_owned(),
+        related_port: 60000,
+        ..Default::default()
+    };
+    let pair = RTCIceCandidatePair::new(local, remote);
+
+    let json: serde_json::Value = serde_json::from_str(&pair.to_json()?).unwrap();
+    assert_eq!(json["statsId"], "local-id-remote-id");
+    // The candidates keep the field names RTCIceCandidate has always been serialized with.
+    assert_eq!(json["local"]["stats_id"], "local-id");
+    assert_eq!(json["local"]["foundation"], "1052353102");
+    assert_eq!(json["local"]["typ"], "host");
+    assert_eq!(json["local"]["address"], "192.168.1.2");
+    assert_eq!(json["local"]["port"], 50000);
+    assert_eq!(json["remote"]["foundation"], "4207374051");
+    assert_eq!(json["remote"]["typ"], "srflx");
+    assert_eq!(json["remote"]["related_address"], "10.0.0.7");
+    assert_eq!(json["remote"]["related_port"], 60000);
+    assert_eq!(json["remote"]["tcp_type"], "");
+
+    Ok(())
+}
+
+#[test]
+fn test_ice_candidate_relay_protocol_json() -> Result<()> {
+    // Candidates serialized before relay_protocol existed still deserialize.
+    let candidate: RTCIceCandidate = serde_json::from_str(
+        r#"{
+            "stats_id": "id",
+            "foundation": "1052353102",
+            "priority": 2130706431,
+            "address": "192.168.1.2",
+            "protocol": "udp",
+            "port": 50000,
+            "typ": "host",
+            "component": 1,
+            "related_address": "",
+            "related_port": 0,
+            "tcp_type": ""
+        }"#,
+    )?;
+    assert_eq!(candidate.relay_protocol, "");
+    let json: serde_json::Value = serde_json::to_value(&candidate)?;
+    assert!(json.get("relay_protocol").is_none());
+
+    let relay = RTCIceCandidate {
+        typ: RTCIceCandidateType::Relay,
+        relay_protocol: "tcp".to_owned(),
+        ..candidate
+    };
+    let json = serde_json::to_string(&relay)?;
+    assert!(json.contains(r#""relay_protocol":"tcp""#));
+    assert_eq!(serde_json::from_str::<RTCIceCandidate>(&json)?, relay);
+
+    Ok(())
+}
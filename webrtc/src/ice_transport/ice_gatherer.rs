@@ -91,8 +91,12 @@ impl RTCIceGatherer {
         }
 
         let mut candidate_types = vec![];
+        let mut urls = self.validated_servers.clone();
         if self.setting_engine.candidates.ice_lite {
+            // Lite agents only provide host candidates, so STUN/TURN servers are never contacted.
+            // RFC 8445 S2.5
             candidate_types.push(ice::candidate::CandidateType::Host);
+            urls.clear();
         } else if self.gather_policy == RTCIceTransportPolicy::Relay {
             candidate_types.push(ice::candidate::CandidateType::Relay);
         }
@@ -108,10 +112,15 @@ impl RTCIceGatherer {
         let mut config = ice::agent::agent_config::AgentConfig {
             udp_network: self.setting_engine.udp_network.clone(),
             lite: self.setting_engine.candidates.ice_lite,
-            urls: self.validated_servers.clone(),
+            urls,
             disconnected_timeout: self.setting_engine.timeout.ice_disconnected_timeout,
             failed_timeout: self.setting_engine.timeout.ice_failed_timeout,
             keepalive_interval: self.setting_engine.timeout.ice_keepalive_interval,
+            check_interval: self
+                .setting_engine
+                .timeout
+                .ice_check_interval
+                .unwrap_or_default(),
             candidate_types,
             host_acceptance_min_wait: self.setting_engine.timeout.ice_host_acceptance_min_wait,
             srflx_acceptance_min_wait: self.setting_engine.timeout.ice_srflx_acceptance_min_wait,
@@ -130,6 +139,11 @@ impl RTCIceGatherer {
                 .clone(),
             local_ufrag: self.setting_engine.candidates.username_fragment.clone(),
             local_pwd: self.setting_engine.candidates.password.clone(),
+            ipv6_flow_label: self.setting_engine.ipv6_flow_label,
+            max_concurrent_gather_probes: self
+                .setting_engine
+                .candidates
+                .max_concurrent_gather_probes,
             //TODO: TCPMux:                 self.setting_engine.iceTCPMux,
             //TODO: ProxyDialer:            self.setting_engine.iceProxyDialer,
             ..Default::default()
@@ -203,6 +217,16 @@ impl RTCIceGatherer {
         Ok(())
     }
 
+    /// stop_gathering completes gathering with the candidates gathered so far, firing the
+    /// gathering complete handlers right away instead of waiting for slow server reflexive and
+    /// relay probes. Candidates found afterwards are discarded. It does nothing unless the
+    /// ICEGatherer is gathering.
+    pub async fn stop_gathering(&self) {
+        if let Some(agent) = self.get_agent().await {
+            agent.stop_gathering().await;
+        }
+    }
+
     /// Close prunes all local candidates, and closes the ports.
     pub async fn close(&self) -> Result<()> {
         self.set_state(RTCIceGathererState::Closed).await;
@@ -219,6 +243,27 @@ impl RTCIceGatherer {
         Ok(())
     }
 
+    /// reset closes the ICE agent and goes back to the new state, so that a fresh agent with new
+    /// local credentials is created when it's needed again.
+    pub(crate) async fn reset(&self) -> Result<()> {
+        let agent = {
+            let mut agent_opt = self.agent.lock().await;
+            agent_opt.take()
+        };
+
+        if let Some(agent) = agent {
+            // Whatever the closing agent reports no longer concerns the gatherer or transport
+            agent.on_candidate(Box::new(|_| Box::pin(async {})));
+            agent.on_connection_state_change(Box::new(|_| Box::pin(async {})));
+            agent.on_selected_candidate_pair_change(Box::new(|_, _| Box::pin(async {})));
+            agent.close().await?;
+        }
+
+        self.set_state(RTCIceGathererState::New).await;
+
+        Ok(())
+    }
+
     /// get_local_parameters returns the ICE parameters of the ICEGatherer.
     pub async fn get_local_parameters(&self) -> Result<RTCIceParameters> {
         self.create_agent().await?;
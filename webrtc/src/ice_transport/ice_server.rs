@@ -1,3 +1,5 @@
+use std::net::{IpAddr, SocketAddr};
+
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
@@ -11,6 +13,10 @@ pub struct RTCIceServer {
     pub username: String,
     pub credential: String,
     pub credential_type: RTCIceCredentialType,
+    /// An already-resolved IP address of the server, used with the port of each URL instead of
+    /// looking up its host. For environments without DNS or to pin the server address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_ip: Option<IpAddr>,
 }
 
 impl RTCIceServer {
@@ -28,6 +34,7 @@ impl RTCIceServer {
 
         for url_str in &self.urls {
             let mut url = self.parse_url(url_str)?;
+            url.resolved_addr = self.resolved_ip.map(|ip| SocketAddr::new(ip, url.port));
             if url.scheme == ice::url::SchemeType::Turn || url.scheme == ice::url::SchemeType::Turns
             {
                 // https://www.w3.org/TR/webrtc/#set-the-configuration (step #11.3.2)
@@ -73,6 +80,7 @@ mod test {
                     username: "unittest".to_owned(),
                     credential: "placeholder".to_owned(),
                     credential_type: RTCIceCredentialType::Password,
+                    ..Default::default()
                 },
                 true,
             ),
@@ -82,6 +90,7 @@ mod test {
                     username: "unittest".to_owned(),
                     credential: "placeholder".to_owned(),
                     credential_type: RTCIceCredentialType::Password,
+                    ..Default::default()
                 },
                 true,
             ),
@@ -102,6 +111,54 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_ice_server_resolved_ip() {
+        let ice_server = RTCIceServer {
+            urls: vec![
+                "stun:stun.webrtc.invalid".to_owned(),
+                "turn:turn.webrtc.invalid:5349?transport=udp".to_owned(),
+            ],
+            username: "unittest".to_owned(),
+            credential: "placeholder".to_owned(),
+            credential_type: RTCIceCredentialType::Password,
+            resolved_ip: Some(IpAddr::from([192, 0, 2, 1])),
+        };
+
+        let resolved: Vec<_> = ice_server
+            .urls()
+            .unwrap()
+            .into_iter()
+            .map(|url| url.resolved_addr)
+            .collect();
+        assert_eq!(
+            resolved,
+            vec![
+                Some(SocketAddr::from(([192, 0, 2, 1], 3478))),
+                Some(SocketAddr::from(([192, 0, 2, 1], 5349))),
+            ]
+        );
+
+        let json = serde_json::to_string(&ice_server).unwrap();
+        assert!(json.contains(r#""resolved_ip":"192.0.2.1""#));
+        let decoded: RTCIceServer = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.resolved_ip, ice_server.resolved_ip);
+    }
+
+    #[test]
+    fn test_ice_server_json_without_resolved_ip() {
+        let ice_server = RTCIceServer {
+            urls: vec!["stun:stun.l.google.com:19302".to_owned()],
+            ..Default::default()
+        };
+
+        // Servers without a resolved IP keep the JSON shape they had before it existed.
+        let json = serde_json::to_string(&ice_server).unwrap();
+        assert!(!json.contains("resolved_ip"));
+        let decoded: RTCIceServer = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.urls, ice_server.urls);
+        assert_eq!(decoded.resolved_ip, None);
+    }
+
     #[test]
     fn test_ice_server_validate_failure() {
         let tests = vec![
@@ -118,6 +175,7 @@ mod test {
                     username: "unittest".to_owned(),
                     credential: String::new(),
                     credential_type: RTCIceCredentialType::Password,
+                    ..Default::default()
                 },
                 Error::ErrNoTurnCredentials,
             ),
@@ -127,6 +185,7 @@ mod test {
                     username: "unittest".to_owned(),
                     credential: String::new(),
                     credential_type: RTCIceCredentialType::Oauth,
+                    ..Default::default()
                 },
                 Error::ErrNoTurnCredentials,
             ),
@@ -136,6 +195,7 @@ mod test {
                     username: "unittest".to_owned(),
                     credential: String::new(),
                     credential_type: RTCIceCredentialType::Unspecified,
+                    ..Default::default()
                 },
                 Error::ErrNoTurnCredentials,
             ),
@@ -158,6 +218,7 @@ mod test {
                 username: "unittest".to_owned(),
                 credential: String::new(),
                 credential_type: RTCIceCredentialType::Oauth,
+                ..Default::default()
             },
             ice::Error::ErrStunQuery,
         )];
@@ -1,6 +1,6 @@
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicBool, AtomicU32};
 
-use tokio::time::Duration;
+use util::vnet::chunk::Chunk;
 use waitgroup::WaitGroup;
 
 use super::*;
@@ -10,7 +10,7 @@ use crate::error::Result;
 use crate::ice_transport::ice_connection_state::RTCIceConnectionState;
 use crate::peer_connection::peer_connection_state::RTCPeerConnectionState;
 use crate::peer_connection::peer_connection_test::{
-    close_pair_now, new_pair, signal_pair, until_connection_state,
+    close_pair_now, create_vnet_pair_with, new_pair, signal_pair, until_connection_state,
 };
 
 #[tokio::test]
@@ -121,3 +121,148 @@ async fn test_ice_transport_get_selected_candidate_pair() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_ice_transport_selected_candidate_pair_to_json() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    let (mut offerer, mut answerer) = new_pair(&api).await?;
+
+    let peer_connection_connected = WaitGroup::new();
+    until_connection_state(
+        &mut offerer,
+        &peer_connection_connected,
+        RTCPeerConnectionState::Connected,
+    )
+    .await;
+    until_connection_state(
+        &mut answerer,
+        &peer_connection_connected,
+        RTCPeerConnectionState::Connected,
+    )
+    .await;
+
+    let dtls_transport = offerer.sctp().transport();
+    let ice_transport = dtls_transport.ice_transport();
+    assert!(ice_transport
+        .get_selected_candidate_pair_stats()
+        .await
+        .is_none());
+
+    signal_pair(&mut offerer, &mut answerer).await?;
+
+    peer_connection_connected.wait().await;
+
+    let pair = ice_transport
+        .get_selected_candidate_pair()
+        .await
+        .expect("no selected candidate pair");
+    let pair_json: serde_json::Value = serde_json::from_str(&pair.to_json()?).unwrap();
+    for candidate in [&pair_json["local"], &pair_json["remote"]] {
+        assert!(!candidate["foundation"].as_str().unwrap().is_empty());
+        assert_eq!(candidate["typ"], "host");
+        assert!(candidate.get("relay_protocol").is_none());
+    }
+
+    let stats = ice_transport
+        .get_selected_candidate_pair_stats()
+        .await
+        .expect("no selected candidate pair stats");
+    let stats_json: serde_json::Value = serde_json::from_str(&stats.to_json()?).unwrap();
+    assert_eq!(stats_json["type"], "candidate-pair");
+    assert_eq!(stats_json["state"], "succeeded");
+    assert_eq!(
+        stats_json["localCandidateId"],
+        pair_json["local"]["stats_id"]
+    );
+    assert_eq!(
+        stats_json["remoteCandidateId"],
+        pair_json["remote"]["stats_id"]
+    );
+    assert!(stats_json["currentRoundTripTime"].is_number());
+
+    close_pair_now(&offerer, &answerer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ice_transport_try_reconnect() -> Result<()> {
+    // Keepalives are too slow to recover on their own within the test
+    let (mut pc_offer, mut pc_answer, wan) = create_vnet_pair_with(|s| {
+        s.set_ice_timeouts(
+            Some(Duration::from_secs(1)),
+            Some(Duration::from_secs(30)),
+            Some(Duration::from_secs(30)),
+        );
+    })
+    .await?;
+
+    let drop_all = Arc::new(AtomicBool::new(false));
+    {
+        let drop_all = Arc::clone(&drop_all);
+        let w = wan.lock().await;
+        w.add_chunk_filter(Box::new(move |_: &(dyn Chunk + Send + Sync)| {
+            !drop_all.load(Ordering::SeqCst)
+        }))
+        .await;
+    }
+
+    let peer_connection_connected = WaitGroup::new();
+    until_connection_state(
+        &mut pc_offer,
+        &peer_connection_connected,
+        RTCPeerConnectionState::Connected,
+    )
+    .await;
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    peer_connection_connected.wait().await;
+
+    let dtls_transport = pc_offer.sctp().transport();
+    let ice_transport = dtls_transport.ice_transport();
+    let agent = ice_transport.gatherer.get_agent().await.unwrap();
+    let candidates_before: Vec<String> = agent
+        .get_local_candidates()
+        .await?
+        .iter()
+        .map(|c| c.id())
+        .collect();
+    let pair_before = ice_transport.get_selected_candidate_pair().await;
+
+    // A brief outage takes the transport to disconnected
+    drop_all.store(true, Ordering::SeqCst);
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while ice_transport.state() != RTCIceTransportState::Disconnected {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("transport never went disconnected");
+    drop_all.store(false, Ordering::SeqCst);
+
+    assert!(ice_transport.try_reconnect(Duration::from_secs(5)).await?);
+    assert_eq!(ice_transport.state(), RTCIceTransportState::Connected);
+
+    // on the candidates and pair it already had
+    let candidates_after: Vec<String> = agent
+        .get_local_candidates()
+        .await?
+        .iter()
+        .map(|c| c.id())
+        .collect();
+    assert_eq!(candidates_before, candidates_after);
+    assert_eq!(
+        pair_before,
+        ice_transport.get_selected_candidate_pair().await
+    );
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(())
+}
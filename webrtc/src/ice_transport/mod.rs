@@ -1,6 +1,6 @@
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use arc_swap::ArcSwapOption;
@@ -11,6 +11,7 @@ use ice_candidate_pair::RTCIceCandidatePair;
 use ice_gatherer::RTCIceGatherer;
 use ice_role::RTCIceRole;
 use tokio::sync::{mpsc, Mutex};
+use tokio::time::{Duration, Instant};
 use util::Conn;
 
 use crate::error::{flatten_errs, Error, Result};
@@ -20,9 +21,12 @@ use crate::mux::endpoint::Endpoint;
 use crate::mux::mux_func::MatchFunc;
 use crate::mux::{Config, Mux};
 use crate::stats::stats_collector::StatsCollector;
-use crate::stats::ICETransportStats;
 use crate::stats::StatsReportType::Transport;
+use crate::stats::{ICECandidatePairStats, ICETransportStats};
+use crate::RECEIVE_MTU;
 
+#[cfg(test)]
+mod ice_candidate_pair_test;
 #[cfg(test)]
 mod ice_transport_test;
 
@@ -40,6 +44,11 @@ pub mod ice_role;
 pub mod ice_server;
 pub mod ice_transport_state;
 
+/// The smallest datagram the path MTU probe starts from, one every IPv4 host can reassemble.
+const PATH_MTU_PROBE_MIN: usize = 508;
+/// How often try_reconnect repeats its consent check while waiting to reconnect
+const RECONNECT_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
 pub type OnConnectionStateChangeHdlrFn = Box<
     dyn (FnMut(RTCIceTransportState) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>)
         + Send
@@ -69,6 +78,7 @@ pub struct RTCIceTransport {
     on_selected_candidate_pair_change_handler:
         Arc<ArcSwapOption<Mutex<OnSelectedCandidatePairChangeHdlrFn>>>,
     state: Arc<AtomicU8>, // ICETransportState
+    pub(crate) path_mtu: Arc<AtomicUsize>,
     internal: Mutex<ICETransportInternal>,
 }
 
@@ -95,6 +105,32 @@ impl RTCIceTransport {
         None
     }
 
+    /// get_selected_candidate_pair_stats returns the stats of the selected candidate pair, with
+    /// its state and round trip time, if there is a selected pair.
+    pub async fn get_selected_candidate_pair_stats(&self) -> Option<ICECandidatePairStats> {
+        let agent = self.gatherer.get_agent().await?;
+        let ice_pair = agent.get_selected_candidate_pair()?;
+        let (local_id, remote_id) = (ice_pair.local.id(), ice_pair.remote.id());
+
+        agent
+            .get_candidate_pairs_stats()
+            .await
+            .into_iter()
+            .find(|stats| {
+                stats.local_candidate_id == local_id && stats.remote_candidate_id == remote_id
+            })
+            .map(ICECandidatePairStats::from)
+    }
+
+    /// path_mtu returns the largest datagram that can be sent over the selected candidate pair,
+    /// if it was discovered with `SettingEngine::set_path_mtu_probe`.
+    pub fn path_mtu(&self) -> Option<usize> {
+        match self.path_mtu.load(Ordering::SeqCst) {
+            0 => None,
+            path_mtu => Some(path_mtu),
+        }
+    }
+
     /// Start incoming connectivity checks based on its configured role.
     pub async fn start(&self, params: &RTCIceParameters, role: Option<RTCIceRole>) -> Result<()> {
         if self.state() != RTCIceTransportState::New {
@@ -150,6 +186,10 @@ impl RTCIceTransport {
             let (cancel_tx, cancel_rx) = mpsc::channel(1);
             {
                 let mut internal = self.internal.lock().await;
+                // stop_connecting may have been called while the agent was being set up
+                if self.state() != RTCIceTransportState::New {
+                    return Err(Error::ErrICETransportNotInNew);
+                }
                 internal.role = role;
                 internal.cancel_tx = Some(cancel_tx);
             }
@@ -189,6 +229,19 @@ impl RTCIceTransport {
                 internal.mux = Some(Mux::new(config));
             }
 
+            if let Some(probe_timeout) = self.gatherer.setting_engine.path_mtu_probe_timeout {
+                let path_mtu = Arc::clone(&self.path_mtu);
+                tokio::spawn(async move {
+                    match agent
+                        .probe_path_mtu(PATH_MTU_PROBE_MIN, RECEIVE_MTU, probe_timeout)
+                        .await
+                    {
+                        Ok(mtu) => path_mtu.store(mtu, Ordering::SeqCst),
+                        Err(err) => log::warn!("Failed to probe path MTU: {}", err),
+                    }
+                });
+            }
+
             Ok(())
         } else {
             Err(Error::ErrICEAgentNotExist)
@@ -215,6 +268,43 @@ impl RTCIceTransport {
         self.gatherer.gather().await
     }
 
+    /// try_reconnect tries to bring a Disconnected transport back to Connected on the
+    /// candidate pair it already selected, sending consent checks until `timeout` elapses.
+    /// It returns false if the transport did not recover, in which case the caller should
+    /// fall back to an ICE restart (an offer created with `ice_restart` set).
+    pub async fn try_reconnect(&self, timeout: Duration) -> Result<bool> {
+        match self.state() {
+            RTCIceTransportState::Connected | RTCIceTransportState::Completed => return Ok(true),
+            RTCIceTransportState::Disconnected => {}
+            _ => return Ok(false),
+        }
+
+        let agent = if let Some(agent) = self.gatherer.get_agent().await {
+            agent
+        } else {
+            return Err(Error::ErrICEAgentNotExist);
+        };
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            agent.check_selected_pair().await?;
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(false);
+            }
+            tokio::time::sleep(RECONNECT_CHECK_INTERVAL.min(deadline - now)).await;
+
+            match self.state() {
+                RTCIceTransportState::Connected | RTCIceTransportState::Completed => {
+                    return Ok(true)
+                }
+                RTCIceTransportState::Disconnected => {}
+                _ => return Ok(false),
+            }
+        }
+    }
+
     /// Stop irreversibly stops the ICETransport.
     pub async fn stop(&self) -> Result<()> {
         self.set_state(RTCIceTransportState::Closed);
@@ -240,6 +330,44 @@ impl RTCIceTransport {
         flatten_errs(errs)
     }
 
+    /// stop_connecting cancels a start still waiting for connectivity and closes the connection
+    /// of a started transport, without closing the ICE agent. A start that hasn't got that far
+    /// yet fails, as the transport is no longer new.
+    pub(crate) async fn stop_connecting(&self) -> Result<()> {
+        let mut internal = self.internal.lock().await;
+        self.set_state(RTCIceTransportState::Closed);
+        internal.cancel_tx.take();
+        if let Some(mut mux) = internal.mux.take() {
+            mux.close().await;
+        }
+        if let Some(conn) = internal.conn.take() {
+            conn.close().await?;
+        }
+
+        Ok(())
+    }
+
+    /// reset takes the transport back to the new state with a fresh ICE agent, as an agent can
+    /// only be started once, so that it can be started again with other remote parameters.
+    pub(crate) async fn reset(&self) -> Result<()> {
+        let mut errs: Vec<Error> = vec![];
+        if let Err(err) = self.stop_connecting().await {
+            errs.push(err);
+        }
+        if let Err(err) = self.gatherer.reset().await {
+            errs.push(err);
+        }
+        self.path_mtu.store(0, Ordering::SeqCst);
+
+        self.set_state(RTCIceTransportState::New);
+        if let Some(handler) = &*self.on_connection_state_change_handler.load() {
+            let mut f = handler.lock().await;
+            f(RTCIceTransportState::New).await;
+        }
+
+        flatten_errs(errs)
+    }
+
     /// on_selected_candidate_pair_change sets a handler that is invoked when a new
     /// ICE candidate pair is selected
     pub fn on_selected_candidate_pair_change(&self, f: OnSelectedCandidatePairChangeHdlrFn) {
@@ -7,6 +7,7 @@ pub mod offer_answer_options;
 pub(crate) mod operation;
 mod peer_connection_internal;
 pub mod peer_connection_state;
+mod pending_tracks;
 pub mod policy;
 pub mod sdp;
 pub mod signaling_state;
@@ -23,8 +24,10 @@ use ::sdp::description::session::*;
 use ::sdp::util::ConnectionRole;
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
+use interceptor::tap::{make_tap_interceptor, TapDirection, TapHandlerFn, TapInterceptor};
 use interceptor::{stats, Attributes, Interceptor, RTCPWriter};
 use peer_connection_internal::*;
+use pending_tracks::PendingTracks;
 use rand::{thread_rng, Rng};
 use rcgen::KeyPair;
 use smol_str::SmolStr;
@@ -60,7 +63,9 @@ use crate::ice_transport::ice_transport_state::RTCIceTransportState;
 use crate::ice_transport::RTCIceTransport;
 use crate::peer_connection::certificate::RTCCertificate;
 use crate::peer_connection::configuration::RTCConfiguration;
-use crate::peer_connection::offer_answer_options::{RTCAnswerOptions, RTCOfferOptions};
+use crate::peer_connection::offer_answer_options::{
+    RTCAnswerOptions, RTCCancellationToken, RTCOfferOptions,
+};
 use crate::peer_connection::operation::{Operation, Operations};
 use crate::peer_connection::peer_connection_state::{
     NegotiationNeededState, RTCPeerConnectionState,
@@ -93,7 +98,7 @@ pub(crate) const SIMULCAST_PROBE_COUNT: usize = 10;
 
 /// SIMULCAST_MAX_PROBE_ROUTINES is how many active routines can be used to probe
 /// If the total amount of incoming SSRCes exceeds this new requests will be ignored
-pub(crate) const SIMULCAST_MAX_PROBE_ROUTINES: u64 = 25;
+pub const SIMULCAST_MAX_PROBE_ROUTINES: u64 = 25;
 
 pub(crate) const MEDIA_SECTION_APPLICATION: &str = "application";
 
@@ -192,6 +197,8 @@ pub struct RTCPeerConnection {
 
     interceptor: Arc<dyn Interceptor + Send + Sync>,
 
+    raw_rtp_tap: Option<Arc<TapInterceptor>>,
+
     pub(crate) internal: Arc<PeerConnectionInternal>,
 }
 
@@ -222,12 +229,23 @@ impl RTCPeerConnection {
     pub(crate) async fn new(api: &API, mut configuration: RTCConfiguration) -> Result<Self> {
         RTCPeerConnection::init_configuration(&mut configuration)?;
 
-        let (interceptor, stats_interceptor): (Arc<dyn Interceptor + Send + Sync>, _) = {
+        let (interceptor, stats_interceptor, raw_rtp_tap): (
+            Arc<dyn Interceptor + Send + Sync>,
+            _,
+            _,
+        ) = {
             let mut chain = api.interceptor_registry.build_chain("")?;
             let stats_interceptor = stats::make_stats_interceptor("");
             chain.add(stats_interceptor.clone());
+            let raw_rtp_tap = if api.setting_engine.raw_rtp_tap {
+                let tap = make_tap_interceptor();
+                chain.add(tap.clone());
+                Some(tap)
+            } else {
+                None
+            };
 
-            (Arc::new(chain), stats_interceptor)
+            (Arc::new(chain), stats_interceptor, raw_rtp_tap)
         };
 
         let weak_interceptor = Arc::downgrade(&interceptor);
@@ -250,6 +268,7 @@ impl RTCPeerConnection {
             ),
             interceptor,
             interceptor_rtcp_writer,
+            raw_rtp_tap,
             internal,
             configuration,
             idp_login_url: None,
@@ -533,7 +552,10 @@ impl RTCPeerConnection {
                                 let current_direction = get_peer_direction(m);
                                 // Step 5.3.3
                                 if current_direction
-                                    != t.direction().intersect(offered_direction.reverse())
+                                    != RTCRtpTransceiverDirection::negotiate(
+                                        t.direction(),
+                                        offered_direction,
+                                    )
                                 {
                                     return true;
                                 }
@@ -576,23 +598,47 @@ impl RTCPeerConnection {
         self.internal.ice_gatherer.on_state_change(f)
     }
 
+    /// on_raw_rtp sets a handler that is called with every RTP packet travelling in
+    /// `direction`: inbound packets after decryption, outbound packets before encryption.
+    /// The tap must have been enabled with `SettingEngine::enable_raw_rtp_tap`.
+    pub fn on_raw_rtp(&self, direction: TapDirection, f: TapHandlerFn) -> Result<()> {
+        match &self.raw_rtp_tap {
+            Some(tap) => {
+                tap.set_handler(direction, f);
+                Ok(())
+            }
+            None => Err(Error::ErrRawRtpTapDisabled),
+        }
+    }
+
     /// on_track sets an event handler which is called when remote track
     /// arrives from a remote peer.
     pub fn on_track(&self, f: OnTrackHdlrFn) {
         self.internal
             .on_track_handler
             .store(Some(Arc::new(Mutex::new(f))));
+
+        self.internal.pending_tracks.release();
     }
 
     fn do_track(
         on_track_handler: Arc<ArcSwapOption<Mutex<OnTrackHdlrFn>>>,
+        pending_tracks: Arc<PendingTracks>,
         track: Arc<TrackRemote>,
         receiver: Arc<RTCRtpReceiver>,
         transceiver: Arc<RTCRtpTransceiver>,
     ) {
         log::debug!("got new track: {:?}", track);
 
+        let track_buffer = pending_tracks.hold(&on_track_handler);
         tokio::spawn(async move {
+            if let Some(track_buffer) = track_buffer {
+                // Held tracks are released without a handler once the PeerConnection is closed
+                if !track_buffer.buffer(&track).await || on_track_handler.load().is_none() {
+                    return;
+                }
+            }
+
             if let Some(handler) = &*on_track_handler.load() {
                 let mut f = handler.lock().await;
                 f(track, receiver, transceiver).await;
@@ -727,6 +773,18 @@ impl RTCPeerConnection {
     pub async fn create_offer(
         &self,
         options: Option<RTCOfferOptions>,
+    ) -> Result<RTCSessionDescription> {
+        self.create_offer_with_cancel(options, &RTCCancellationToken::new())
+            .await
+    }
+
+    /// create_offer_with_cancel is create_offer, aborting with ErrCreateCancelled once
+    /// `cancel` is cancelled, and with ErrConnectionClosed if the PeerConnection gets closed
+    /// meanwhile.
+    pub async fn create_offer_with_cancel(
+        &self,
+        options: Option<RTCOfferOptions>,
+        cancel: &RTCCancellationToken,
     ) -> Result<RTCSessionDescription> {
         let use_identity = self.idp_login_url.is_some();
         if use_identity {
@@ -749,6 +807,12 @@ impl RTCPeerConnection {
         let mut offer;
 
         loop {
+            if cancel.is_cancelled() {
+                return Err(Error::ErrCreateCancelled);
+            } else if self.internal.is_closed.load(Ordering::SeqCst) {
+                return Err(Error::ErrConnectionClosed);
+            }
+
             // We cache current transceivers to ensure they aren't
             // mutated during offer generation. We later check if they have
             // been mutated and recompute the offer if necessary.
@@ -841,6 +905,10 @@ impl RTCPeerConnection {
                 parsed: Some(d),
             };
 
+            if cancel.is_cancelled() {
+                return Err(Error::ErrCreateCancelled);
+            }
+
             // Verify local media hasn't changed during offer
             // generation. Recompute if necessary
             if !self.internal.has_local_description_changed(&offer).await {
@@ -909,9 +977,24 @@ impl RTCPeerConnection {
 
     /// create_answer starts the PeerConnection and generates the localDescription
     pub async fn create_answer(
+        &self,
+        options: Option<RTCAnswerOptions>,
+    ) -> Result<RTCSessionDescription> {
+        self.create_answer_with_cancel(options, &RTCCancellationToken::new())
+            .await
+    }
+
+    /// create_answer_with_cancel is create_answer, aborting with ErrCreateCancelled once
+    /// `cancel` is cancelled.
+    pub async fn create_answer_with_cancel(
         &self,
         _options: Option<RTCAnswerOptions>,
+        cancel: &RTCCancellationToken,
     ) -> Result<RTCSessionDescription> {
+        if cancel.is_cancelled() {
+            return Err(Error::ErrCreateCancelled);
+        }
+
         let use_identity = self.idp_login_url.is_some();
         let remote_desc = self.remote_description().await;
         let remote_description: RTCSessionDescription;
@@ -938,8 +1021,17 @@ impl RTCPeerConnection {
         if connection_role == ConnectionRole::Unspecified {
             connection_role = DEFAULT_DTLS_ROLE_ANSWER.to_connection_role();
             if let Some(parsed) = remote_description.parsed {
-                if Self::is_lite_set(&parsed) && !self.internal.setting_engine.candidates.ice_lite {
-                    connection_role = DTLSRole::Server.to_connection_role();
+                // Take the other role if the offer picked one, else the full agent facing an
+                // ICE lite one is the controlling agent and answers as the server
+                match DTLSRole::from(&parsed) {
+                    DTLSRole::Client => connection_role = DTLSRole::Server.to_connection_role(),
+                    DTLSRole::Server => connection_role = DTLSRole::Client.to_connection_role(),
+                    _ if Self::is_lite_set(&parsed)
+                        && !self.internal.setting_engine.candidates.ice_lite =>
+                    {
+                        connection_role = DTLSRole::Server.to_connection_role();
+                    }
+                    _ => {}
                 }
             }
         }
@@ -967,6 +1059,10 @@ impl RTCPeerConnection {
             parsed: Some(d),
         };
 
+        if cancel.is_cancelled() {
+            return Err(Error::ErrCreateCancelled);
+        }
+
         {
             let mut last_answer = self.internal.last_answer.lock().await;
             *last_answer = answer.sdp.clone();
@@ -1159,9 +1255,12 @@ impl RTCPeerConnection {
                                 sd.sdp_type,
                             );
                             if next_state.is_ok() {
-                                let mut pending_remote_description =
-                                    self.internal.pending_remote_description.lock().await;
-                                *pending_remote_description = None;
+                                {
+                                    let mut pending_remote_description =
+                                        self.internal.pending_remote_description.lock().await;
+                                    *pending_remote_description = None;
+                                }
+                                self.internal.rollback_remote_offer().await;
                             }
                             next_state
                         }
@@ -1192,11 +1291,19 @@ impl RTCPeerConnection {
                     .signaling_state
                     .store(next_state as u8, Ordering::SeqCst);
                 if self.signaling_state() == RTCSignalingState::Stable {
+                    // The remote offer, if any, was answered or rolled back
+                    self.internal.remote_offer_transceivers.lock().await.clear();
+                    self.internal
+                        .remote_offer_started_transports
+                        .store(false, Ordering::SeqCst);
+
                     self.internal
                         .is_negotiation_needed
                         .store(false, Ordering::SeqCst);
                     self.internal.trigger_negotiation_needed().await;
                 }
+                #[cfg(debug_assertions)]
+                self.check_description_invariants(next_state).await;
                 self.do_signaling_state_change(next_state).await;
                 Ok(())
             }
@@ -1204,12 +1311,51 @@ impl RTCPeerConnection {
         }
     }
 
+    /// Checks that the pending descriptions agree with the signaling state, as they must for the
+    /// transitions of JSEP section 4.1.8 to be applied consistently. Only debug builds pay for
+    /// the extra locks.
+    #[cfg(debug_assertions)]
+    async fn check_description_invariants(&self, state: RTCSignalingState) {
+        let has_pending_local = self
+            .internal
+            .pending_local_description
+            .lock()
+            .await
+            .is_some();
+        let has_pending_remote = self
+            .internal
+            .pending_remote_description
+            .lock()
+            .await
+            .is_some();
+
+        let expected = match state {
+            RTCSignalingState::Stable => (false, false),
+            RTCSignalingState::HaveLocalOffer => (true, false),
+            RTCSignalingState::HaveRemoteOffer => (false, true),
+            RTCSignalingState::HaveLocalPranswer | RTCSignalingState::HaveRemotePranswer => {
+                (true, true)
+            }
+            _ => return,
+        };
+        assert_eq!(
+            (has_pending_local, has_pending_remote),
+            expected,
+            "pending (local, remote) descriptions inconsistent with signaling state {state}"
+        );
+    }
+
     /// set_local_description sets the SessionDescription of the local peer
     pub async fn set_local_description(&self, mut desc: RTCSessionDescription) -> Result<()> {
         if self.internal.is_closed.load(Ordering::SeqCst) {
             return Err(Error::ErrConnectionClosed);
         }
 
+        // A rollback carries no SDP, it only discards the pending local offer
+        if desc.sdp_type == RTCSdpType::Rollback {
+            return self.set_description(&desc, StateChangeOp::SetLocal).await;
+        }
+
         let have_local_description = {
             let current_local_description = self.internal.current_local_description.lock().await;
             current_local_description.is_some()
@@ -1321,18 +1467,45 @@ impl RTCPeerConnection {
         false
     }
 
+    /// Checks that a remote answer settles the DTLS role the local offer left open, before the
+    /// answer is applied. Left at actpass both sides would fall back to picking theirs from the
+    /// ICE role, and the handshake stalls if they agree on one.
+    fn validate_remote_answer_dtls_role(answer: &SessionDescription) -> Result<()> {
+        if DTLSRole::from_remote_description(answer, false) == DTLSRole::Auto {
+            return Err(Error::ErrPeerConnRemoteAnswerDtlsRoleActpass);
+        }
+
+        Ok(())
+    }
+
     /// set_remote_description sets the SessionDescription of the remote peer
     pub async fn set_remote_description(&self, mut desc: RTCSessionDescription) -> Result<()> {
         if self.internal.is_closed.load(Ordering::SeqCst) {
             return Err(Error::ErrConnectionClosed);
         }
 
+        // A rollback carries no SDP, it only discards the pending remote offer
+        if desc.sdp_type == RTCSdpType::Rollback {
+            return self.set_description(&desc, StateChangeOp::SetRemote).await;
+        }
+
         let is_renegotiation = {
             let current_remote_description = self.internal.current_remote_description.lock().await;
             current_remote_description.is_some()
         };
 
         desc.parsed = Some(desc.unmarshal()?);
+        if let Some(parsed) = &desc.parsed {
+            let max_media_sections = self.internal.setting_engine.max_media_sections;
+            if max_media_sections != 0 && parsed.media_descriptions.len() > max_media_sections {
+                return Err(Error::ErrPeerConnTooManyMediaSections);
+            }
+            if desc.sdp_type == RTCSdpType::Answer {
+                Self::validate_remote_answer_dtls_role(parsed)?;
+            } else if desc.sdp_type == RTCSdpType::Offer {
+                self.internal.check_dtls_role(DTLSRole::from(parsed))?;
+            }
+        }
         self.set_description(&desc, StateChangeOp::SetRemote)
             .await?;
 
@@ -1427,6 +1600,11 @@ impl RTCPeerConnection {
                             .await;
 
                             self.internal.add_rtp_transceiver(Arc::clone(&t)).await;
+                            if desc.sdp_type == RTCSdpType::Offer {
+                                let mut remote_offer_transceivers =
+                                    self.internal.remote_offer_transceivers.lock().await;
+                                remote_offer_transceivers.push(Arc::clone(&t));
+                            }
 
                             if t.mid().is_none() {
                                 t.set_mid(SmolStr::from(mid_value))?;
@@ -1544,7 +1722,7 @@ impl RTCPeerConnection {
 
             let (fingerprint, fingerprint_hash) = extract_fingerprint(parsed)?;
 
-            // If one of the agents is lite and the other one is not, the lite agent must be the controlling agent.
+            // If one of the agents is lite and the other one is not, the full agent must be the controlling agent.
             // If both or neither agents are lite the offering agent is controlling.
             // RFC 8445 S6.1.1
             let ice_role = if (we_offer
@@ -1564,8 +1742,14 @@ impl RTCPeerConnection {
 
             //log::trace!("start_transports: parsed={:?}", parsed);
 
+            if !we_offer {
+                self.internal
+                    .remote_offer_started_transports
+                    .store(true, Ordering::SeqCst);
+            }
+
             let pci = Arc::clone(&self.internal);
-            let dtls_role = DTLSRole::from(parsed);
+            let dtls_role = DTLSRole::from_remote_description(parsed, !we_offer);
             let remote_desc = Arc::new(desc);
             self.internal
                 .ops
@@ -1578,6 +1762,16 @@ impl RTCPeerConnection {
                         let fp = fingerprint.clone();
                         let fp_hash = fingerprint_hash.clone();
                         Box::pin(async move {
+                            // The remote offer may have been rolled back in the meantime
+                            if !we_offer
+                                && pc
+                                    .remote_description()
+                                    .await
+                                    .is_none_or(|d| d.sdp != rd.sdp)
+                            {
+                                return false;
+                            }
+
                             log::trace!(
                                 "start_transports: ice_role={}, dtls_role={}",
                                 ice_role,
@@ -1588,6 +1782,8 @@ impl RTCPeerConnection {
 
                             if we_offer {
                                 let _ = pc.start_rtp(false, rd).await;
+                            } else {
+                                let _ = pc.start_remote_offer_receivers(rd).await;
                             }
                             false
                         })
@@ -1770,6 +1966,72 @@ impl RTCPeerConnection {
         Ok(())
     }
 
+    /// pause puts all media of the PeerConnection on hold without tearing down ICE and DTLS.
+    ///
+    /// Every sender stops writing RTP right away and its transceiver's direction loses `send`,
+    /// so the renegotiation that follows signals `recvonly`, or `inactive`, to the remote.
+    /// Call [`RTCPeerConnection::resume`] to start sending again.
+    pub async fn pause(&self) -> Result<()> {
+        if self.internal.is_closed.load(Ordering::SeqCst) {
+            return Err(Error::ErrConnectionClosed);
+        }
+
+        let mut paused_transceivers = self.internal.paused_transceivers.lock().await;
+        let mut changed = false;
+        for t in self.get_transceivers().await {
+            let direction = t.direction();
+            if t.stopped.load(Ordering::SeqCst) || !direction.has_send() {
+                continue;
+            }
+
+            t.sender().await.set_paused(true);
+            t.set_direction_internal(RTCRtpTransceiverDirection::from_send_recv(
+                false,
+                direction.has_recv(),
+            ));
+            paused_transceivers.push(t);
+            changed = true;
+        }
+
+        if changed {
+            self.internal.trigger_negotiation_needed().await;
+        }
+        Ok(())
+    }
+
+    /// resume undoes [`RTCPeerConnection::pause`], restoring `send` on the directions of the
+    /// paused transceivers. Sending resumes once the renegotiation that follows completes.
+    pub async fn resume(&self) -> Result<()> {
+        if self.internal.is_closed.load(Ordering::SeqCst) {
+            return Err(Error::ErrConnectionClosed);
+        }
+
+        let paused_transceivers =
+            std::mem::take(&mut *self.internal.paused_transceivers.lock().await);
+        let mut changed = false;
+        for t in paused_transceivers {
+            if t.stopped.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            t.set_direction_internal(RTCRtpTransceiverDirection::from_send_recv(
+                true,
+                t.direction().has_recv(),
+            ));
+            // If no renegotiation happened since pausing, the negotiated direction still allows
+            // sending and won't change, so unpause the sender here.
+            if t.current_direction().has_send() {
+                t.sender().await.set_paused(false);
+            }
+            changed = true;
+        }
+
+        if changed {
+            self.internal.trigger_negotiation_needed().await;
+        }
+        Ok(())
+    }
+
     /// add_transceiver_from_kind Create a new RtpTransceiver and adds it to the set of transceivers.
     pub async fn add_transceiver_from_kind(
         &self,
@@ -1926,6 +2188,8 @@ impl RTCPeerConnection {
             close_errs.push(Error::new(format!("interceptor: {err}")));
         }
 
+        self.internal.pending_tracks.release();
+
         // https://www.w3.org/TR/webrtc/#dom-rtcpeerconnection-close (step #4)
         {
             let mut rtp_transceivers = self.internal.rtp_transceivers.lock().await;
@@ -2096,6 +2360,35 @@ impl RTCPeerConnection {
         gathering_complete_rx
     }
 
+    /// get_local_candidates returns the local ICE candidates gathered so far.
+    ///
+    /// This is useful for applications doing their own signaling, which would otherwise
+    /// have to parse the candidates back out of the local description. Use
+    /// gathering_complete_promise to wait for gathering to finish before calling it.
+    pub async fn get_local_candidates(&self) -> Result<Vec<RTCIceCandidate>> {
+        self.internal.ice_gatherer.get_local_candidates().await
+    }
+
+    /// create_offer_and_gather is a helper for non-trickle signaling, such as WHIP ingest.
+    /// It creates an offer, sets it as the local description, waits for ICE gathering
+    /// to complete and returns the resulting local description with all candidates included.
+    ///
+    /// As with gathering_complete_promise, trickling candidates is preferable where possible.
+    pub async fn create_offer_and_gather(
+        &self,
+        options: Option<RTCOfferOptions>,
+    ) -> Result<RTCSessionDescription> {
+        let offer = self.create_offer(options).await?;
+
+        let mut gathering_complete = self.gathering_complete_promise().await;
+        self.set_local_description(offer).await?;
+        let _ = gathering_complete.recv().await;
+
+        self.local_description()
+            .await
+            .ok_or(Error::ErrPeerConnLocalDescriptionNil)
+    }
+
     /// Returns the internal [`RTCDtlsTransport`].
     pub fn dtls_transport(&self) -> Arc<RTCDtlsTransport> {
         Arc::clone(&self.internal.dtls_transport)
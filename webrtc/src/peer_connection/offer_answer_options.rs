@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 /// AnswerOptions structure describes the options used to control the answer
 /// creation process.
 #[derive(Default, Debug, PartialEq, Eq, Copy, Clone)]
@@ -20,3 +23,27 @@ pub struct RTCOfferOptions {
     /// credentials that are different from the current credentials
     pub ice_restart: bool,
 }
+
+/// RTCCancellationToken aborts an in-progress create_offer_with_cancel or
+/// create_answer_with_cancel, which then fail with ErrCreateCancelled. Clones share the
+/// same cancellation state, so a clone can be kept to cancel from another task.
+#[derive(Default, Debug, Clone)]
+pub struct RTCCancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl RTCCancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// cancel aborts the offer or answer generation using this token. Generation that
+    /// has already completed is unaffected.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
@@ -106,6 +106,17 @@ impl Operations {
         wg.wait().await;
     }
 
+    /// drain blocks until the queue is empty, including operations that were
+    /// requeued while waiting. Useful for tests that must not race negotiation.
+    pub(crate) async fn drain(&self) {
+        loop {
+            self.done().await;
+            if self.is_empty().await {
+                break;
+            }
+        }
+    }
+
     pub(crate) async fn start(
         length: Arc<AtomicUsize>,
         ops_tx: Arc<mpsc::UnboundedSender<Operation>>,
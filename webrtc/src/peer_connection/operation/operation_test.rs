@@ -45,3 +45,33 @@ async fn test_operations_done() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_operations_drain() -> Result<()> {
+    let ops = Operations::new();
+    let counter = Arc::new(AtomicUsize::new(0));
+    for _ in 0..8 {
+        let c = Arc::clone(&counter);
+        let mut remaining = 3;
+        ops.enqueue(Operation::new(
+            move || {
+                let c2 = Arc::clone(&c);
+                remaining -= 1;
+                let requeue = remaining > 0;
+                Box::pin(async move {
+                    tokio::task::yield_now().await;
+                    c2.fetch_add(1, Ordering::SeqCst);
+                    requeue
+                })
+            },
+            "test_operations_drain",
+        ))
+        .await?;
+    }
+
+    ops.drain().await;
+    assert!(ops.is_empty().await);
+    assert_eq!(counter.load(Ordering::SeqCst), 24);
+
+    Ok(())
+}
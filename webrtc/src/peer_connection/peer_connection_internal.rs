@@ -3,12 +3,15 @@ use std::sync::atomic::AtomicIsize;
 use std::sync::Weak;
 
 use arc_swap::ArcSwapOption;
+use sctp::association::DEFAULT_SCTP_PORT;
 use smol_str::SmolStr;
 use tokio::time::Instant;
-use util::Unmarshal;
+use util::{Marshal, Unmarshal};
 
 use super::*;
-use crate::rtp_transceiver::create_stream_info;
+use crate::rtp_transceiver::{
+    create_stream_info, RTCRtpDecodingParameters, RTCRtpReceiveParameters,
+};
 use crate::stats::stats_collector::StatsCollector;
 use crate::stats::{
     InboundRTPStats, OutboundRTPStats, RTCStatsType, RemoteInboundRTPStats, RemoteOutboundRTPStats,
@@ -47,8 +50,17 @@ pub(crate) struct PeerConnectionInternal {
 
     pub(super) sctp_transport: Arc<RTCSctpTransport>,
     pub(super) rtp_transceivers: Arc<Mutex<Vec<Arc<RTCRtpTransceiver>>>>,
+    /// Transceivers whose sending was stopped by `RTCPeerConnection::pause`.
+    pub(super) paused_transceivers: Mutex<Vec<Arc<RTCRtpTransceiver>>>,
+    /// Transceivers created by applying the pending remote offer, removed if it's rolled back.
+    pub(super) remote_offer_transceivers: Mutex<Vec<Arc<RTCRtpTransceiver>>>,
+    /// Whether applying the pending remote offer started the transports, undone if it's rolled back.
+    pub(super) remote_offer_started_transports: AtomicBool,
 
     pub(super) on_track_handler: Arc<ArcSwapOption<Mutex<OnTrackHdlrFn>>>,
+    pub(super) pending_tracks: Arc<PendingTracks>,
+    /// The number of undeclared SSRCs currently being probed for their MID/RID.
+    pub(super) simulcast_probe_routines: Arc<AtomicU64>,
     pub(super) on_signaling_state_change_handler:
         ArcSwapOption<Mutex<OnSignalingStateChangeHdlrFn>>,
     pub(super) on_ice_connection_state_change_handler:
@@ -93,7 +105,15 @@ impl PeerConnectionInternal {
             ice_connection_state: Arc::new(AtomicU8::new(RTCIceConnectionState::New as u8)),
             sctp_transport: Arc::new(Default::default()),
             rtp_transceivers: Arc::new(Default::default()),
+            paused_transceivers: Mutex::new(vec![]),
+            remote_offer_transceivers: Mutex::new(vec![]),
+            remote_offer_started_transports: AtomicBool::new(false),
             on_track_handler: Arc::new(ArcSwapOption::empty()),
+            pending_tracks: Arc::new(PendingTracks::new(
+                api.setting_engine.unhandled_track_buffer,
+                api.setting_engine.get_receive_mtu(),
+            )),
+            simulcast_probe_routines: Arc::new(AtomicU64::new(0)),
             on_signaling_state_change_handler: ArcSwapOption::empty(),
             on_ice_connection_state_change_handler: Arc::new(ArcSwapOption::empty()),
             on_data_channel_handler: Arc::new(Default::default()),
@@ -226,22 +246,53 @@ impl PeerConnectionInternal {
             .await?;
         if let Some(parsed) = &remote_desc.parsed {
             if have_application_media_section(parsed) {
-                self.start_sctp().await;
+                self.start_sctp(
+                    get_sctp_port(parsed).unwrap_or(DEFAULT_SCTP_PORT),
+                    get_max_message_size(parsed),
+                )
+                .await;
             }
         }
 
         Ok(())
     }
 
+    /// start_remote_offer_receivers opens the inbound SRTP streams declared by a remote
+    /// offer, so media can be received before the local answer has been applied.
+    pub(super) async fn start_remote_offer_receivers(
+        self: &Arc<Self>,
+        remote_desc: Arc<RTCSessionDescription>,
+    ) -> Result<()> {
+        // The SRTP keys are only available once DTLS has connected
+        if self.dtls_transport.state() != RTCDtlsTransportState::Connected {
+            return Ok(());
+        }
+
+        let mut track_details = if let Some(parsed) = &remote_desc.parsed {
+            track_details_from_sdp(parsed, false)
+        } else {
+            return Ok(());
+        };
+
+        let current_transceivers = {
+            let current_transceivers = self.rtp_transceivers.lock().await;
+            current_transceivers.clone()
+        };
+
+        self.start_rtp_receivers(&mut track_details, &current_transceivers)
+            .await
+    }
+
     /// undeclared_media_processor handles RTP/RTCP packets that don't match any a:ssrc lines
     fn undeclared_media_processor(self: &Arc<Self>) {
         let dtls_transport = Arc::clone(&self.dtls_transport);
         let is_closed = Arc::clone(&self.is_closed);
+        let simulcast_routine_count = Arc::clone(&self.simulcast_probe_routines);
+        let max_probe_routines = self.setting_engine.get_simulcast_max_probe_routines();
         let pci = Arc::clone(self);
 
         // SRTP acceptor
         tokio::spawn(async move {
-            let simulcast_routine_count = Arc::new(AtomicU64::new(0));
             loop {
                 let srtp_session = match dtls_transport.get_srtp_session().await {
                     Some(s) => s,
@@ -266,11 +317,12 @@ impl PeerConnectionInternal {
                     continue;
                 }
 
-                if simulcast_routine_count.fetch_add(1, Ordering::SeqCst) + 1
-                    >= SIMULCAST_MAX_PROBE_ROUTINES
-                {
+                if simulcast_routine_count.fetch_add(1, Ordering::SeqCst) + 1 > max_probe_routines {
                     simulcast_routine_count.fetch_sub(1, Ordering::SeqCst);
                     log::warn!("{:?}", Error::ErrSimulcastProbeOverflow);
+                    if let Err(err) = stream.close().await {
+                        log::warn!("Failed to close RTP stream {}", err);
+                    }
                     continue;
                 }
 
@@ -365,6 +417,15 @@ impl PeerConnectionInternal {
                     continue;
                 }
 
+                // Nothing is received on a section negotiated e.g. inactive, so don't open SRTP
+                // streams for it. The receiver starts once a renegotiation enables receiving.
+                let current_direction = t.current_direction();
+                if current_direction != RTCRtpTransceiverDirection::Unspecified
+                    && !current_direction.has_recv()
+                {
+                    continue;
+                }
+
                 let receiver = t.receiver().await;
                 if receiver.have_received().await {
                     continue;
@@ -375,6 +436,7 @@ impl PeerConnectionInternal {
                     receiver,
                     Arc::clone(t),
                     Arc::clone(&self.on_track_handler),
+                    Arc::clone(&self.pending_tracks),
                 )
                 .await;
                 track_handled = true;
@@ -388,13 +450,14 @@ impl PeerConnectionInternal {
         Ok(())
     }
 
-    /// Start SCTP subsystem
-    async fn start_sctp(&self) {
+    /// Start SCTP subsystem, talking to the remote SCTP port
+    async fn start_sctp(&self, remote_port: u16, remote_max_message_size: u32) {
         // Start sctp
+        self.sctp_transport.set_remote_port(remote_port);
         if let Err(err) = self
             .sctp_transport
             .start(SCTPTransportCapabilities {
-                max_message_size: 0,
+                max_message_size: remote_max_message_size,
             })
             .await
         {
@@ -532,6 +595,99 @@ impl PeerConnectionInternal {
         .await)
     }
 
+    /// Undoes what applying the remote offer being rolled back did: the transceivers it created
+    /// are removed and, if it was the initial offer, the transports started with its ICE
+    /// credentials and DTLS fingerprint are reset along with the receivers started on its media.
+    /// The mids the offer gave to existing transceivers are kept, as a mid can't be unset.
+    pub(super) async fn rollback_remote_offer(self: &Arc<Self>) {
+        let created = {
+            let mut remote_offer_transceivers = self.remote_offer_transceivers.lock().await;
+            std::mem::take(&mut *remote_offer_transceivers)
+        };
+        if !created.is_empty() {
+            {
+                let mut rtp_transceivers = self.rtp_transceivers.lock().await;
+                rtp_transceivers.retain(|t| !created.iter().any(|c| Arc::ptr_eq(c, t)));
+            }
+            for t in created {
+                if let Err(err) = t.stop().await {
+                    log::warn!("Failed to stop rolled back transceiver: {}", err);
+                }
+            }
+        }
+
+        if !self
+            .remote_offer_started_transports
+            .swap(false, Ordering::SeqCst)
+        {
+            return;
+        }
+
+        // Unblock the start of the transports first, it waits for ICE to connect and holds up
+        // the operations queue until then
+        if let Err(err) = self.ice_transport.stop_connecting().await {
+            log::warn!("Failed to stop rolled back ICE transport: {}", err);
+        }
+
+        let pci = Arc::clone(self);
+        let result = self
+            .ops
+            .enqueue(Operation::new(
+                move || {
+                    let pc = Arc::clone(&pci);
+                    Box::pin(async move {
+                        pc.reset_transports().await;
+                        false
+                    })
+                },
+                "rollback_remote_offer",
+            ))
+            .await;
+        if let Err(err) = result {
+            log::warn!("Failed to enqueue rollback of the transports: {}", err);
+            return;
+        }
+        self.ops.done().await;
+    }
+
+    /// Takes the ICE and DTLS transports back to the new state and replaces the receivers that
+    /// were started over them, so that they can all be started again by the next remote offer.
+    async fn reset_transports(&self) {
+        if let Err(err) = self.dtls_transport.reset().await {
+            log::warn!("Failed to reset DTLS transport: {}", err);
+        }
+        if let Err(err) = self.ice_transport.reset().await {
+            log::warn!("Failed to reset ICE transport: {}", err);
+        }
+
+        let interceptor = match self.interceptor.upgrade() {
+            Some(interceptor) => interceptor,
+            None => return,
+        };
+        let current_transceivers = {
+            let rtp_transceivers = self.rtp_transceivers.lock().await;
+            rtp_transceivers.clone()
+        };
+        for t in current_transceivers {
+            let receiver = t.receiver().await;
+            if !receiver.have_received().await {
+                continue;
+            }
+
+            if let Err(err) = receiver.stop().await {
+                log::warn!("Failed to stop RtpReceiver: {}", err);
+            }
+            t.set_receiver(Arc::new(RTCRtpReceiver::new(
+                self.setting_engine.get_receive_mtu(),
+                receiver.kind(),
+                Arc::clone(&self.dtls_transport),
+                Arc::clone(&self.media_engine),
+                Arc::clone(&interceptor),
+            )))
+            .await;
+        }
+    }
+
     /// add_rtp_transceiver appends t into rtp_transceivers
     /// and fires onNegotiationNeeded;
     /// caller of this method should hold `self.mu` lock
@@ -593,6 +749,20 @@ impl PeerConnectionInternal {
         self.ice_gatherer.on_gathering_complete(f);
     }
 
+    /// check_dtls_role checks that the DTLS role a remote offer takes leaves the other one to
+    /// the local answer. create_answer follows an offer that picks a role, so only an answering
+    /// role set through the SettingEngine can collide with it. Both sides would then take the
+    /// same role, and the handshake stalls.
+    pub(super) fn check_dtls_role(&self, remote_role: DTLSRole) -> Result<()> {
+        if matches!(remote_role, DTLSRole::Client | DTLSRole::Server)
+            && remote_role == self.setting_engine.answering_dtls_role
+        {
+            return Err(Error::ErrPeerConnDtlsRoleConflict);
+        }
+
+        Ok(())
+    }
+
     /// Start all transports. PeerConnection now has enough state
     pub(super) async fn start_transports(
         self: &Arc<Self>,
@@ -700,6 +870,8 @@ impl PeerConnectionInternal {
             is_icelite: self.setting_engine.candidates.ice_lite,
             connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
             ice_gathering_state: self.ice_gathering_state(),
+            extmap_allow_mixed: true,
+            sctp_port: self.setting_engine.get_sctp_port(),
         };
         populate_sdp(
             d,
@@ -757,7 +929,11 @@ impl PeerConnectionInternal {
                         }
 
                         if let Some(t) = find_by_mid(mid_value, &mut local_transceivers).await {
-                            t.sender().await.set_negotiated();
+                            // When answering, a media section without a common codec is rejected,
+                            // so its sender must not be started.
+                            if include_unmatched || !t.get_codecs().await.is_empty() {
+                                t.sender().await.set_negotiated();
+                            }
                             let media_transceivers = vec![t];
 
                             // NB: The below could use `then_some`, but with our current MSRV
@@ -816,6 +992,13 @@ impl PeerConnectionInternal {
             is_icelite: self.setting_engine.candidates.ice_lite,
             connection_role,
             ice_gathering_state: self.ice_gathering_state(),
+            // Offers always allow mixing, answers only if the offer did.
+            extmap_allow_mixed: include_unmatched
+                || remote_description
+                    .as_ref()
+                    .and_then(|rd| rd.parsed.as_ref())
+                    .is_some_and(|parsed| parsed.has_extmap_allow_mixed()),
+            sctp_port: self.setting_engine.get_sctp_port(),
         };
         populate_sdp(
             d,
@@ -895,6 +1078,7 @@ impl PeerConnectionInternal {
             receiver,
             t,
             Arc::clone(&self.on_track_handler),
+            Arc::clone(&self.pending_tracks),
         )
         .await;
         Ok(true)
@@ -926,16 +1110,15 @@ impl PeerConnectionInternal {
             return Err(Error::ErrPeerConnSimulcastMidRTPExtensionRequired);
         }
 
-        // Get RID extension ID
+        // Get RID extension ID. It is only required for simulcast, BUNDLEd media sections
+        // without simulcast can be routed by MID alone.
         let (sid_extension_id, audio_supported, video_supported) = self
             .media_engine
             .get_header_extension_id(RTCRtpHeaderExtensionCapability {
                 uri: ::sdp::extmap::SDES_RTP_STREAM_ID_URI.to_owned(),
             })
             .await;
-        if !audio_supported && !video_supported {
-            return Err(Error::ErrPeerConnSimulcastStreamIDRTPExtensionRequired);
-        }
+        let sid_supported = audio_supported || video_supported;
 
         let (rsid_extension_id, _, _) = self
             .media_engine
@@ -985,78 +1168,108 @@ impl PeerConnectionInternal {
             .streams_for_ssrc(ssrc, &stream_info, &icpr)
             .await?;
 
-        let a = Attributes::new();
-        for _ in 0..=SIMULCAST_PROBE_COUNT {
-            if mid.is_empty() || (rid.is_empty() && rsid.is_empty()) {
-                let (pkt, _) = rtp_interceptor.read(&mut buf, &a).await?;
-                let (m, r, rs, _) = handle_unknown_rtp_packet(
-                    &buf[..n],
-                    mid_extension_id as u8,
-                    sid_extension_id as u8,
-                    rsid_extension_id as u8,
-                )?;
-                mid = m;
-                rid = r;
-                rsid = rs;
-
-                buffered_packets.push_back((pkt, a.clone()));
-                continue;
-            }
-
-            let transceivers = self.rtp_transceivers.lock().await;
-            for t in &*transceivers {
-                if t.mid().as_ref() != Some(&SmolStr::from(&mid)) {
+        // The streams are released again if probing fails, so that an SSRC that never
+        // resolves to a track doesn't leak them.
+        let probed: Result<bool> = async {
+            let a = Attributes::new();
+            for _ in 0..=SIMULCAST_PROBE_COUNT {
+                if mid.is_empty()
+                    || (rid.is_empty() && rsid.is_empty() && is_simulcast_mid(&parsed, &mid))
+                {
+                    let (pkt, _) = rtp_interceptor.read(&mut buf, &a).await?;
+                    let (m, r, rs, _) = handle_unknown_rtp_packet(
+                        &pkt.marshal()?,
+                        mid_extension_id as u8,
+                        sid_extension_id as u8,
+                        rsid_extension_id as u8,
+                    )?;
+                    mid = m;
+                    rid = r;
+                    rsid = rs;
+
+                    buffered_packets.push_back((pkt, a.clone()));
                     continue;
                 }
 
-                let receiver = t.receiver().await;
+                let transceivers = self.rtp_transceivers.lock().await;
+                for t in &*transceivers {
+                    if t.mid().as_ref() != Some(&SmolStr::from(&mid)) {
+                        continue;
+                    }
+
+                    let receiver = t.receiver().await;
+
+                    // A media section without simulcast is identified by its MID alone. Only route
+                    // to it if the SSRC wasn't already declared and started through the SDP.
+                    if rid.is_empty() && rsid.is_empty() {
+                        if receiver.have_received().await {
+                            break;
+                        }
+                        receiver
+                            .receive(&RTCRtpReceiveParameters {
+                                encodings: vec![RTCRtpDecodingParameters::default()],
+                            })
+                            .await?;
+                    }
+
+                    if !rsid.is_empty() {
+                        return receiver
+                            .receive_for_rtx(
+                                0,
+                                rsid,
+                                TrackStream {
+                                    stream_info: Some(stream_info.clone()),
+                                    rtp_read_stream: Some(Arc::clone(&rtp_read_stream)),
+                                    rtp_interceptor: Some(Arc::clone(&rtp_interceptor)),
+                                    rtcp_read_stream: Some(Arc::clone(&rtcp_read_stream)),
+                                    rtcp_interceptor: Some(Arc::clone(&rtcp_interceptor)),
+                                },
+                            )
+                            .await
+                            .map(|_| true);
+                    }
 
-                if !rsid.is_empty() {
-                    return receiver
-                        .receive_for_rtx(
-                            0,
-                            rsid,
+                    let track = receiver
+                        .receive_for_rid(
+                            SmolStr::from(rid),
+                            params,
                             TrackStream {
                                 stream_info: Some(stream_info.clone()),
-                                rtp_read_stream: Some(rtp_read_stream),
-                                rtp_interceptor: Some(rtp_interceptor),
-                                rtcp_read_stream: Some(rtcp_read_stream),
-                                rtcp_interceptor: Some(rtcp_interceptor),
+                                rtp_read_stream: Some(Arc::clone(&rtp_read_stream)),
+                                rtp_interceptor: Some(Arc::clone(&rtp_interceptor)),
+                                rtcp_read_stream: Some(Arc::clone(&rtcp_read_stream)),
+                                rtcp_interceptor: Some(Arc::clone(&rtcp_interceptor)),
                             },
                         )
-                        .await;
+                        .await?;
+                    track.prepopulate_peeked_data(buffered_packets).await;
+
+                    RTCPeerConnection::do_track(
+                        Arc::clone(&self.on_track_handler),
+                        Arc::clone(&self.pending_tracks),
+                        track,
+                        receiver,
+                        Arc::clone(t),
+                    );
+                    return Ok(true);
                 }
-
-                let track = receiver
-                    .receive_for_rid(
-                        SmolStr::from(rid),
-                        params,
-                        TrackStream {
-                            stream_info: Some(stream_info.clone()),
-                            rtp_read_stream: Some(rtp_read_stream),
-                            rtp_interceptor: Some(rtp_interceptor),
-                            rtcp_read_stream: Some(rtcp_read_stream),
-                            rtcp_interceptor: Some(rtcp_interceptor),
-                        },
-                    )
-                    .await?;
-                track.prepopulate_peeked_data(buffered_packets).await;
-
-                RTCPeerConnection::do_track(
-                    Arc::clone(&self.on_track_handler),
-                    track,
-                    receiver,
-                    Arc::clone(t),
-                );
-                return Ok(());
             }
+            Ok(false)
+        }
+        .await;
+        if let Ok(true) = probed {
+            return Ok(());
         }
 
         let _ = rtp_read_stream.close().await;
         let _ = rtcp_read_stream.close().await;
         icpr.unbind_remote_stream(&stream_info).await;
         self.dtls_transport.remove_simulcast_stream(ssrc).await;
+        probed?;
 
+        if !sid_supported {
+            return Err(Error::ErrPeerConnSimulcastStreamIDRTPExtensionRequired);
+        }
         Err(Error::ErrPeerConnSimulcastIncomingSSRCFailed)
     }
 
@@ -1066,6 +1279,7 @@ impl PeerConnectionInternal {
         receiver: Arc<RTCRtpReceiver>,
         transceiver: Arc<RTCRtpTransceiver>,
         on_track_handler: Arc<ArcSwapOption<Mutex<OnTrackHdlrFn>>>,
+        pending_tracks: Arc<PendingTracks>,
     ) {
         receiver.start(incoming).await;
         for t in receiver.tracks().await {
@@ -1076,6 +1290,7 @@ impl PeerConnectionInternal {
             let receiver = Arc::clone(&receiver);
             let transceiver = Arc::clone(&transceiver);
             let on_track_handler = Arc::clone(&on_track_handler);
+            let pending_tracks = Arc::clone(&pending_tracks);
             tokio::spawn(async move {
                 if let Some(track) = receiver.track().await {
                     let mut b = vec![0u8; receive_mtu];
@@ -1100,7 +1315,13 @@ impl PeerConnectionInternal {
                         return;
                     }
 
-                    RTCPeerConnection::do_track(on_track_handler, track, receiver, transceiver);
+                    RTCPeerConnection::do_track(
+                        on_track_handler,
+                        pending_tracks,
+                        track,
+                        receiver,
+                        transceiver,
+                    );
                 }
             });
         }
@@ -1246,6 +1467,7 @@ impl PeerConnectionInternal {
             let kind = info.kind;
 
             let id = format!("RTCInboundRTP{}Stream_{}", capitalize(kind), ssrc);
+            let sequence_gaps = stats.sequence_gaps();
             let (
                 packets_received,
                 header_bytes_received,
@@ -1281,6 +1503,7 @@ impl PeerConnectionInternal {
                     ssrc,
                     kind,
                     packets_received,
+                    jitter: stats.jitter(),
                     track_identifier: info.track_id,
                     mid: info.mid,
                     last_packet_received_timestamp,
@@ -1290,6 +1513,10 @@ impl PeerConnectionInternal {
 
                     fir_count: (info.kind == "video").then(|| stats.firs_sent()),
                     pli_count: (info.kind == "video").then(|| stats.plis_sent()),
+
+                    single_loss_gaps: sequence_gaps.single(),
+                    double_loss_gaps: sequence_gaps.double(),
+                    burst_loss_gaps: sequence_gaps.burst(),
                 }),
             );
 
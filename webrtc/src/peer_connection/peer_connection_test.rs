@@ -1,27 +1,47 @@
-use std::sync::atomic::AtomicU32;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU32};
+use std::sync::{Arc, Weak};
 
 use bytes::Bytes;
 use interceptor::registry::Registry;
+use interceptor::tap::TapDirection;
 use media::Sample;
 use tokio::time::Duration;
+use util::conn::conn_inject::InjectConn;
+use util::vnet::chunk::Chunk;
 use util::vnet::net::{Net, NetConfig};
 use util::vnet::router::{Router, RouterConfig};
+use util::Conn;
 use waitgroup::WaitGroup;
 
 use super::*;
-use crate::api::interceptor_registry::register_default_interceptors;
-use crate::api::media_engine::{MediaEngine, MIME_TYPE_VP8};
+use crate::api::interceptor_registry::{configure_twcc_sender_only, register_default_interceptors};
+use crate::api::media_engine::{MediaEngine, MIME_TYPE_OPUS, MIME_TYPE_VP8};
 use crate::api::APIBuilder;
+use crate::data_channel::data_channel_message::DataChannelMessage;
 use crate::ice_transport::ice_candidate_pair::RTCIceCandidatePair;
+use crate::ice_transport::ice_candidate_type::RTCIceCandidateType;
+use crate::ice_transport::ice_protocol::RTCIceProtocol;
 use crate::ice_transport::ice_server::RTCIceServer;
 use crate::peer_connection::configuration::RTCConfiguration;
-use crate::rtp_transceiver::rtp_codec::RTCRtpCodecCapability;
+use crate::peer_connection::offer_answer_options::RTCCancellationToken;
+use crate::rtp_transceiver::rtp_codec::{RTCRtpCodecCapability, RTCRtpCodecParameters};
 use crate::stats::StatsReportType;
+use crate::track::track_local::track_local_static_rtp::TrackLocalStaticRTP;
 use crate::track::track_local::track_local_static_sample::TrackLocalStaticSample;
+use crate::track::track_local::TrackLocalWriter;
+use crate::track::RTP_OUTBOUND_MTU;
 use crate::Error;
 
 pub(crate) async fn create_vnet_pair(
+) -> Result<(RTCPeerConnection, RTCPeerConnection, Arc<Mutex<Router>>)> {
+    create_vnet_pair_with(|_| {}).await
+}
+
+/// create_vnet_pair_with is create_vnet_pair with `configure` applied to both setting engines.
+pub(crate) async fn create_vnet_pair_with(
+    configure: fn(&mut SettingEngine),
 ) -> Result<(RTCPeerConnection, RTCPeerConnection, Arc<Mutex<Router>>)> {
     // Create a root router
     let wan = Arc::new(Mutex::new(Router::new(RouterConfig {
@@ -53,6 +73,7 @@ pub(crate) async fn create_vnet_pair(
         Some(Duration::from_secs(1)),
         Some(Duration::from_millis(200)),
     );
+    configure(&mut offer_setting_engine);
 
     // Create a network interface for answerer
     let answer_vnet = Arc::new(Net::new(Some(NetConfig {
@@ -78,6 +99,7 @@ pub(crate) async fn create_vnet_pair(
         Some(Duration::from_secs(1)),
         Some(Duration::from_millis(200)),
     );
+    configure(&mut answer_setting_engine);
 
     // Start the virtual network by calling Start() on the root router
     {
@@ -106,6 +128,36 @@ pub(crate) async fn create_vnet_pair(
     Ok((offer_peer_connection, answer_peer_connection, wan))
 }
 
+/// create_vnet creates a started virtual network with a single interface at 1.2.3.4, for tests
+/// that only need one peer connection.
+pub(crate) async fn create_vnet() -> Result<(Arc<Net>, Arc<Mutex<Router>>)> {
+    let wan = Arc::new(Mutex::new(Router::new(RouterConfig {
+        cidr: "1.2.3.0/24".to_owned(),
+        ..Default::default()
+    })?));
+
+    let vnet = Arc::new(Net::new(Some(NetConfig {
+        static_ips: vec!["1.2.3.4".to_owned()],
+        ..Default::default()
+    })));
+
+    let nic = vnet.get_nic()?;
+    {
+        let mut w = wan.lock().await;
+        w.add_net(Arc::clone(&nic)).await?;
+    }
+    {
+        let n = nic.lock().await;
+        n.set_router(Arc::clone(&wan)).await?;
+    }
+    {
+        let mut w = wan.lock().await;
+        w.start().await?;
+    }
+
+    Ok((vnet, wan))
+}
+
 /// new_pair creates two new peer connections (an offerer and an answerer)
 /// *without* using an api (i.e. using the default settings).
 pub(crate) async fn new_pair(api: &API) -> Result<(RTCPeerConnection, RTCPeerConnection)> {
@@ -160,6 +212,80 @@ pub(crate) async fn signal_pair(
         .await
 }
 
+/// signal_pair_trickle connects two PeerConnections like signal_pair, but exchanges the offer and
+/// answer right away and trickles the ICE candidates through the on_ice_candidate handlers.
+/// Candidates gathered before the other side has a remote description are held back until it has.
+pub(crate) async fn signal_pair_trickle(
+    pc_offer: &Arc<RTCPeerConnection>,
+    pc_answer: &Arc<RTCPeerConnection>,
+) -> Result<()> {
+    let offer_candidates = trickle_candidates(pc_offer, pc_answer);
+    let answer_candidates = trickle_candidates(pc_answer, pc_offer);
+
+    let offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(offer.clone()).await?;
+    pc_answer.set_remote_description(offer).await?;
+    add_pending_candidates(pc_answer, &offer_candidates).await?;
+
+    let answer = pc_answer.create_answer(None).await?;
+    pc_answer.set_local_description(answer.clone()).await?;
+    pc_offer.set_remote_description(answer).await?;
+    add_pending_candidates(pc_offer, &answer_candidates).await
+}
+
+/// trickle_candidates forwards the local candidates of `from` to `to`, holding them in the returned
+/// buffer while `to` has no remote description.
+fn trickle_candidates(
+    from: &RTCPeerConnection,
+    to: &Arc<RTCPeerConnection>,
+) -> Arc<Mutex<Vec<RTCIceCandidateInit>>> {
+    let pending = Arc::new(Mutex::new(vec![]));
+    let pending2 = Arc::clone(&pending);
+    // Weak, so that the two PeerConnections don't keep each other alive through their handlers.
+    let to = Arc::downgrade(to);
+    from.on_ice_candidate(Box::new(move |candidate: Option<RTCIceCandidate>| {
+        let pending = Arc::clone(&pending2);
+        let to: Weak<RTCPeerConnection> = to.clone();
+        Box::pin(async move {
+            let (candidate, to) = match (candidate, to.upgrade()) {
+                (Some(candidate), Some(to)) => (candidate, to),
+                _ => return,
+            };
+            let candidate = match candidate.to_json() {
+                Ok(candidate) => candidate,
+                Err(err) => {
+                    log::error!("Failed to serialize candidate: {}", err);
+                    return;
+                }
+            };
+
+            // Checked under the lock, so that add_pending_candidates can't miss the candidate.
+            {
+                let mut pending = pending.lock().await;
+                if to.remote_description().await.is_none() {
+                    pending.push(candidate);
+                    return;
+                }
+            }
+            if let Err(err) = to.add_ice_candidate(candidate).await {
+                log::error!("Failed to add candidate: {}", err);
+            }
+        })
+    }));
+    pending
+}
+
+async fn add_pending_candidates(
+    pc: &RTCPeerConnection,
+    pending: &Mutex<Vec<RTCIceCandidateInit>>,
+) -> Result<()> {
+    let candidates = std::mem::take(&mut *pending.lock().await);
+    for candidate in candidates {
+        pc.add_ice_candidate(candidate).await?;
+    }
+    Ok(())
+}
+
 pub(crate) async fn close_pair_now(pc1: &RTCPeerConnection, pc2: &RTCPeerConnection) {
     let mut fail = false;
     if let Err(err) = pc1.close().await {
@@ -266,6 +392,401 @@ pub(crate) async fn until_connection_state(
     }));
 }
 
+#[tokio::test]
+async fn test_raw_rtp_tap() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+    assert_eq!(
+        pc.on_raw_rtp(TapDirection::Outbound, Box::new(|_| {})),
+        Err(Error::ErrRawRtpTapDisabled)
+    );
+    pc.close().await?;
+
+    let mut s = SettingEngine::default();
+    s.enable_raw_rtp_tap(true);
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new()
+        .with_setting_engine(s)
+        .with_media_engine(m)
+        .build();
+    let (mut pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    let (tapped_tx, mut tapped_rx) = mpsc::unbounded_channel();
+    for (pc, direction) in [
+        (&pc_offer, TapDirection::Outbound),
+        (&pc_answer, TapDirection::Inbound),
+    ] {
+        let tapped_tx = tapped_tx.clone();
+        pc.on_raw_rtp(
+            direction,
+            Box::new(move |pkt: &rtp::packet::Packet| {
+                let _ = tapped_tx.send((direction, pkt.payload.clone()));
+            }),
+        )?;
+    }
+    pc_answer.on_track(Box::new(|track, _, _| {
+        Box::pin(async move { while track.read_rtp().await.is_ok() {} })
+    }));
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+    let payload = Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]);
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    let mut seen = vec![];
+    while seen.len() < 2 {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for tapped packets"),
+            Some((direction, tapped)) = tapped_rx.recv() => {
+                assert_eq!(tapped, payload);
+                if !seen.contains(&direction) {
+                    seen.push(direction);
+                }
+            }
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                track
+                    .write_rtp(&rtp::packet::Packet {
+                        header: rtp::header::Header {
+                            version: 2,
+                            ..Default::default()
+                        },
+                        payload: payload.clone(),
+                    })
+                    .await?;
+            }
+        }
+    }
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_path_mtu_probe_constrained_link() -> Result<()> {
+    const LINK_MTU: usize = 1000;
+
+    let (mut pc_offer, mut pc_answer, wan) = create_vnet_pair_with(|s| {
+        s.set_path_mtu_probe(Some(Duration::from_millis(100)));
+    })
+    .await?;
+    {
+        let w = wan.lock().await;
+        w.add_chunk_filter(Box::new(|c: &(dyn Chunk + Send + Sync)| {
+            c.user_data().len() <= LINK_MTU
+        }))
+        .await;
+    }
+
+    let track = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    // Packets of the default size are dropped by the link, so large ones only make it across if
+    // frames are fragmented to fit the discovered MTU
+    let (done_tx, done_rx) = mpsc::channel::<()>(1);
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let done_tx = done_tx.clone();
+        Box::pin(async move {
+            while let Ok((pkt, _)) = track.read_rtp().await {
+                if pkt.payload.len() > RTP_OUTBOUND_MTU / 2 {
+                    let _ = done_tx.try_send(());
+                }
+            }
+        })
+    }));
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+    let timeout = tokio::time::timeout(
+        Duration::from_secs(10),
+        send_video_until_done(done_rx, vec![track], Bytes::from(vec![0xAA; 4000]), None),
+    );
+    assert!(timeout.await.is_ok(), "no large packet got through");
+
+    assert_eq!(
+        pc_offer.sctp().transport().ice_transport().path_mtu(),
+        Some(LINK_MTU)
+    );
+    assert_eq!(
+        pc_answer.sctp().transport().ice_transport().path_mtu(),
+        Some(LINK_MTU)
+    );
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(())
+}
+
+/// Sends a video frame across a vnet pair until it's received, returning whether its payload
+/// was seen in the clear on the wire.
+async fn media_seen_in_clear(configure: fn(&mut SettingEngine)) -> Result<bool> {
+    const MARKER: &[u8] = b"plaintext media";
+
+    let (mut pc_offer, mut pc_answer, wan) = create_vnet_pair_with(configure).await?;
+    let seen_in_clear = Arc::new(AtomicBool::new(false));
+    {
+        let seen_in_clear = Arc::clone(&seen_in_clear);
+        let w = wan.lock().await;
+        w.add_chunk_filter(Box::new(move |c: &(dyn Chunk + Send + Sync)| {
+            if c.user_data().windows(MARKER.len()).any(|w| w == MARKER) {
+                seen_in_clear.store(true, Ordering::SeqCst);
+            }
+            true
+        }))
+        .await;
+    }
+
+    let track = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    let (done_tx, done_rx) = mpsc::channel::<()>(1);
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let done_tx = done_tx.clone();
+        Box::pin(async move {
+            while let Ok((pkt, _)) = track.read_rtp().await {
+                if pkt.payload.ends_with(MARKER) {
+                    let _ = done_tx.try_send(());
+                }
+            }
+        })
+    }));
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+    let timeout = tokio::time::timeout(
+        Duration::from_secs(10),
+        send_video_until_done(done_rx, vec![track], Bytes::from_static(MARKER), None),
+    );
+    assert!(timeout.await.is_ok(), "media was never received");
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(seen_in_clear.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+async fn test_srtp_null_cipher() -> Result<()> {
+    assert!(
+        media_seen_in_clear(|s| s.enable_srtp_null_cipher(true)).await?,
+        "media should be readable on the wire with the null cipher"
+    );
+    assert!(
+        !media_seen_in_clear(|_| {}).await?,
+        "media should be encrypted by default"
+    );
+
+    Ok(())
+}
+
+fn offer_mids(desc: &RTCSessionDescription) -> Result<Vec<String>> {
+    Ok(desc
+        .unmarshal()?
+        .media_descriptions
+        .iter()
+        .filter_map(|media| get_mid_value(media).map(ToOwned::to_owned))
+        .collect())
+}
+
+async fn assert_no_pending_descriptions(pc: &RTCPeerConnection) {
+    assert_eq!(pc.signaling_state(), RTCSignalingState::Stable);
+    assert!(pc.pending_local_description().await.is_none());
+    assert!(pc.pending_remote_description().await.is_none());
+}
+
+#[tokio::test]
+async fn test_rollback_media_and_data_offer() -> Result<()> {
+    let (pc_offer, pc_answer, wan) = create_vnet_pair().await?;
+
+    let track = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+    pc_offer.create_data_channel("data", None).await?;
+
+    // The answerer starts connecting to a stale peer's offer and rolls it back
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let pc_stale = api.new_peer_connection(RTCConfiguration::default()).await?;
+    pc_stale
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+    pc_stale.create_data_channel("data", None).await?;
+    let stale_offer = pc_stale.create_offer(None).await?;
+
+    let (checking_tx, mut checking_rx) = mpsc::channel::<()>(1);
+    pc_answer.on_ice_connection_state_change(Box::new(move |state| {
+        if state == RTCIceConnectionState::Checking {
+            let _ = checking_tx.try_send(());
+        }
+        Box::pin(async {})
+    }));
+    pc_answer.set_remote_description(stale_offer).await?;
+    assert_eq!(
+        pc_answer.signaling_state(),
+        RTCSignalingState::HaveRemoteOffer
+    );
+    assert_eq!(pc_answer.get_transceivers().await.len(), 1);
+    assert!(
+        tokio::time::timeout(Duration::from_secs(5), checking_rx.recv())
+            .await
+            .is_ok(),
+        "the stale offer never started ICE"
+    );
+
+    pc_answer
+        .set_remote_description(RTCSessionDescription::rollback())
+        .await?;
+    assert_no_pending_descriptions(&pc_answer).await;
+    assert!(pc_answer.remote_description().await.is_none());
+    assert!(
+        pc_answer.get_transceivers().await.is_empty(),
+        "transceivers created by the rolled back offer should be removed"
+    );
+    assert_eq!(
+        pc_answer.ice_connection_state(),
+        RTCIceConnectionState::New,
+        "transports started by the rolled back offer should be reset"
+    );
+    assert_eq!(
+        pc_answer.dtls_transport().state(),
+        RTCDtlsTransportState::New
+    );
+    pc_stale.close().await?;
+
+    // The offerer rolls back its first offer
+    let first_offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(first_offer.clone()).await?;
+    assert_eq!(
+        pc_offer.signaling_state(),
+        RTCSignalingState::HaveLocalOffer
+    );
+    pc_offer
+        .set_local_description(RTCSessionDescription::rollback())
+        .await?;
+    assert_no_pending_descriptions(&pc_offer).await;
+    assert!(pc_offer.local_description().await.is_none());
+    assert_eq!(
+        pc_offer
+            .set_local_description(RTCSessionDescription::rollback())
+            .await,
+        Err(Error::ErrSignalingStateCannotRollback)
+    );
+
+    // The next offer has the same media and data sections and ICE credentials
+    let offer = pc_offer.create_offer(None).await?;
+    assert_eq!(offer_mids(&offer)?, offer_mids(&first_offer)?);
+    assert_eq!(offer_mids(&offer)?.len(), 2);
+    let (first_ufrag, _, _) = extract_ice_details(&first_offer.unmarshal()?).await?;
+    let (ufrag, _, _) = extract_ice_details(&offer.unmarshal()?).await?;
+    assert_eq!(ufrag, first_ufrag);
+
+    let (track_tx, mut track_rx) = mpsc::channel::<()>(1);
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let track_tx = track_tx.clone();
+        Box::pin(async move {
+            if track.read_rtp().await.is_ok() {
+                let _ = track_tx.try_send(());
+            }
+        })
+    }));
+    let (open_tx, mut open_rx) = mpsc::channel::<()>(1);
+    pc_answer.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+        let open_tx = open_tx.clone();
+        Box::pin(async move {
+            d.on_open(Box::new(move || {
+                Box::pin(async move {
+                    let _ = open_tx.try_send(());
+                })
+            }));
+        })
+    }));
+
+    let mut offer_gathering_complete = pc_offer.gathering_complete_promise().await;
+    pc_offer.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+    pc_answer
+        .set_remote_description(pc_offer.local_description().await.unwrap())
+        .await?;
+    let answer = pc_answer.create_answer(None).await?;
+    let mut answer_gathering_complete = pc_answer.gathering_complete_promise().await;
+    pc_answer.set_local_description(answer).await?;
+    let _ = answer_gathering_complete.recv().await;
+    pc_offer
+        .set_remote_description(pc_answer.local_description().await.unwrap())
+        .await?;
+
+    // Both media and data flow over the renegotiated transports
+    let (done_tx, done_rx) = mpsc::channel::<()>(1);
+    let media_and_data = async move {
+        let _ = track_rx.recv().await;
+        let _ = open_rx.recv().await;
+        let _ = done_tx.send(()).await;
+    };
+    let timeout = tokio::time::timeout(Duration::from_secs(10), async {
+        tokio::join!(
+            media_and_data,
+            send_video_until_done(done_rx, vec![track], Bytes::from_static(&[0xAA]), None)
+        )
+    });
+    assert!(timeout.await.is_ok(), "media and data never flowed");
+
+    for pc in [&pc_offer, &pc_answer] {
+        assert_no_pending_descriptions(pc).await;
+        assert!(pc.current_local_description().await.is_some());
+        assert!(pc.current_remote_description().await.is_some());
+    }
+    assert_eq!(pc_answer.get_transceivers().await.len(), 1);
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_stats() -> Result<()> {
     let mut m = MediaEngine::default();
@@ -376,6 +897,7 @@ async fn test_get_stats() -> Result<()> {
     assert_eq!(inbound_stats.kind, "video");
     assert_eq!(inbound_stats.bytes_received, 8);
     assert_eq!(inbound_stats.header_bytes_received, 12);
+    assert_eq!(inbound_stats.jitter, 0.0);
 
     close_pair_now(&pc_offer, &pc_answer).await;
 
@@ -383,25 +905,107 @@ async fn test_get_stats() -> Result<()> {
 }
 
 #[tokio::test]
-async fn test_peer_connection_close_is_send() -> Result<()> {
-    let handle = tokio::spawn(async move { peer().await });
-    tokio::join!(handle).0.unwrap()
-}
-
-async fn peer() -> Result<()> {
+async fn test_get_stats_sequence_gaps() -> Result<()> {
     let mut m = MediaEngine::default();
     m.register_default_codecs()?;
-    let mut registry = Registry::new();
-    registry = register_default_interceptors(registry, &mut m)?;
-    let api = APIBuilder::new()
-        .with_media_engine(m)
-        .with_interceptor_registry(registry)
-        .build();
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let (mut pc_offer, mut pc_answer) = new_pair(&api).await?;
 
-    let config = RTCConfiguration {
-        ice_servers: vec![RTCIceServer {
-            urls: vec!["stun:stun.l.google.com:19302".to_owned()],
-            ..Default::default()
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    let (received_tx, mut received_rx) = mpsc::unbounded_channel();
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let received_tx = received_tx.clone();
+        Box::pin(async move {
+            while let Ok((pkt, _)) = track.read_rtp().await {
+                let _ = received_tx.send(pkt.header.sequence_number);
+            }
+        })
+    }));
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+    let packet = |sequence_number| rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            sequence_number,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0xaa]),
+    };
+
+    // Resend the first packet until the track is read.
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    loop {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for on_track"),
+            Some(_) = received_rx.recv() => break,
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                track.write_rtp(&packet(0)).await?;
+            }
+        }
+    }
+
+    // One gap of a single lost packet, one of two and one of three.
+    for sequence_number in [2, 3, 6, 7, 11] {
+        track.write_rtp(&packet(sequence_number)).await?;
+    }
+    loop {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for RTP"),
+            Some(sequence_number) = received_rx.recv() => if sequence_number == 11 {
+                break;
+            },
+        }
+    }
+
+    let answer_stats = pc_answer.get_stats().await;
+    let inbound_stats = answer_stats
+        .reports
+        .values()
+        .find_map(|v| match v {
+            StatsReportType::InboundRTP(d) => Some(d),
+            _ => None,
+        })
+        .expect("Should have produced an RTP inbound stat");
+    assert_eq!(inbound_stats.single_loss_gaps, 1);
+    assert_eq!(inbound_stats.double_loss_gaps, 1);
+    assert_eq!(inbound_stats.burst_loss_gaps, 1);
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_peer_connection_close_is_send() -> Result<()> {
+    let handle = tokio::spawn(async move { peer().await });
+    tokio::join!(handle).0.unwrap()
+}
+
+async fn peer() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let mut registry = Registry::new();
+    registry = register_default_interceptors(registry, &mut m)?;
+    let api = APIBuilder::new()
+        .with_media_engine(m)
+        .with_interceptor_registry(registry)
+        .build();
+
+    let config = RTCConfiguration {
+        ice_servers: vec![RTCIceServer {
+            urls: vec!["stun:stun.l.google.com:19302".to_owned()],
+            ..Default::default()
         }],
         ..Default::default()
     };
@@ -421,3 +1025,1530 @@ async fn peer() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_get_local_candidates() -> Result<()> {
+    let (pc_offer, pc_answer, _wan) = create_vnet_pair().await?;
+
+    pc_offer
+        .create_data_channel("initial_data_channel", None)
+        .await?;
+
+    let offer = pc_offer.create_offer(None).await?;
+    let mut offer_gathering_complete = pc_offer.gathering_complete_promise().await;
+    pc_offer.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+
+    let candidates = pc_offer.get_local_candidates().await?;
+    let host = candidates
+        .iter()
+        .find(|c| c.typ == RTCIceCandidateType::Host && c.address == "1.2.3.4")
+        .expect("expected a host candidate for the offerer's static ip");
+
+    assert_eq!(host.protocol, RTCIceProtocol::Udp);
+    assert_eq!(host.component, 1);
+    // type preference 126, local preference 65535, component 1
+    assert_eq!(host.priority, (126 << 24) + (65535 << 8) + (256 - 1));
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_offer_and_gather() -> Result<()> {
+    let (pc_offer, pc_answer, _wan) = create_vnet_pair().await?;
+
+    pc_offer
+        .create_data_channel("initial_data_channel", None)
+        .await?;
+
+    let offer = pc_offer.create_offer_and_gather(None).await?;
+
+    assert_eq!(offer.sdp_type, RTCSdpType::Offer);
+    assert_eq!(
+        pc_offer.ice_gathering_state(),
+        RTCIceGatheringState::Complete
+    );
+
+    let parsed = offer.unmarshal()?;
+    let candidates: Vec<_> = parsed
+        .media_descriptions
+        .iter()
+        .flat_map(|m| m.attributes.iter())
+        .filter(|a| a.key == "candidate")
+        .collect();
+    assert!(!candidates.is_empty(), "expected candidates in the offer");
+    assert!(offer.sdp.contains("a=end-of-candidates"));
+
+    pc_answer.set_remote_description(offer).await?;
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_offer_with_cancel() -> Result<()> {
+    let cancel = RTCCancellationToken::new();
+
+    // Cancel from within offer generation, which picks a mid for the new transceiver.
+    let mut s = SettingEngine::default();
+    let cancel_mid_generation = cancel.clone();
+    s.set_mid_generator(move |greatest| {
+        cancel_mid_generation.cancel();
+        format!("{}", greatest + 1)
+    });
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new()
+        .with_media_engine(m)
+        .with_setting_engine(s)
+        .build();
+    let (pc_offer, pc_answer) = new_pair(&api).await?;
+
+    pc_offer
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+
+    let result = pc_offer.create_offer_with_cancel(None, &cancel).await;
+    assert_eq!(result.unwrap_err(), Error::ErrCreateCancelled);
+    assert!(cancel.is_cancelled());
+
+    // The token stays cancelled, while a new one lets generation go through.
+    let result = pc_offer.create_offer_with_cancel(None, &cancel).await;
+    assert_eq!(result.unwrap_err(), Error::ErrCreateCancelled);
+    let offer = pc_offer
+        .create_offer_with_cancel(None, &RTCCancellationToken::new())
+        .await?;
+
+    pc_offer.set_local_description(offer.clone()).await?;
+    pc_answer.set_remote_description(offer).await?;
+    let result = pc_answer.create_answer_with_cancel(None, &cancel).await;
+    assert_eq!(result.unwrap_err(), Error::ErrCreateCancelled);
+    pc_answer.create_answer(None).await?;
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ice_lite_gathers_host_candidates_only() -> Result<()> {
+    let (vnet, wan) = create_vnet().await?;
+
+    let mut setting_engine = SettingEngine::default();
+    setting_engine.set_vnet(Some(vnet));
+    setting_engine.set_lite(true);
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let pc = APIBuilder::new()
+        .with_setting_engine(setting_engine)
+        .with_media_engine(m)
+        .build()
+        .new_peer_connection(RTCConfiguration {
+            // Lite agents must not contact ICE servers, even if some are configured.
+            ice_servers: vec![RTCIceServer {
+                urls: vec!["stun:1.2.3.100:3478".to_owned()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .await?;
+
+    pc.create_data_channel("initial_data_channel", None).await?;
+
+    let offer = pc.create_offer_and_gather(None).await?;
+    assert!(offer.sdp.contains("a=ice-lite"));
+
+    let candidates = pc.get_local_candidates().await?;
+    assert!(!candidates.is_empty());
+    assert!(candidates
+        .iter()
+        .all(|c| c.typ == RTCIceCandidateType::Host));
+
+    pc.close().await?;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_gathering_completes_with_host_candidates() -> Result<()> {
+    let (vnet, wan) = create_vnet().await?;
+
+    let mut setting_engine = SettingEngine::default();
+    setting_engine.set_vnet(Some(vnet));
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let pc = APIBuilder::new()
+        .with_setting_engine(setting_engine)
+        .with_media_engine(m)
+        .build()
+        .new_peer_connection(RTCConfiguration {
+            // Nothing answers at this address, so the srflx probe only ends with its timeout.
+            ice_servers: vec![RTCIceServer {
+                urls: vec!["stun:1.2.3.100:3478".to_owned()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .await?;
+
+    let (host_tx, mut host_rx) = mpsc::channel::<()>(1);
+    pc.on_ice_candidate(Box::new(move |c: Option<RTCIceCandidate>| {
+        let host_tx = host_tx.clone();
+        Box::pin(async move {
+            if c.is_some_and(|c| c.typ == RTCIceCandidateType::Host) {
+                let _ = host_tx.try_send(());
+            }
+        })
+    }));
+
+    pc.create_data_channel("initial_data_channel", None).await?;
+
+    let offer = pc.create_offer(None).await?;
+    let mut gathering_complete = pc.gathering_complete_promise().await;
+    pc.set_local_description(offer).await?;
+
+    let _ = host_rx.recv().await;
+    pc.internal.ice_gatherer.stop_gathering().await;
+
+    tokio::time::timeout(Duration::from_secs(1), gathering_complete.recv())
+        .await
+        .expect("gathering should complete right after it is stopped");
+    assert_eq!(pc.ice_gathering_state(), RTCIceGatheringState::Complete);
+
+    let candidates = pc.get_local_candidates().await?;
+    assert!(!candidates.is_empty());
+    assert!(candidates
+        .iter()
+        .all(|c| c.typ == RTCIceCandidateType::Host));
+
+    pc.close().await?;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_answer_rejects_media_section_without_common_codec() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_codec(
+        RTCRtpCodecParameters {
+            capability: RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_OPUS.to_owned(),
+                clock_rate: 48000,
+                channels: 2,
+                sdp_fmtp_line: "minptime=10;useinbandfec=1".to_owned(),
+                rtcp_feedback: vec![],
+            },
+            payload_type: 111,
+            ..Default::default()
+        },
+        RTPCodecType::Audio,
+    )?;
+    m.register_codec(
+        RTCRtpCodecParameters {
+            capability: RTCRtpCodecCapability {
+                mime_type: "video/H265".to_owned(),
+                clock_rate: 90000,
+                channels: 0,
+                sdp_fmtp_line: String::new(),
+                rtcp_feedback: vec![],
+            },
+            payload_type: 98,
+            ..Default::default()
+        },
+        RTPCodecType::Video,
+    )?;
+    let pc_offer = APIBuilder::new()
+        .with_media_engine(m)
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let pc_answer = APIBuilder::new()
+        .with_media_engine(m)
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+
+    pc_offer
+        .add_transceiver_from_kind(RTPCodecType::Audio, None)
+        .await?;
+    pc_offer
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+
+    // The answerer wants to send video, but can't since it doesn't support H265.
+    let track = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_answer.add_track(track).await?;
+
+    let offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(offer.clone()).await?;
+    pc_answer.set_remote_description(offer).await?;
+
+    let answer = pc_answer.create_answer(None).await?;
+    let parsed = answer.unmarshal()?;
+    assert_eq!(parsed.media_descriptions.len(), 2);
+
+    for t in pc_answer.get_transceivers().await {
+        match t.kind() {
+            RTPCodecType::Video => assert_eq!(t.rejection(), Some(Error::ErrNoCommonCodec)),
+            _ => assert_eq!(t.rejection(), None),
+        }
+    }
+
+    let audio = &parsed.media_descriptions[0];
+    assert_eq!(audio.media_name.media, "audio");
+    assert_ne!(audio.media_name.port.value, 0);
+    assert_eq!(audio.media_name.formats, vec!["111".to_owned()]);
+
+    let video = &parsed.media_descriptions[1];
+    assert_eq!(video.media_name.media, "video");
+    assert_eq!(
+        video.media_name.port.value, 0,
+        "video without a common codec should be rejected"
+    );
+
+    pc_answer.set_local_description(answer.clone()).await?;
+    pc_offer.set_remote_description(answer).await?;
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_offer_without_transport_cc_disables_twcc_sender() -> Result<()> {
+    // The offerer negotiates the transport wide sequence number extension, but not the
+    // transport-cc feedback.
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    m.register_header_extension(
+        RTCRtpHeaderExtensionCapability {
+            uri: ::sdp::extmap::TRANSPORT_CC_URI.to_owned(),
+        },
+        RTPCodecType::Video,
+        None,
+    )?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let mut pc_offer = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let registry = configure_twcc_sender_only(Registry::new(), &mut m)?;
+    let api = APIBuilder::new()
+        .with_media_engine(m)
+        .with_interceptor_registry(registry)
+        .build();
+    let mut pc_answer = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    pc_offer
+        .add_transceiver_from_kind(
+            RTPCodecType::Video,
+            Some(RTCRtpTransceiverInit {
+                direction: RTCRtpTransceiverDirection::Recvonly,
+                send_encodings: vec![],
+            }),
+        )
+        .await?;
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_answer.add_track(Arc::clone(&track) as _).await?;
+
+    let (header_tx, mut header_rx) = mpsc::channel(1);
+    pc_offer.on_track(Box::new(move |track, _, _| {
+        let header_tx = header_tx.clone();
+        Box::pin(async move {
+            while let Ok((pkt, _)) = track.read_rtp().await {
+                let _ = header_tx.try_send(pkt.header);
+            }
+        })
+    }));
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+    let offer = pc_offer.local_description().await.unwrap().unmarshal()?;
+    let video = &offer.media_descriptions[0];
+    assert!(
+        !video.attributes.iter().any(
+            |a| a.key == "rtcp-fb" && a.value.as_deref().unwrap_or("").contains("transport-cc")
+        ),
+        "the offer should not negotiate transport-cc"
+    );
+    let twcc_id = video
+        .attributes
+        .iter()
+        .filter(|a| a.key == "extmap")
+        .filter_map(|a| a.value.as_deref())
+        .find_map(|v| {
+            let (id, uri) = v.split_once(' ')?;
+            (uri == ::sdp::extmap::TRANSPORT_CC_URI).then(|| id.parse::<u8>().ok())?
+        })
+        .expect("the offer should negotiate the transport wide sequence number extension");
+
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    let header = loop {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for RTP"),
+            Some(header) = header_rx.recv() => break header,
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                track
+                    .write_rtp(&rtp::packet::Packet {
+                        header: rtp::header::Header {
+                            version: 2,
+                            ..Default::default()
+                        },
+                        payload: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+                    })
+                    .await?;
+            }
+        }
+    };
+
+    assert!(
+        header.get_extension(twcc_id).is_none(),
+        "no transport wide sequence number should be stamped without transport-cc"
+    );
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+async fn renegotiate(pc_offer: &RTCPeerConnection, pc_answer: &RTCPeerConnection) -> Result<()> {
+    let offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(offer.clone()).await?;
+    pc_answer.set_remote_description(offer).await?;
+
+    let answer = pc_answer.create_answer(None).await?;
+    pc_answer.set_local_description(answer.clone()).await?;
+    pc_offer.set_remote_description(answer).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pause_resume() -> Result<()> {
+    let (mut pc_offer, mut pc_answer, _wan) = create_vnet_pair().await?;
+
+    let mut tracks = vec![];
+    for (mime_type, kind) in [(MIME_TYPE_OPUS, "audio"), (MIME_TYPE_VP8, "video")] {
+        let track = Arc::new(TrackLocalStaticRTP::new(
+            RTCRtpCodecCapability {
+                mime_type: mime_type.to_owned(),
+                ..Default::default()
+            },
+            kind.to_owned(),
+            "webrtc-rs".to_owned(),
+        ));
+        pc_offer.add_track(Arc::clone(&track) as _).await?;
+        tracks.push(track);
+    }
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_offer, &wg, RTCPeerConnectionState::Connected).await;
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    wg.wait().await;
+
+    let pkt = rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+    };
+
+    for track in &tracks {
+        assert_ne!(track.write_rtp(&pkt).await?, 0, "RTP should be sent");
+    }
+
+    pc_offer.pause().await?;
+
+    // Sending stops right away, before renegotiating.
+    for track in &tracks {
+        assert_eq!(track.write_rtp(&pkt).await?, 0, "RTP should not be sent");
+    }
+
+    renegotiate(&pc_offer, &pc_answer).await?;
+
+    for t in pc_offer.get_transceivers().await {
+        assert!(!t.current_direction().has_send());
+    }
+    for track in &tracks {
+        assert_eq!(track.write_rtp(&pkt).await?, 0, "RTP should not be sent");
+    }
+
+    pc_offer.resume().await?;
+    renegotiate(&pc_offer, &pc_answer).await?;
+
+    for t in pc_offer.get_transceivers().await {
+        assert!(t.current_direction().has_send());
+    }
+    for track in &tracks {
+        assert_ne!(track.write_rtp(&pkt).await?, 0, "RTP should be sent");
+    }
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_inactive_transceiver_renegotiation() -> Result<()> {
+    let (mut pc_offer, mut pc_answer, _wan) = create_vnet_pair().await?;
+
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    let (packet_tx, mut packet_rx) = mpsc::unbounded_channel();
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let packet_tx = packet_tx.clone();
+        tokio::spawn(async move {
+            while let Ok((pkt, _)) = track.read_rtp().await {
+                let _ = packet_tx.send(pkt.header.sequence_number);
+            }
+        });
+        Box::pin(async {})
+    }));
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_offer, &wg, RTCPeerConnectionState::Connected).await;
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    wg.wait().await;
+
+    let mut sequence_number = 0u16;
+    let write = |sequence_number| {
+        let pkt = rtp::packet::Packet {
+            header: rtp::header::Header {
+                version: 2,
+                sequence_number,
+                ..Default::default()
+            },
+            payload: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+        };
+        let track = Arc::clone(&track);
+        async move { track.write_rtp(&pkt).await }
+    };
+
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    loop {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for media"),
+            _ = packet_rx.recv() => break,
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                sequence_number += 1;
+                write(sequence_number).await?;
+            }
+        }
+    }
+
+    let offer_transceiver = pc_offer.get_transceivers().await[0].clone();
+    let negotiated_direction = offer_transceiver.current_direction();
+    assert!(negotiated_direction.has_send());
+    offer_transceiver
+        .set_direction(RTCRtpTransceiverDirection::Inactive)
+        .await;
+    renegotiate(&pc_offer, &pc_answer).await?;
+
+    assert_eq!(
+        offer_transceiver.current_direction(),
+        RTCRtpTransceiverDirection::Inactive
+    );
+    for t in pc_answer.get_transceivers().await {
+        assert_eq!(t.current_direction(), RTCRtpTransceiverDirection::Inactive);
+    }
+    assert!(pc_answer
+        .local_description()
+        .await
+        .is_some_and(|d| d.sdp.contains("a=inactive")));
+
+    // Let what was in flight arrive, then nothing more may get through.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    while packet_rx.try_recv().is_ok() {}
+    for _ in 0..5 {
+        sequence_number += 1;
+        assert_eq!(
+            write(sequence_number).await?,
+            0,
+            "RTP should not be sent while inactive"
+        );
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(packet_rx.try_recv().is_err(), "RTP received while inactive");
+
+    offer_transceiver
+        .set_direction(RTCRtpTransceiverDirection::Sendrecv)
+        .await;
+    renegotiate(&pc_offer, &pc_answer).await?;
+
+    assert_eq!(offer_transceiver.current_direction(), negotiated_direction);
+    let resumed_from = sequence_number;
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    let received = loop {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for media to resume"),
+            seq = packet_rx.recv() => break seq,
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                sequence_number += 1;
+                write(sequence_number).await?;
+            }
+        }
+    };
+    assert!(received.is_some_and(|seq| seq > resumed_from));
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_inactive_answer_starts_no_receiver() -> Result<()> {
+    let (mut pc_offer, pc_answer, _wan) = create_vnet_pair().await?;
+
+    let mut tracks = vec![];
+    for pc in [&pc_offer, &pc_answer] {
+        let track = Arc::new(TrackLocalStaticRTP::new(
+            RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_VP8.to_owned(),
+                ..Default::default()
+            },
+            "video".to_owned(),
+            "webrtc-rs".to_owned(),
+        ));
+        pc.add_track(Arc::clone(&track) as _).await?;
+        tracks.push(track);
+    }
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_offer, &wg, RTCPeerConnectionState::Connected).await;
+
+    let offer = pc_offer.create_offer(None).await?;
+    let mut offer_gathering_complete = pc_offer.gathering_complete_promise().await;
+    pc_offer.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+    pc_answer
+        .set_remote_description(pc_offer.local_description().await.unwrap())
+        .await?;
+    for t in pc_answer.get_transceivers().await {
+        t.set_direction(RTCRtpTransceiverDirection::Inactive).await;
+    }
+    let answer = pc_answer.create_answer(None).await?;
+    assert!(answer.sdp.contains("a=inactive"));
+    assert!(answer.sdp.contains("a=ssrc:"));
+    let mut answer_gathering_complete = pc_answer.gathering_complete_promise().await;
+    pc_answer.set_local_description(answer).await?;
+    let _ = answer_gathering_complete.recv().await;
+    pc_offer
+        .set_remote_description(pc_answer.local_description().await.unwrap())
+        .await?;
+    wg.wait().await;
+
+    // The answerer's SSRCs are declared, but nothing is received on an inactive section.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    for pc in [&pc_offer, &pc_answer] {
+        for t in pc.get_transceivers().await {
+            assert_eq!(t.current_direction(), RTCRtpTransceiverDirection::Inactive);
+            assert!(!t.receiver().await.have_received().await);
+        }
+    }
+
+    // Once the answerer enables the section again, its media gets through.
+    let (track_tx, mut track_rx) = mpsc::channel(1);
+    pc_offer.on_track(Box::new(move |track, _, _| {
+        let track_tx = track_tx.clone();
+        Box::pin(async move {
+            let _ = track_tx.try_send(track.ssrc());
+        })
+    }));
+    let answer_transceiver = pc_answer.get_transceivers().await[0].clone();
+    answer_transceiver
+        .set_direction(RTCRtpTransceiverDirection::Sendrecv)
+        .await;
+    renegotiate(&pc_offer, &pc_answer).await?;
+    assert_eq!(
+        answer_transceiver.current_direction(),
+        RTCRtpTransceiverDirection::Sendrecv
+    );
+
+    let answer_ssrc = answer_transceiver
+        .sender()
+        .await
+        .get_parameters()
+        .await
+        .encodings[0]
+        .ssrc;
+    let pkt = rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+    };
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    let received_ssrc = loop {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for on_track"),
+            ssrc = track_rx.recv() => break ssrc,
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                tracks[1].write_rtp(&pkt).await?;
+            }
+        }
+    };
+    assert_eq!(received_ssrc, Some(answer_ssrc));
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_receive_before_local_answer() -> Result<()> {
+    let (mut pc_offer, pc_answer, _wan) = create_vnet_pair().await?;
+
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    let sender = pc_offer.add_track(Arc::clone(&track) as _).await?;
+    let offered_ssrc = sender.get_parameters().await.encodings[0].ssrc;
+
+    let (track_tx, mut track_rx) = mpsc::channel(1);
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let track_tx = track_tx.clone();
+        Box::pin(async move {
+            let _ = track_tx.try_send(track.ssrc());
+        })
+    }));
+
+    let offer = pc_offer.create_offer(None).await?;
+    let mut offer_gathering_complete = pc_offer.gathering_complete_promise().await;
+    pc_offer.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+
+    pc_answer
+        .set_remote_description(pc_offer.local_description().await.unwrap())
+        .await?;
+
+    // Gather and signal the answer without ever applying it locally.
+    let mut answer_gathering_complete = pc_answer.gathering_complete_promise().await;
+    pc_answer.internal.ice_gatherer.gather().await?;
+    let _ = answer_gathering_complete.recv().await;
+    let answer = pc_answer.create_answer(None).await?;
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_offer, &wg, RTCPeerConnectionState::Connected).await;
+    pc_offer.set_remote_description(answer).await?;
+    wg.wait().await;
+
+    let pkt = rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+    };
+
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    let received_ssrc = loop {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for on_track"),
+            ssrc = track_rx.recv() => break ssrc,
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                track.write_rtp(&pkt).await?;
+            }
+        }
+    };
+
+    assert_eq!(received_ssrc, Some(offered_ssrc));
+    assert!(pc_answer.local_description().await.is_none());
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bundle_routes_undeclared_ssrcs_by_mid() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    m.register_header_extension(
+        RTCRtpHeaderExtensionCapability {
+            uri: ::sdp::extmap::SDES_MID_URI.to_owned(),
+        },
+        RTPCodecType::Video,
+        None,
+    )?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let (pc_offer, pc_answer) = new_pair(&api).await?;
+
+    let mut tracks = vec![];
+    let mut mids = HashMap::new();
+    for (i, marker) in [0xaau8, 0xbb].into_iter().enumerate() {
+        let track = Arc::new(TrackLocalStaticRTP::new(
+            RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_VP8.to_owned(),
+                ..Default::default()
+            },
+            format!("video{i}"),
+            "webrtc-rs".to_owned(),
+        ));
+        pc_offer.add_track(Arc::clone(&track) as _).await?;
+        tracks.push((track, marker));
+    }
+
+    let (track_tx, mut track_rx) = mpsc::channel(2);
+    pc_answer.on_track(Box::new(move |track, _, transceiver| {
+        let track_tx = track_tx.clone();
+        Box::pin(async move {
+            if let Ok((pkt, _)) = track.read_rtp().await {
+                let _ = track_tx.send((transceiver.mid(), pkt.payload[0])).await;
+            }
+        })
+    }));
+
+    pc_offer
+        .create_data_channel("initial_data_channel", None)
+        .await?;
+    let offer = pc_offer.create_offer(None).await?;
+    let mut offer_gathering_complete = pc_offer.gathering_complete_promise().await;
+    pc_offer.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+
+    // Strip the SSRC declarations so the answerer has to route by MID.
+    let mut offer = pc_offer.local_description().await.unwrap();
+    offer.sdp = offer
+        .sdp
+        .lines()
+        .filter(|line| !line.starts_with("a=ssrc"))
+        .map(|line| format!("{line}\r\n"))
+        .collect();
+    pc_answer.set_remote_description(offer).await?;
+
+    for t in pc_offer.get_transceivers().await {
+        if let Some(track) = t.sender().await.track().await {
+            mids.insert(track.id().to_owned(), t.mid().unwrap());
+        }
+    }
+
+    let answer = pc_answer.create_answer(None).await?;
+    let mut answer_gathering_complete = pc_answer.gathering_complete_promise().await;
+    pc_answer.set_local_description(answer).await?;
+    let _ = answer_gathering_complete.recv().await;
+    pc_offer
+        .set_remote_description(pc_answer.local_description().await.unwrap())
+        .await?;
+
+    let timeout = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(timeout);
+    let mut received = HashMap::new();
+    while received.len() < tracks.len() {
+        tokio::select! {
+            _ = timeout.as_mut() => panic!("timed out waiting for on_track"),
+            Some((mid, marker)) = track_rx.recv() => {
+                received.insert(marker, mid.unwrap());
+            }
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {
+                for (track, marker) in &tracks {
+                    track
+                        .write_rtp(&rtp::packet::Packet {
+                            header: rtp::header::Header {
+                                version: 2,
+                                ..Default::default()
+                            },
+                            payload: Bytes::from(vec![*marker]),
+                        })
+                        .await?;
+                }
+            }
+        }
+    }
+
+    for (track, marker) in &tracks {
+        assert_eq!(received.get(marker), mids.get(track.id()));
+    }
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+/// undeclared_ssrc_media_engine returns a MediaEngine that negotiates the MID header extension
+/// the answerer needs to probe undeclared SSRCs.
+fn undeclared_ssrc_media_engine() -> Result<MediaEngine> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    m.register_header_extension(
+        RTCRtpHeaderExtensionCapability {
+            uri: ::sdp::extmap::SDES_MID_URI.to_owned(),
+        },
+        RTPCodecType::Video,
+        None,
+    )?;
+    Ok(m)
+}
+
+/// signal_pair_with_undeclared_ssrcs negotiates the pair with every media section announced as
+/// simulcast without declaring its SSRC, so each probe of the answerer waits for a RID that
+/// never comes.
+async fn signal_pair_with_undeclared_ssrcs(
+    pc_offer: &RTCPeerConnection,
+    pc_answer: &RTCPeerConnection,
+) -> Result<()> {
+    let offer = pc_offer.create_offer(None).await?;
+    let mut offer_gathering_complete = pc_offer.gathering_complete_promise().await;
+    pc_offer.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+
+    let mut offer = pc_offer.local_description().await.unwrap();
+    offer.sdp = offer
+        .sdp
+        .lines()
+        .filter(|line| !line.starts_with("a=ssrc"))
+        .flat_map(|line| {
+            let rid = line.starts_with("a=mid:").then_some("a=rid:a send\r\n");
+            std::iter::once(format!("{line}\r\n")).chain(rid.map(str::to_owned))
+        })
+        .collect();
+    pc_answer.set_remote_description(offer).await?;
+
+    let answer = pc_answer.create_answer(None).await?;
+    let mut answer_gathering_complete = pc_answer.gathering_complete_promise().await;
+    pc_answer.set_local_description(answer).await?;
+    let _ = answer_gathering_complete.recv().await;
+    pc_offer
+        .set_remote_description(pc_answer.local_description().await.unwrap())
+        .await
+}
+
+fn probe_packet(sequence_number: u16) -> rtp::packet::Packet {
+    rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            sequence_number,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0xaa]),
+    }
+}
+
+async fn wait_for_probe_routines(pc: &RTCPeerConnection, count: u64) {
+    let wait = async {
+        while pc.internal.simulcast_probe_routines.load(Ordering::SeqCst) != count {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    if tokio::time::timeout(Duration::from_secs(5), wait)
+        .await
+        .is_err()
+    {
+        panic!("timed out waiting for {count} simulcast probe routines");
+    }
+}
+
+#[tokio::test]
+async fn test_undeclared_ssrc_probes_are_capped() -> Result<()> {
+    const MAX_PROBE_ROUTINES: u64 = 2;
+
+    let m = undeclared_ssrc_media_engine()?;
+    let mut s = SettingEngine::default();
+    s.set_simulcast_max_probe_routines(MAX_PROBE_ROUTINES);
+    let api = APIBuilder::new()
+        .with_media_engine(m)
+        .with_setting_engine(s)
+        .build();
+    let (pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+    let mut tracks = vec![];
+    let mut ssrcs = vec![];
+    for i in 0..=MAX_PROBE_ROUTINES {
+        let track = Arc::new(TrackLocalStaticRTP::new(
+            RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_VP8.to_owned(),
+                ..Default::default()
+            },
+            format!("video{i}"),
+            "webrtc-rs".to_owned(),
+        ));
+        let sender = pc_offer.add_track(Arc::clone(&track) as _).await?;
+        ssrcs.push(sender.get_parameters().await.encodings[0].ssrc);
+        tracks.push(track);
+    }
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_answer, &wg, RTCPeerConnectionState::Connected).await;
+
+    signal_pair_with_undeclared_ssrcs(&pc_offer, &pc_answer).await?;
+
+    wg.wait().await;
+
+    let simulcast_ssrcs = || async {
+        let mut ssrcs: Vec<SSRC> = pc_answer
+            .internal
+            .dtls_transport
+            .simulcast_streams
+            .lock()
+            .await
+            .keys()
+            .copied()
+            .collect();
+        ssrcs.sort();
+        ssrcs
+    };
+
+    // Fill every probe slot.
+    for track in &tracks[..MAX_PROBE_ROUTINES as usize] {
+        track.write_rtp(&probe_packet(0)).await?;
+    }
+    wait_for_probe_routines(&pc_answer, MAX_PROBE_ROUTINES).await;
+
+    // The stream of the SSRC above the cap is dropped instead of probed.
+    let extra = &tracks[MAX_PROBE_ROUTINES as usize];
+    for seq in 0..5 {
+        extra.write_rtp(&probe_packet(seq)).await?;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(
+        pc_answer
+            .internal
+            .simulcast_probe_routines
+            .load(Ordering::SeqCst),
+        MAX_PROBE_ROUTINES
+    );
+    let mut probed = ssrcs[..MAX_PROBE_ROUTINES as usize].to_vec();
+    probed.sort();
+    assert_eq!(simulcast_ssrcs().await, probed);
+
+    // Once the probes give up, the slots are free again.
+    for track in &tracks[..MAX_PROBE_ROUTINES as usize] {
+        for seq in 1..=SIMULCAST_PROBE_COUNT as u16 + 1 {
+            track.write_rtp(&probe_packet(seq)).await?;
+        }
+    }
+    wait_for_probe_routines(&pc_answer, 0).await;
+    extra.write_rtp(&probe_packet(5)).await?;
+    wait_for_probe_routines(&pc_answer, 1).await;
+    assert_eq!(
+        simulcast_ssrcs().await,
+        vec![ssrcs[MAX_PROBE_ROUTINES as usize]]
+    );
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undeclared_ssrc_probe_is_released() -> Result<()> {
+    let m = undeclared_ssrc_media_engine()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let (pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    let on_track_fired = Arc::new(AtomicBool::new(false));
+    let on_track_fired2 = Arc::clone(&on_track_fired);
+    pc_answer.on_track(Box::new(move |_, _, _| {
+        on_track_fired2.store(true, Ordering::SeqCst);
+        Box::pin(async {})
+    }));
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_answer, &wg, RTCPeerConnectionState::Connected).await;
+
+    signal_pair_with_undeclared_ssrcs(&pc_offer, &pc_answer).await?;
+
+    wg.wait().await;
+
+    // The first packet starts the probe, which then reads SIMULCAST_PROBE_COUNT + 1 more.
+    track.write_rtp(&probe_packet(0)).await?;
+    wait_for_probe_routines(&pc_answer, 1).await;
+    for seq in 1..=SIMULCAST_PROBE_COUNT as u16 + 1 {
+        track.write_rtp(&probe_packet(seq)).await?;
+    }
+    wait_for_probe_routines(&pc_answer, 0).await;
+
+    assert!(pc_answer
+        .internal
+        .dtls_transport
+        .simulcast_streams
+        .lock()
+        .await
+        .is_empty());
+    assert!(!on_track_fired.load(Ordering::SeqCst));
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unhandled_track_buffer_handler_set_during_read() -> Result<()> {
+    const PACKETS: u8 = 5;
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let mut s = SettingEngine::default();
+    s.set_unhandled_track_buffer(PACKETS as usize * 2);
+    let api = APIBuilder::new()
+        .with_media_engine(m)
+        .with_setting_engine(s)
+        .build();
+    let (mut pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_answer, &wg, RTCPeerConnectionState::Connected).await;
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    wg.wait().await;
+
+    let write_packet = |i: u8| {
+        let track = Arc::clone(&track);
+        async move {
+            track
+                .write_rtp(&rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        version: 2,
+                        sequence_number: i as u16,
+                        ..Default::default()
+                    },
+                    payload: Bytes::from(vec![i]),
+                })
+                .await
+        }
+    };
+
+    // The first packet announces the track, which is then waiting for the second one.
+    write_packet(0).await?;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let (payload_tx, mut payload_rx) = mpsc::channel(PACKETS as usize);
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let payload_tx = payload_tx.clone();
+        Box::pin(async move {
+            while let Ok((pkt, _)) = track.read_rtp().await {
+                let _ = payload_tx.send(pkt.payload[0]).await;
+            }
+        })
+    }));
+
+    for i in 1..PACKETS {
+        write_packet(i).await?;
+    }
+
+    for i in 0..PACKETS {
+        let payload = tokio::time::timeout(Duration::from_secs(5), payload_rx.recv())
+            .await
+            .expect("timed out waiting for a packet");
+        assert_eq!(payload, Some(i));
+    }
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unhandled_track_buffer_delivers_initial_packets() -> Result<()> {
+    const PACKETS: u8 = 5;
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let mut s = SettingEngine::default();
+    s.set_unhandled_track_buffer(PACKETS as usize);
+    let api = APIBuilder::new()
+        .with_media_engine(m)
+        .with_setting_engine(s)
+        .build();
+    let (mut pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+    let track = Arc::new(TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer.add_track(Arc::clone(&track) as _).await?;
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_answer, &wg, RTCPeerConnectionState::Connected).await;
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    wg.wait().await;
+
+    for i in 0..PACKETS {
+        track
+            .write_rtp(&rtp::packet::Packet {
+                header: rtp::header::Header {
+                    version: 2,
+                    sequence_number: i as u16,
+                    ..Default::default()
+                },
+                payload: Bytes::from(vec![i]),
+            })
+            .await?;
+    }
+    // Give the track time to arrive while nobody listens for it.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let (payload_tx, mut payload_rx) = mpsc::channel(PACKETS as usize);
+    pc_answer.on_track(Box::new(move |track, _, _| {
+        let payload_tx = payload_tx.clone();
+        Box::pin(async move {
+            while let Ok((pkt, _)) = track.read_rtp().await {
+                let _ = payload_tx.send(pkt.payload[0]).await;
+            }
+        })
+    }));
+
+    for i in 0..PACKETS {
+        let payload = tokio::time::timeout(Duration::from_secs(5), payload_rx.recv())
+            .await
+            .expect("timed out waiting for a buffered packet");
+        assert_eq!(payload, Some(i));
+    }
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remote_answer_with_actpass_dtls_role() -> Result<()> {
+    let pc_offer = APIBuilder::new()
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+    let mut s = SettingEngine::default();
+    s.set_lite(true);
+    let pc_answer = APIBuilder::new()
+        .with_setting_engine(s)
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+
+    pc_offer
+        .create_data_channel("initial_data_channel", None)
+        .await?;
+    let offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(offer.clone()).await?;
+    pc_answer.set_remote_description(offer).await?;
+
+    // The lite answerer leaves the DTLS role open, which leaves the full offerer with its ICE
+    // role to guess from.
+    let mut answer = pc_answer.create_answer(None).await?;
+    assert!(answer.sdp.contains("a=ice-lite"));
+    answer.sdp = answer
+        .sdp
+        .replace("a=setup:active", "a=setup:actpass")
+        .replace("a=setup:passive", "a=setup:actpass");
+
+    assert_eq!(
+        pc_offer.set_remote_description(answer.clone()).await,
+        Err(Error::ErrPeerConnRemoteAnswerDtlsRoleActpass)
+    );
+    // The answer is rejected before it's applied
+    assert_eq!(
+        pc_offer.signaling_state(),
+        RTCSignalingState::HaveLocalOffer
+    );
+    assert!(pc_offer.remote_description().await.is_none());
+
+    answer.sdp = answer.sdp.replace("a=setup:actpass", "a=setup:passive");
+    pc_offer.set_remote_description(answer).await?;
+    assert_eq!(pc_offer.signaling_state(), RTCSignalingState::Stable);
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remote_lite_offer_with_inconsistent_dtls_role() -> Result<()> {
+    let mut s = SettingEngine::default();
+    s.set_lite(true);
+    let pc_offer = APIBuilder::new()
+        .with_setting_engine(s)
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+    let pc_answer = APIBuilder::new()
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+
+    pc_offer
+        .create_data_channel("initial_data_channel", None)
+        .await?;
+    let mut offer = pc_offer.create_offer(None).await?;
+    assert!(offer.sdp.contains("a=ice-lite"));
+    pc_offer.set_local_description(offer.clone()).await?;
+
+    // The full answerer is the controlling agent and would answer a lite offer as the DTLS
+    // server, but follows an offer that takes that role already.
+    offer.sdp = offer.sdp.replace("a=setup:actpass", "a=setup:passive");
+    pc_answer.set_remote_description(offer).await?;
+    let answer = pc_answer.create_answer(None).await?;
+    assert!(answer.sdp.contains("a=setup:active"));
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    // An answering role set through the SettingEngine can't follow the offer.
+    let mut s = SettingEngine::default();
+    s.set_lite(true);
+    let pc_offer = APIBuilder::new()
+        .with_setting_engine(s)
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+    let mut s = SettingEngine::default();
+    s.set_answering_dtls_role(DTLSRole::Server)?;
+    let pc_answer = APIBuilder::new()
+        .with_setting_engine(s)
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+
+    pc_offer
+        .create_data_channel("initial_data_channel", None)
+        .await?;
+    let mut offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(offer.clone()).await?;
+    offer.sdp = offer.sdp.replace("a=setup:actpass", "a=setup:passive");
+    assert_eq!(
+        pc_answer.set_remote_description(offer).await,
+        Err(Error::ErrPeerConnDtlsRoleConflict)
+    );
+    // The offer is rejected before it's applied
+    assert_eq!(pc_answer.signaling_state(), RTCSignalingState::Stable);
+    assert!(pc_answer.remote_description().await.is_none());
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signal_pair_trickle_data_channel() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    let (pc_offer, pc_answer) = new_pair(&api).await?;
+    let (pc_offer, pc_answer) = (Arc::new(pc_offer), Arc::new(pc_answer));
+
+    let (message_tx, mut message_rx) = mpsc::channel::<Bytes>(1);
+    pc_answer.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+        let message_tx = message_tx.clone();
+        Box::pin(async move {
+            d.on_message(Box::new(move |msg: DataChannelMessage| {
+                let message_tx = message_tx.clone();
+                Box::pin(async move {
+                    let _ = message_tx.try_send(msg.data);
+                })
+            }));
+        })
+    }));
+
+    let dc = pc_offer.create_data_channel("trickle", None).await?;
+    let dc2 = Arc::clone(&dc);
+    dc.on_open(Box::new(move || {
+        Box::pin(async move {
+            let _ = dc2.send_text("ping".to_owned()).await;
+        })
+    }));
+
+    signal_pair_trickle(&pc_offer, &pc_answer).await?;
+
+    let msg = tokio::time::timeout(Duration::from_secs(10), message_rx.recv())
+        .await
+        .expect("the message never arrived");
+    assert_eq!(msg, Some(Bytes::from_static(b"ping")));
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_stats_sctp_transport() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    let (mut pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+    let (message_tx, mut message_rx) = mpsc::channel::<Bytes>(1);
+    pc_answer.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+        let message_tx = message_tx.clone();
+        Box::pin(async move {
+            d.on_message(Box::new(move |msg: DataChannelMessage| {
+                let message_tx = message_tx.clone();
+                Box::pin(async move {
+                    let _ = message_tx.try_send(msg.data);
+                })
+            }));
+        })
+    }));
+
+    let dc = pc_offer.create_data_channel("stats", None).await?;
+    let dc2 = Arc::clone(&dc);
+    dc.on_open(Box::new(move || {
+        Box::pin(async move {
+            let _ = dc2.send_text("ping".to_owned()).await;
+        })
+    }));
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+    let msg = tokio::time::timeout(Duration::from_secs(10), message_rx.recv())
+        .await
+        .expect("the message never arrived");
+    assert_eq!(msg, Some(Bytes::from_static(b"ping")));
+
+    let offer_stats = pc_offer.get_stats().await;
+    match offer_stats.reports.get("sctp_transport") {
+        Some(StatsReportType::SCTPTransport(sctp_transport_stats)) => {
+            assert!(sctp_transport_stats.bytes_sent > 0);
+            assert!(sctp_transport_stats.bytes_received > 0);
+        }
+        Some(_other) => panic!("found the wrong type"),
+        None => panic!("missed it"),
+    }
+    match offer_stats.reports.get(pc_offer.get_stats_id()) {
+        Some(StatsReportType::PeerConnection(pc_stats)) => {
+            assert_eq!(pc_stats.data_channels_closed, 0);
+        }
+        Some(_other) => panic!("found the wrong type"),
+        None => panic!("missed it"),
+    }
+    let dc_stats = offer_stats
+        .reports
+        .values()
+        .find_map(|v| match v {
+            StatsReportType::DataChannel(d) if d.label == "stats" => Some(d),
+            _ => None,
+        })
+        .expect("Should have produced data channel stats");
+    assert_eq!(dc_stats.messages_sent, 1);
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_peer_connection_over_injected_packets() -> Result<()> {
+    let mut pcs = vec![];
+    let mut pumps = vec![];
+    for ip in ["1.2.3.4", "1.2.3.5"] {
+        let (conn, outbound_rx) = InjectConn::new(SocketAddr::new(ip.parse().unwrap(), 5000));
+        pumps.push((conn.local_addr()?, conn.injector(), outbound_rx));
+
+        let mut s = SettingEngine::default();
+        s.set_udp_conn(conn);
+        s.set_ice_multicast_dns_mode(ice::mdns::MulticastDnsMode::Disabled);
+
+        let mut m = MediaEngine::default();
+        m.register_default_codecs()?;
+        let api = APIBuilder::new()
+            .with_setting_engine(s)
+            .with_media_engine(m)
+            .build();
+        pcs.push(api.new_peer_connection(RTCConfiguration::default()).await?);
+    }
+
+    // Hand every datagram sent by one peer straight to the other one.
+    let (addr_b, injector_b, outbound_b) = pumps.pop().unwrap();
+    let (addr_a, injector_a, outbound_a) = pumps.pop().unwrap();
+    for (from, mut outbound_rx, injector) in [
+        (addr_a, outbound_a, injector_b),
+        (addr_b, outbound_b, injector_a),
+    ] {
+        tokio::spawn(async move {
+            while let Some((data, _)) = outbound_rx.recv().await {
+                if injector.handle_inbound(&data, from).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    let mut pc_answer = pcs.pop().unwrap();
+    let mut pc_offer = pcs.pop().unwrap();
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_offer, &wg, RTCPeerConnectionState::Connected).await;
+    until_connection_state(&mut pc_answer, &wg, RTCPeerConnectionState::Connected).await;
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+    tokio::time::timeout(Duration::from_secs(10), wg.wait())
+        .await
+        .expect("timed out waiting for peers to connect over injected packets");
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+use tokio::sync::{watch, Mutex};
+
+use super::OnTrackHdlrFn;
+use crate::track::track_remote::TrackRemote;
+
+/// PendingTracks holds remote tracks that arrived before an on_track handler was set, see
+/// `SettingEngine::set_unhandled_track_buffer`.
+pub(crate) struct PendingTracks {
+    max_packets: usize,
+    receive_mtu: usize,
+    handler_set: watch::Sender<bool>,
+}
+
+impl PendingTracks {
+    pub(crate) fn new(max_packets: usize, receive_mtu: usize) -> Self {
+        PendingTracks {
+            max_packets,
+            receive_mtu,
+            handler_set: watch::channel(false).0,
+        }
+    }
+
+    /// hold returns a TrackBuffer that keeps a track until a handler is set. It returns None if
+    /// the track has to be handled right away, because buffering is disabled or a handler has
+    /// been set already.
+    pub(crate) fn hold(
+        &self,
+        on_track_handler: &ArcSwapOption<Mutex<OnTrackHdlrFn>>,
+    ) -> Option<TrackBuffer> {
+        if self.max_packets == 0 {
+            return None;
+        }
+
+        // Subscribed before checking the handler, so that release can't be missed.
+        let handler_set = self.handler_set.subscribe();
+        if on_track_handler.load().is_some() {
+            return None;
+        }
+        Some(TrackBuffer {
+            max_packets: self.max_packets,
+            receive_mtu: self.receive_mtu,
+            handler_set,
+        })
+    }
+
+    /// release hands the held tracks over once a handler has been set, or ends holding them
+    /// once the PeerConnection is closed.
+    pub(crate) fn release(&self) {
+        self.handler_set.send_replace(true);
+    }
+}
+
+/// TrackBuffer reads the first packets of a held track for a later read.
+pub(crate) struct TrackBuffer {
+    max_packets: usize,
+    receive_mtu: usize,
+    handler_set: watch::Receiver<bool>,
+}
+
+impl TrackBuffer {
+    /// buffer reads the first packets of the track until the buffer is full, the track is closed
+    /// or a handler is set, and then waits for the handler. It returns false if the
+    /// PeerConnection is gone before a handler was set.
+    pub(crate) async fn buffer(mut self, track: &Arc<TrackRemote>) -> bool {
+        track.set_buffering(true);
+        let mut b = vec![0u8; self.receive_mtu];
+        let mut full = true;
+        for _ in 0..self.max_packets {
+            let read_track = Arc::clone(track);
+            let mut read = Box::pin(async move {
+                let result = read_track.buffer(&mut b).await;
+                (result, b)
+            });
+
+            // The read is polled first, so that it holds the track before the handler reads.
+            tokio::select! {
+                biased;
+                (result, read_b) = &mut read => {
+                    if let Err(err) = result {
+                        log::debug!("stopped buffering track SSRC {}: {}", track.ssrc(), err);
+                        full = false;
+                        break;
+                    }
+                    b = read_b;
+                }
+                result = self.handler_set.wait_for(|set| *set) => {
+                    // Reads aren't cancel safe, the packet being read is still buffered ahead
+                    // of the ones the handler reads.
+                    let track = Arc::clone(track);
+                    tokio::spawn(async move {
+                        let _ = read.await;
+                        track.set_buffering(false);
+                    });
+                    return result.is_ok();
+                }
+            }
+        }
+
+        if full {
+            log::warn!(
+                "on_track unset, buffered the first {} packets of track SSRC {}",
+                self.max_packets,
+                track.ssrc()
+            );
+        }
+        track.set_buffering(false);
+        self.handler_set.wait_for(|set| *set).await.is_ok()
+    }
+}
@@ -4,6 +4,10 @@ use serde::{Deserialize, Serialize};
 
 /// RTCPMuxPolicy affects what ICE candidates are gathered to support
 /// non-multiplexed RTCP.
+///
+/// Only multiplexed RTCP is implemented for now: no RTCP (component 2)
+/// candidates are gathered, and the `a=rtcp` port of a remote media section
+/// is ignored.
 #[derive(Default, Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub enum RTCRtcpMuxPolicy {
     #[default]
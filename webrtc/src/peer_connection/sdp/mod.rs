@@ -28,12 +28,14 @@ use sdp::description::common::{Address, ConnectionInformation};
 use sdp::description::media::{MediaDescription, MediaName, RangedPort};
 use sdp::description::session::*;
 use sdp::extmap::ExtMap;
+use sdp::group::Group;
+use sdp::ssrc_group::SsrcGroup;
 use sdp::util::ConnectionRole;
 use smol_str::SmolStr;
 use url::Url;
 
 use crate::peer_connection::MEDIA_SECTION_APPLICATION;
-use crate::{SDP_ATTRIBUTE_RID, SDP_ATTRIBUTE_SIMULCAST};
+use crate::{SDP_ATTRIBUTE_RID, SDP_ATTRIBUTE_SCTP_PORT, SDP_ATTRIBUTE_SIMULCAST};
 
 /// TrackDetails represents any media source that can be represented in a SDP
 /// This isn't keyed by SSRC because it also needs to support rid based sources
@@ -101,34 +103,26 @@ pub(crate) fn track_details_from_sdp(
             match attr.key.as_str() {
                 ATTR_KEY_SSRCGROUP => {
                     if let Some(value) = &attr.value {
-                        let split: Vec<&str> = value.split(' ').collect();
-                        if split[0] == SEMANTIC_TOKEN_FLOW_IDENTIFICATION {
-                            // Add rtx ssrcs to blacklist, to avoid adding them as tracks
-                            // Essentially lines like `a=ssrc-group:FID 2231627014 632943048` are processed by this section
-                            // as this declares that the second SSRC (632943048) is a rtx repair flow (RFC4588) for the first
-                            // (2231627014) as specified in RFC5576
-                            if split.len() == 3 {
-                                let base_ssrc = match split[1].parse::<u32>() {
-                                    Ok(ssrc) => ssrc,
-                                    Err(err) => {
-                                        log::warn!("Failed to parse SSRC: {}", err);
-                                        continue;
-                                    }
-                                };
-                                let rtx_repair_flow = match split[2].parse::<u32>() {
-                                    Ok(n) => n,
-                                    Err(err) => {
-                                        log::warn!("Failed to parse SSRC: {}", err);
-                                        continue;
-                                    }
-                                };
-                                rtx_repair_flows.insert(rtx_repair_flow, base_ssrc);
-                                // Remove if rtx was added as track before
-                                filter_track_with_ssrc(
-                                    &mut tracks_in_media_section,
-                                    rtx_repair_flow as SSRC,
-                                );
+                        let group = match SsrcGroup::parse_value(value) {
+                            Ok(group) => group,
+                            Err(err) => {
+                                log::warn!("Failed to parse ssrc-group: {}", err);
+                                continue;
                             }
+                        };
+                        // Add rtx ssrcs to blacklist, to avoid adding them as tracks
+                        // Essentially lines like `a=ssrc-group:FID 2231627014 632943048` are processed by this section
+                        // as this declares that the second SSRC (632943048) is a rtx repair flow (RFC4588) for the first
+                        // (2231627014) as specified in RFC5576
+                        if let (SEMANTIC_TOKEN_FLOW_IDENTIFICATION, [base_ssrc, rtx_repair_flow]) =
+                            (group.semantics.as_str(), group.ssrcs.as_slice())
+                        {
+                            rtx_repair_flows.insert(*rtx_repair_flow, *base_ssrc);
+                            // Remove if rtx was added as track before
+                            filter_track_with_ssrc(
+                                &mut tracks_in_media_section,
+                                *rtx_repair_flow as SSRC,
+                            );
                         }
                     }
                 }
@@ -293,10 +287,8 @@ pub(crate) async fn add_candidates_to_media_descriptions(
     let append_candidate_if_new = |c: &dyn Candidate, m: MediaDescription| -> MediaDescription {
         let marshaled = c.marshal();
         for a in &m.attributes {
-            if let Some(value) = &a.value {
-                if &marshaled == value {
-                    return m;
-                }
+            if a.is_ice_candidate() && a.value.as_ref() == Some(&marshaled) {
+                return m;
             }
         }
 
@@ -331,6 +323,7 @@ pub(crate) struct AddDataMediaSectionParams {
     ice_params: RTCIceParameters,
     dtls_role: ConnectionRole,
     ice_gathering_state: RTCIceGatheringState,
+    sctp_port: u16,
 }
 
 pub(crate) async fn add_data_media_section(
@@ -369,7 +362,10 @@ pub(crate) async fn add_data_media_section(
     )
     .with_value_attribute(ATTR_KEY_MID.to_owned(), params.mid_value)
     .with_property_attribute(RTCRtpTransceiverDirection::Sendrecv.to_string())
-    .with_property_attribute("sctp-port:5000".to_owned())
+    .with_value_attribute(
+        SDP_ATTRIBUTE_SCTP_PORT.to_owned(),
+        params.sctp_port.to_string(),
+    )
     .with_ice_credentials(
         params.ice_params.username_fragment,
         params.ice_params.password,
@@ -469,6 +465,8 @@ pub(crate) async fn add_transceiver_sdp(
         .with_property_attribute(ATTR_KEY_RTCPRSIZE.to_owned());
 
     let codecs = t.get_codecs().await;
+    // Only an answer rejects a media section for lack of a common codec, a new offer clears it.
+    t.set_no_common_codec(params.offered_direction.is_some() && codecs.is_empty());
     for codec in &codecs {
         let name = codec
             .capability
@@ -495,8 +493,12 @@ pub(crate) async fn add_transceiver_sdp(
         }
     }
     if codecs.is_empty() {
-        // If we are sender and we have no codecs throw an error early
-        if t.sender().await.track().await.is_some() {
+        if params.offered_direction.is_some() {
+            // When answering, the remote offered nothing we can use. Reject the media section
+            // instead of failing, so the other media sections can still be negotiated.
+            log::warn!("{}: mid {}", Error::ErrNoCommonCodec, mid_value);
+        } else if t.sender().await.track().await.is_some() {
+            // If we are sender and we have no codecs throw an error early
             return Err(Error::ErrSenderWithNoCodecs);
         }
 
@@ -643,7 +645,7 @@ pub(crate) async fn add_transceiver_sdp(
                     // If a media stream is
                     // listed as recvonly in the offer, the answer MUST be marked as
                     // sendonly or inactive in the answer.
-                    offered_direction.reverse().intersect(transceiver_direction)
+                    RTCRtpTransceiverDirection::negotiate(transceiver_direction, offered_direction)
                 }
                 // If an offered media stream is
                 // listed as sendrecv (or if there is no direction attribute at the
@@ -758,6 +760,8 @@ pub(crate) struct PopulateSdpParams {
     pub(crate) is_icelite: bool,
     pub(crate) connection_role: ConnectionRole,
     pub(crate) ice_gathering_state: RTCIceGatheringState,
+    pub(crate) extmap_allow_mixed: bool,
+    pub(crate) sctp_port: u16,
 }
 
 /// populate_sdp serializes a PeerConnections state into an SDP
@@ -776,11 +780,9 @@ pub(crate) async fn populate_sdp(
         vec![]
     };
 
-    let mut bundle_value = "BUNDLE".to_owned();
-    let mut bundle_count = 0;
-    let append_bundle = |mid_value: &str, value: &mut String, count: &mut i32| {
-        *value = value.clone() + " " + mid_value;
-        *count += 1;
+    let mut bundle = Group {
+        semantics: SEMANTIC_TOKEN_BUNDLE.to_owned(),
+        mids: vec![],
     };
 
     for (i, m) in media_sections.iter().enumerate() {
@@ -799,6 +801,7 @@ pub(crate) async fn populate_sdp(
                 ice_params: ice_params.clone(),
                 dtls_role: params.connection_role,
                 ice_gathering_state: params.ice_gathering_state,
+                sctp_port: params.sctp_port,
             };
             d = add_data_media_section(d, &media_dtls_fingerprints, candidates, params).await?;
             true
@@ -825,7 +828,7 @@ pub(crate) async fn populate_sdp(
         };
 
         if should_add_id {
-            append_bundle(&m.id, &mut bundle_value, &mut bundle_count);
+            bundle.mids.push(m.id.clone());
         }
     }
 
@@ -838,12 +841,16 @@ pub(crate) async fn populate_sdp(
         }
     }
 
+    if params.extmap_allow_mixed {
+        d = d.with_extmap_allow_mixed();
+    }
+
     if params.is_icelite {
         // RFC 5245 S15.3
         d = d.with_value_attribute(ATTR_KEY_ICELITE.to_owned(), ATTR_KEY_ICELITE.to_owned());
     }
 
-    Ok(d.with_value_attribute(ATTR_KEY_GROUP.to_owned(), bundle_value))
+    Ok(d.with_group(bundle))
 }
 
 pub(crate) fn get_mid_value(media: &MediaDescription) -> Option<&String> {
@@ -855,6 +862,18 @@ pub(crate) fn get_mid_value(media: &MediaDescription) -> Option<&String> {
     None
 }
 
+/// is_simulcast_mid returns true if the media section identified by mid declares rids,
+/// in which case incoming SSRCs can't be routed by MID alone.
+pub(crate) fn is_simulcast_mid(desc: &SessionDescription, mid: &str) -> bool {
+    desc.media_descriptions.iter().any(|media| {
+        get_mid_value(media).map(|m| m.as_str()) == Some(mid)
+            && media
+                .attributes
+                .iter()
+                .any(|attr| attr.key == SDP_ATTRIBUTE_RID)
+    })
+}
+
 pub(crate) fn get_peer_direction(media: &MediaDescription) -> RTCRtpTransceiverDirection {
     for a in &media.attributes {
         let direction = RTCRtpTransceiverDirection::from(a.key.as_str());
@@ -960,6 +979,38 @@ pub(crate) fn have_application_media_section(desc: &SessionDescription) -> bool
     false
 }
 
+/// get_sctp_port returns the SCTP port of the application media section, taken from its
+/// 'a=sctp-port' attribute or, for the legacy `DTLS/SCTP` format, from the m= line. None if there
+/// is no such section or it carries no valid port.
+pub(crate) fn get_sctp_port(desc: &SessionDescription) -> Option<u16> {
+    let m = desc
+        .media_descriptions
+        .iter()
+        .find(|m| m.media_name.media == MEDIA_SECTION_APPLICATION)?;
+
+    if let Some(port) = m.attribute(SDP_ATTRIBUTE_SCTP_PORT) {
+        return port.and_then(|port| port.trim().parse().ok());
+    }
+
+    if m.media_name.protos == ["DTLS", "SCTP"] {
+        return m.media_name.formats.first()?.parse().ok();
+    }
+
+    None
+}
+
+/// get_max_message_size returns the max-message-size (RFC 8841) advertised in the application
+/// media section. An advertised 0 means the remote has no limit and is returned as is, while a
+/// missing or malformed attribute falls back to the 64 KiB default of RFC 8841 section 6.
+pub(crate) fn get_max_message_size(desc: &SessionDescription) -> u32 {
+    desc.media_descriptions
+        .iter()
+        .filter(|m| m.media_name.media == MEDIA_SECTION_APPLICATION)
+        .find_map(|m| m.attribute(ATTR_KEY_MAX_MESSAGE_SIZE).flatten())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(65536)
+}
+
 pub(crate) fn get_by_mid<'a>(
     search_mid: &str,
     desc: &'a session_description::RTCSessionDescription,
@@ -1,3 +1,5 @@
+use std::io::Cursor;
+
 use rcgen::KeyPair;
 use sdp::description::common::Attribute;
 
@@ -256,6 +258,58 @@ async fn test_extract_ice_details() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_extract_ice_details_mixed_candidates() -> Result<()> {
+    let candidates = [
+        "1052353102 1 udp 2122260223 192.168.1.10 50001 typ host",
+        "2367254911 1 tcp 1518280447 192.168.1.10 9 typ host tcptype active",
+        "3120894542 1 udp 2122194687 10.0.0.5 50002 typ host",
+        "842163049 1 udp 1686052607 203.0.113.7 61234 typ srflx raddr 192.168.1.10 rport 50001",
+        "1409817582 1 udp 41885439 198.51.100.20 3478 typ relay raddr 203.0.113.7 rport 61234",
+    ];
+
+    let mut sdp = "v=0\r\n\
+o=- 4215775240449105457 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:ufrag\r\n\
+a=ice-pwd:pwd\r\n\
+a=mid:0\r\n"
+        .to_owned();
+    for c in &candidates {
+        sdp += &format!("a=candidate:{c}\r\n");
+    }
+
+    let s = SessionDescription::unmarshal(&mut Cursor::new(sdp.as_bytes()))?;
+    assert_eq!(s.marshal(), sdp);
+
+    let (_, _, extracted) = extract_ice_details(&s).await?;
+    let marshaled: Vec<String> = extracted
+        .iter()
+        .map(|c| Ok(c.to_ice()?.marshal()))
+        .collect::<Result<_>>()?;
+    assert_eq!(marshaled, candidates);
+
+    // Writing them back keeps every candidate of component 1, in order.
+    let m = add_candidates_to_media_descriptions(
+        &extracted,
+        MediaDescription::default(),
+        RTCIceGatheringState::Gathering,
+    )
+    .await?;
+    let component1: Vec<&str> = m
+        .attributes
+        .iter()
+        .filter_map(|a| a.value.as_deref())
+        .filter(|v| v.split_whitespace().nth(1) == Some("1"))
+        .collect();
+    assert_eq!(component1, candidates);
+
+    Ok(())
+}
+
 #[test]
 fn test_track_details_from_sdp() -> Result<()> {
     //"Tracks unknown, audio and video with RTX"
@@ -537,6 +591,8 @@ async fn fingerprint_test(
         is_icelite: false,
         connection_role: ConnectionRole::Active,
         ice_gathering_state: RTCIceGatheringState::New,
+        extmap_allow_mixed: false,
+        sctp_port: 5000,
     };
 
     let s = populate_sdp(
@@ -731,6 +787,8 @@ async fn test_populate_sdp() -> Result<()> {
             is_icelite: se.candidates.ice_lite,
             connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
             ice_gathering_state: RTCIceGatheringState::Complete,
+            extmap_allow_mixed: false,
+            sctp_port: 5000,
         };
         let offer_sdp = populate_sdp(
             d,
@@ -835,6 +893,8 @@ async fn test_populate_sdp() -> Result<()> {
             is_icelite: se.candidates.ice_lite,
             connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
             ice_gathering_state: RTCIceGatheringState::Complete,
+            extmap_allow_mixed: false,
+            sctp_port: 5000,
         };
         let offer_sdp = populate_sdp(
             d,
@@ -937,6 +997,10 @@ async fn test_populate_sdp_reject() -> Result<()> {
     )
     .await;
 
+    // As if an earlier answer rejected the section, which a new offer must not report anymore.
+    tra.set_no_common_codec(true);
+    let audio_transceiver = Arc::clone(&tra);
+
     let media_sections = vec![
         MediaSection {
             id: "video".to_owned(),
@@ -961,6 +1025,8 @@ async fn test_populate_sdp_reject() -> Result<()> {
         is_icelite: se.candidates.ice_lite,
         connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
         ice_gathering_state: RTCIceGatheringState::Complete,
+        extmap_allow_mixed: false,
+        sctp_port: 5000,
     };
     let offer_sdp = populate_sdp(
         d,
@@ -1000,6 +1066,7 @@ async fn test_populate_sdp_reject() -> Result<()> {
         found_rejected_track,
         "There should've been a rejected track"
     );
+    assert_eq!(audio_transceiver.rejection(), None);
 
     Ok(())
 }
@@ -1148,3 +1215,95 @@ fn test_rtp_extensions_from_media_description() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_get_sctp_port() {
+    let application =
+        |protos: &[&str], formats: &[&str], attributes: Vec<Attribute>| SessionDescription {
+            media_descriptions: vec![MediaDescription {
+                media_name: MediaName {
+                    media: MEDIA_SECTION_APPLICATION.to_owned(),
+                    protos: protos.iter().map(|p| p.to_string()).collect(),
+                    formats: formats.iter().map(|f| f.to_string()).collect(),
+                    ..Default::default()
+                },
+                attributes,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+    let sctp_port = |port: &str| Attribute {
+        key: "sctp-port".to_owned(),
+        value: Some(port.to_owned()),
+    };
+
+    let tests = vec![
+        (
+            application(
+                &["UDP", "DTLS", "SCTP"],
+                &["webrtc-datachannel"],
+                vec![sctp_port("5001")],
+            ),
+            Some(5001),
+        ),
+        (
+            application(&["UDP", "DTLS", "SCTP"], &["webrtc-datachannel"], vec![]),
+            None,
+        ),
+        (
+            application(
+                &["UDP", "DTLS", "SCTP"],
+                &["webrtc-datachannel"],
+                vec![sctp_port("none")],
+            ),
+            None,
+        ),
+        // The legacy format carries the port as the format of the m= line.
+        (
+            application(&["DTLS", "SCTP"], &["5002"], vec![]),
+            Some(5002),
+        ),
+        (SessionDescription::default(), None),
+    ];
+
+    for (i, (desc, expected)) in tests.into_iter().enumerate() {
+        assert_eq!(get_sctp_port(&desc), expected, "{i}");
+    }
+}
+
+#[test]
+fn test_get_max_message_size() {
+    let application = |value: Option<&str>| SessionDescription {
+        media_descriptions: vec![MediaDescription {
+            media_name: MediaName {
+                media: MEDIA_SECTION_APPLICATION.to_owned(),
+                protos: vec!["UDP".to_owned(), "DTLS".to_owned(), "SCTP".to_owned()],
+                formats: vec!["webrtc-datachannel".to_owned()],
+                ..Default::default()
+            },
+            attributes: value
+                .map(|value| Attribute {
+                    key: "max-message-size".to_owned(),
+                    value: Some(value.to_owned()),
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let tests = vec![
+        (application(Some("1024")), 1024),
+        // 0 means the remote accepts messages of any size.
+        (application(Some("0")), 0),
+        // Without a usable attribute the RFC 8841 default applies.
+        (application(None), 65536),
+        (application(Some("none")), 65536),
+        (SessionDescription::default(), 65536),
+    ];
+
+    for (i, (desc, expected)) in tests.into_iter().enumerate() {
+        assert_eq!(get_max_message_size(&desc), expected, "{i}");
+    }
+}
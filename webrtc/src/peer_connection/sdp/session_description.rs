@@ -66,6 +66,15 @@ impl RTCSessionDescription {
         Ok(desc)
     }
 
+    /// Returns an RTCSessionDescription that discards the pending offer when given to
+    /// `set_local_description` or `set_remote_description`, going back to the stable state.
+    pub fn rollback() -> RTCSessionDescription {
+        RTCSessionDescription {
+            sdp_type: RTCSdpType::Rollback,
+            ..Default::default()
+        }
+    }
+
     /// Unmarshal is a helper to deserialize the sdp
     pub fn unmarshal(&self) -> Result<SessionDescription> {
         let mut reader = Cursor::new(self.sdp.as_bytes());
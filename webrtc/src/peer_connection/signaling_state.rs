@@ -143,24 +143,27 @@ pub(crate) fn check_next_signaling_state(
             if op == StateChangeOp::SetRemote {
                 match sdp_type {
                     // have-local-offer->SetRemote(answer)->stable
-                    RTCSdpType::Answer => {
-                        if next == RTCSignalingState::Stable {
-                            return Ok(next);
-                        }
+                    RTCSdpType::Answer if next == RTCSignalingState::Stable => {
+                        return Ok(next);
                     }
                     // have-local-offer->SetRemote(pranswer)->have-remote-pranswer
-                    RTCSdpType::Pranswer => {
-                        if next == RTCSignalingState::HaveRemotePranswer {
-                            return Ok(next);
-                        }
+                    RTCSdpType::Pranswer if next == RTCSignalingState::HaveRemotePranswer => {
+                        return Ok(next);
+                    }
+                    _ => {}
+                }
+            } else if op == StateChangeOp::SetLocal {
+                match sdp_type {
+                    // have-local-offer->SetLocal(offer)->have-local-offer
+                    RTCSdpType::Offer if next == RTCSignalingState::HaveLocalOffer => {
+                        return Ok(next);
+                    }
+                    // have-local-offer->SetLocal(rollback)->stable
+                    RTCSdpType::Rollback if next == RTCSignalingState::Stable => {
+                        return Ok(next);
                     }
                     _ => {}
                 }
-            } else if op == StateChangeOp::SetLocal
-                && sdp_type == RTCSdpType::Offer
-                && next == RTCSignalingState::HaveLocalOffer
-            {
-                return Ok(next);
             }
         }
         RTCSignalingState::HaveRemotePranswer => {
@@ -175,19 +178,18 @@ pub(crate) fn check_next_signaling_state(
             if op == StateChangeOp::SetLocal {
                 match sdp_type {
                     // have-remote-offer->SetLocal(answer)->stable
-                    RTCSdpType::Answer => {
-                        if next == RTCSignalingState::Stable {
-                            return Ok(next);
-                        }
+                    RTCSdpType::Answer if next == RTCSignalingState::Stable => {
+                        return Ok(next);
                     }
                     // have-remote-offer->SetLocal(pranswer)->have-local-pranswer
-                    RTCSdpType::Pranswer => {
-                        if next == RTCSignalingState::HaveLocalPranswer {
-                            return Ok(next);
-                        }
+                    RTCSdpType::Pranswer if next == RTCSignalingState::HaveLocalPranswer => {
+                        return Ok(next);
                     }
                     _ => {}
                 }
+            } else if sdp_type == RTCSdpType::Rollback && next == RTCSignalingState::Stable {
+                // have-remote-offer->SetRemote(rollback)->stable
+                return Ok(next);
             }
         }
         RTCSignalingState::HaveLocalPranswer => {
@@ -337,6 +339,34 @@ mod test {
                     applying: RTCSdpType::Pranswer,
                 }),
             ),
+            (
+                "have-local-offer->SetLocal(rollback)->stable",
+                RTCSignalingState::HaveLocalOffer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetLocal,
+                RTCSdpType::Rollback,
+                None,
+            ),
+            (
+                "have-remote-offer->SetRemote(rollback)->stable",
+                RTCSignalingState::HaveRemoteOffer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetRemote,
+                RTCSdpType::Rollback,
+                None,
+            ),
+            (
+                "(invalid) have-local-offer->SetRemote(rollback)->stable",
+                RTCSignalingState::HaveLocalOffer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetRemote,
+                RTCSdpType::Rollback,
+                Some(Error::ErrSignalingStateProposedTransitionInvalid {
+                    from: RTCSignalingState::HaveLocalOffer,
+                    is_local: false,
+                    applying: RTCSdpType::Rollback,
+                }),
+            ),
             (
                 "(invalid) stable->SetRemote(rollback)->have-local-offer",
                 RTCSignalingState::Stable,
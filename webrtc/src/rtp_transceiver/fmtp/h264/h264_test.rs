@@ -112,6 +112,12 @@ fn test_h264_fmtp_compare() {
             "profile-level-id=42e029",
             false,
         ),
+        (
+            "EqualWithDefaultPacketizationMode",
+            "packetization-mode=0;profile-level-id=42e029",
+            "profile-level-id=42e029",
+            true,
+        ),
         (
             "Inconsistent_MissingProfileLevelID",
             "packetization-mode=1;profile-level-id=42e029",
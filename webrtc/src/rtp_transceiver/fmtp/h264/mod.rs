@@ -32,6 +32,14 @@ pub(crate) struct H264Fmtp {
     pub(crate) parameters: HashMap<String, String>,
 }
 
+impl H264Fmtp {
+    fn packetization_mode(&self) -> &str {
+        self.parameters
+            .get("packetization-mode")
+            .map_or("0", String::as_str)
+    }
+}
+
 impl Fmtp for H264Fmtp {
     fn mime_type(&self) -> &str {
         "video/h264"
@@ -51,15 +59,9 @@ impl Fmtp for H264Fmtp {
     ///     for the other stream properties and capability parameters.
     fn match_fmtp(&self, f: &(dyn Fmtp)) -> bool {
         if let Some(c) = f.as_any().downcast_ref::<H264Fmtp>() {
-            // test packetization-mode
-            let hpmode = match self.parameters.get("packetization-mode") {
-                Some(s) => s,
-                None => return false,
-            };
-            let cpmode = match c.parameters.get("packetization-mode") {
-                Some(s) => s,
-                None => return false,
-            };
+            // test packetization-mode, which defaults to 0 when absent (RFC6184 Section 8.1)
+            let hpmode = self.packetization_mode();
+            let cpmode = c.packetization_mode();
 
             if hpmode != cpmode {
                 return false;
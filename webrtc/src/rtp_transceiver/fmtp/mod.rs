@@ -5,6 +5,8 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
 
+use sdp::util::parse_fmtp_parameters;
+
 use crate::rtp_transceiver::fmtp::generic::GenericFmtp;
 use crate::rtp_transceiver::fmtp::h264::H264Fmtp;
 
@@ -35,17 +37,7 @@ impl PartialEq for dyn Fmtp {
 
 /// parse parses an fmtp string based on the MimeType
 pub fn parse(mime_type: &str, line: &str) -> Box<dyn Fmtp> {
-    let mut parameters = HashMap::new();
-    for p in line.split(';').collect::<Vec<&str>>() {
-        let pp: Vec<&str> = p.trim().splitn(2, '=').collect();
-        let key = pp[0].to_lowercase();
-        let value = if pp.len() > 1 {
-            pp[1].to_owned()
-        } else {
-            String::new()
-        };
-        parameters.insert(key, value);
-    }
+    let parameters = parse_fmtp_parameters(line);
 
     if mime_type.to_uppercase() == "video/h264".to_uppercase() {
         Box::new(H264Fmtp { parameters })
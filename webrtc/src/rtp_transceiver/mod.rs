@@ -7,12 +7,13 @@ use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::Arc;
 
-use interceptor::stream_info::{RTPHeaderExtension, StreamInfo};
+use interceptor::stream_info::{NackSettings, RTPHeaderExtension, StreamInfo};
 use interceptor::Attributes;
 use log::trace;
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 use tokio::sync::{Mutex, OnceCell};
+use util::sync::Mutex as SyncMutex;
 use util::Unmarshal;
 
 use crate::api::media_engine::MediaEngine;
@@ -164,6 +165,9 @@ pub(crate) fn create_stream_info(
         channels: codec.channels,
         sdp_fmtp_line: codec.sdp_fmtp_line,
         rtcp_feedback: feedbacks,
+        nack_settings: NackSettings::default(),
+        ssrc_retransmission: 0,
+        payload_type_retransmission: 0,
     }
 }
 
@@ -180,8 +184,10 @@ pub struct RTCRtpTransceiver {
     current_direction: AtomicU8, //RTPTransceiverDirection
 
     codecs: Arc<Mutex<Vec<RTCRtpCodecParameters>>>, // User provided codecs via set_codec_preferences
+    nack_settings: SyncMutex<NackSettings>,
 
     pub(crate) stopped: AtomicBool,
+    no_common_codec: AtomicBool,
     pub(crate) kind: RTPCodecType,
 
     media_engine: Arc<MediaEngine>,
@@ -211,7 +217,9 @@ impl RTCRtpTransceiver {
             current_direction: AtomicU8::new(RTCRtpTransceiverDirection::Unspecified as u8),
 
             codecs,
+            nack_settings: SyncMutex::new(NackSettings::default()),
             stopped: AtomicBool::new(false),
+            no_common_codec: AtomicBool::new(false),
             kind,
             media_engine,
             trigger_negotiation_needed: Mutex::new(trigger_negotiation_needed),
@@ -241,6 +249,23 @@ impl RTCRtpTransceiver {
         Ok(())
     }
 
+    /// set_nack_settings configures retransmission of lost packets for the transceiver's sender.
+    /// The settings are applied when the sender starts sending. A history larger than
+    /// 2^[`MAX_LOG2_SIZE`](interceptor::nack::MAX_LOG2_SIZE) packets is rejected with
+    /// `interceptor::Error::ErrInvalidSize`.
+    pub fn set_nack_settings(&self, settings: NackSettings) -> Result<()> {
+        settings.validate()?;
+
+        let mut nack_settings = self.nack_settings.lock();
+        *nack_settings = settings;
+        Ok(())
+    }
+
+    /// nack_settings returns the retransmission settings of the transceiver's sender.
+    pub fn nack_settings(&self) -> NackSettings {
+        *self.nack_settings.lock()
+    }
+
     /// Codecs returns list of supported codecs
     pub(crate) async fn get_codecs(&self) -> Vec<RTCRtpCodecParameters> {
         let mut codecs = self.codecs.lock().await;
@@ -354,6 +379,20 @@ impl RTCRtpTransceiver {
         self.current_direction.load(Ordering::SeqCst).into()
     }
 
+    /// rejection returns why the last answer created for this transceiver rejected its media
+    /// section, which is [`Error::ErrNoCommonCodec`] when the remote offered no codec the
+    /// MediaEngine supports. It's cleared once a new offer is created.
+    pub fn rejection(&self) -> Option<Error> {
+        self.no_common_codec
+            .load(Ordering::SeqCst)
+            .then_some(Error::ErrNoCommonCodec)
+    }
+
+    pub(crate) fn set_no_common_codec(&self, no_common_codec: bool) {
+        self.no_common_codec
+            .store(no_common_codec, Ordering::SeqCst);
+    }
+
     pub(crate) fn set_current_direction(&self, d: RTCRtpTransceiverDirection) {
         let previous: RTCRtpTransceiverDirection = self
             .current_direction
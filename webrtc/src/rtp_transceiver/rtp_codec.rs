@@ -4,6 +4,7 @@ use super::*;
 use crate::api::media_engine::*;
 use crate::error::{Error, Result};
 use crate::rtp_transceiver::fmtp;
+use crate::rtp_transceiver::fmtp::h264::H264Fmtp;
 
 /// RTPCodecType determines the type of a codec
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
@@ -152,8 +153,16 @@ pub(crate) fn codec_parameters_fuzzy_search(
         }
     }
 
-    // Fallback to just mime_type
+    // Fallback to just mime_type. H264 configurations must be used symmetrically (RFC6184
+    // Section 8.2.2), so H264 only falls back when one side doesn't specify its configuration.
+    let needle_is_h264 = needle_fmtp.as_any().is::<H264Fmtp>();
     for c in haystack {
+        if needle_is_h264
+            && !needle.capability.sdp_fmtp_line.is_empty()
+            && !c.capability.sdp_fmtp_line.is_empty()
+        {
+            continue;
+        }
         if c.capability.mime_type.to_uppercase() == needle.capability.mime_type.to_uppercase() {
             return (c.clone(), CodecMatch::Partial);
         }
@@ -4,17 +4,21 @@ mod rtp_sender_test;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 
+use bytes::Bytes;
 use ice::rand::generate_crypto_random_string;
 use interceptor::stream_info::StreamInfo;
 use interceptor::{Attributes, Interceptor, RTCPReader, RTPWriter};
 use tokio::sync::{mpsc, Mutex, Notify};
 use util::sync::Mutex as SyncMutex;
 
-use super::srtp_writer_future::SequenceTransformer;
-use crate::api::media_engine::MediaEngine;
+use super::srtp_writer_future::{ExtensionStamper, SequenceTransformer};
+use crate::api::media_engine::{MediaEngine, MIME_TYPE_AUDIO_RTX, MIME_TYPE_RTX};
 use crate::dtls_transport::RTCDtlsTransport;
 use crate::error::{Error, Result};
-use crate::rtp_transceiver::rtp_codec::{RTCRtpCodecParameters, RTPCodecType};
+use crate::rtp_transceiver::fmtp;
+use crate::rtp_transceiver::rtp_codec::{
+    RTCRtpCodecParameters, RTCRtpHeaderExtensionParameters, RTPCodecType,
+};
 use crate::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
 use crate::rtp_transceiver::srtp_writer_future::SrtpWriterFuture;
 use crate::rtp_transceiver::{
@@ -24,6 +28,7 @@ use crate::rtp_transceiver::{
 use crate::track::track_local::{
     InterceptorToTrackLocalWriter, TrackLocal, TrackLocalContext, TrackLocalWriter,
 };
+use crate::SDES_REPAIR_RTP_STREAM_ID_URI;
 
 pub(crate) struct RTPSenderInternal {
     pub(crate) send_called_rx: Mutex<mpsc::Receiver<()>>,
@@ -85,6 +90,7 @@ pub struct RTCRtpSender {
     pub(crate) srtp_stream: Arc<SrtpWriterFuture>,
     pub(crate) stream_info: Mutex<StreamInfo>,
     seq_trans: Arc<SequenceTransformer>,
+    ext_stamper: Arc<ExtensionStamper>,
 
     pub(crate) context: Mutex<TrackLocalContext>,
 
@@ -155,6 +161,7 @@ impl RTCRtpSender {
         });
 
         let seq_trans = Arc::new(SequenceTransformer::new());
+        let ext_stamper = Arc::new(ExtensionStamper::new());
         let srtp_stream = Arc::new(SrtpWriterFuture {
             closed: AtomicBool::new(false),
             ssrc,
@@ -163,6 +170,7 @@ impl RTCRtpSender {
             rtcp_read_stream: Mutex::new(None),
             rtp_write_session: Mutex::new(None),
             seq_trans: Arc::clone(&seq_trans),
+            ext_stamper: Arc::clone(&ext_stamper),
         });
 
         let srtp_rtcp_reader = Arc::clone(&srtp_stream) as Arc<dyn RTCPReader + Send + Sync>;
@@ -182,6 +190,7 @@ impl RTCRtpSender {
             srtp_stream,
             stream_info: Mutex::new(StreamInfo::default()),
             seq_trans,
+            ext_stamper,
 
             context: Mutex::new(TrackLocalContext::default()),
             transport,
@@ -340,6 +349,8 @@ impl RTCRtpSender {
                 ssrc: context.ssrc,
                 write_stream: context.write_stream.clone(),
                 paused: self.paused.clone(),
+                extmap_allow_mixed: self.media_engine.extmap_allow_mixed(),
+                path_mtu: context.path_mtu.clone(),
             };
 
             t.bind(&new_context).await
@@ -398,6 +409,8 @@ impl RTCRtpSender {
                     Arc::clone(&write_stream) as Arc<dyn TrackLocalWriter + Send + Sync>
                 ),
                 paused: self.paused.clone(),
+                extmap_allow_mixed: self.media_engine.extmap_allow_mixed(),
+                path_mtu: Arc::clone(&self.transport.ice_transport().path_mtu),
             };
 
             let codec = if let Some(t) = &*track {
@@ -407,14 +420,53 @@ impl RTCRtpSender {
             };
             let payload_type = codec.payload_type;
             let capability = codec.capability.clone();
+            let rtx_payload_type = context
+                .params
+                .codecs
+                .iter()
+                .find(|c| {
+                    let mime_type = &c.capability.mime_type;
+                    (mime_type.eq_ignore_ascii_case(MIME_TYPE_RTX)
+                        || mime_type.eq_ignore_ascii_case(MIME_TYPE_AUDIO_RTX))
+                        && fmtp::parse(mime_type, &c.capability.sdp_fmtp_line)
+                            .parameter("apt")
+                            .and_then(|apt| apt.parse::<u8>().ok())
+                            == Some(payload_type)
+                })
+                .map(|c| c.payload_type);
             context.params.codecs = vec![codec];
-            let stream_info = create_stream_info(
+            let mut stream_info = create_stream_info(
                 self.id.clone(),
                 parameters.encodings[0].ssrc,
                 payload_type,
                 capability,
                 &parameters.rtp_parameters.header_extensions,
             );
+            // Retransmissions go out on the RTX stream if one was negotiated for the codec.
+            match rtx_payload_type {
+                Some(rtx_payload_type) if parameters.encodings[0].rtx.ssrc != 0 => {
+                    stream_info.ssrc_retransmission = parameters.encodings[0].rtx.ssrc;
+                    stream_info.payload_type_retransmission = rtx_payload_type;
+                }
+                _ => {}
+            }
+            self.seq_trans.set_rtx_ssrc(stream_info.ssrc_retransmission);
+            let mid = if let Some(t) = self
+                .rtp_transceiver
+                .lock()
+                .as_ref()
+                .and_then(|t| t.upgrade())
+            {
+                stream_info.nack_settings = t.nack_settings();
+                t.mid()
+            } else {
+                None
+            };
+            self.configure_extension_stamping(
+                &context.params.header_extensions,
+                &parameters.encodings[0],
+                mid.as_deref(),
+            );
 
             (context, stream_info)
         };
@@ -446,6 +498,44 @@ impl RTCRtpSender {
         Ok(())
     }
 
+    /// configure_extension_stamping makes outgoing packets carry the negotiated mid, rid and
+    /// repaired-rid header extensions until the remote peer acknowledges the SSRC.
+    fn configure_extension_stamping(
+        &self,
+        header_extensions: &[RTCRtpHeaderExtensionParameters],
+        encoding: &RTCRtpEncodingParameters,
+        mid: Option<&str>,
+    ) {
+        let extension_id = |uri: &str| {
+            header_extensions
+                .iter()
+                .find(|ext| ext.uri == uri)
+                .map(|ext| ext.id as u8)
+        };
+
+        let mut media = vec![];
+        let mut repair = vec![];
+        if let (Some(id), Some(mid)) = (extension_id(::sdp::extmap::SDES_MID_URI), mid) {
+            media.push((id, Bytes::copy_from_slice(mid.as_bytes())));
+            repair.push((id, Bytes::copy_from_slice(mid.as_bytes())));
+        }
+        if !encoding.rid.is_empty() {
+            if let Some(id) = extension_id(::sdp::extmap::SDES_RTP_STREAM_ID_URI) {
+                media.push((id, Bytes::copy_from_slice(encoding.rid.as_bytes())));
+            }
+            if let Some(id) = extension_id(SDES_REPAIR_RTP_STREAM_ID_URI) {
+                repair.push((id, Bytes::copy_from_slice(encoding.rid.as_bytes())));
+            }
+        }
+
+        self.ext_stamper.configure(
+            media,
+            repair,
+            encoding.rtx.ssrc,
+            self.media_engine.extmap_allow_mixed(),
+        );
+    }
+
     /// stop irreversibly stops the RTPSender
     pub async fn stop(&self) -> Result<()> {
         if self.stop_called_signal.load(Ordering::SeqCst) {
@@ -14,7 +14,7 @@ use crate::peer_connection::peer_connection_test::{
     close_pair_now, create_vnet_pair, new_pair, send_video_until_done, signal_pair,
     until_connection_state,
 };
-use crate::rtp_transceiver::rtp_codec::RTCRtpCodecCapability;
+use crate::rtp_transceiver::rtp_codec::{RTCRtpCodecCapability, RTCRtpHeaderExtensionCapability};
 use crate::track::track_local::track_local_static_sample::TrackLocalStaticSample;
 
 #[tokio::test]
@@ -334,3 +334,82 @@ async fn test_rtp_sender_replace_track_invalid_codec_change() -> Result<()> {
     close_pair_now(&sender, &receiver).await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_rtp_sender_stamps_mid_extension() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    m.register_header_extension(
+        RTCRtpHeaderExtensionCapability {
+            uri: ::sdp::extmap::SDES_MID_URI.to_owned(),
+        },
+        RTPCodecType::Video,
+        None,
+    )?;
+
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    let (mut sender, mut receiver) = new_pair(&api).await?;
+
+    let track = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    let rtp_sender = sender
+        .add_track(Arc::clone(&track) as Arc<dyn TrackLocal + Send + Sync>)
+        .await?;
+
+    let (header_tx, mut header_rx) = mpsc::channel::<rtp::header::Header>(1);
+    let (seen_packet_tx, seen_packet_rx) = mpsc::channel::<()>(1);
+    receiver.on_track(Box::new(move |track, _, _| {
+        let header_tx = header_tx.clone();
+        let seen_packet_tx = seen_packet_tx.clone();
+        Box::pin(async move {
+            if let Ok((pkt, _)) = track.read_rtp().await {
+                let _ = seen_packet_tx.send(()).await;
+                let _ = header_tx.send(pkt.header).await;
+            }
+        })
+    }));
+
+    signal_pair(&mut sender, &mut receiver).await?;
+
+    tokio::spawn(async move {
+        send_video_until_done(
+            seen_packet_rx,
+            vec![track],
+            Bytes::from_static(&[0xAA]),
+            None,
+        )
+        .await;
+    });
+
+    let header = header_rx.recv().await.expect("no packet received");
+    let id = rtp_sender
+        .get_parameters()
+        .await
+        .rtp_parameters
+        .header_extensions
+        .iter()
+        .find(|ext| ext.uri == ::sdp::extmap::SDES_MID_URI)
+        .map(|ext| ext.id as u8)
+        .expect("mid extension not negotiated");
+    let expected = rtp_sender
+        .rtp_transceiver
+        .lock()
+        .as_ref()
+        .and_then(|t| t.upgrade())
+        .and_then(|t| t.mid())
+        .expect("transceiver has no mid");
+    assert_eq!(
+        header.get_extension(id),
+        Some(Bytes::copy_from_slice(expected.as_bytes()))
+    );
+
+    close_pair_now(&sender, &receiver).await;
+    Ok(())
+}
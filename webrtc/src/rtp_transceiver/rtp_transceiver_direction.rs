@@ -81,6 +81,8 @@ impl RTCRtpTransceiverDirection {
         }
     }
 
+    /// intersect returns the direction allowed by both `self` and `other`,
+    /// both expressed from the same peer's point of view.
     pub fn intersect(&self, other: RTCRtpTransceiverDirection) -> RTCRtpTransceiverDirection {
         Self::from_send_recv(
             self.has_send() && other.has_send(),
@@ -88,6 +90,18 @@ impl RTCRtpTransceiverDirection {
         )
     }
 
+    /// negotiate computes the direction this peer ends up with, given its `local`
+    /// desired direction and the direction the `remote` peer signaled from its own
+    /// point of view, e.g. a local sendrecv against a remote sendonly yields recvonly.
+    ///
+    /// <https://datatracker.ietf.org/doc/html/rfc3264#section-6.1>
+    pub fn negotiate(
+        local: RTCRtpTransceiverDirection,
+        remote: RTCRtpTransceiverDirection,
+    ) -> RTCRtpTransceiverDirection {
+        local.intersect(remote.reverse())
+    }
+
     pub fn from_send_recv(send: bool, recv: bool) -> RTCRtpTransceiverDirection {
         match (send, recv) {
             (true, true) => Self::Sendrecv,
@@ -187,24 +201,82 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_rtp_transceiver_reverse() {
+        use RTCRtpTransceiverDirection::*;
+
+        let tests = vec![
+            (Unspecified, Unspecified),
+            (Sendrecv, Sendrecv),
+            (Sendonly, Recvonly),
+            (Recvonly, Sendonly),
+            (Inactive, Inactive),
+        ];
+
+        for (d, expected_direction) in tests {
+            assert_eq!(d.reverse(), expected_direction);
+            assert_eq!(d.reverse().reverse(), d);
+        }
+    }
+
     #[test]
     fn test_rtp_transceiver_intersect() {
         use RTCRtpTransceiverDirection::*;
 
         let tests = vec![
-            ((Sendrecv, Recvonly), Recvonly),
+            ((Sendrecv, Sendrecv), Sendrecv),
             ((Sendrecv, Sendonly), Sendonly),
+            ((Sendrecv, Recvonly), Recvonly),
             ((Sendrecv, Inactive), Inactive),
-            ((Sendonly, Inactive), Inactive),
-            ((Recvonly, Inactive), Inactive),
-            ((Recvonly, Sendrecv), Recvonly),
             ((Sendonly, Sendrecv), Sendonly),
+            ((Sendonly, Sendonly), Sendonly),
             ((Sendonly, Recvonly), Inactive),
+            ((Sendonly, Inactive), Inactive),
+            ((Recvonly, Sendrecv), Recvonly),
+            ((Recvonly, Sendonly), Inactive),
             ((Recvonly, Recvonly), Recvonly),
+            ((Recvonly, Inactive), Inactive),
+            ((Inactive, Sendrecv), Inactive),
+            ((Inactive, Sendonly), Inactive),
+            ((Inactive, Recvonly), Inactive),
+            ((Inactive, Inactive), Inactive),
         ];
 
         for ((a, b), expected_direction) in tests {
             assert_eq!(a.intersect(b), expected_direction);
         }
     }
+
+    #[test]
+    fn test_rtp_transceiver_negotiate() {
+        use RTCRtpTransceiverDirection::*;
+
+        // (local, remote) => negotiated local direction
+        let tests = vec![
+            ((Sendrecv, Sendrecv), Sendrecv),
+            ((Sendrecv, Sendonly), Recvonly),
+            ((Sendrecv, Recvonly), Sendonly),
+            ((Sendrecv, Inactive), Inactive),
+            ((Sendonly, Sendrecv), Sendonly),
+            ((Sendonly, Sendonly), Inactive),
+            ((Sendonly, Recvonly), Sendonly),
+            ((Sendonly, Inactive), Inactive),
+            ((Recvonly, Sendrecv), Recvonly),
+            ((Recvonly, Sendonly), Recvonly),
+            ((Recvonly, Recvonly), Inactive),
+            ((Recvonly, Inactive), Inactive),
+            ((Inactive, Sendrecv), Inactive),
+            ((Inactive, Sendonly), Inactive),
+            ((Inactive, Recvonly), Inactive),
+            ((Inactive, Inactive), Inactive),
+        ];
+
+        for ((local, remote), expected_direction) in tests {
+            assert_eq!(
+                RTCRtpTransceiverDirection::negotiate(local, remote),
+                expected_direction,
+                "local {local} remote {remote}"
+            );
+        }
+    }
 }
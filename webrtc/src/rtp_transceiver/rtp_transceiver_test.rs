@@ -6,6 +6,7 @@ use crate::api::APIBuilder;
 use crate::dtls_transport::RTCDtlsTransport;
 use crate::peer_connection::configuration::RTCConfiguration;
 use crate::peer_connection::peer_connection_test::{close_pair_now, create_vnet_pair};
+use crate::track::track_local::track_local_static_sample::TrackLocalStaticSample;
 
 #[tokio::test]
 async fn test_rtp_transceiver_set_codec_preferences() -> Result<()> {
@@ -354,3 +355,51 @@ async fn test_rtp_transceiver_stopping() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_rtp_transceiver_nack_settings() -> Result<()> {
+    let (offer_pc, answer_pc, _) = create_vnet_pair().await?;
+
+    let track = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    let transceiver = offer_pc.add_transceiver_from_track(track, None).await?;
+    assert_eq!(transceiver.nack_settings(), NackSettings::default());
+
+    // A history of 1 << 16 packets doesn't fit the u16 sequence space
+    assert!(transceiver
+        .set_nack_settings(NackSettings {
+            log2_history_size: Some(16),
+            ..Default::default()
+        })
+        .is_err());
+    assert_eq!(transceiver.nack_settings(), NackSettings::default());
+
+    let nack_settings = NackSettings {
+        rtx_enabled: false,
+        log2_history_size: Some(5),
+        max_retransmissions: Some(2),
+    };
+    transceiver.set_nack_settings(nack_settings)?;
+
+    let offer = offer_pc.create_offer(None).await?;
+    offer_pc.set_local_description(offer.clone()).await?;
+    answer_pc.set_remote_description(offer).await?;
+
+    let answer = answer_pc.create_answer(None).await?;
+    answer_pc.set_local_description(answer.clone()).await?;
+    offer_pc.set_remote_description(answer).await?;
+
+    let sender = transceiver.sender().await;
+    assert!(sender.has_sent());
+    assert_eq!(sender.stream_info.lock().await.nack_settings, nack_settings);
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
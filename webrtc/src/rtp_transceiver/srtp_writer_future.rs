@@ -1,8 +1,11 @@
+#[cfg(test)]
+mod srtp_writer_future_test;
+
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 
 use async_trait::async_trait;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use interceptor::{Attributes, RTCPReader, RTPWriter};
 use srtp::session::Session;
 use srtp::stream::Stream;
@@ -24,6 +27,13 @@ pub(crate) struct SequenceTransformer(util::sync::Mutex<SequenceTransformerInner
 /// [`SequenceTransformer`] inner.
 struct SequenceTransformerInner {
     offset: u16,
+    /// First and last raw sequence numbers sent with `offset`.
+    sent: Option<(u16, u16)>,
+    /// Offset before the last reset, with the first and last raw sequence
+    /// numbers sent with it.
+    previous: Option<(u16, u16, u16)>,
+    /// `SSRC` of the negotiated RTX stream, 0 if there is none.
+    rtx_ssrc: SSRC,
     last_sq: u16,
     reset_needed: bool,
     enabled: bool,
@@ -35,6 +45,9 @@ impl SequenceTransformer {
     pub(crate) fn new() -> Self {
         Self(util::sync::Mutex::new(SequenceTransformerInner {
             offset: 0,
+            sent: None,
+            previous: None,
+            rtx_ssrc: 0,
             last_sq: rand::random(),
             reset_needed: false,
             enabled: false,
@@ -65,6 +78,13 @@ impl SequenceTransformer {
             .ok_or(Error::ErrRTPSenderDataSent)
     }
 
+    /// Sets the `SSRC` of the negotiated RTX stream. Its packets keep their
+    /// own sequence numbers, only the original sequence number they start
+    /// with is shifted.
+    pub(crate) fn set_rtx_ssrc(&self, ssrc: SSRC) {
+        self.0.lock().rtx_ssrc = ssrc;
+    }
+
     /// Indicates [`SequenceTransformer`] about necessity of recalculating
     /// `offset`.
     pub(crate) fn reset_offset(&self) {
@@ -87,6 +107,10 @@ impl SequenceTransformer {
             .reset_needed
             .then(|| {
                 guard.reset_needed = false;
+                guard.previous = guard
+                    .sent
+                    .take()
+                    .map(|(first, last)| (guard.offset, first, last));
                 let offset = guard.last_sq.overflowing_sub(raw_sn.overflowing_sub(1).0).0;
                 guard.offset = offset;
                 offset
@@ -94,9 +118,146 @@ impl SequenceTransformer {
             .unwrap_or(guard.offset);
         let next = raw_sn.overflowing_add(offset).0;
         guard.last_sq = next;
+        guard.sent = Some((guard.sent.map_or(raw_sn, |(first, _)| first), raw_sn));
 
         Some(next)
     }
+
+    /// Gets [`Some`] `sequence number` shifted by the `offset` the packet with
+    /// `raw_sn` was sent with if this [`SequenceTransformer`] is enabled or
+    /// [`None`] if it is not.
+    ///
+    /// Unlike [`SequenceTransformer::seq_number`], doesn't advance the stream, so
+    /// it can map the sequence numbers of already sent packets. Retransmissions
+    /// are only requested for recent packets, so only the offset before the
+    /// last reset is remembered.
+    fn shift(&self, raw_sn: u16) -> Option<u16> {
+        let guard = self.0.lock();
+        if !guard.enabled {
+            return None;
+        }
+
+        let contains =
+            |first: u16, last: u16| raw_sn.wrapping_sub(first) <= last.wrapping_sub(first);
+        let offset = match guard.previous {
+            Some((offset, first, last))
+                if contains(first, last)
+                    && !guard
+                        .sent
+                        .is_some_and(|(first, last)| contains(first, last)) =>
+            {
+                offset
+            }
+            _ => guard.offset,
+        };
+
+        Some(raw_sn.overflowing_add(offset).0)
+    }
+
+    /// Rewrites the sequence number of `pkt` into `new_pkt`, or the original
+    /// sequence number if `pkt` is a packet of the RTX stream, like the media
+    /// packet it repairs was. `new_pkt` is left as is if there is nothing to
+    /// rewrite.
+    fn transform(&self, pkt: &rtp::packet::Packet, new_pkt: &mut Option<rtp::packet::Packet>) {
+        let rtx_ssrc = self.0.lock().rtx_ssrc;
+        if rtx_ssrc != 0 && pkt.header.ssrc == rtx_ssrc {
+            if pkt.payload.len() >= 2 {
+                let osn = u16::from_be_bytes([pkt.payload[0], pkt.payload[1]]);
+                if let Some(osn) = self.shift(osn) {
+                    let new_pkt = new_pkt.get_or_insert_with(|| pkt.clone());
+                    let mut payload = BytesMut::from(&new_pkt.payload[..]);
+                    payload[..2].copy_from_slice(&osn.to_be_bytes());
+                    new_pkt.payload = payload.freeze();
+                }
+            }
+        } else if let Some(seq_num) = self.seq_number(pkt.header.sequence_number) {
+            new_pkt
+                .get_or_insert_with(|| pkt.clone())
+                .header
+                .sequence_number = seq_num;
+        }
+    }
+}
+
+/// Stamps the `mid`, `rid` and `repaired-rid` header extensions on outgoing
+/// `RTP` packets until the remote peer acknowledges the `SSRC`, so it can
+/// demultiplex BUNDLEd and simulcast streams before learning the `SSRC`.
+pub(crate) struct ExtensionStamper(util::sync::Mutex<ExtensionStamperInner>);
+
+/// [`ExtensionStamper`] inner.
+#[derive(Default)]
+struct ExtensionStamperInner {
+    media: Vec<(u8, Bytes)>,
+    repair: Vec<(u8, Bytes)>,
+    repair_ssrc: SSRC,
+    allow_mixed: bool,
+    acknowledged: bool,
+}
+
+impl ExtensionStamper {
+    /// Creates a new [`ExtensionStamper`] which doesn't stamp anything.
+    pub(crate) fn new() -> Self {
+        Self(util::sync::Mutex::new(ExtensionStamperInner::default()))
+    }
+
+    /// Sets the extensions stamped on packets of the media `SSRC` and of the
+    /// `repair_ssrc`, as `(extension id, value)` pairs. With `allow_mixed` the
+    /// two-byte form is used for extensions that don't fit the one-byte form.
+    pub(crate) fn configure(
+        &self,
+        media: Vec<(u8, Bytes)>,
+        repair: Vec<(u8, Bytes)>,
+        repair_ssrc: SSRC,
+        allow_mixed: bool,
+    ) {
+        let mut guard = self.0.lock();
+        guard.media = media;
+        guard.repair = repair;
+        guard.repair_ssrc = repair_ssrc;
+        guard.allow_mixed = allow_mixed;
+        guard.acknowledged = false;
+    }
+
+    /// Stops stamping, the remote peer knows the `SSRC` from now on.
+    pub(crate) fn acknowledge(&self) {
+        self.0.lock().acknowledged = true;
+    }
+
+    /// Returns a copy of `pkt` carrying the configured extensions, or [`None`]
+    /// if there is nothing to stamp. Packets of SSRCs other than `media_ssrc`
+    /// and the repair `SSRC`, such as RTX packets of an unconfigured repair
+    /// stream, are left as is.
+    fn stamp(&self, pkt: &rtp::packet::Packet, media_ssrc: SSRC) -> Option<rtp::packet::Packet> {
+        let guard = self.0.lock();
+        if guard.acknowledged {
+            return None;
+        }
+
+        let extensions = if pkt.header.ssrc == media_ssrc {
+            &guard.media
+        } else if guard.repair_ssrc != 0 && pkt.header.ssrc == guard.repair_ssrc {
+            &guard.repair
+        } else {
+            return None;
+        };
+        if extensions.is_empty() {
+            return None;
+        }
+
+        let mut new_pkt = pkt.clone();
+        for (id, value) in extensions {
+            let result = if guard.allow_mixed {
+                new_pkt.header.set_extension_allow_mixed(*id, value.clone())
+            } else {
+                new_pkt.header.set_extension(*id, value.clone())
+            };
+            if let Err(err) = result {
+                log::warn!("failed to stamp header extension {}: {}", id, err);
+            }
+        }
+
+        Some(new_pkt)
+    }
 }
 
 /// SrtpWriterFuture blocks Read/Write calls until
@@ -109,6 +270,7 @@ pub(crate) struct SrtpWriterFuture {
     pub(crate) rtcp_read_stream: Mutex<Option<Arc<Stream>>>, // atomic.Value // *
     pub(crate) rtp_write_session: Mutex<Option<Arc<Session>>>, // atomic.Value // *
     pub(crate) seq_trans: Arc<SequenceTransformer>,
+    pub(crate) ext_stamper: Arc<ExtensionStamper>,
 }
 
 impl SrtpWriterFuture {
@@ -268,6 +430,14 @@ impl RTCPReader for SrtpWriterFuture {
         let read = self.read(buf).await?;
         let pkt = rtcp::packet::unmarshal(&mut &buf[..read])?;
 
+        // Feedback about our SSRC means the remote can demultiplex it without extensions
+        if pkt
+            .iter()
+            .any(|p| p.destination_ssrc().contains(&self.ssrc))
+        {
+            self.ext_stamper.acknowledge();
+        }
+
         Ok((pkt, a.clone()))
     }
 }
@@ -275,15 +445,9 @@ impl RTCPReader for SrtpWriterFuture {
 #[async_trait]
 impl RTPWriter for SrtpWriterFuture {
     async fn write(&self, pkt: &rtp::packet::Packet, _a: &Attributes) -> IResult<usize> {
-        Ok(
-            match self.seq_trans.seq_number(pkt.header.sequence_number) {
-                Some(seq_num) => {
-                    let mut new_pkt = pkt.clone();
-                    new_pkt.header.sequence_number = seq_num;
-                    self.write_rtp(&new_pkt).await?
-                }
-                None => self.write_rtp(pkt).await?,
-            },
-        )
+        let mut new_pkt = self.ext_stamper.stamp(pkt, self.ssrc);
+        self.seq_trans.transform(pkt, &mut new_pkt);
+
+        Ok(self.write_rtp(new_pkt.as_ref().unwrap_or(pkt)).await?)
     }
 }
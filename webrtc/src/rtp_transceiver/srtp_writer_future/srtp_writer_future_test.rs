@@ -0,0 +1,121 @@
+use super::*;
+
+#[test]
+fn test_sequence_transformer_shift() -> Result<()> {
+    let seq_trans = SequenceTransformer::new();
+    assert_eq!(
+        seq_trans.shift(10),
+        None,
+        "disabled transformer shifts nothing"
+    );
+
+    seq_trans.enable()?;
+    seq_trans.reset_offset();
+    let first = seq_trans.seq_number(10).expect("enabled transformer");
+    assert_eq!(seq_trans.seq_number(11), Some(first.wrapping_add(1)));
+
+    // Retransmitted packets are mapped like the originals, without advancing the stream.
+    assert_eq!(seq_trans.shift(10), Some(first));
+    assert_eq!(seq_trans.shift(11), Some(first.wrapping_add(1)));
+    assert_eq!(seq_trans.seq_number(12), Some(first.wrapping_add(2)));
+
+    Ok(())
+}
+
+#[test]
+fn test_sequence_transformer_shift_after_reset() -> Result<()> {
+    let seq_trans = SequenceTransformer::new();
+    seq_trans.enable()?;
+    seq_trans.reset_offset();
+    let first = seq_trans.seq_number(10).expect("enabled transformer");
+    assert_eq!(seq_trans.seq_number(11), Some(first.wrapping_add(1)));
+
+    // A replaced track starts over with its own sequence numbers.
+    seq_trans.reset_offset();
+    assert_eq!(seq_trans.shift(11), Some(first.wrapping_add(1)));
+    assert_eq!(seq_trans.seq_number(500), Some(first.wrapping_add(2)));
+    assert_eq!(seq_trans.seq_number(501), Some(first.wrapping_add(3)));
+
+    // Packets of both tracks are mapped with the offset they were sent with.
+    assert_eq!(seq_trans.shift(10), Some(first));
+    assert_eq!(seq_trans.shift(11), Some(first.wrapping_add(1)));
+    assert_eq!(seq_trans.shift(501), Some(first.wrapping_add(3)));
+
+    Ok(())
+}
+
+#[test]
+fn test_sequence_transformer_transform_rtx() -> Result<()> {
+    let seq_trans = SequenceTransformer::new();
+    seq_trans.enable()?;
+    seq_trans.reset_offset();
+    seq_trans.set_rtx_ssrc(5678);
+
+    let packet = |ssrc, sequence_number| rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            ssrc,
+            sequence_number,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x00, 0x0a, 0xff]),
+    };
+
+    let mut media = None;
+    seq_trans.transform(&packet(1234, 10), &mut media);
+    let first = media.expect("media packet").header.sequence_number;
+
+    // RTX packets keep their sequence number, the original sequence number is shifted.
+    let mut rtx = None;
+    seq_trans.transform(&packet(5678, 300), &mut rtx);
+    let rtx = rtx.expect("RTX packet");
+    assert_eq!(rtx.header.sequence_number, 300);
+    assert_eq!(&rtx.payload[..2], &first.to_be_bytes());
+    assert_eq!(rtx.payload[2], 0xff);
+
+    // Packets of other SSRCs aren't taken for RTX packets.
+    let mut other = None;
+    seq_trans.transform(&packet(9999, 11), &mut other);
+    let other = other.expect("other packet");
+    assert_eq!(other.header.sequence_number, first.wrapping_add(1));
+    assert_eq!(&other.payload[..], &[0x00, 0x0a, 0xff]);
+
+    Ok(())
+}
+
+#[test]
+fn test_extension_stamper_skips_other_ssrcs() {
+    let stamper = ExtensionStamper::new();
+    stamper.configure(
+        vec![(1, Bytes::from_static(b"0")), (2, Bytes::from_static(b"h"))],
+        vec![(1, Bytes::from_static(b"0")), (3, Bytes::from_static(b"h"))],
+        5678,
+        false,
+    );
+
+    let packet = |ssrc| rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            ssrc,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let media = stamper.stamp(&packet(1234), 1234).expect("media packet");
+    assert_eq!(
+        media.header.get_extension(2),
+        Some(Bytes::from_static(b"h"))
+    );
+    assert_eq!(media.header.get_extension(3), None);
+
+    let repair = stamper.stamp(&packet(5678), 1234).expect("repair packet");
+    assert_eq!(repair.header.get_extension(2), None);
+    assert_eq!(
+        repair.header.get_extension(3),
+        Some(Bytes::from_static(b"h"))
+    );
+
+    // An RTX SSRC the stamper doesn't know never gets the rid of the media stream.
+    assert!(stamper.stamp(&packet(9999), 1234).is_none());
+}
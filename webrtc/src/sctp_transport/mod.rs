@@ -7,13 +7,13 @@ pub mod sctp_transport_state;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU8, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use arc_swap::ArcSwapOption;
 use data::data_channel::DataChannel;
 use data::message::message_channel_open::ChannelType;
-use sctp::association::Association;
+use sctp::association::{Association, DEFAULT_SCTP_PORT};
 use sctp_transport_state::RTCSctpTransportState;
 use tokio::sync::{Mutex, Notify};
 use util::Conn;
@@ -28,10 +28,13 @@ use crate::error::*;
 use crate::sctp_transport::sctp_transport_capabilities::SCTPTransportCapabilities;
 use crate::stats::stats_collector::StatsCollector;
 use crate::stats::StatsReportType::{PeerConnection, SCTPTransport};
-use crate::stats::{ICETransportStats, PeerConnectionStats};
+use crate::stats::{PeerConnectionStats, SCTPTransportStats};
 
 const SCTP_MAX_CHANNELS: u16 = u16::MAX;
 
+// The largest message we can send, which is also the limit when the remote has none.
+const SCTP_MAX_MESSAGE_SIZE: usize = 65536;
+
 pub type OnDataChannelHdlrFn = Box<
     dyn (FnMut(Arc<RTCDataChannel>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>)
         + Send
@@ -53,6 +56,7 @@ struct AcceptDataChannelParams {
     on_data_channel_opened_handler: Arc<ArcSwapOption<Mutex<OnDataChannelOpenedHdlrFn>>>,
     data_channels_opened: Arc<AtomicU32>,
     data_channels_accepted: Arc<AtomicU32>,
+    max_message_size: usize,
     setting_engine: Arc<SettingEngine>,
 }
 
@@ -70,12 +74,15 @@ pub struct RTCSctpTransport {
 
     // max_message_size represents the maximum size of data that can be passed to
     // DataChannel's send() method.
-    max_message_size: usize,
+    max_message_size: AtomicUsize,
 
     // max_channels represents the maximum amount of DataChannel's that can
     // be used simultaneously.
     max_channels: u16,
 
+    // remote_port is the SCTP port of the remote, from its 'a=sctp-port'.
+    remote_port: AtomicU16,
+
     sctp_association: Mutex<Option<Arc<Association>>>,
 
     on_error_handler: Arc<ArcSwapOption<Mutex<OnErrorHdlrFn>>>,
@@ -102,8 +109,12 @@ impl RTCSctpTransport {
             dtls_transport,
             state: AtomicU8::new(RTCSctpTransportState::Connecting as u8),
             is_started: AtomicBool::new(false),
-            max_message_size: RTCSctpTransport::calc_message_size(65536, 65536),
+            max_message_size: AtomicUsize::new(RTCSctpTransport::calc_message_size(
+                SCTP_MAX_MESSAGE_SIZE,
+                SCTP_MAX_MESSAGE_SIZE,
+            )),
             max_channels: SCTP_MAX_CHANNELS,
+            remote_port: AtomicU16::new(DEFAULT_SCTP_PORT),
             sctp_association: Mutex::new(None),
             on_error_handler: Arc::new(ArcSwapOption::empty()),
             on_data_channel_handler: Arc::new(ArcSwapOption::empty()),
@@ -135,12 +146,19 @@ impl RTCSctpTransport {
     /// Start the SCTPTransport. Since both local and remote parties must mutually
     /// create an SCTPTransport, SCTP SO (Simultaneous Open) is used to establish
     /// a connection over SCTP.
-    pub async fn start(&self, _remote_caps: SCTPTransportCapabilities) -> Result<()> {
+    pub async fn start(&self, remote_caps: SCTPTransportCapabilities) -> Result<()> {
         if self.is_started.load(Ordering::SeqCst) {
             return Ok(());
         }
         self.is_started.store(true, Ordering::SeqCst);
 
+        let max_message_size = RTCSctpTransport::calc_message_size(
+            remote_caps.max_message_size as usize,
+            SCTP_MAX_MESSAGE_SIZE,
+        );
+        self.max_message_size
+            .store(max_message_size, Ordering::SeqCst);
+
         let dtls_transport = self.transport();
         if let Some(net_conn) = &dtls_transport.conn().await {
             let sctp_association = loop {
@@ -153,12 +171,16 @@ impl RTCSctpTransport {
                             return Err(Error::ErrSCTPTransportDTLS);
                         }
                     },
-                    association = sctp::association::Association::client(sctp::association::Config {
-                        net_conn: Arc::clone(net_conn) as Arc<dyn Conn + Send + Sync>,
-                        max_receive_buffer_size: 0,
-                        max_message_size: 0,
-                        name: String::new(),
-                    }) => {
+                    association = sctp::association::Association::client(
+                        sctp::association::Config::new(
+                            Arc::clone(net_conn) as Arc<dyn Conn + Send + Sync>,
+                            String::new(),
+                        )
+                        .with_max_message_size(max_message_size as u32)
+                        .with_heartbeat_interval(self.setting_engine.sctp_heartbeat_interval)
+                        .with_local_port(self.local_port())
+                        .with_remote_port(self.remote_port()),
+                    ) => {
                         break Arc::new(association?);
                     }
                 };
@@ -180,6 +202,7 @@ impl RTCSctpTransport {
                 on_data_channel_opened_handler: Arc::clone(&self.on_data_channel_opened_handler),
                 data_channels_opened: Arc::clone(&self.data_channels_opened),
                 data_channels_accepted: Arc::clone(&self.data_channels_accepted),
+                max_message_size,
                 setting_engine: Arc::clone(&self.setting_engine),
             };
             tokio::spawn(async move {
@@ -192,6 +215,21 @@ impl RTCSctpTransport {
         }
     }
 
+    /// local_port returns the SCTP port of this end, as advertised in the local description.
+    /// See [`SettingEngine::set_sctp_port`].
+    pub fn local_port(&self) -> u16 {
+        self.setting_engine.get_sctp_port()
+    }
+
+    /// remote_port returns the SCTP port of the remote, as advertised in the remote description.
+    pub fn remote_port(&self) -> u16 {
+        self.remote_port.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn set_remote_port(&self, port: u16) {
+        self.remote_port.store(port, Ordering::SeqCst);
+    }
+
     /// Stop stops the SCTPTransport
     pub async fn stop(&self) -> Result<()> {
         {
@@ -289,6 +327,9 @@ impl RTCSctpTransport {
                 },
                 Arc::clone(&param.setting_engine),
             ));
+            rtc_dc
+                .max_message_size
+                .store(param.max_message_size, Ordering::SeqCst);
 
             if let Some(handler) = &*param.on_data_channel_handler.load() {
                 let mut f = handler.lock().await;
@@ -342,6 +383,12 @@ impl RTCSctpTransport {
         }
     }
 
+    /// max_message_size returns the maximum size of a message that can be passed to a
+    /// DataChannel's send methods, taking the limit advertised by the remote into account.
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size.load(Ordering::SeqCst)
+    }
+
     /// max_channels is the maximum number of RTCDataChannels that can be open simultaneously.
     pub fn max_channels(&self) -> u16 {
         if self.max_channels == 0 {
@@ -361,15 +408,10 @@ impl RTCSctpTransport {
         collector: &StatsCollector,
         peer_connection_id: String,
     ) {
-        /*let dtls_transport = self.transport();
-
-        // TODO: should this be collected?
-        dtls_transport.collect_stats(collector).await;
-
         // data channels
         let mut data_channels_closed = 0;
-        let data_channels = self.data_channels.lock().await;
-        for data_channel in &*data_channels {
+        let data_channels = { self.data_channels.lock().await.clone() };
+        for data_channel in &data_channels {
             match data_channel.ready_state() {
                 RTCDataChannelState::Connecting => (),
                 RTCDataChannelState::Open => (),
@@ -383,13 +425,14 @@ impl RTCSctpTransport {
             PeerConnectionStats::new(self, peer_connection_id.clone(), data_channels_closed);
         reports.insert(peer_connection_id, PeerConnection(peer_connection_stats));
 
-        // conn
-        if let Some(agent) = dtls_transport.ice_transport.gatherer.get_agent().await {
-            let stats = ICETransportStats::new("sctp_transport".to_owned(), agent);
+        // association
+        if let Some(association) = self.association().await {
+            let stats =
+                SCTPTransportStats::new("sctp_transport".to_owned(), association.stats().await);
             reports.insert(stats.id.clone(), SCTPTransport(stats));
         }
 
-        collector.merge(reports);*/
+        collector.merge(reports);
     }
 
     pub(crate) async fn generate_and_set_data_channel_id(
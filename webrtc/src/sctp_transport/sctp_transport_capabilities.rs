@@ -3,5 +3,6 @@ use serde::{Deserialize, Serialize};
 /// SCTPTransportCapabilities indicates the capabilities of the SCTPTransport.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub struct SCTPTransportCapabilities {
+    /// max_message_size is the largest message the remote accepts, 0 if it has no limit.
     pub max_message_size: u32,
 }
@@ -1,6 +1,15 @@
 use std::sync::atomic::AtomicU16;
 
+use tokio::sync::mpsc;
+use tokio::time::Duration;
+
 use super::*;
+use crate::api::media_engine::MediaEngine;
+use crate::api::{APIBuilder, API};
+use crate::peer_connection::configuration::RTCConfiguration;
+use crate::peer_connection::peer_connection_test::{
+    close_pair_now, create_vnet_pair_with, signal_pair,
+};
 
 #[tokio::test]
 async fn test_generate_data_channel_id() -> Result<()> {
@@ -41,3 +50,91 @@ async fn test_generate_data_channel_id() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_sctp_port_offer_answer_round_trip() -> Result<()> {
+    let api_with_port = |port: u16| -> Result<API> {
+        let mut m = MediaEngine::default();
+        m.register_default_codecs()?;
+        let mut s = SettingEngine::default();
+        s.set_sctp_port(port);
+        Ok(APIBuilder::new()
+            .with_media_engine(m)
+            .with_setting_engine(s)
+            .build())
+    };
+
+    let mut offer_pc = api_with_port(5001)?
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+    let mut answer_pc = api_with_port(5002)?
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+
+    let (accepted_tx, mut accepted_rx) = mpsc::channel::<()>(1);
+    answer_pc.on_data_channel(Box::new(move |_: Arc<RTCDataChannel>| {
+        let _ = accepted_tx.try_send(());
+        Box::pin(async {})
+    }));
+
+    signal_pair(&mut offer_pc, &mut answer_pc).await?;
+
+    let offer = offer_pc.local_description().await.expect("an offer");
+    assert!(offer.sdp.contains("a=sctp-port:5001\r\n"));
+    let answer = answer_pc.local_description().await.expect("an answer");
+    assert!(answer.sdp.contains("a=sctp-port:5002\r\n"));
+
+    tokio::time::timeout(Duration::from_secs(10), accepted_rx.recv())
+        .await
+        .expect("the data channel should be accepted");
+
+    for (pc, local_port, remote_port) in [(&offer_pc, 5001, 5002), (&answer_pc, 5002, 5001)] {
+        let sctp = pc.sctp();
+        assert_eq!(sctp.local_port(), local_port);
+        assert_eq!(sctp.remote_port(), remote_port);
+
+        let association = sctp.association().await.expect("an association");
+        assert_eq!(association.local_port().await, local_port);
+        assert_eq!(association.remote_port().await, remote_port);
+    }
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sctp_heartbeat_interval_setting() -> Result<()> {
+    let (mut offer_pc, mut answer_pc, wan) =
+        create_vnet_pair_with(|s| s.set_sctp_heartbeat_interval(Duration::from_millis(50))).await?;
+
+    let (accepted_tx, mut accepted_rx) = mpsc::channel::<()>(1);
+    answer_pc.on_data_channel(Box::new(move |_: Arc<RTCDataChannel>| {
+        let _ = accepted_tx.try_send(());
+        Box::pin(async {})
+    }));
+    offer_pc.create_data_channel("data", None).await?;
+
+    signal_pair(&mut offer_pc, &mut answer_pc).await?;
+    tokio::time::timeout(Duration::from_secs(10), accepted_rx.recv())
+        .await
+        .expect("the data channel should be accepted");
+
+    // Leave the associations idle for a while
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    for pc in [&offer_pc, &answer_pc] {
+        let association = pc.sctp().association().await.expect("an association");
+        let stats = association.stats().await;
+        assert!(stats.n_heartbeats > 0, "no heartbeats were sent");
+        assert!(stats.n_heartbeat_acks > 0, "no heartbeats were answered");
+    }
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use ice::agent::agent_stats::{CandidatePairStats, CandidateStats};
 use ice::agent::Agent;
 use ice::candidate::{CandidatePairState, CandidateType};
 use ice::network_type::NetworkType;
+use sctp::association::AssociationStatsSnapshot;
 use serde::{Serialize, Serializer};
 use smol_str::SmolStr;
 use stats_collector::StatsCollector;
@@ -50,6 +51,8 @@ pub enum RTCStatsType {
     RemoteInboundRTP,
     #[serde(rename = "remote-outbound-rtp")]
     RemoteOutboundRTP,
+    #[serde(rename = "sctp-transport")]
+    SCTPTransport,
     #[serde(rename = "sender")]
     Sender,
     #[serde(rename = "transport")]
@@ -70,7 +73,7 @@ pub enum StatsReportType {
     LocalCandidate(ICECandidateStats),
     PeerConnection(PeerConnectionStats),
     RemoteCandidate(ICECandidateStats),
-    SCTPTransport(ICETransportStats),
+    SCTPTransport(SCTPTransportStats),
     Transport(ICETransportStats),
     InboundRTP(InboundRTPStats),
     OutboundRTP(OutboundRTPStats),
@@ -183,6 +186,13 @@ pub struct ICECandidatePairStats {
     pub retransmissions_sent: u64,
 }
 
+impl ICECandidatePairStats {
+    /// to_json returns the stats as a JSON object, with the same shape as in a `StatsReport`.
+    pub fn to_json(&self) -> crate::error::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
 impl From<CandidatePairStats> for ICECandidatePairStats {
     fn from(stats: CandidatePairStats) -> Self {
         ICECandidatePairStats {
@@ -235,6 +245,7 @@ pub struct ICECandidateStats {
     pub port: u16,
     pub priority: u32,
     pub relay_protocol: String,
+    pub relay_server_addr: String,
     pub url: String,
 }
 
@@ -249,6 +260,7 @@ impl ICECandidateStats {
             port: stats.port,
             priority: stats.priority,
             relay_protocol: stats.relay_protocol,
+            relay_server_addr: stats.relay_server_addr,
             stats_type,
             timestamp: stats.timestamp,
             url: stats.url,
@@ -283,6 +295,37 @@ impl ICETransportStats {
     }
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SCTPTransportStats {
+    // RTCStats
+    #[serde(with = "serialize::instant_to_epoch_seconds")]
+    pub timestamp: Instant,
+    #[serde(rename = "type")]
+    pub stats_type: RTCStatsType,
+    pub id: String,
+
+    // TODO: Add the RTCSctpTransportStats members (`smoothedRoundTripTime`, `congestionWindow`, ...).
+
+    // Non-canon
+    pub bytes_received: usize,
+    pub bytes_sent: usize,
+    pub buffered_amount: usize,
+}
+
+impl SCTPTransportStats {
+    pub(crate) fn new(id: String, stats: AssociationStatsSnapshot) -> Self {
+        SCTPTransportStats {
+            id,
+            bytes_received: stats.bytes_received,
+            bytes_sent: stats.bytes_sent,
+            buffered_amount: stats.buffered_amount,
+            stats_type: RTCStatsType::SCTPTransport,
+            timestamp: Instant::now(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CertificateStats {
@@ -454,7 +497,8 @@ pub struct InboundRTPStats {
     // RTCReceivedRtpStreamStats
     pub packets_received: u64,
     // TODO: packetsLost
-    // TODO: jitter(maybe, might be uattainable for the same reason as `framesDropped`)
+    /// Interarrival jitter in seconds, see [RFC 3550 section 6.4.1](https://www.rfc-editor.org/rfc/rfc3550#section-6.4.1).
+    pub jitter: f64,
     // NB: `framesDropped` can't be produced since we aren't decoding, might be worth introducing a
     // way for consumers to control this in the future.
 
@@ -480,6 +524,15 @@ pub struct InboundRTPStats {
     // `insertedSamplesForDeceleration`, `removedSamplesForAcceleration`, `audioLevel`,
     // `totalAudioEneregy`, `totalSampleDuration`, `framesReceived, and `decoderImplementation` are
     // all decoder specific and can't be produced since we aren't decoding.
+
+    // Non-canon
+    /// The number of gaps of a single lost packet in the received sequence numbers.
+    pub single_loss_gaps: u64,
+    /// The number of gaps of two consecutive lost packets in the received sequence numbers.
+    pub double_loss_gaps: u64,
+    /// The number of gaps of three or more consecutive lost packets in the received sequence
+    /// numbers, telling bursts of loss apart from random loss.
+    pub burst_loss_gaps: u64,
 }
 
 #[derive(Debug, Serialize)]
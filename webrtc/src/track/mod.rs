@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod track_test;
+
 pub mod track_local;
 pub mod track_remote;
 
@@ -5,11 +8,28 @@ use std::sync::Arc;
 
 use interceptor::stream_info::StreamInfo;
 use interceptor::{RTCPReader, RTPReader};
+use track_local::track_local_static_rtp::TrackLocalStaticRTP;
+use track_local::TrackLocalWriter;
 use track_remote::*;
 
+use crate::error::{Error, Result};
+
 pub(crate) const RTP_OUTBOUND_MTU: usize = 1200;
+/// Room left in a datagram for the SRTP auth tag and the header extensions added after
+/// packetization, when packets are sized from a discovered path MTU.
+pub(crate) const RTP_OUTBOUND_OVERHEAD: usize = 40;
 pub(crate) const RTP_PAYLOAD_TYPE_BITMASK: u8 = 0x7F;
 
+/// outbound_mtu returns the largest RTP packet to send given the discovered path MTU, or 0 if
+/// it's unknown.
+pub(crate) fn outbound_mtu(path_mtu: usize) -> usize {
+    if path_mtu == 0 {
+        RTP_OUTBOUND_MTU
+    } else {
+        RTP_OUTBOUND_MTU.min(path_mtu.saturating_sub(RTP_OUTBOUND_OVERHEAD))
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct TrackStream {
     pub(crate) stream_info: Option<StreamInfo>,
@@ -27,3 +47,36 @@ pub(crate) struct TrackStreams {
     pub(crate) stream: TrackStream,
     pub(crate) repair_stream: TrackStream,
 }
+
+/// pipe forwards the RTP packets read from `remote` to `local`, e.g. for selective forwarding.
+/// The SSRC and payload type of each packet are rewritten per binding of the local track.
+///
+/// A packet is only read from `remote` once the previous one has been written to `local`,
+/// so a slow writer applies backpressure to the reader. pipe returns `Ok(())` once `remote`
+/// has been closed or all other references to `local` have been dropped, and any other read
+/// error otherwise. Failing to write to a single binding of `local` is logged but not fatal.
+pub async fn pipe(remote: Arc<TrackRemote>, local: Arc<TrackLocalStaticRTP>) -> Result<()> {
+    let local_weak = Arc::downgrade(&local);
+    drop(local);
+
+    loop {
+        let pkt = match remote.read_rtp().await {
+            Ok((pkt, _)) => pkt,
+            Err(Error::ErrTrackClosed) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        let local = match local_weak.upgrade() {
+            Some(local) => local,
+            None => return Ok(()),
+        };
+
+        if let Err(err) = local.write_rtp(&pkt).await {
+            log::warn!(
+                "pipe: failed to forward packet from {}: {}",
+                remote.id(),
+                err
+            );
+        }
+    }
+}
@@ -6,17 +6,20 @@ pub mod track_local_static_sample;
 
 use std::any::Any;
 use std::fmt;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use interceptor::{Attributes, RTPWriter};
-use tokio::sync::Mutex;
-use util::Unmarshal;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, Mutex};
+use util::sync::Mutex as SyncMutex;
+use util::{MarshalSize, Unmarshal};
 
 use crate::error::{Error, Result};
 use crate::rtp_transceiver::rtp_codec::*;
 use crate::rtp_transceiver::*;
+use crate::track::outbound_mtu;
 
 /// TrackLocalWriter is the Writer for outbound RTP Packets
 #[async_trait]
@@ -26,6 +29,13 @@ pub trait TrackLocalWriter: fmt::Debug {
 
     /// write encrypts and writes a full RTP packet
     async fn write(&self, b: &[u8]) -> Result<usize>;
+
+    /// try_write_rtp writes a RTP packet only if the transport can take it right away,
+    /// otherwise the packet is dropped and `Error::ErrWouldBlock` is returned. Writers without
+    /// any notion of backpressure simply write the packet.
+    async fn try_write_rtp(&self, p: &rtp::packet::Packet) -> Result<usize> {
+        self.write_rtp(p).await
+    }
 }
 
 /// TrackLocalContext is the Context passed when a TrackLocal has been Binded/Unbinded from a PeerConnection, and used
@@ -37,6 +47,8 @@ pub struct TrackLocalContext {
     pub(crate) ssrc: SSRC,
     pub(crate) write_stream: Option<Arc<dyn TrackLocalWriter + Send + Sync>>,
     pub(crate) paused: Arc<AtomicBool>,
+    pub(crate) extmap_allow_mixed: bool,
+    pub(crate) path_mtu: Arc<AtomicUsize>,
 }
 
 impl TrackLocalContext {
@@ -68,6 +80,18 @@ impl TrackLocalContext {
     pub fn id(&self) -> String {
         self.id.clone()
     }
+
+    /// extmap_allow_mixed returns whether the remote peer allows mixing one-byte and two-byte
+    /// header extensions, in which case extensions that need it are written in the two-byte form
+    pub fn extmap_allow_mixed(&self) -> bool {
+        self.extmap_allow_mixed
+    }
+
+    /// mtu returns the largest RTP packet the track should produce, header included. It shrinks
+    /// once the path MTU is discovered, see `SettingEngine::set_path_mtu_probe`.
+    pub fn mtu(&self) -> usize {
+        outbound_mtu(self.path_mtu.load(Ordering::SeqCst))
+    }
 }
 /// TrackLocal is an interface that controls how the user can send media
 /// The user can provide their own TrackLocal implementations, or use
@@ -108,30 +132,67 @@ pub(crate) struct TrackBinding {
     params: RTCRtpParameters,
     write_stream: Option<Arc<dyn TrackLocalWriter + Send + Sync>>,
     sender_paused: Arc<AtomicBool>,
+    extmap_allow_mixed: bool,
+    path_mtu: Arc<AtomicUsize>,
 }
 
 impl TrackBinding {
     pub fn is_sender_paused(&self) -> bool {
         self.sender_paused.load(Ordering::SeqCst)
     }
+
+    pub fn mtu(&self) -> usize {
+        outbound_mtu(self.path_mtu.load(Ordering::SeqCst))
+    }
 }
 
+/// The number of packets queued by `try_write_rtp` before it reports `Error::ErrWouldBlock`.
+pub(crate) const TRY_WRITE_QUEUE_SIZE: usize = 128;
+
 pub(crate) struct InterceptorToTrackLocalWriter {
-    pub(crate) interceptor_rtp_writer: Mutex<Option<Arc<dyn RTPWriter + Send + Sync>>>,
+    pub(crate) interceptor_rtp_writer: Arc<Mutex<Option<Arc<dyn RTPWriter + Send + Sync>>>>,
     sender_paused: Arc<AtomicBool>,
+    try_write_tx: SyncMutex<Option<mpsc::Sender<rtp::packet::Packet>>>,
 }
 
 impl InterceptorToTrackLocalWriter {
     pub(crate) fn new(paused: Arc<AtomicBool>) -> Self {
         InterceptorToTrackLocalWriter {
-            interceptor_rtp_writer: Mutex::new(None),
+            interceptor_rtp_writer: Arc::new(Mutex::new(None)),
             sender_paused: paused,
+            try_write_tx: SyncMutex::new(None),
         }
     }
 
     fn is_sender_paused(&self) -> bool {
         self.sender_paused.load(Ordering::SeqCst)
     }
+
+    /// try_write_queue returns the sender of the queue used by `try_write_rtp`, starting the
+    /// task that drains it into the interceptors on first use. The task ends once the writer
+    /// is dropped.
+    fn try_write_queue(&self) -> mpsc::Sender<rtp::packet::Packet> {
+        let mut try_write_tx = self.try_write_tx.lock();
+        if let Some(tx) = &*try_write_tx {
+            return tx.clone();
+        }
+
+        let (tx, mut rx) = mpsc::channel::<rtp::packet::Packet>(TRY_WRITE_QUEUE_SIZE);
+        let interceptor_rtp_writer = Arc::clone(&self.interceptor_rtp_writer);
+        tokio::spawn(async move {
+            while let Some(pkt) = rx.recv().await {
+                let writer = interceptor_rtp_writer.lock().await.clone();
+                if let Some(writer) = writer {
+                    if let Err(err) = writer.write(&pkt, &Attributes::new()).await {
+                        log::warn!("failed to write queued RTP packet: {err}");
+                    }
+                }
+            }
+        });
+
+        *try_write_tx = Some(tx.clone());
+        tx
+    }
 }
 
 impl std::fmt::Debug for InterceptorToTrackLocalWriter {
@@ -160,4 +221,20 @@ impl TrackLocalWriter for InterceptorToTrackLocalWriter {
         let pkt = rtp::packet::Packet::unmarshal(&mut b)?;
         self.write_rtp(&pkt).await
     }
+
+    /// try_write_rtp queues the packet to be written through the interceptors and transport by
+    /// a background task, in the order the packets were queued. It fails with
+    /// `Error::ErrWouldBlock` when the transport doesn't keep up and the queue is full.
+    async fn try_write_rtp(&self, pkt: &rtp::packet::Packet) -> Result<usize> {
+        if self.is_sender_paused() {
+            return Ok(0);
+        }
+
+        let n = pkt.marshal_size();
+        match self.try_write_queue().try_send(pkt.clone()) {
+            Ok(()) => Ok(n),
+            Err(TrySendError::Full(_)) => Err(Error::ErrWouldBlock),
+            Err(TrySendError::Closed(_)) => Err(Error::ErrClosedPipe),
+        }
+    }
 }
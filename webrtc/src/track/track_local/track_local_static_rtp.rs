@@ -6,6 +6,7 @@ use util::{Marshal, MarshalSize};
 
 use super::*;
 use crate::error::flatten_errs;
+use crate::track::RTP_OUTBOUND_MTU;
 
 /// TrackLocalStaticRTP  is a TrackLocal that has a pre-set codec and accepts RTP Packets.
 /// If you wish to send a media.Sample use TrackLocalStaticSample
@@ -40,6 +41,16 @@ impl TrackLocalStaticRTP {
             .any(|b| b.sender_paused.load(Ordering::SeqCst))
     }
 
+    /// mtu returns the largest RTP packet that fits every binding of the track.
+    pub async fn mtu(&self) -> usize {
+        let bindings = self.bindings.lock().await;
+        bindings
+            .iter()
+            .map(|b| b.mtu())
+            .min()
+            .unwrap_or(RTP_OUTBOUND_MTU)
+    }
+
     pub async fn all_binding_paused(&self) -> bool {
         let bindings = self.bindings.lock().await;
         bindings
@@ -63,8 +74,18 @@ impl TrackLocalStaticRTP {
         &self,
         p: &rtp::packet::Packet,
         extensions: &[rtp::extension::HeaderExtension],
+    ) -> Result<usize> {
+        self.write_rtp_to_bindings(p, extensions, false).await
+    }
+
+    async fn write_rtp_to_bindings(
+        &self,
+        p: &rtp::packet::Packet,
+        extensions: &[rtp::extension::HeaderExtension],
+        try_write: bool,
     ) -> Result<usize> {
         let mut n = 0;
+        let mut accepted = false;
         let mut write_errs = vec![];
         let mut pkt = p.clone();
 
@@ -107,7 +128,12 @@ impl TrackLocalStaticRTP {
                     .find(|ext| &ext.uri == uri)
                     .map(|ext| ext.id)
                 {
-                    if let Err(err) = pkt.header.set_extension(id as u8, data.clone()) {
+                    let result = if b.extmap_allow_mixed {
+                        pkt.header.set_extension_allow_mixed(id as u8, data.clone())
+                    } else {
+                        pkt.header.set_extension(id as u8, data.clone())
+                    };
+                    if let Err(err) = result {
                         write_errs.push(Error::Rtp(err));
                         continue;
                     }
@@ -115,9 +141,15 @@ impl TrackLocalStaticRTP {
             }
 
             if let Some(write_stream) = &b.write_stream {
-                match write_stream.write_rtp(&pkt).await {
+                let result = if try_write {
+                    write_stream.try_write_rtp(&pkt).await
+                } else {
+                    write_stream.write_rtp(&pkt).await
+                };
+                match result {
                     Ok(m) => {
                         n += m;
+                        accepted = true;
                     }
                     Err(err) => {
                         write_errs.push(err);
@@ -128,6 +160,13 @@ impl TrackLocalStaticRTP {
             }
         }
 
+        if accepted {
+            // The packet is dropped for the bindings that would block, retrying it would send a
+            // duplicate to the ones that took it.
+            write_errs.retain(|e| *e != Error::ErrWouldBlock);
+        } else if !write_errs.is_empty() && write_errs.iter().all(|e| *e == Error::ErrWouldBlock) {
+            return Err(Error::ErrWouldBlock);
+        }
         flatten_errs(write_errs)?;
         Ok(n)
     }
@@ -155,6 +194,8 @@ impl TrackLocal for TrackLocalStaticRTP {
                     params: t.params.clone(),
                     id: t.id(),
                     sender_paused: t.paused.clone(),
+                    extmap_allow_mixed: t.extmap_allow_mixed,
+                    path_mtu: t.path_mtu.clone(),
                 }));
             }
 
@@ -235,4 +276,12 @@ impl TrackLocalWriter for TrackLocalStaticRTP {
         self.write_rtp(&pkt).await?;
         Ok(b.len())
     }
+
+    /// try_write_rtp writes a RTP Packet to the TrackLocalStaticRTP without waiting for
+    /// PeerConnections whose transport can't keep up. The packet is dropped for
+    /// those as long as another PeerConnection took it. If none of them could take the packet,
+    /// `Error::ErrWouldBlock` is returned.
+    async fn try_write_rtp(&self, p: &rtp::packet::Packet) -> Result<usize> {
+        self.write_rtp_to_bindings(p, &[], true).await
+    }
 }
@@ -5,7 +5,6 @@ use tokio::sync::Mutex;
 use super::track_local_static_rtp::TrackLocalStaticRTP;
 use super::*;
 use crate::error::flatten_errs;
-use crate::track::RTP_OUTBOUND_MTU;
 
 #[derive(Debug, Clone)]
 struct TrackLocalStaticSampleInternal {
@@ -104,6 +103,9 @@ impl TrackLocalStaticSample {
             }
         }
 
+        // The path MTU may have been discovered since the last sample
+        let mtu = self.rtp_track.mtu().await;
+
         // skip packets by the number of previously dropped packets
         if let Some(sequencer) = &internal.sequencer {
             for _ in 0..sample.prev_dropped_packets {
@@ -118,6 +120,7 @@ impl TrackLocalStaticSample {
             if sample.prev_dropped_packets > 0 {
                 packetizer.skip_samples(samples * sample.prev_dropped_packets as u32);
             }
+            packetizer.set_mtu(mtu);
             packetizer.packetize(&sample.data, samples)?
         } else {
             vec![]
@@ -195,7 +198,7 @@ impl TrackLocal for TrackLocalStaticSample {
         let sequencer: Box<dyn rtp::sequence::Sequencer + Send + Sync> =
             Box::new(rtp::sequence::new_random_sequencer());
         internal.packetizer = Some(Box::new(rtp::packetizer::new_packetizer(
-            RTP_OUTBOUND_MTU,
+            t.mtu(),
             0, // Value is handled when writing
             0, // Value is handled when writing
             payloader,
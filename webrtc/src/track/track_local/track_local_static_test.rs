@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use bytes::Bytes;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio::time::Duration;
 
 use super::track_local_static_rtp::*;
 use super::track_local_static_sample::*;
@@ -406,6 +407,169 @@ async fn test_track_local_static_binding_non_blocking() -> Result<()> {
     Ok(())
 }
 
+/// FullRTPWriter never completes a write while `full` is set, like a transport whose send
+/// buffer has no room left.
+struct FullRTPWriter {
+    full: AtomicBool,
+    written: AtomicUsize,
+    entered_tx: mpsc::Sender<()>,
+    drained: Notify,
+}
+
+impl FullRTPWriter {
+    fn new(full: bool, entered_tx: mpsc::Sender<()>) -> Self {
+        FullRTPWriter {
+            full: AtomicBool::new(full),
+            written: AtomicUsize::new(0),
+            entered_tx,
+            drained: Notify::new(),
+        }
+    }
+
+    fn drain(&self) {
+        self.full.store(false, Ordering::SeqCst);
+        self.drained.notify_one();
+    }
+
+    async fn wait_written(&self, count: usize) {
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while self.written.load(Ordering::SeqCst) < count {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("queued packets should be written");
+    }
+}
+
+#[async_trait]
+impl RTPWriter for FullRTPWriter {
+    async fn write(
+        &self,
+        pkt: &rtp::packet::Packet,
+        _attributes: &Attributes,
+    ) -> std::result::Result<usize, interceptor::Error> {
+        let _ = self.entered_tx.try_send(());
+        while self.full.load(Ordering::SeqCst) {
+            self.drained.notified().await;
+        }
+        self.written.fetch_add(1, Ordering::SeqCst);
+        Ok(pkt.payload.len())
+    }
+}
+
+async fn bind_rtp_writer(
+    track: &TrackLocalStaticRTP,
+    ssrc: SSRC,
+    rtp_writer: Arc<dyn RTPWriter + Send + Sync>,
+) -> Result<()> {
+    let write_stream = Arc::new(InterceptorToTrackLocalWriter::new(Arc::new(
+        AtomicBool::new(false),
+    )));
+    *write_stream.interceptor_rtp_writer.lock().await = Some(rtp_writer);
+    track
+        .bind(&TrackLocalContext {
+            id: format!("test{ssrc}"),
+            params: RTCRtpParameters {
+                codecs: vec![RTCRtpCodecParameters {
+                    capability: RTCRtpCodecCapability {
+                        mime_type: MIME_TYPE_VP8.to_owned(),
+                        ..Default::default()
+                    },
+                    payload_type: 96,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ssrc,
+            write_stream: Some(write_stream),
+            paused: Arc::new(AtomicBool::new(false)),
+            extmap_allow_mixed: false,
+            path_mtu: Default::default(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_track_local_static_try_write_would_block() -> Result<()> {
+    let (entered_tx, mut entered_rx) = mpsc::channel(1);
+    let rtp_writer = Arc::new(FullRTPWriter::new(true, entered_tx));
+
+    let track = TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    );
+    bind_rtp_writer(&track, 1, Arc::clone(&rtp_writer) as _).await?;
+
+    let pkt = rtp::packet::Packet {
+        payload: Bytes::from_static(&[0x01, 0x02]),
+        ..Default::default()
+    };
+    let size = pkt.marshal_size();
+
+    // The first packet gets stuck in the full transport, the following ones fill the queue.
+    assert_eq!(track.try_write_rtp(&pkt).await?, size);
+    entered_rx.recv().await;
+    for _ in 0..TRY_WRITE_QUEUE_SIZE {
+        assert_eq!(track.try_write_rtp(&pkt).await?, size);
+    }
+    assert_eq!(track.try_write_rtp(&pkt).await, Err(Error::ErrWouldBlock));
+
+    // Once the transport drains, every queued packet is written and there is room again.
+    rtp_writer.drain();
+    rtp_writer.wait_written(TRY_WRITE_QUEUE_SIZE + 1).await;
+    assert_eq!(track.try_write_rtp(&pkt).await?, size);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_track_local_static_try_write_partially_blocked() -> Result<()> {
+    let (full_entered_tx, mut full_entered_rx) = mpsc::channel(1);
+    let full_writer = Arc::new(FullRTPWriter::new(true, full_entered_tx));
+    let (free_entered_tx, _free_entered_rx) = mpsc::channel(1);
+    let free_writer = Arc::new(FullRTPWriter::new(false, free_entered_tx));
+
+    let track = TrackLocalStaticRTP::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    );
+    bind_rtp_writer(&track, 1, Arc::clone(&free_writer) as _).await?;
+    bind_rtp_writer(&track, 2, Arc::clone(&full_writer) as _).await?;
+
+    let pkt = rtp::packet::Packet {
+        payload: Bytes::from_static(&[0x01, 0x02]),
+        ..Default::default()
+    };
+    let size = pkt.marshal_size();
+
+    assert_eq!(track.try_write_rtp(&pkt).await?, 2 * size);
+    full_entered_rx.recv().await;
+    for _ in 0..TRY_WRITE_QUEUE_SIZE {
+        assert_eq!(track.try_write_rtp(&pkt).await?, 2 * size);
+        tokio::task::yield_now().await;
+    }
+
+    // The free binding takes the packet, so the caller must not retry it.
+    assert_eq!(track.try_write_rtp(&pkt).await?, size);
+    free_writer.wait_written(TRY_WRITE_QUEUE_SIZE + 2).await;
+
+    full_writer.drain();
+    full_writer.wait_written(TRY_WRITE_QUEUE_SIZE + 1).await;
+
+    Ok(())
+}
+
 /*
 //TODO: func BenchmarkTrackLocalWrite(b *testing.B) {
     offerPC, answerPC, err := newPair()
@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicU32, AtomicU8, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, AtomicUsize, Ordering};
 use std::sync::{Arc, Weak};
 
 use arc_swap::ArcSwapOption;
@@ -56,6 +56,9 @@ pub struct TrackRemote {
 
     receiver: Option<Weak<RTPReceiverInternal>>,
     internal: Mutex<TrackRemoteInternal>,
+    /// Held while a packet is read by `buffer`, which reads wait for while `buffering_active`.
+    buffering: Mutex<()>,
+    buffering_active: AtomicBool,
 }
 
 impl std::fmt::Debug for TrackRemote {
@@ -100,6 +103,8 @@ impl TrackRemote {
             handlers: Default::default(),
 
             internal: Default::default(),
+            buffering: Default::default(),
+            buffering_active: AtomicBool::new(false),
         }
     }
 
@@ -211,27 +216,56 @@ impl TrackRemote {
 
     /// Reads data from the track.
     ///
+    /// Returns [`Error::ErrTrackClosed`] once the track's RTPReceiver has been stopped or dropped,
+    /// or the underlying SRTP stream has been closed.
+    ///
     /// **Cancel Safety:** This method is not cancel safe. Dropping the resulting [`Future`] before
     /// it returns [`Poll::Ready`] will cause data loss.
     pub async fn read(&self, b: &mut [u8]) -> Result<(rtp::packet::Packet, Attributes)> {
-        {
-            // Internal lock scope
-            let mut internal = self.internal.lock().await;
-            if let Some((pkt, attributes)) = internal.peeked.pop_front() {
-                self.check_and_update_track(&pkt).await?;
+        // Loaded before the peeked packets are looked at, buffering ends only once the last
+        // buffered packet is among them.
+        let buffering = self.buffering_active.load(Ordering::SeqCst);
+        if let Some(peeked) = self.read_peeked().await? {
+            return Ok(peeked);
+        }
 
-                return Ok((pkt, attributes));
+        // A packet being buffered comes before the ones still to be read.
+        if buffering {
+            drop(self.buffering.lock().await);
+            if let Some(peeked) = self.read_peeked().await? {
+                return Ok(peeked);
             }
-        };
+        }
+
+        let (pkt, attributes) = self.read_from_receiver(b).await?;
+        self.check_and_update_track(&pkt).await?;
+        Ok((pkt, attributes))
+    }
+
+    async fn read_peeked(&self) -> Result<Option<(rtp::packet::Packet, Attributes)>> {
+        // Internal lock scope
+        let mut internal = self.internal.lock().await;
+        if let Some((pkt, attributes)) = internal.peeked.pop_front() {
+            self.check_and_update_track(&pkt).await?;
+
+            return Ok(Some((pkt, attributes)));
+        }
+        Ok(None)
+    }
 
+    async fn read_from_receiver(&self, b: &mut [u8]) -> Result<(rtp::packet::Packet, Attributes)> {
         let receiver = match self.receiver.as_ref().and_then(|r| r.upgrade()) {
             Some(r) => r,
-            None => return Err(Error::ErrRTPReceiverNil),
+            None => return Err(Error::ErrTrackClosed),
         };
 
-        let (pkt, attributes) = receiver.read_rtp(b, self.tid).await?;
-        self.check_and_update_track(&pkt).await?;
-        Ok((pkt, attributes))
+        receiver.read_rtp(b, self.tid).await.map_err(|err| {
+            if is_closed_error(&err) {
+                Error::ErrTrackClosed
+            } else {
+                err
+            }
+        })
     }
 
     /// check_and_update_track checks payloadType for every incoming packet
@@ -289,6 +323,23 @@ impl TrackRemote {
         Ok((pkt, a))
     }
 
+    /// set_buffering marks whether packets are being buffered by `buffer`. Only then do reads
+    /// wait for a packet being buffered.
+    pub(crate) fn set_buffering(&self, active: bool) {
+        self.buffering_active.store(active, Ordering::SeqCst);
+    }
+
+    /// buffer reads the next packet from the stream, behind any already peeked ones, and keeps
+    /// it for a later read. While buffering is set, a read meanwhile waits for this one.
+    pub(crate) async fn buffer(&self, b: &mut [u8]) -> Result<()> {
+        let _buffering = self.buffering.lock().await;
+        let (pkt, a) = self.read_from_receiver(b).await?;
+
+        let mut internal = self.internal.lock().await;
+        internal.peeked.push_back((pkt, a));
+        Ok(())
+    }
+
     /// Set the initially peeked data for this track.
     ///
     /// This is useful when a track is first created to populate data read from the track in the
@@ -318,3 +369,20 @@ impl TrackRemote {
         };
     }
 }
+
+/// is_closed_error reports whether `err`, returned while reading from the RTPReceiver, means
+/// that no more packets can be read.
+fn is_closed_error(err: &Error) -> bool {
+    const BUFFER_CLOSED: util::Error = util::Error::ErrBufferClosed;
+
+    match err {
+        Error::ErrClosedPipe => true,
+        Error::Util(err) => *err == BUFFER_CLOSED,
+        Error::Srtp(srtp::Error::Util(err)) => *err == BUFFER_CLOSED,
+        Error::Interceptor(interceptor::Error::Util(err)) => *err == BUFFER_CLOSED,
+        Error::Interceptor(interceptor::Error::Srtp(srtp::Error::Util(err))) => {
+            *err == BUFFER_CLOSED
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::Weak;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use interceptor::noop::NoOp;
+use interceptor::Attributes;
+use smol_str::SmolStr;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::Duration;
+use util::Unmarshal;
+
+use super::track_local::{TrackLocal, TrackLocalContext};
+use super::*;
+use crate::api::media_engine::{MediaEngine, MIME_TYPE_VP8};
+use crate::error::Error;
+use crate::peer_connection::peer_connection_test::{
+    close_pair_now, create_vnet_pair, send_video_until_done, signal_pair,
+};
+use crate::rtp_transceiver::rtp_codec::{
+    RTCRtpCodecCapability, RTCRtpCodecParameters, RTCRtpHeaderExtensionCapability,
+    RTCRtpParameters, RTPCodecType,
+};
+use crate::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
+use crate::track::track_local::track_local_static_sample::TrackLocalStaticSample;
+
+#[derive(Debug, Default)]
+struct PacketCollector {
+    packets: Mutex<Vec<rtp::packet::Packet>>,
+}
+
+#[async_trait]
+impl TrackLocalWriter for PacketCollector {
+    async fn write_rtp(&self, p: &rtp::packet::Packet) -> Result<usize> {
+        let mut packets = self.packets.lock().await;
+        packets.push(p.clone());
+        Ok(p.payload.len())
+    }
+
+    async fn write(&self, mut b: &[u8]) -> Result<usize> {
+        let pkt = rtp::packet::Packet::unmarshal(&mut b)?;
+        self.write_rtp(&pkt).await
+    }
+}
+
+fn vp8_capability() -> RTCRtpCodecCapability {
+    RTCRtpCodecCapability {
+        mime_type: MIME_TYPE_VP8.to_owned(),
+        clock_rate: 90000,
+        ..Default::default()
+    }
+}
+
+async fn remote_track_with_packets(packets: &[rtp::packet::Packet]) -> Arc<TrackRemote> {
+    let track = Arc::new(TrackRemote::new(
+        1460,
+        RTPCodecType::Video,
+        1234,
+        SmolStr::default(),
+        Weak::new(),
+        Arc::new(MediaEngine::default()),
+        Arc::new(NoOp),
+    ));
+    track.set_payload_type(96);
+    track
+        .prepopulate_peeked_data(
+            packets
+                .iter()
+                .map(|p| (p.clone(), Attributes::new()))
+                .collect::<VecDeque<_>>(),
+        )
+        .await;
+
+    track
+}
+
+async fn bind_local_track(
+    local: &TrackLocalStaticRTP,
+    collector: Arc<PacketCollector>,
+) -> Result<()> {
+    local
+        .bind(&TrackLocalContext {
+            id: "forwarded".to_owned(),
+            params: RTCRtpParameters {
+                header_extensions: vec![],
+                codecs: vec![RTCRtpCodecParameters {
+                    capability: vp8_capability(),
+                    payload_type: 100,
+                    ..Default::default()
+                }],
+            },
+            ssrc: 4444,
+            write_stream: Some(collector),
+            paused: Arc::new(AtomicBool::new(false)),
+            extmap_allow_mixed: false,
+            path_mtu: Default::default(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+fn packets(n: u16) -> Vec<rtp::packet::Packet> {
+    (0..n)
+        .map(|i| rtp::packet::Packet {
+            header: rtp::header::Header {
+                version: 2,
+                payload_type: 96,
+                sequence_number: 1000 + i,
+                timestamp: 3000 * u32::from(i),
+                ssrc: 1234,
+                ..Default::default()
+            },
+            payload: Bytes::from(vec![i as u8; 4]),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_pipe_forwards_packets() -> Result<()> {
+    let input = packets(5);
+    let remote = remote_track_with_packets(&input).await;
+
+    let local = Arc::new(TrackLocalStaticRTP::new(
+        vp8_capability(),
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    let collector = Arc::new(PacketCollector::default());
+    bind_local_track(&local, Arc::clone(&collector)).await?;
+
+    // The remote track has no receiver, so it is closed once the queued packets are read.
+    pipe(remote, Arc::clone(&local)).await?;
+
+    let forwarded = collector.packets.lock().await;
+    assert_eq!(forwarded.len(), input.len());
+    for (expected, actual) in input.iter().zip(forwarded.iter()) {
+        assert_eq!(actual.header.ssrc, 4444);
+        assert_eq!(actual.header.payload_type, 100);
+        assert_eq!(
+            actual.header.sequence_number,
+            expected.header.sequence_number
+        );
+        assert_eq!(actual.header.timestamp, expected.header.timestamp);
+        assert_eq!(actual.payload, expected.payload);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pipe_stops_when_local_dropped() -> Result<()> {
+    let remote = remote_track_with_packets(&packets(5)).await;
+
+    let local = Arc::new(TrackLocalStaticRTP::new(
+        vp8_capability(),
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    let collector = Arc::new(PacketCollector::default());
+    bind_local_track(&local, Arc::clone(&collector)).await?;
+
+    // Handing over the only reference lets pipe notice that nobody else uses the local track.
+    pipe(remote, local).await?;
+
+    assert!(collector.packets.lock().await.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_track_remote_read_without_receiver() -> Result<()> {
+    let remote = remote_track_with_packets(&packets(1)).await;
+
+    // Queued packets are still returned after the receiver is gone.
+    assert!(remote.read_rtp().await.is_ok());
+    assert_eq!(remote.read_rtp().await.unwrap_err(), Error::ErrTrackClosed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_track_remote_read_after_receiver_stopped() -> Result<()> {
+    let (mut pc_offer, mut pc_answer, _wan) = create_vnet_pair().await?;
+
+    let track = Arc::new(TrackLocalStaticSample::new(
+        vp8_capability(),
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+    pc_offer
+        .add_track(Arc::clone(&track) as Arc<dyn TrackLocal + Send + Sync>)
+        .await?;
+
+    let (done_tx, done_rx) = mpsc::channel::<()>(1);
+    let (track_tx, mut track_rx) = mpsc::channel(1);
+    pc_answer.on_track(Box::new(move |track, receiver, _| {
+        let (done_tx, track_tx) = (done_tx.clone(), track_tx.clone());
+        Box::pin(async move {
+            let _ = track_tx.send((track, receiver)).await;
+            let _ = done_tx.send(()).await;
+        })
+    }));
+
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    send_video_until_done(
+        done_rx,
+        vec![track],
+        Bytes::from_static(b"\xDE\xAD\xBE\xEF"),
+        None,
+    )
+    .await;
+
+    let (remote, receiver) = track_rx.recv().await.expect("on_track to be called");
+    receiver.stop().await?;
+
+    let err = loop {
+        match tokio::time::timeout(Duration::from_secs(5), remote.read_rtp()).await {
+            Ok(Ok(_)) => continue,
+            Ok(Err(err)) => break err,
+            Err(_) => panic!("read did not return after the receiver was stopped"),
+        }
+    };
+    assert_eq!(err, Error::ErrTrackClosed);
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_track_local_two_byte_extension_with_extmap_allow_mixed() -> Result<()> {
+    for allow_mixed in [true, false] {
+        let remote = format!(
+            "v=0\r\n\
+             o=- 0 0 IN IP4 127.0.0.1\r\n\
+             s=-\r\n\
+             t=0 0\r\n\
+             {}\
+             m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+             a=rtpmap:96 VP8/90000\r\n\
+             a=extmap:15 urn:3gpp:video-orientation\r\n",
+            if allow_mixed {
+                "a=extmap-allow-mixed\r\n"
+            } else {
+                ""
+            },
+        );
+
+        let mut m = MediaEngine::default();
+        m.register_default_codecs()?;
+        m.register_header_extension(
+            RTCRtpHeaderExtensionCapability {
+                uri: "urn:3gpp:video-orientation".to_owned(),
+            },
+            RTPCodecType::Video,
+            None,
+        )?;
+        m.update_from_remote_description(&::sdp::SessionDescription::unmarshal(
+            &mut std::io::Cursor::new(remote.as_bytes()),
+        )?)
+        .await?;
+
+        let local =
+            TrackLocalStaticRTP::new(vp8_capability(), "video".to_owned(), "pion".to_owned());
+        let collector = Arc::new(PacketCollector::default());
+        local
+            .bind(&TrackLocalContext {
+                id: "mixed".to_owned(),
+                params: m.get_rtp_parameters_by_kind(
+                    RTPCodecType::Video,
+                    RTCRtpTransceiverDirection::Sendonly,
+                ),
+                ssrc: 4444,
+                write_stream: Some(Arc::clone(&collector) as _),
+                paused: Arc::new(AtomicBool::new(false)),
+                extmap_allow_mixed: m.extmap_allow_mixed(),
+                path_mtu: Default::default(),
+            })
+            .await?;
+
+        local
+            .write_rtp_with_extensions(
+                &rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        version: 2,
+                        ..Default::default()
+                    },
+                    payload: Bytes::from_static(&[0x01]),
+                },
+                &[rtp::extension::HeaderExtension::VideoOrientation(
+                    Default::default(),
+                )],
+            )
+            .await?;
+
+        let packets = collector.packets.lock().await;
+        let header = &packets[0].header;
+        if allow_mixed {
+            assert_eq!(
+                header.extension_profile,
+                rtp::header::EXTENSION_PROFILE_TWO_BYTE
+            );
+            assert!(header.get_extension(15).is_some());
+        } else {
+            // Without the attribute the id can't be used, so it isn't negotiated at all.
+            assert!(!header.extension);
+        }
+    }
+
+    Ok(())
+}